@@ -0,0 +1,25 @@
+pub mod bloom;
+pub mod booleans;
+pub mod byte_map;
+pub mod checksums;
+pub mod column_rollup;
+pub mod decompression;
+pub mod dictionary;
+pub mod distinct;
+pub mod encodings;
+pub mod levels;
+pub mod memory;
+pub mod offsets;
+pub mod page_counts;
+pub mod page_index;
+pub mod page_overhead;
+pub mod pii;
+pub mod pushdown;
+pub mod row_group_layout;
+pub mod row_group_sizes;
+pub mod scan;
+pub mod size_breakdown;
+pub mod sortedness;
+pub mod stats_presence;
+pub mod strings;
+pub mod value_sizes;
@@ -0,0 +1,200 @@
+use std::{
+    collections::HashSet,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+use parquet_format_safe::{thrift::protocol::TCompactInputProtocol, BloomFilterHeader};
+
+use crate::{
+    parquet::{ColumnChunkMetaDataExt, StatValue, ValueStream},
+    source::FileSource,
+};
+
+/// Upper bound on the size of a serialized bloom filter header.
+const MAX_BLOOM_FILTER_HEADER_SIZE: usize = 1024;
+
+/// Bits a split block bloom filter sets per inserted value, one in each word of a block.
+const BITS_PER_VALUE: f64 = 8.0;
+
+/// Fraction of distinct values among the sampled values of a string column from which it is
+/// considered high-cardinality, i.e. a good candidate for point lookups through a bloom filter.
+pub const HIGH_CARDINALITY: f64 = 0.5;
+
+/// Read the header of the bloom filter at `offset`. Returns it with its serialized size.
+pub fn read_bloom_filter_header<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+) -> color_eyre::Result<(BloomFilterHeader, u64)> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut protocol = TCompactInputProtocol::new(&mut *reader, MAX_BLOOM_FILTER_HEADER_SIZE);
+    let header = BloomFilterHeader::read_from_in_protocol(&mut protocol)?;
+    let header_size = reader.stream_position()? - offset;
+    Ok((header, header_size))
+}
+
+/// Estimated false positive probability of a split block bloom filter of `bytes` holding
+/// `distinct` values, approximating it as a classic bloom filter with 8 hash functions.
+pub fn estimate_fpp(bytes: u64, distinct: u64) -> f64 {
+    let bits = (bytes * 8).max(1) as f64;
+    (1.0 - (-BITS_PER_VALUE * distinct as f64 / bits).exp()).powf(BITS_PER_VALUE)
+}
+
+/// Where the distinct value count of a column chunk comes from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DistinctSource {
+    /// The distinct count of the chunk statistics.
+    Statistics,
+    /// The distinct ratio of sampled values, scaled to the chunk's non-null values.
+    Sample,
+}
+
+impl DistinctSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DistinctSource::Statistics => "statistics",
+            DistinctSource::Sample => "sample",
+        }
+    }
+}
+
+/// A bloom filter of a column chunk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilterInfo {
+    pub row_group: usize,
+    pub column: String,
+    pub offset: u64,
+    /// Size of the header and bitset.
+    pub size: u64,
+    pub bitset_bytes: u64,
+    pub distinct: Option<(u64, DistinctSource)>,
+    pub fpp: Option<f64>,
+}
+
+/// Bloom filter coverage of a leaf column across row groups.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnCoverage {
+    pub column: String,
+    pub physical_type: PhysicalType,
+    pub row_groups_with_filter: usize,
+    pub row_groups: usize,
+    /// Total size of the column's bloom filters.
+    pub bytes: u64,
+    /// Fraction of distinct values among the values sampled from the first row group, for
+    /// BYTE_ARRAY columns.
+    pub distinct_ratio: Option<f64>,
+}
+
+impl ColumnCoverage {
+    /// Whether the column is a high-cardinality string column that some row groups lack a
+    /// bloom filter for.
+    pub fn lacks_filter(&self) -> bool {
+        self.physical_type == PhysicalType::ByteArray
+            && self.row_groups_with_filter < self.row_groups
+            && self
+                .distinct_ratio
+                .is_some_and(|ratio| ratio >= HIGH_CARDINALITY)
+    }
+}
+
+/// Which column chunks of a file have bloom filters, and which columns should.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BloomCoverage {
+    pub filters: Vec<BloomFilterInfo>,
+    pub columns: Vec<ColumnCoverage>,
+}
+
+/// Distinct and total counts of up to `sample` non-null values of a column chunk, or `None`
+/// when its values cannot be read.
+fn sample_distinct(
+    path: &Path,
+    row_group: usize,
+    column: usize,
+    sample: usize,
+) -> Option<(usize, usize)> {
+    let mut distinct = HashSet::new();
+    let mut sampled = 0;
+    for batch in ValueStream::open(path, row_group, column, sample).ok()? {
+        for value in batch.ok()? {
+            sampled += 1;
+            distinct.insert(match value {
+                StatValue::Bytes(bytes) => bytes,
+                value => value.to_string().into_bytes(),
+            });
+        }
+    }
+    Some((distinct.len(), sampled))
+}
+
+/// List the bloom filters of every column chunk stored in the file, with their estimated false
+/// positive probability, and the coverage of every column. Distinct counts come from the chunk
+/// statistics, or from up to `sample` values of the chunk.
+pub fn bloom_coverage(
+    path: &Path,
+    metadata: &FileMetaData,
+    sample: usize,
+) -> color_eyre::Result<BloomCoverage> {
+    let mut reader = FileSource::open(path)?.reader()?;
+    let mut coverage = BloomCoverage::default();
+
+    for (column, descriptor) in metadata.schema().columns().iter().enumerate() {
+        let name = descriptor.path_in_schema.join(".");
+        let mut column_coverage = ColumnCoverage {
+            column: name.clone(),
+            physical_type: descriptor.descriptor.primitive_type.physical_type,
+            row_groups_with_filter: 0,
+            row_groups: metadata.row_groups.len(),
+            bytes: 0,
+            distinct_ratio: None,
+        };
+
+        for (row_group, metadata) in metadata.row_groups.iter().enumerate() {
+            let chunk = &metadata.columns()[column];
+            let Some(offset) = chunk
+                .metadata()
+                .bloom_filter_offset
+                .filter(|_| chunk.file_path().is_none())
+            else {
+                continue;
+            };
+            let offset = offset.max(0) as u64;
+            let (header, header_size) = read_bloom_filter_header(&mut reader, offset)?;
+            let bitset_bytes = header.num_bytes.max(0) as u64;
+
+            let distinct = match chunk.stats().distinct_values {
+                Some(distinct) => Some((distinct.max(0) as u64, DistinctSource::Statistics)),
+                None => sample_distinct(path, row_group, column, sample)
+                    .filter(|(_, sampled)| *sampled > 0)
+                    .map(|(distinct, sampled)| {
+                        let non_null = chunk.num_values() - chunk.stats().null_count.unwrap_or(0);
+                        let estimate = distinct as f64 / sampled as f64 * non_null.max(0) as f64;
+                        (estimate.round() as u64, DistinctSource::Sample)
+                    }),
+            };
+
+            column_coverage.row_groups_with_filter += 1;
+            column_coverage.bytes += header_size + bitset_bytes;
+            coverage.filters.push(BloomFilterInfo {
+                row_group,
+                column: name.clone(),
+                offset,
+                size: header_size + bitset_bytes,
+                bitset_bytes,
+                distinct,
+                fpp: distinct.map(|(distinct, _)| estimate_fpp(bitset_bytes, distinct)),
+            });
+        }
+
+        if column_coverage.physical_type == PhysicalType::ByteArray
+            && !metadata.row_groups.is_empty()
+        {
+            column_coverage.distinct_ratio = sample_distinct(path, 0, column, sample)
+                .filter(|(_, sampled)| *sampled > 0)
+                .map(|(distinct, sampled)| distinct as f64 / sampled as f64);
+        }
+        coverage.columns.push(column_coverage);
+    }
+
+    Ok(coverage)
+}
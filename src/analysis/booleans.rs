@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use color_eyre::eyre::eyre;
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+
+use crate::parquet::{StatValue, ValueStream};
+
+/// Number of true, false and null values of a BOOLEAN column chunk. Footers only declare the
+/// min and max of boolean columns, which say little more than whether both values occur.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct BooleanCounts {
+    pub trues: u64,
+    pub falses: u64,
+    /// Values declared by the footer but not decoded, i.e. nulls, and empty or null lists of
+    /// repeated columns.
+    pub nulls: u64,
+}
+
+impl BooleanCounts {
+    pub fn total(&self) -> u64 {
+        self.trues + self.falses + self.nulls
+    }
+
+    /// Widths of the true, false and null parts of a bar of `width` cells, each at least one
+    /// cell wide when the chunk has such values.
+    pub fn bar(&self, width: usize) -> [usize; 3] {
+        let total = self.total();
+        if total == 0 {
+            return [0; 3];
+        }
+        let mut parts = [self.trues, self.falses, self.nulls].map(|count| match count {
+            0 => 0,
+            count => ((count as u128 * width as u128 / total as u128) as usize).max(1),
+        });
+        // Rounding down leaves cells over, and rounding tiny parts up may take too many: the
+        // difference goes to the largest part.
+        let largest = (0..3).max_by_key(|&part| parts[part]).unwrap();
+        let used: usize = parts.iter().sum();
+        parts[largest] = (parts[largest] + width).saturating_sub(used);
+        parts
+    }
+}
+
+/// Decode every value of a BOOLEAN column chunk and count them.
+pub fn count_booleans<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    column: usize,
+) -> color_eyre::Result<BooleanCounts> {
+    let chunk = &metadata.row_groups[row_group].columns()[column];
+    let physical_type = chunk.descriptor().descriptor.primitive_type.physical_type;
+    if physical_type != PhysicalType::Boolean {
+        return Err(eyre!(
+            "{} is not a BOOLEAN column",
+            chunk.descriptor().path_in_schema.join(".")
+        ));
+    }
+
+    let mut counts = BooleanCounts::default();
+    for batch in ValueStream::open(path.as_ref(), row_group, column, usize::MAX)? {
+        for value in batch? {
+            match value {
+                StatValue::Boolean(true) => counts.trues += 1,
+                StatValue::Boolean(false) => counts.falses += 1,
+                _ => {}
+            }
+        }
+    }
+    counts.nulls = (chunk.num_values().max(0) as u64).saturating_sub(counts.trues + counts.falses);
+    Ok(counts)
+}
@@ -0,0 +1,165 @@
+use std::{
+    io::{Seek, SeekFrom},
+    path::Path,
+};
+
+use parquet2::metadata::FileMetaData;
+use parquet_format_safe::PageType;
+
+use super::{bloom::read_bloom_filter_header, row_group_layout::metadata_copy_size};
+use crate::{
+    cache::read_footer_bytes,
+    pages::read_page_headers,
+    parquet::{read_row_group_footers, RowGroupFooter},
+    source::FileSource,
+};
+
+/// Kind of an [Element] of the byte map.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ElementKind {
+    /// The leading magic bytes.
+    Magic,
+    RowGroup,
+    ColumnChunk,
+    /// A page, header included.
+    DictionaryPage,
+    DataPage,
+    IndexPage,
+    /// Copy of a chunk's column metadata that some writers place after the chunk.
+    ColumnMetadata,
+    BloomFilter,
+    ColumnIndex,
+    OffsetIndex,
+    /// The file metadata, its length and the trailing magic bytes.
+    Footer,
+}
+
+impl ElementKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ElementKind::Magic => "magic",
+            ElementKind::RowGroup => "row_group",
+            ElementKind::ColumnChunk => "column_chunk",
+            ElementKind::DictionaryPage => "dictionary_page",
+            ElementKind::DataPage => "data_page",
+            ElementKind::IndexPage => "index_page",
+            ElementKind::ColumnMetadata => "column_metadata",
+            ElementKind::BloomFilter => "bloom_filter",
+            ElementKind::ColumnIndex => "column_index",
+            ElementKind::OffsetIndex => "offset_index",
+            ElementKind::Footer => "footer",
+        }
+    }
+}
+
+/// A structural element of a Parquet file and the bytes it spans. Elements nest: a row group
+/// spans its column chunks, which span their pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub kind: ElementKind,
+    pub row_group: Option<usize>,
+    pub column: Option<String>,
+    pub start: u64,
+    pub length: u64,
+}
+
+/// Every structural element of a file, from the leading magic bytes to the footer, sorted by
+/// offset, containers before their contents. Returned with the file size. Chunks stored in
+/// other files, and their pages, are left out.
+pub fn byte_map(path: &Path, metadata: &FileMetaData) -> color_eyre::Result<(Vec<Element>, u64)> {
+    let mut reader = FileSource::open(path)?.reader()?;
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let footer_length = read_footer_bytes(&mut reader)?.len() as u64 + 8;
+    let footers: Vec<RowGroupFooter> = read_row_group_footers(path)?;
+
+    let mut elements = vec![
+        Element {
+            kind: ElementKind::Magic,
+            row_group: None,
+            column: None,
+            start: 0,
+            length: 4,
+        },
+        Element {
+            kind: ElementKind::Footer,
+            row_group: None,
+            column: None,
+            start: file_size - footer_length,
+            length: footer_length,
+        },
+    ];
+
+    for extent in super::row_group_layout::row_group_extents(metadata, &footers) {
+        elements.push(Element {
+            kind: ElementKind::RowGroup,
+            row_group: Some(extent.row_group),
+            column: None,
+            start: extent.start,
+            length: extent.end - extent.start,
+        });
+    }
+
+    for (row_group, metadata) in metadata.row_groups.iter().enumerate() {
+        for chunk in metadata.columns() {
+            let column = chunk.descriptor().path_in_schema.join(".");
+            let mut push = |kind, start: u64, length: u64| {
+                elements.push(Element {
+                    kind,
+                    row_group: Some(row_group),
+                    column: Some(column.clone()),
+                    start,
+                    length,
+                })
+            };
+
+            // Indexes and bloom filters are written next to the footer, even for chunks
+            // stored in other files.
+            let column_chunk = chunk.column_chunk();
+            for (kind, offset, length) in [
+                (
+                    ElementKind::ColumnIndex,
+                    column_chunk.column_index_offset,
+                    column_chunk.column_index_length,
+                ),
+                (
+                    ElementKind::OffsetIndex,
+                    column_chunk.offset_index_offset,
+                    column_chunk.offset_index_length,
+                ),
+            ] {
+                if let (Some(offset), Some(length)) = (offset, length) {
+                    push(kind, offset.max(0) as u64, length.max(0) as u64);
+                }
+            }
+            if let Some(offset) = chunk.metadata().bloom_filter_offset {
+                let offset = offset.max(0) as u64;
+                let (header, header_size) = read_bloom_filter_header(&mut reader, offset)?;
+                push(
+                    ElementKind::BloomFilter,
+                    offset,
+                    header_size + header.num_bytes.max(0) as u64,
+                );
+            }
+
+            if chunk.file_path().is_some() {
+                continue;
+            }
+            let (start, length) = chunk.byte_range();
+            push(ElementKind::ColumnChunk, start, length);
+            for page in read_page_headers(&mut reader, chunk)? {
+                let kind = match page.page_type() {
+                    PageType::DICTIONARY_PAGE => ElementKind::DictionaryPage,
+                    PageType::INDEX_PAGE => ElementKind::IndexPage,
+                    _ => ElementKind::DataPage,
+                };
+                push(kind, page.offset, page.header_size + page.compressed_size());
+            }
+            if let Some(size) = metadata_copy_size(&mut reader, start + length, chunk) {
+                push(ElementKind::ColumnMetadata, start + length, size);
+            }
+        }
+    }
+
+    elements.sort_by_key(|element| (element.start, std::cmp::Reverse(element.length)));
+    Ok((elements, file_size))
+}
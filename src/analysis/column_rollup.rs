@@ -0,0 +1,117 @@
+use std::cmp::Ordering;
+
+use parquet2::{metadata::FileMetaData, schema::types::PrimitiveType};
+
+use crate::{
+    decimal::{decimal_type, Decimal},
+    parquet::{ColumnChunkMetaDataExt, StatValue},
+};
+
+/// Statistics of a column over every row group of a file, rolled up from the footer
+/// statistics of its chunks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnRollup {
+    pub column: String,
+    /// Smallest min and largest max of the chunks with both, rendered as their logical type.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Sum of the null counts of the chunks declaring one.
+    pub nulls: u64,
+    pub values: u64,
+    pub chunks: usize,
+    pub chunks_with_min_max: usize,
+    pub chunks_with_null_count: usize,
+}
+
+impl ColumnRollup {
+    /// Whether the bounds cover every chunk: otherwise values of the chunks without statistics
+    /// may lie beyond them.
+    pub fn is_complete(&self) -> bool {
+        self.chunks_with_min_max == self.chunks
+    }
+}
+
+/// Order two statistics of a column, DECIMAL ones by their value rather than their bytes.
+fn compare(primitive_type: &PrimitiveType, left: &StatValue, right: &StatValue) -> Ordering {
+    if let Some((_, scale)) = decimal_type(primitive_type) {
+        if let (Some(left), Some(right)) = (
+            Decimal::from_stat(left, scale),
+            Decimal::from_stat(right, scale),
+        ) {
+            return left.cmp(&right);
+        }
+    }
+    left.partial_cmp(right).unwrap_or(Ordering::Equal)
+}
+
+fn render(primitive_type: &PrimitiveType, value: &StatValue) -> String {
+    crate::renderers::registry()
+        .render(primitive_type, value)
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Roll up the footer statistics of every column across the row groups of a file, e.g. for
+/// the overall time range of a timestamp column. NaN bounds are skipped.
+pub fn column_rollups(metadata: &FileMetaData) -> Vec<ColumnRollup> {
+    metadata
+        .schema()
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(column, descriptor)| {
+            let primitive_type = &descriptor.descriptor.primitive_type;
+            let mut rollup = ColumnRollup {
+                column: descriptor.path_in_schema.join("."),
+                min: None,
+                max: None,
+                nulls: 0,
+                values: 0,
+                chunks: metadata.row_groups.len(),
+                chunks_with_min_max: 0,
+                chunks_with_null_count: 0,
+            };
+            let mut bounds: Option<(StatValue, StatValue)> = None;
+            for row_group in &metadata.row_groups {
+                let chunk = &row_group.columns()[column];
+                rollup.values += chunk.num_values().max(0) as u64;
+                if let Some(null_count) = chunk
+                    .metadata()
+                    .statistics
+                    .as_ref()
+                    .and_then(|stats| stats.null_count)
+                {
+                    rollup.nulls += null_count.max(0) as u64;
+                    rollup.chunks_with_null_count += 1;
+                }
+                let Some((min, max)) = chunk.min_max() else {
+                    continue;
+                };
+                let is_nan = |value: &StatValue| matches!(value, StatValue::Float(x) if x.is_nan());
+                if is_nan(&min) || is_nan(&max) {
+                    continue;
+                }
+                rollup.chunks_with_min_max += 1;
+                bounds = Some(match bounds {
+                    None => (min, max),
+                    Some((low, high)) => (
+                        if compare(primitive_type, &min, &low).is_lt() {
+                            min
+                        } else {
+                            low
+                        },
+                        if compare(primitive_type, &max, &high).is_gt() {
+                            max
+                        } else {
+                            high
+                        },
+                    ),
+                });
+            }
+            if let Some((min, max)) = bounds {
+                rollup.min = Some(render(primitive_type, &min));
+                rollup.max = Some(render(primitive_type, &max));
+            }
+            rollup
+        })
+        .collect()
+}
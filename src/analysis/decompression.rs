@@ -0,0 +1,101 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{eyre, Context};
+use parquet2::metadata::ColumnChunkMetaData;
+
+use crate::{
+    codecs::{codec_name, codec_support},
+    pages::{decompress_page, page_type_name, read_page_headers, read_page_payload},
+    parquet::chunk_data_path,
+    source::FileSource,
+};
+
+/// Time taken to decompress a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageTiming {
+    /// File offset of the page header.
+    pub offset: u64,
+    pub page_type: &'static str,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub duration: Duration,
+}
+
+impl PageTiming {
+    /// Decompressed bytes per compressed byte.
+    pub fn ratio(&self) -> f64 {
+        self.uncompressed_size as f64 / self.compressed_size.max(1) as f64
+    }
+
+    /// Decompressed megabytes (10^6 bytes) per second.
+    pub fn throughput(&self) -> f64 {
+        self.uncompressed_size as f64 / 1e6 / self.duration.as_secs_f64().max(1e-9)
+    }
+}
+
+/// Decompression timings of every page of a column chunk, to compare the cost of codecs on
+/// real data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecompressionProfile {
+    pub column: String,
+    pub codec: &'static str,
+    pub pages: Vec<PageTiming>,
+}
+
+impl DecompressionProfile {
+    /// Timing of the whole chunk, as the sum of its pages.
+    pub fn total(&self) -> PageTiming {
+        PageTiming {
+            offset: self.pages.first().map_or(0, |page| page.offset),
+            page_type: "all",
+            compressed_size: self.pages.iter().map(|page| page.compressed_size).sum(),
+            uncompressed_size: self.pages.iter().map(|page| page.uncompressed_size).sum(),
+            duration: self.pages.iter().map(|page| page.duration).sum(),
+        }
+    }
+}
+
+/// Decompress every page of a column chunk, timing each. Payloads are read before timing
+/// starts, and around the page cache, so that only decompression is measured.
+pub fn profile_decompression(
+    path: &Path,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<DecompressionProfile> {
+    let support = codec_support(chunk.compression());
+    if !support.is_supported() {
+        return Err(eyre!("the chunk's codec is {}", support));
+    }
+
+    let data_path = chunk_data_path(path, chunk.file_path().as_deref());
+    let mut reader = FileSource::open(&data_path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    let mut payloads = Vec::new();
+    for page in read_page_headers(&mut reader, chunk)? {
+        let payload = read_page_payload(&mut reader, &page)?;
+        payloads.push((page, payload));
+    }
+
+    let mut pages = Vec::new();
+    for (page, payload) in payloads {
+        let start = Instant::now();
+        decompress_page(&page, &payload, chunk.compression())
+            .wrap_err_with(|| format!("failed to decompress the page at offset {}", page.offset))?;
+        pages.push(PageTiming {
+            offset: page.offset,
+            page_type: page_type_name(page.page_type()),
+            compressed_size: page.compressed_size(),
+            uncompressed_size: page.uncompressed_size(),
+            duration: start.elapsed(),
+        });
+    }
+
+    Ok(DecompressionProfile {
+        column: chunk.descriptor().path_in_schema.join("."),
+        codec: codec_name(chunk.compression()),
+        pages,
+    })
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use color_eyre::eyre::Context;
+use parquet2::metadata::ColumnChunkMetaData;
+use parquet_format_safe::{Encoding, PageType};
+
+use crate::{
+    pages::{encoding_name, read_page_headers},
+    parquet::chunk_data_path,
+    source::FileSource,
+};
+
+/// A column chunk whose data pages started out dictionary-encoded and switched to another
+/// encoding partway, which writers do when the dictionary outgrows its size limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryFallback {
+    pub data_pages: usize,
+    /// Data pages that are not dictionary-encoded.
+    pub fallback_pages: usize,
+    /// Encoding of the fallback pages, e.g. PLAIN.
+    pub fallback_encoding: String,
+    /// Index among the data pages of the first fallback page, when known from the page
+    /// headers rather than the footer's encoding stats.
+    pub first_fallback_page: Option<usize>,
+}
+
+impl DictionaryFallback {
+    /// Which data pages fell back, e.g. `PLAIN on 18 of 20 data pages from data page 2`.
+    pub fn summary(&self) -> String {
+        let from = self
+            .first_fallback_page
+            .map(|page| format!(" from data page {}", page))
+            .unwrap_or_default();
+        format!(
+            "{} on {} of {} data pages{}",
+            self.fallback_encoding, self.fallback_pages, self.data_pages, from
+        )
+    }
+}
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+    )
+}
+
+/// Tally the encodings of a chunk's data pages, in file order, into a fallback if there are
+/// both dictionary-encoded and other data pages.
+fn fallback_of(
+    encodings: impl Iterator<Item = (Encoding, usize)>,
+    ordered: bool,
+) -> Option<DictionaryFallback> {
+    let mut data_pages = 0;
+    let mut dictionary_pages = 0;
+    let mut fallback_encoding = None;
+    let mut first_fallback_page = None;
+    for (encoding, count) in encodings {
+        if is_dictionary_encoding(encoding) {
+            dictionary_pages += count;
+        } else {
+            fallback_encoding.get_or_insert(encoding);
+            first_fallback_page.get_or_insert(data_pages);
+        }
+        data_pages += count;
+    }
+
+    let fallback_encoding = fallback_encoding?;
+    (dictionary_pages > 0).then(|| DictionaryFallback {
+        data_pages,
+        fallback_pages: data_pages - dictionary_pages,
+        fallback_encoding: encoding_name(fallback_encoding),
+        first_fallback_page: first_fallback_page.filter(|_| ordered),
+    })
+}
+
+/// Detect a dictionary fallback in a column chunk, from the footer's page encoding stats when
+/// the writer recorded them, and otherwise from the page headers.
+pub fn detect_fallback(
+    path: &Path,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<Option<DictionaryFallback>> {
+    if chunk.dictionary_page_offset().is_none() {
+        return Ok(None);
+    }
+
+    if let Some(stats) = &chunk.metadata().encoding_stats {
+        let data_pages = stats.iter().filter(|stats| {
+            matches!(
+                stats.page_type,
+                PageType::DATA_PAGE | PageType::DATA_PAGE_V2
+            )
+        });
+        return Ok(fallback_of(
+            data_pages.map(|stats| (stats.encoding, stats.count.max(0) as usize)),
+            false,
+        ));
+    }
+
+    let data_path = chunk_data_path(path, chunk.file_path().as_deref());
+    let mut reader = FileSource::open(&data_path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    let pages = read_page_headers(&mut reader, chunk)?;
+    Ok(fallback_of(
+        pages
+            .iter()
+            .filter(|page| page.is_data())
+            .filter_map(|page| Some((page.encoding()?, 1))),
+        true,
+    ))
+}
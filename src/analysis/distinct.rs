@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use parquet2::bloom_filter::{hash_byte, hash_native};
+
+use crate::parquet::{StatValue, ValueStream};
+
+/// Bits of the hash that pick a register: 2^14 registers, 16 KiB of state for a standard error
+/// of 0.8%.
+const PRECISION: u32 = 14;
+
+const REGISTERS: usize = 1 << PRECISION;
+
+/// Standard errors between the estimate and its reported bounds, for about 95% confidence.
+const CONFIDENCE: f64 = 2.0;
+
+/// A HyperLogLog sketch: the longest run of leading zeros seen in the hashes of each register.
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0; REGISTERS],
+        }
+    }
+
+    fn insert(&mut self, hash: u64) {
+        let register = (hash >> (64 - PRECISION)) as usize;
+        let rank = (hash << PRECISION | 1 << (PRECISION - 1)).leading_zeros() as u8 + 1;
+        self.registers[register] = self.registers[register].max(rank);
+    }
+
+    /// Estimated number of distinct hashes inserted, by linear counting of the empty registers
+    /// while many are empty, where the raw estimate is biased.
+    fn estimate(&self) -> f64 {
+        let m = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-(rank as i32)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let empty = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if raw <= 2.5 * m && empty > 0 {
+            m * (m / empty as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+/// Approximate number of distinct values of a column chunk. Writers rarely fill in the
+/// `distinct_count` of the footer statistics, though it tells whether a column suits
+/// dictionary encoding or a bloom filter.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DistinctEstimate {
+    /// Number of non-null values streamed through the sketch.
+    pub values: u64,
+    pub estimate: u64,
+    /// Relative error of the bounds, e.g. `0.016` for ±1.6%.
+    pub error: f64,
+}
+
+impl DistinctEstimate {
+    /// Bounds of the estimate at about 95% confidence, never above the number of values.
+    pub fn bounds(&self) -> (u64, u64) {
+        let margin = self.estimate as f64 * self.error;
+        (
+            (self.estimate as f64 - margin).max(0.0).floor() as u64,
+            ((self.estimate as f64 + margin).ceil() as u64).min(self.values),
+        )
+    }
+}
+
+fn hash(value: &StatValue) -> u64 {
+    match value {
+        StatValue::Boolean(b) => hash_native(i32::from(*b)),
+        StatValue::Int(i) => hash_native(*i),
+        StatValue::Float(x) => hash_native(*x),
+        StatValue::Bytes(bytes) => hash_byte(bytes),
+    }
+}
+
+/// Stream every value of a column chunk through a HyperLogLog sketch and estimate its
+/// number of distinct values. Nulls are not counted, as in footer statistics.
+pub fn estimate_distinct<P: AsRef<Path>>(
+    path: P,
+    row_group: usize,
+    column: usize,
+) -> color_eyre::Result<DistinctEstimate> {
+    let mut sketch = HyperLogLog::new();
+    let mut values = 0;
+    for batch in ValueStream::open(path.as_ref(), row_group, column, usize::MAX)? {
+        for value in batch? {
+            values += 1;
+            sketch.insert(hash(&value));
+        }
+    }
+    let estimate = (sketch.estimate().round() as u64).min(values);
+    Ok(DistinctEstimate {
+        values,
+        estimate,
+        error: CONFIDENCE * 1.04 / (REGISTERS as f64).sqrt(),
+    })
+}
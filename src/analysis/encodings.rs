@@ -0,0 +1,269 @@
+use std::{collections::HashMap, path::Path};
+
+use parquet2::{
+    compression::{Compression, CompressionOptions},
+    encoding::{delta_bitpacked, hybrid_rle, Encoding},
+    metadata::FileMetaData,
+    schema::types::PhysicalType,
+};
+
+use crate::{
+    pages::encoding_name,
+    parquet::{StatValue, ValueStream},
+};
+
+/// Estimated size of a column's sampled values under an alternative encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingEstimate {
+    pub encoding: Encoding,
+    /// Size of the sampled values once encoded and compressed with the column's codec.
+    pub bytes: usize,
+    /// Fraction of the current size saved, negative when the encoding is larger.
+    pub savings: f64,
+}
+
+/// Alternative encodings of a column, sorted by estimated savings, best first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnEncodings {
+    pub column: String,
+    /// Encodings the column is currently written with, as listed in the footer.
+    pub current_encodings: Vec<String>,
+    /// Encoding of the column's values, which the candidates are compared with.
+    pub current: Encoding,
+    pub sampled: usize,
+    /// Estimated size of the sampled values in the current encoding.
+    pub current_bytes: usize,
+    /// The other encodings that apply to the column.
+    pub candidates: Vec<EncodingEstimate>,
+}
+
+impl ColumnEncodings {
+    /// Best candidate, if it saves at least `threshold` of the current size.
+    pub fn suggestion(&self, threshold: f64) -> Option<&EncodingEstimate> {
+        self.candidates
+            .first()
+            .filter(|candidate| candidate.savings >= threshold)
+    }
+}
+
+/// PLAIN encoding of values of the given physical type.
+fn plain(values: &[StatValue], physical_type: PhysicalType) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    if physical_type == PhysicalType::Boolean {
+        let bits = values.iter().map(|v| matches!(v, StatValue::Boolean(true)));
+        hybrid_rle::encode_bool(&mut buffer, bits).unwrap();
+        return buffer;
+    }
+    for value in values {
+        plain_value(value, physical_type, &mut buffer);
+    }
+    buffer
+}
+
+fn plain_value(value: &StatValue, physical_type: PhysicalType, buffer: &mut Vec<u8>) {
+    match (value, physical_type) {
+        (StatValue::Int(i), PhysicalType::Int32) => {
+            buffer.extend_from_slice(&(*i as i32).to_le_bytes())
+        }
+        (StatValue::Int(i), _) => buffer.extend_from_slice(&i.to_le_bytes()),
+        (StatValue::Float(x), PhysicalType::Float) => {
+            buffer.extend_from_slice(&(*x as f32).to_le_bytes())
+        }
+        (StatValue::Float(x), _) => buffer.extend_from_slice(&x.to_le_bytes()),
+        (StatValue::Bytes(bytes), PhysicalType::ByteArray) => {
+            buffer.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(bytes);
+        }
+        (StatValue::Bytes(bytes), _) => buffer.extend_from_slice(bytes),
+        (StatValue::Boolean(b), _) => buffer.push(u8::from(*b)),
+    }
+}
+
+/// RLE_DICTIONARY encoding: the PLAIN dictionary page and the bit-packed indices.
+fn dictionary(values: &[StatValue], physical_type: PhysicalType) -> (Vec<u8>, Vec<u8>) {
+    let mut dictionary = Vec::new();
+    let mut entries: HashMap<Vec<u8>, u32> = HashMap::new();
+    let mut indices = Vec::with_capacity(values.len());
+    for value in values {
+        let mut key = Vec::new();
+        plain_value(value, physical_type, &mut key);
+        let next = entries.len() as u32;
+        let index = *entries.entry(key).or_insert_with_key(|key| {
+            dictionary.extend_from_slice(key);
+            next
+        });
+        indices.push(index);
+    }
+
+    let bit_width = 32 - (entries.len().saturating_sub(1) as u32).leading_zeros();
+    let mut encoded = vec![bit_width as u8];
+    hybrid_rle::encode_u32(&mut encoded, indices.into_iter(), bit_width).unwrap();
+    (dictionary, encoded)
+}
+
+/// BYTE_STREAM_SPLIT encoding: the k-th bytes of every value, for each byte position k.
+fn byte_stream_split(plain: &[u8], width: usize) -> Vec<u8> {
+    let count = plain.len() / width;
+    let mut split = vec![0; plain.len()];
+    for (i, value) in plain.chunks_exact(width).enumerate() {
+        for (k, byte) in value.iter().enumerate() {
+            split[k * count + i] = *byte;
+        }
+    }
+    split
+}
+
+fn compression_options(compression: Compression) -> Option<CompressionOptions> {
+    Some(match compression {
+        Compression::Snappy => CompressionOptions::Snappy,
+        Compression::Gzip => CompressionOptions::Gzip(None),
+        Compression::Brotli => CompressionOptions::Brotli(None),
+        Compression::Lz4 | Compression::Lz4Raw => CompressionOptions::Lz4Raw,
+        Compression::Zstd => CompressionOptions::Zstd(None),
+        Compression::Uncompressed | Compression::Lzo => return None,
+    })
+}
+
+/// Size of a buffer once compressed with the given codec.
+fn compressed_len(buffer: &[u8], compression: Option<CompressionOptions>) -> usize {
+    let Some(compression) = compression else {
+        return buffer.len();
+    };
+    let mut output = Vec::new();
+    match parquet2::compression::compress(compression, buffer, &mut output) {
+        Ok(()) => output.len(),
+        Err(_) => buffer.len(),
+    }
+}
+
+/// Estimate how alternative encodings would change the size of every column, sampling up to
+/// `sample` values from each column chunk.
+///
+/// Candidates are PLAIN and RLE_DICTIONARY for every column, DELTA_BINARY_PACKED for
+/// integers and BYTE_STREAM_SPLIT for floating point numbers. Each is encoded from the
+/// sample and compressed with the column's codec, and compared with the current encoding
+/// estimated the same way, so that levels and page headers do not skew the comparison.
+pub fn analyze<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    sample: usize,
+) -> color_eyre::Result<Vec<ColumnEncodings>> {
+    let mut columns = Vec::new();
+
+    for (column, descriptor) in metadata.schema().columns().iter().enumerate() {
+        let physical_type = descriptor.descriptor.primitive_type.physical_type;
+        if physical_type == PhysicalType::Int96 {
+            continue;
+        }
+
+        let mut values = Vec::new();
+        let mut encodings: Vec<Encoding> = Vec::new();
+        let mut dictionary_encoded = false;
+        let mut compression = None;
+        for (row_group, metadata) in metadata.row_groups.iter().enumerate() {
+            let chunk = &metadata.columns()[column];
+            compression.get_or_insert(chunk.compression());
+            dictionary_encoded |= chunk.dictionary_page_offset().is_some();
+            for encoding in chunk.column_encoding() {
+                if let Ok(encoding) = Encoding::try_from(*encoding) {
+                    if !encodings.contains(&encoding) {
+                        encodings.push(encoding);
+                    }
+                }
+            }
+
+            for batch in ValueStream::open(path.as_ref(), row_group, column, sample)? {
+                values.extend(batch?);
+            }
+        }
+        if values.is_empty() {
+            continue;
+        }
+        let compression = compression.and_then(compression_options);
+        let current = if dictionary_encoded {
+            Encoding::RleDictionary
+        } else {
+            [Encoding::DeltaBinaryPacked, Encoding::ByteStreamSplit]
+                .into_iter()
+                .find(|encoding| encodings.contains(encoding))
+                .unwrap_or(Encoding::Plain)
+        };
+
+        let plain = plain(&values, physical_type);
+        let mut candidates = vec![(Encoding::Plain, compressed_len(&plain, compression))];
+        if physical_type != PhysicalType::Boolean {
+            let (dictionary, indices) = dictionary(&values, physical_type);
+            candidates.push((
+                Encoding::RleDictionary,
+                compressed_len(&dictionary, compression) + compressed_len(&indices, compression),
+            ));
+        }
+        if matches!(physical_type, PhysicalType::Int32 | PhysicalType::Int64) {
+            let mut encoded = Vec::new();
+            delta_bitpacked::encode(
+                values.iter().map(|v| match v {
+                    StatValue::Int(i) => *i,
+                    _ => 0,
+                }),
+                &mut encoded,
+            );
+            candidates.push((
+                Encoding::DeltaBinaryPacked,
+                compressed_len(&encoded, compression),
+            ));
+        }
+        if matches!(physical_type, PhysicalType::Float | PhysicalType::Double) {
+            let width = if physical_type == PhysicalType::Float {
+                4
+            } else {
+                8
+            };
+            let split = byte_stream_split(&plain, width);
+            candidates.push((
+                Encoding::ByteStreamSplit,
+                compressed_len(&split, compression),
+            ));
+        }
+
+        // Columns in an encoding that is not estimated are compared with PLAIN.
+        let current = if candidates.iter().any(|(encoding, _)| *encoding == current) {
+            current
+        } else {
+            Encoding::Plain
+        };
+        let current_bytes = candidates
+            .iter()
+            .find(|(encoding, _)| *encoding == current)
+            .map(|(_, bytes)| *bytes)
+            .unwrap_or_default();
+
+        let mut candidates: Vec<EncodingEstimate> = candidates
+            .into_iter()
+            .filter(|(encoding, _)| *encoding != current)
+            .map(|(encoding, bytes)| EncodingEstimate {
+                encoding,
+                bytes,
+                savings: if current_bytes > 0 {
+                    1.0 - bytes as f64 / current_bytes as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+        candidates.sort_by_key(|candidate| candidate.bytes);
+
+        columns.push(ColumnEncodings {
+            column: descriptor.path_in_schema.join("."),
+            current_encodings: encodings
+                .into_iter()
+                .map(|encoding| encoding_name(encoding.into()))
+                .collect(),
+            current,
+            sampled: values.len(),
+            current_bytes,
+            candidates,
+        });
+    }
+
+    Ok(columns)
+}
@@ -0,0 +1,223 @@
+use std::{
+    io::{Read, Seek},
+    path::Path,
+};
+
+use bytes::Bytes;
+use color_eyre::eyre::{eyre, Context};
+use parquet2::{
+    encoding::hybrid_rle::HybridRleDecoder,
+    metadata::{ColumnChunkMetaData, ColumnDescriptor},
+    schema::{types::ParquetType, Repetition},
+};
+use parquet_format_safe::Encoding;
+
+use crate::{
+    codecs::codec_support,
+    pages::{
+        level_lengths, read_decompressed_page, read_page_headers, read_page_payload, PageInfo,
+    },
+    parquet::chunk_data_path,
+    source::FileSource,
+};
+
+/// Number of values of a column chunk at a definition or repetition level, and what the level
+/// means for them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelCount {
+    pub level: i16,
+    pub count: u64,
+    pub meaning: String,
+}
+
+/// Definition and repetition levels observed in the data pages of a column chunk, to debug
+/// writers that produce unexpected nullability structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelDistribution {
+    pub column: String,
+    pub max_def_level: i16,
+    pub max_rep_level: i16,
+    /// Values counted, nulls and empty lists included.
+    pub values: u64,
+    /// Counts of every level from 0 to the maximum, including unobserved levels.
+    pub definition: Vec<LevelCount>,
+    pub repetition: Vec<LevelCount>,
+}
+
+/// Fields along the path of a column, from the top-level field to the leaf, with their
+/// repetition.
+fn path_repetitions(descriptor: &ColumnDescriptor) -> Vec<(String, Repetition)> {
+    let mut fields = Vec::new();
+    let mut node = Some(&descriptor.base_type);
+    let mut path = descriptor.path_in_schema.iter().skip(1);
+    while let Some(current) = node {
+        fields.push((
+            current.name().to_string(),
+            current.get_field_info().repetition,
+        ));
+        node = match current {
+            ParquetType::GroupType { fields, .. } => path
+                .next()
+                .and_then(|name| fields.iter().find(|field| field.name() == name)),
+            ParquetType::PrimitiveType(_) => None,
+        };
+    }
+    fields
+}
+
+/// What each definition and repetition level of a column means, indexed by level. Every
+/// optional or repeated field along the path adds a definition level, every repeated field a
+/// repetition level.
+pub fn level_meanings(descriptor: &ColumnDescriptor) -> (Vec<String>, Vec<String>) {
+    let mut definition = Vec::new();
+    let mut repetition = vec!["first value of a record".to_string()];
+    let mut path = Vec::new();
+    for (name, field_repetition) in path_repetitions(descriptor) {
+        path.push(name);
+        let dotted = path.join(".");
+        match field_repetition {
+            Repetition::Required => {}
+            Repetition::Optional => definition.push(format!("{} is null", dotted)),
+            Repetition::Repeated => {
+                definition.push(format!("{} is empty", dotted));
+                repetition.push(format!("next element of {}", dotted));
+            }
+        }
+    }
+    definition.push("value present".to_string());
+    (definition, repetition)
+}
+
+/// Decode `num_values` levels of at most `max_level`, encoded with the RLE/bit-packing hybrid,
+/// adding them to `counts`.
+fn count_levels(
+    data: &[u8],
+    max_level: i16,
+    num_values: usize,
+    counts: &mut [u64],
+) -> color_eyre::Result<()> {
+    let bit_width = 16 - (max_level as u16).leading_zeros();
+    for level in HybridRleDecoder::try_new(data, bit_width, num_values)? {
+        let level = level? as usize;
+        *counts
+            .get_mut(level)
+            .ok_or_else(|| eyre!("level {} exceeds the maximum level {}", level, max_level))? += 1;
+    }
+    Ok(())
+}
+
+/// Count the repetition and definition levels of a data page.
+fn count_page_levels<R: Read + Seek>(
+    path: &Path,
+    reader: &mut R,
+    chunk: &ColumnChunkMetaData,
+    page: &PageInfo,
+    rep_counts: &mut [u64],
+    def_counts: &mut [u64],
+) -> color_eyre::Result<()> {
+    let descriptor = &chunk.descriptor().descriptor;
+    let (max_rep_level, max_def_level) = (descriptor.max_rep_level, descriptor.max_def_level);
+    let num_values = page.num_values().max(0) as usize;
+
+    // Levels of v2 pages are stored uncompressed, and without a length prefix.
+    let (levels, prefix) = match &page.header.data_page_header_v2 {
+        Some(_) => (Bytes::from(read_page_payload(reader, page)?), 0),
+        None => {
+            let header = page.header.data_page_header.as_ref().unwrap();
+            if [
+                header.repetition_level_encoding,
+                header.definition_level_encoding,
+            ]
+            .contains(&Encoding::BIT_PACKED)
+            {
+                return Err(eyre!("levels use the deprecated BIT_PACKED encoding"));
+            }
+            (
+                read_decompressed_page(path, reader, page, chunk.compression())?,
+                4,
+            )
+        }
+    };
+    let (rep_length, def_length) = level_lengths(page, &levels, max_rep_level, max_def_level);
+    let (rep_length, def_length) = (rep_length as usize, def_length as usize);
+    if rep_length + def_length > levels.len() {
+        return Err(eyre!("page level lengths exceed the page size"));
+    }
+
+    if max_rep_level > 0 {
+        let data = &levels[prefix.min(rep_length)..rep_length];
+        count_levels(data, max_rep_level, num_values, rep_counts)?;
+    }
+    if max_def_level > 0 {
+        let data = &levels[rep_length + prefix.min(def_length)..rep_length + def_length];
+        count_levels(data, max_def_level, num_values, def_counts)?;
+    }
+    Ok(())
+}
+
+/// Decode the definition and repetition levels of every data page of a column chunk.
+pub fn level_distribution(
+    path: &Path,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<LevelDistribution> {
+    let support = codec_support(chunk.compression());
+    if !support.is_supported() {
+        return Err(eyre!("the chunk's codec is {}", support));
+    }
+
+    let descriptor = &chunk.descriptor().descriptor;
+    let (max_rep_level, max_def_level) = (descriptor.max_rep_level, descriptor.max_def_level);
+    let mut rep_counts = vec![0; max_rep_level.max(0) as usize + 1];
+    let mut def_counts = vec![0; max_def_level.max(0) as usize + 1];
+    let mut values = 0;
+
+    let data_path = chunk_data_path(path, chunk.file_path().as_deref());
+    let mut reader = FileSource::open(&data_path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    for page in read_page_headers(&mut reader, chunk)? {
+        if !page.is_data() {
+            continue;
+        }
+        values += page.num_values().max(0) as u64;
+        count_page_levels(
+            &data_path,
+            &mut reader,
+            chunk,
+            &page,
+            &mut rep_counts,
+            &mut def_counts,
+        )
+        .wrap_err_with(|| format!("failed to decode the levels of the page at {}", page.offset))?;
+    }
+
+    // Levels are not stored when their maximum is 0: every value is at level 0.
+    if max_rep_level == 0 {
+        rep_counts[0] = values;
+    }
+    if max_def_level == 0 {
+        def_counts[0] = values;
+    }
+
+    let (def_meanings, rep_meanings) = level_meanings(chunk.descriptor());
+    let counts = |counts: Vec<u64>, meanings: Vec<String>| {
+        counts
+            .into_iter()
+            .zip(meanings)
+            .enumerate()
+            .map(|(level, (count, meaning))| LevelCount {
+                level: level as i16,
+                count,
+                meaning,
+            })
+            .collect()
+    };
+    Ok(LevelDistribution {
+        column: chunk.descriptor().path_in_schema.join("."),
+        max_def_level,
+        max_rep_level,
+        values,
+        definition: counts(def_counts, def_meanings),
+        repetition: counts(rep_counts, rep_meanings),
+    })
+}
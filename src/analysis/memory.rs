@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use parquet2::{
+    metadata::{ColumnChunkMetaData, FileMetaData},
+    schema::types::{PhysicalType, PrimitiveLogicalType},
+};
+
+use crate::parquet::{ColumnChunkMetaDataExt, StatValue, ValueStream};
+
+/// Values sampled from each BYTE_ARRAY column chunk to estimate its average value length.
+pub const DEFAULT_SAMPLE: usize = 1000;
+
+/// Estimated decoded, in-memory (Arrow) footprint of a column chunk, next to its on-disk sizes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    pub column: String,
+    pub compressed_bytes: i64,
+    pub uncompressed_bytes: i64,
+    pub in_memory_bytes: u64,
+    /// Whether the average value length of a BYTE_ARRAY chunk came from sampled values rather
+    /// than its uncompressed size.
+    pub sampled: bool,
+}
+
+/// Width in bytes of a value of a fixed-width column once decoded into Arrow, or `None` for
+/// variable-length BYTE_ARRAY columns. INT96 decodes into 64-bit timestamps and decimals stored
+/// as FIXED_LEN_BYTE_ARRAY into 128-bit decimals.
+fn arrow_width(chunk: &ColumnChunkMetaData) -> Option<f64> {
+    let primitive_type = &chunk.descriptor().descriptor.primitive_type;
+    Some(match primitive_type.physical_type {
+        PhysicalType::Boolean => 1.0 / 8.0,
+        PhysicalType::Int32 | PhysicalType::Float => 4.0,
+        PhysicalType::Int64 | PhysicalType::Double | PhysicalType::Int96 => 8.0,
+        PhysicalType::FixedLenByteArray(_)
+            if matches!(
+                primitive_type.logical_type,
+                Some(PrimitiveLogicalType::Decimal(..))
+            ) =>
+        {
+            16.0
+        }
+        PhysicalType::FixedLenByteArray(length) => length as f64,
+        PhysicalType::ByteArray => return None,
+    })
+}
+
+/// Estimate the in-memory size of a column chunk from its type and value count. BYTE_ARRAY
+/// values take their data plus a 4-byte offset each, with the average length taken from up to
+/// `sample` values, or from the uncompressed size when they cannot be read. Nullable columns
+/// add a validity bitmap and repeated columns a 4-byte list offset per value.
+pub fn estimate_chunk(
+    path: &Path,
+    chunk: &ColumnChunkMetaData,
+    row_group: usize,
+    column: usize,
+    sample: usize,
+) -> MemoryEstimate {
+    let descriptor = &chunk.descriptor().descriptor;
+    let num_values = chunk.num_values().max(0) as f64;
+    let non_null = match chunk.stats().null_count {
+        Some(nulls) => (num_values - nulls as f64).max(0.0),
+        None => num_values,
+    };
+
+    let mut sampled = false;
+    let mut bytes = match arrow_width(chunk) {
+        Some(width) => num_values * width,
+        None => {
+            let data = average_length(path, row_group, column, sample)
+                .map(|average| {
+                    sampled = true;
+                    average * non_null
+                })
+                .unwrap_or(chunk.uncompressed_size() as f64);
+            data + 4.0 * (num_values + 1.0)
+        }
+    };
+    if descriptor.max_def_level > 0 {
+        bytes += (num_values / 8.0).ceil();
+    }
+    if descriptor.max_rep_level > 0 {
+        bytes += 4.0 * num_values;
+    }
+
+    MemoryEstimate {
+        column: chunk.descriptor().path_in_schema.join("."),
+        compressed_bytes: chunk.compressed_size(),
+        uncompressed_bytes: chunk.uncompressed_size(),
+        in_memory_bytes: bytes.ceil() as u64,
+        sampled,
+    }
+}
+
+/// Average length of up to `sample` non-null values of a BYTE_ARRAY column chunk, or `None`
+/// when its values cannot be read.
+fn average_length(path: &Path, row_group: usize, column: usize, sample: usize) -> Option<f64> {
+    let mut count = 0;
+    let mut total = 0;
+    for batch in ValueStream::open(path, row_group, column, sample).ok()? {
+        for value in batch.ok()? {
+            if let StatValue::Bytes(bytes) = value {
+                count += 1;
+                total += bytes.len();
+            }
+        }
+    }
+    Some(if count > 0 {
+        total as f64 / count as f64
+    } else {
+        0.0
+    })
+}
+
+/// Estimate the in-memory size of every column chunk of a row group.
+pub fn estimate_row_group(
+    path: &Path,
+    metadata: &FileMetaData,
+    row_group: usize,
+    sample: usize,
+) -> Vec<MemoryEstimate> {
+    metadata.row_groups[row_group]
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(column, chunk)| estimate_chunk(path, chunk, row_group, column, sample))
+        .collect()
+}
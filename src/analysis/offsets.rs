@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use parquet::file::{footer::parse_metadata, page_index::index_reader::read_pages_locations};
+use parquet2::metadata::FileMetaData;
+
+use crate::{
+    pages::{page_type_name, read_page_headers},
+    source::FileSource,
+};
+
+/// Part of a Parquet file covering a byte offset.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OffsetLocation {
+    /// The `PAR1` magic bytes at the start of the file.
+    LeadingMagic,
+    /// A column chunk, and the page within it when its headers could be read.
+    Chunk(ChunkHit),
+    /// The footer: the serialized metadata, its length and the trailing magic bytes.
+    Footer,
+    /// Bytes between column chunks that no chunk claims, e.g. page indexes or bloom filters.
+    Unclaimed,
+    /// Past the end of the file.
+    PastEnd,
+}
+
+/// Column chunk covering an offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkHit {
+    pub row_group: usize,
+    pub column: usize,
+    pub column_path: String,
+    /// Byte range of the chunk, end exclusive.
+    pub chunk_start: u64,
+    pub chunk_end: u64,
+    pub page: Option<PageHit>,
+    /// Global rows of the page when they are known, otherwise of the row group, end
+    /// exclusive.
+    pub rows: (u64, u64),
+    /// Whether `rows` is the range of the page rather than of the whole row group.
+    pub page_rows: bool,
+}
+
+/// Page covering an offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageHit {
+    /// Index of the page within the chunk, counting dictionary pages.
+    pub index: usize,
+    pub page_type: &'static str,
+    /// Offset of the page header.
+    pub offset: u64,
+    pub header_size: u64,
+    pub compressed_size: u64,
+    /// Whether the offset falls in the page header rather than its payload.
+    in_header: bool,
+}
+
+impl PageHit {
+    pub fn part(&self) -> &'static str {
+        if self.in_header {
+            "header"
+        } else {
+            "payload"
+        }
+    }
+}
+
+/// Global row ranges of the data pages of a chunk, from the offset index, keyed by page
+/// offset. Empty when the file has no offset index.
+fn page_row_ranges(
+    path: &Path,
+    row_group: usize,
+    column: usize,
+    row_group_rows: u64,
+) -> color_eyre::Result<Vec<(u64, (u64, u64))>> {
+    let source = FileSource::open(path)?;
+    let metadata = parse_metadata(&source)?;
+    let chunks = metadata.row_group(row_group).columns();
+    let Some(locations) = read_pages_locations(&source, chunks)?
+        .into_iter()
+        .nth(column)
+    else {
+        return Ok(Vec::new());
+    };
+
+    let starts: Vec<u64> = locations
+        .iter()
+        .map(|location| location.first_row_index.max(0) as u64)
+        .collect();
+    Ok(locations
+        .iter()
+        .enumerate()
+        .map(|(i, location)| {
+            let end = starts.get(i + 1).copied().unwrap_or(row_group_rows);
+            (location.offset.max(0) as u64, (starts[i], end))
+        })
+        .collect())
+}
+
+/// Find the row group, column chunk and page covering a byte offset of a file, and the
+/// global rows they hold.
+///
+/// Only chunks stored in the file itself are considered, not those in external files. Page
+/// row ranges come from the offset index; without one, the rows of the whole row group are
+/// reported.
+pub fn locate_offset(
+    path: &Path,
+    metadata: &FileMetaData,
+    offset: u64,
+) -> color_eyre::Result<OffsetLocation> {
+    let file_size = crate::source::file_size(path)?;
+    if offset >= file_size {
+        return Ok(OffsetLocation::PastEnd);
+    }
+    if offset < 4 {
+        return Ok(OffsetLocation::LeadingMagic);
+    }
+
+    let mut first_row = 0;
+    for (row_group, metadata) in metadata.row_groups.iter().enumerate() {
+        let rows = metadata.num_rows() as u64;
+        for (column, chunk) in metadata.columns().iter().enumerate() {
+            if chunk.file_path().is_some() {
+                continue;
+            }
+            let (start, length) = chunk.byte_range();
+            if !(start..start + length).contains(&offset) {
+                continue;
+            }
+
+            let mut reader = FileSource::open(path)?.reader()?;
+            let page = read_page_headers(&mut reader, chunk)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .find(|(_, page)| {
+                    (page.offset..page.data_offset() + page.compressed_size()).contains(&offset)
+                })
+                .map(|(index, page)| PageHit {
+                    index,
+                    page_type: page_type_name(page.page_type()),
+                    offset: page.offset,
+                    header_size: page.header_size,
+                    compressed_size: page.compressed_size(),
+                    in_header: offset < page.data_offset(),
+                });
+
+            let page_rows = page.as_ref().and_then(|page| {
+                page_row_ranges(path, row_group, column, rows)
+                    .ok()?
+                    .into_iter()
+                    .find(|(page_offset, _)| *page_offset == page.offset)
+                    .map(|(_, rows)| rows)
+            });
+            let (rows, page_rows) = match page_rows {
+                Some((start, end)) => ((first_row + start, first_row + end), true),
+                None => ((first_row, first_row + rows), false),
+            };
+
+            return Ok(OffsetLocation::Chunk(ChunkHit {
+                row_group,
+                column,
+                column_path: chunk.descriptor().path_in_schema.join("."),
+                chunk_start: start,
+                chunk_end: start + length,
+                page,
+                rows,
+                page_rows,
+            }));
+        }
+        first_row += rows;
+    }
+
+    // The footer follows the last chunk, possibly after page indexes and bloom filters.
+    let footer_len = {
+        let mut reader = FileSource::open(path)?.reader()?;
+        crate::cache::read_footer_bytes(&mut reader)?.len() as u64
+    };
+    if offset >= file_size - footer_len - 8 {
+        return Ok(OffsetLocation::Footer);
+    }
+    Ok(OffsetLocation::Unclaimed)
+}
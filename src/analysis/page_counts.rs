@@ -0,0 +1,93 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use color_eyre::eyre::Context;
+use parquet2::metadata::ColumnChunkMetaData;
+use parquet_format_safe::{thrift::protocol::TCompactInputProtocol, OffsetIndex};
+
+use crate::{pages::read_page_headers, parquet::chunk_data_path, source::FileSource};
+
+/// Where the page counts of a column chunk come from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PageCountSource {
+    /// The page locations of the offset index, plus the dictionary page the footer declares.
+    OffsetIndex,
+    /// A scan of the chunk's page headers.
+    PageHeaders,
+}
+
+impl PageCountSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageCountSource::OffsetIndex => "offset index",
+            PageCountSource::PageHeaders => "page headers",
+        }
+    }
+}
+
+/// Number of data and dictionary pages of a column chunk. Many small pages make readers pay
+/// for page headers and decoder setup, while few large ones defeat page-level pruning.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PageCounts {
+    pub data_pages: usize,
+    pub dictionary_pages: usize,
+    pub source: PageCountSource,
+}
+
+impl PageCounts {
+    /// Short form for the column browser, e.g. `12 pages +dict`.
+    pub fn badge(&self) -> String {
+        let pages = match self.data_pages {
+            1 => "1 page".to_string(),
+            n => format!("{} pages", n),
+        };
+        match self.dictionary_pages {
+            0 => pages,
+            _ => format!("{} +dict", pages),
+        }
+    }
+}
+
+/// Read the offset index at `offset`, of `length` bytes.
+fn read_offset_index<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    length: usize,
+) -> color_eyre::Result<OffsetIndex> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut protocol = TCompactInputProtocol::new(&mut *reader, length);
+    Ok(OffsetIndex::read_from_in_protocol(&mut protocol)?)
+}
+
+/// Count the pages of a column chunk, from its offset index when the file has one, and
+/// otherwise by scanning its page headers.
+pub fn count_pages(path: &Path, chunk: &ColumnChunkMetaData) -> color_eyre::Result<PageCounts> {
+    let column_chunk = chunk.column_chunk();
+    if let (Some(offset), Some(length)) = (
+        column_chunk.offset_index_offset,
+        column_chunk.offset_index_length,
+    ) {
+        // Indexes are written next to the footer, even for chunks stored in other files.
+        let mut reader = FileSource::open(path)?.reader()?;
+        let index = read_offset_index(&mut reader, offset.max(0) as u64, length.max(0) as usize)
+            .wrap_err_with(|| format!("failed to read the offset index at offset {}", offset))?;
+        return Ok(PageCounts {
+            data_pages: index.page_locations.len(),
+            dictionary_pages: usize::from(chunk.dictionary_page_offset().is_some()),
+            source: PageCountSource::OffsetIndex,
+        });
+    }
+
+    let data_path = chunk_data_path(path, chunk.file_path().as_deref());
+    let mut reader = FileSource::open(&data_path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    let pages = read_page_headers(&mut reader, chunk)?;
+    Ok(PageCounts {
+        data_pages: pages.iter().filter(|page| page.is_data()).count(),
+        dictionary_pages: pages.iter().filter(|page| page.is_dictionary()).count(),
+        source: PageCountSource::PageHeaders,
+    })
+}
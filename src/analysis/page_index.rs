@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use parquet::file::{
+    footer::parse_metadata,
+    page_index::{index::Index, index_reader::read_columns_indexes},
+};
+
+use super::sortedness::{order_of, overlap_of, Order};
+use crate::{parquet::StatValue, source::FileSource};
+
+/// Min/max range of a data page, from the column index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRange {
+    /// `None` when every value of the page is null.
+    pub min: Option<StatValue>,
+    pub max: Option<StatValue>,
+    pub null_count: Option<i64>,
+}
+
+/// Page-level ranges of a column chunk, to tell whether it is ordered within its row group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnPageIndex {
+    pub column: String,
+    pub pages: Vec<PageRange>,
+    /// Order of the page ranges, ignoring all-null pages.
+    pub order: Order,
+    /// Average fraction of the other pages whose range overlaps each page.
+    pub overlap: f64,
+}
+
+impl ColumnPageIndex {
+    /// Smallest min and largest max over all pages, if any page has a range.
+    pub fn bounds(&self) -> Option<(&StatValue, &StatValue)> {
+        let min = self
+            .pages
+            .iter()
+            .filter_map(|page| page.min.as_ref())
+            .fold(None::<&StatValue>, |min, v| match min {
+                Some(min) if min <= v => Some(min),
+                _ => Some(v),
+            })?;
+        let max = self
+            .pages
+            .iter()
+            .filter_map(|page| page.max.as_ref())
+            .fold(None::<&StatValue>, |max, v| match max {
+                Some(max) if max >= v => Some(max),
+                _ => Some(v),
+            })?;
+        Some((min, max))
+    }
+}
+
+/// Page ranges of a typed column index, converting values with `$to_value`.
+macro_rules! page_ranges {
+    ($index:expr, $to_value:expr) => {
+        $index
+            .indexes
+            .iter()
+            .map(|page| PageRange {
+                min: page.min().map($to_value),
+                max: page.max().map($to_value),
+                null_count: page.null_count(),
+            })
+            .collect()
+    };
+}
+
+/// Read the column index of a column chunk. Returns `None` when the file was written without
+/// one, or for INT96 columns.
+pub fn read_column_index(
+    path: &Path,
+    row_group: usize,
+    column: usize,
+) -> color_eyre::Result<Option<ColumnPageIndex>> {
+    let source = FileSource::open(path)?;
+    let metadata = parse_metadata(&source)?;
+    let chunk = metadata.row_group(row_group).column(column);
+
+    let pages: Vec<PageRange> =
+        match read_columns_indexes(&source, std::slice::from_ref(chunk))?.pop() {
+            Some(Index::BOOLEAN(index)) => page_ranges!(index, |v| StatValue::Boolean(*v)),
+            Some(Index::INT32(index)) => page_ranges!(index, |v| StatValue::Int(i64::from(*v))),
+            Some(Index::INT64(index)) => page_ranges!(index, |v| StatValue::Int(*v)),
+            Some(Index::FLOAT(index)) => page_ranges!(index, |v| StatValue::Float(f64::from(*v))),
+            Some(Index::DOUBLE(index)) => page_ranges!(index, |v| StatValue::Float(*v)),
+            Some(Index::BYTE_ARRAY(index)) => {
+                page_ranges!(index, |v| StatValue::Bytes(v.data().to_vec()))
+            }
+            Some(Index::FIXED_LEN_BYTE_ARRAY(index)) => {
+                page_ranges!(index, |v| StatValue::Bytes(v.data().to_vec()))
+            }
+            Some(Index::INT96(_)) | Some(Index::NONE) | None => return Ok(None),
+        };
+
+    let ranges: Vec<(StatValue, StatValue)> = pages
+        .iter()
+        .filter_map(|page| Some((page.min.clone()?, page.max.clone()?)))
+        .collect();
+
+    Ok(Some(ColumnPageIndex {
+        column: chunk.column_path().string(),
+        order: order_of(&ranges),
+        overlap: overlap_of(&ranges),
+        pages,
+    }))
+}
+
+/// Position of a value on a numeric axis, preserving the order of values of the same column.
+/// Byte arrays are placed by their first 8 bytes.
+pub fn axis_position(value: &StatValue) -> f64 {
+    match value {
+        StatValue::Boolean(b) => f64::from(u8::from(*b)),
+        StatValue::Int(i) => *i as f64,
+        StatValue::Float(x) => *x,
+        StatValue::Bytes(bytes) => {
+            let mut prefix = [0u8; 8];
+            let len = bytes.len().min(8);
+            prefix[..len].copy_from_slice(&bytes[..len]);
+            u64::from_be_bytes(prefix) as f64
+        }
+    }
+}
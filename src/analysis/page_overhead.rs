@@ -0,0 +1,106 @@
+use std::{
+    io::{Read, Seek},
+    path::Path,
+};
+
+use parquet2::metadata::{ColumnChunkMetaData, FileMetaData};
+
+use crate::{
+    pages::{level_lengths, read_decompressed_page, read_page_headers},
+    source::FileSource,
+};
+
+/// Breakdown of where the bytes of a column chunk's pages go.
+///
+/// Header bytes are measured as stored. All other sizes are measured after decompression,
+/// since in v1 data pages the levels and values are compressed together.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PageOverhead {
+    pub column: String,
+    pub data_pages: usize,
+    pub dictionary_pages: usize,
+    pub header_bytes: u64,
+    pub dictionary_bytes: u64,
+    pub rep_level_bytes: u64,
+    pub def_level_bytes: u64,
+    pub value_bytes: u64,
+}
+
+impl PageOverhead {
+    pub fn total_bytes(&self) -> u64 {
+        self.header_bytes
+            + self.dictionary_bytes
+            + self.rep_level_bytes
+            + self.def_level_bytes
+            + self.value_bytes
+    }
+
+    /// Fraction of the total bytes that is not value data.
+    pub fn overhead_ratio(&self) -> f64 {
+        let total = self.total_bytes();
+        if total == 0 {
+            return 0.0;
+        }
+        (total - self.value_bytes) as f64 / total as f64
+    }
+}
+
+/// Analyze the pages of a single column chunk.
+pub fn analyze_chunk<R: Read + Seek>(
+    path: &Path,
+    reader: &mut R,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<PageOverhead> {
+    let descriptor = &chunk.descriptor().descriptor;
+    let (max_rep_level, max_def_level) = (descriptor.max_rep_level, descriptor.max_def_level);
+
+    let mut overhead = PageOverhead {
+        column: chunk.descriptor().path_in_schema.join("."),
+        ..Default::default()
+    };
+
+    for page in read_page_headers(reader, chunk)? {
+        overhead.header_bytes += page.header_size;
+
+        if page.is_dictionary() {
+            overhead.dictionary_pages += 1;
+            overhead.dictionary_bytes += page.uncompressed_size();
+            continue;
+        }
+        if !page.is_data() {
+            continue;
+        }
+        overhead.data_pages += 1;
+
+        // Level lengths of v1 pages are only known after decompressing the page.
+        let (rep, def) = if page.header.data_page_header.is_some()
+            && (max_rep_level > 0 || max_def_level > 0)
+        {
+            let decompressed = read_decompressed_page(path, reader, &page, chunk.compression())?;
+            level_lengths(&page, &decompressed, max_rep_level, max_def_level)
+        } else {
+            level_lengths(&page, &[], max_rep_level, max_def_level)
+        };
+
+        overhead.rep_level_bytes += rep;
+        overhead.def_level_bytes += def;
+        overhead.value_bytes += page.uncompressed_size().saturating_sub(rep + def);
+    }
+
+    Ok(overhead)
+}
+
+/// Analyze every column chunk of a row group.
+pub fn analyze_row_group<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+) -> color_eyre::Result<Vec<PageOverhead>> {
+    let mut reader = FileSource::open(&path)?.reader()?;
+
+    metadata.row_groups[row_group]
+        .columns()
+        .iter()
+        .map(|chunk| analyze_chunk(path.as_ref(), &mut reader, chunk))
+        .collect()
+}
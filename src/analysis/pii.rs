@@ -0,0 +1,314 @@
+use std::{net::IpAddr, path::Path};
+
+use glob::{MatchOptions, Pattern};
+use parquet2::{
+    metadata::FileMetaData,
+    schema::types::{PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType},
+};
+
+use crate::parquet::{StatValue, ValueStream};
+
+/// Fraction of sampled values matching a pattern from which they count as evidence, and from
+/// which they are strong enough evidence on their own.
+const SOME_VALUES: f64 = 0.2;
+const MOST_VALUES: f64 = 0.8;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Kind of personal data a column may hold.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    IpAddress,
+    Ssn,
+    CardNumber,
+    Name,
+    Address,
+    BirthDate,
+}
+
+impl PiiKind {
+    /// Every kind, the ones with the most specific value formats first: SSNs and card numbers
+    /// also look like phone numbers.
+    const ALL: [PiiKind; 8] = [
+        PiiKind::Email,
+        PiiKind::Ssn,
+        PiiKind::CardNumber,
+        PiiKind::IpAddress,
+        PiiKind::Phone,
+        PiiKind::Name,
+        PiiKind::Address,
+        PiiKind::BirthDate,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::Phone => "phone number",
+            PiiKind::IpAddress => "IP address",
+            PiiKind::Ssn => "SSN",
+            PiiKind::CardNumber => "card number",
+            PiiKind::Name => "name",
+            PiiKind::Address => "postal address",
+            PiiKind::BirthDate => "birth date",
+        }
+    }
+
+    /// Glob patterns of the column fields named after the kind.
+    fn name_patterns(&self) -> &'static [&'static str] {
+        match self {
+            PiiKind::Email => &["*email*", "*e_mail*", "*mail_addr*"],
+            PiiKind::Phone => &["*phone*", "*mobile*", "*msisdn*", "*fax*"],
+            PiiKind::IpAddress => &["ip", "ip_*", "*_ip", "*ipaddr*", "*ip_addr*"],
+            PiiKind::Ssn => &["*ssn*", "*social_security*", "*national_id*", "*tax_id*"],
+            PiiKind::CardNumber => &["*card_num*", "*cardnum*", "*credit_card*", "*ccnum*"],
+            PiiKind::Name => &["*name*", "*surname*"],
+            PiiKind::Address => &["*address*", "*street*", "*zip*", "*postal*", "*postcode*"],
+            PiiKind::BirthDate => &["*birth*", "dob", "*_dob"],
+        }
+    }
+
+    /// Whether values of the kind are stored with this type: dates for birth dates, text for
+    /// the others, and integers or 16-byte binaries for IP addresses as well.
+    fn fits_type(&self, primitive_type: &PrimitiveType) -> bool {
+        let is_string = matches!(
+            primitive_type.logical_type,
+            Some(PrimitiveLogicalType::String)
+        ) || matches!(
+            primitive_type.converted_type,
+            Some(PrimitiveConvertedType::Utf8)
+        );
+        match self {
+            PiiKind::BirthDate => {
+                is_string
+                    || matches!(
+                        primitive_type.logical_type,
+                        Some(PrimitiveLogicalType::Date | PrimitiveLogicalType::Timestamp { .. })
+                    )
+                    || matches!(
+                        primitive_type.converted_type,
+                        Some(PrimitiveConvertedType::Date)
+                    )
+            }
+            PiiKind::IpAddress => {
+                is_string
+                    || matches!(
+                        primitive_type.physical_type,
+                        PhysicalType::Int32 | PhysicalType::FixedLenByteArray(16)
+                    )
+            }
+            _ => is_string || primitive_type.physical_type == PhysicalType::ByteArray,
+        }
+    }
+
+    /// Whether a value looks like the kind. Kinds without a recognizable format never match.
+    fn matches_value(&self, value: &str) -> bool {
+        match self {
+            PiiKind::Email => is_email(value),
+            PiiKind::Phone => is_phone(value),
+            PiiKind::IpAddress => value.parse::<IpAddr>().is_ok(),
+            PiiKind::Ssn => is_ssn(value),
+            PiiKind::CardNumber => is_card_number(value),
+            PiiKind::Name | PiiKind::Address | PiiKind::BirthDate => false,
+        }
+    }
+}
+
+/// How likely a column is to hold personal data.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        }
+    }
+}
+
+/// A column that likely holds personal data, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub column: String,
+    pub kind: PiiKind,
+    pub confidence: Confidence,
+    pub evidence: Vec<String>,
+}
+
+/// `local@domain.tld`, without spaces.
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !value.contains(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+/// 7 to 15 digits, optionally with a leading `+` and spaces, dashes, dots or parentheses.
+fn is_phone(value: &str) -> bool {
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let digits = value.chars().filter(char::is_ascii_digit).count();
+    (7..=15).contains(&digits)
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')'))
+}
+
+/// `ddd-dd-dddd`.
+fn is_ssn(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    matches!(parts.as_slice(), [a, b, c] if a.len() == 3 && b.len() == 2 && c.len() == 4)
+        && parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// 13 to 19 digits, optionally grouped by spaces or dashes, passing the Luhn checksum.
+fn is_card_number(value: &str) -> bool {
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ' ' || c == '-')
+    {
+        return false;
+    }
+    let digits: Vec<u32> = value.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| match i % 2 {
+            0 => digit,
+            _ if digit * 2 > 9 => digit * 2 - 9,
+            _ => digit * 2,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Up to `sample` non-null text values of a column, read from its row groups in order. Columns
+/// whose values cannot be read yield none.
+fn sample_strings(
+    path: &Path,
+    metadata: &FileMetaData,
+    column: usize,
+    sample: usize,
+) -> Vec<String> {
+    let mut values = Vec::new();
+    for row_group in 0..metadata.row_groups.len() {
+        if values.len() >= sample {
+            break;
+        }
+        let Ok(stream) = ValueStream::open(path, row_group, column, sample - values.len()) else {
+            break;
+        };
+        for batch in stream {
+            let Ok(batch) = batch else {
+                return values;
+            };
+            values.extend(batch.into_iter().filter_map(|value| match value {
+                StatValue::Bytes(bytes) => String::from_utf8(bytes).ok(),
+                _ => None,
+            }));
+        }
+    }
+    values
+}
+
+/// Flag the columns likely to contain personal data, from their names, their types and the
+/// format of up to `sample` sampled values. Each column is reported at most once, as the kind
+/// with the most evidence.
+pub fn audit<P: AsRef<Path>>(path: P, metadata: &FileMetaData, sample: usize) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (column, descriptor) in metadata.schema().columns().iter().enumerate() {
+        let name = descriptor.path_in_schema.join(".");
+        let primitive_type = &descriptor.descriptor.primitive_type;
+        let values = if primitive_type.physical_type == PhysicalType::ByteArray {
+            sample_strings(path.as_ref(), metadata, column, sample)
+        } else {
+            Vec::new()
+        };
+
+        // Score every kind: a matching name counts once, and once more with a fitting type;
+        // values count once when some match and three times when most do.
+        // Ties go to the kind listed first, hence the reversed search for the last maximum.
+        let best = PiiKind::ALL
+            .iter()
+            .rev()
+            .filter_map(|kind| {
+                let mut score = 0;
+                let mut evidence = Vec::new();
+                let pattern = kind.name_patterns().iter().find(|pattern| {
+                    let pattern = Pattern::new(pattern).expect("valid pattern");
+                    descriptor
+                        .path_in_schema
+                        .iter()
+                        .any(|field| pattern.matches_with(field, MATCH_OPTIONS))
+                });
+                if let Some(pattern) = pattern {
+                    score += 1;
+                    evidence.push(format!("name matches {}", pattern));
+                    if kind.fits_type(primitive_type) {
+                        score += 1;
+                        evidence.push(format!(
+                            "type {}",
+                            crate::dataset::type_name(primitive_type)
+                        ));
+                    }
+                }
+
+                let matched = values
+                    .iter()
+                    .filter(|value| kind.matches_value(value))
+                    .count();
+                let ratio = matched as f64 / values.len().max(1) as f64;
+                if ratio >= SOME_VALUES {
+                    score += if ratio >= MOST_VALUES { 3 } else { 1 };
+                    evidence.push(format!(
+                        "{:.0}% of {} sampled values match the {} format",
+                        ratio * 100.0,
+                        values.len(),
+                        kind.as_str()
+                    ));
+                }
+
+                (score > 0).then_some((score, *kind, evidence))
+            })
+            .max_by_key(|(score, _, _)| *score);
+
+        if let Some((score, kind, evidence)) = best {
+            findings.push(Finding {
+                column: name,
+                kind,
+                confidence: match score {
+                    3.. => Confidence::High,
+                    2 => Confidence::Medium,
+                    _ => Confidence::Low,
+                },
+                evidence,
+            });
+        }
+    }
+
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.confidence));
+    findings
+}
@@ -0,0 +1,229 @@
+use std::{cmp::Ordering, path::Path};
+
+use color_eyre::eyre::eyre;
+use parquet::file::{footer::parse_metadata, page_index::index_reader::read_pages_locations};
+use parquet2::metadata::FileMetaData;
+
+use super::page_index::{read_column_index, PageRange};
+use crate::{
+    decimal::Decimal,
+    export::resolve_leaves,
+    goto::column_paths,
+    parquet::{ColumnChunkMetaDataExt, StatValue},
+    preview::{Op, Predicate},
+    source::FileSource,
+};
+
+/// Bytes a filtered scan must read once row groups and pages are pruned with statistics.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanEstimate {
+    pub row_groups: usize,
+    pub row_groups_kept: usize,
+    /// Pages of the filter column in the kept row groups that have a page index, and how many
+    /// of them survive page pruning.
+    pub pages: usize,
+    pub pages_kept: usize,
+    /// Compressed bytes of the scanned columns: in total, after row group pruning and after
+    /// page pruning.
+    pub total_bytes: u64,
+    pub row_group_bytes: u64,
+    pub page_bytes: u64,
+}
+
+impl ScanEstimate {
+    /// Fraction of the scanned columns' bytes still read after pruning.
+    pub fn scan_ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.page_bytes as f64 / self.total_bytes as f64
+    }
+}
+
+/// Order of a statistics value relative to a predicate literal, with the predicate's semantics:
+/// decimals compare as decimals at the column's scale, other numbers numerically and everything
+/// else as strings. `None` when statistics cannot tell, e.g. for numeric literals against
+/// string columns, whose statistics are ordered as bytes.
+fn compare(
+    value: &StatValue,
+    literal: &str,
+    decimal: Option<(Decimal, usize)>,
+) -> Option<Ordering> {
+    if let Some((literal, scale)) = decimal {
+        return Some(Decimal::from_stat(value, scale)?.cmp(&literal));
+    }
+    match value {
+        StatValue::Int(i) => match literal.parse::<f64>() {
+            Ok(literal) => (*i as f64).partial_cmp(&literal),
+            Err(_) => None,
+        },
+        StatValue::Float(x) => x.partial_cmp(&literal.parse::<f64>().ok()?),
+        StatValue::Boolean(b) => Some(b.to_string().as_str().cmp(literal)),
+        StatValue::Bytes(bytes) => {
+            if literal.parse::<f64>().is_ok() {
+                return None;
+            }
+            Some(std::str::from_utf8(bytes).ok()?.cmp(literal))
+        }
+    }
+}
+
+/// Whether values within `[min, max]` with `null_count` nulls out of `num_values` may match
+/// the predicate. Unknown statistics never prune.
+fn may_match(
+    predicate: &Predicate,
+    decimal: Option<(Decimal, usize)>,
+    range: Option<(&StatValue, &StatValue)>,
+    null_count: Option<i64>,
+    num_values: Option<i64>,
+) -> bool {
+    let all_null =
+        matches!((null_count, num_values), (Some(nulls), Some(values)) if nulls >= values);
+    match predicate.op {
+        Op::IsNull => return null_count.is_none_or(|nulls| nulls > 0),
+        Op::IsNotNull => return !all_null,
+        _ if all_null => return false,
+        _ => {}
+    }
+    let Some((min, max)) = range else {
+        return true;
+    };
+    let (Some(min), Some(max)) = (
+        compare(min, &predicate.value, decimal),
+        compare(max, &predicate.value, decimal),
+    ) else {
+        return true;
+    };
+
+    match predicate.op {
+        Op::Eq => min != Ordering::Greater && max != Ordering::Less,
+        Op::Ne => !(min == Ordering::Equal && max == Ordering::Equal),
+        Op::Lt => min == Ordering::Less,
+        Op::Le => min != Ordering::Greater,
+        Op::Gt => max == Ordering::Greater,
+        Op::Ge => max != Ordering::Less,
+        Op::IsNull | Op::IsNotNull => unreachable!(),
+    }
+}
+
+fn page_may_match(
+    predicate: &Predicate,
+    decimal: Option<(Decimal, usize)>,
+    page: &PageRange,
+) -> bool {
+    let range = page.min.as_ref().zip(page.max.as_ref());
+    // Pages without a range hold only nulls.
+    let num_values = if range.is_none() {
+        page.null_count
+    } else {
+        None
+    };
+    may_match(predicate, decimal, range, page.null_count, num_values)
+}
+
+/// Estimate the compressed bytes a scan of `columns` (all columns when empty) filtered by
+/// `predicate` reads, after pruning row groups with chunk statistics and pages with the page
+/// index of the filter column. Pages of the other columns are kept when they overlap the rows
+/// of a surviving filter page; chunks without an offset index are read whole.
+pub fn estimate_scan(
+    path: &Path,
+    metadata: &FileMetaData,
+    predicate: &Predicate,
+    columns: &[String],
+) -> color_eyre::Result<ScanEstimate> {
+    let filter_column = column_paths(metadata)
+        .iter()
+        .position(|path| *path == predicate.column)
+        .ok_or_else(|| eyre!("no column named {:?}", predicate.column))?;
+    let decimal = predicate.decimal_literal(metadata)?;
+
+    let source = FileSource::open(path)?;
+    let footer = parse_metadata(&source)?;
+    let mut leaves = resolve_leaves(footer.file_metadata().schema_descr(), columns)?;
+    if !leaves.contains(&filter_column) {
+        leaves.push(filter_column);
+    }
+
+    let mut estimate = ScanEstimate {
+        row_groups: metadata.row_groups.len(),
+        ..Default::default()
+    };
+
+    for (index, row_group) in metadata.row_groups.iter().enumerate() {
+        let chunks = row_group.columns();
+        let chunk_bytes: u64 = leaves
+            .iter()
+            .map(|&leaf| chunks[leaf].compressed_size().max(0) as u64)
+            .sum();
+        estimate.total_bytes += chunk_bytes;
+
+        let filter_chunk = &chunks[filter_column];
+        let min_max = filter_chunk.min_max();
+        if !may_match(
+            predicate,
+            decimal,
+            min_max.as_ref().map(|(min, max)| (min, max)),
+            filter_chunk.stats().null_count,
+            Some(filter_chunk.num_values()),
+        ) {
+            continue;
+        }
+        estimate.row_groups_kept += 1;
+        estimate.row_group_bytes += chunk_bytes;
+
+        let row_group_metadata = footer.row_group(index);
+        let locations = read_pages_locations(&source, row_group_metadata.columns())?;
+        let page_index = read_column_index(path, index, filter_column)?;
+        let filter_locations = locations.get(filter_column).filter(|l| !l.is_empty());
+        let (Some(page_index), Some(filter_locations)) = (page_index, filter_locations) else {
+            estimate.page_bytes += chunk_bytes;
+            continue;
+        };
+        if page_index.pages.len() != filter_locations.len() {
+            estimate.page_bytes += chunk_bytes;
+            continue;
+        }
+
+        // Row ranges of the filter pages that survive page pruning.
+        let num_rows = row_group.num_rows() as i64;
+        let row_end = |locations: &[parquet::format::PageLocation], i: usize| {
+            locations
+                .get(i + 1)
+                .map_or(num_rows, |next| next.first_row_index)
+        };
+        let kept_rows: Vec<(i64, i64)> = page_index
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| page_may_match(predicate, decimal, page))
+            .map(|(i, _)| {
+                (
+                    filter_locations[i].first_row_index,
+                    row_end(filter_locations, i),
+                )
+            })
+            .collect();
+        estimate.pages += page_index.pages.len();
+        estimate.pages_kept += kept_rows.len();
+
+        for &leaf in &leaves {
+            let chunk = row_group_metadata.column(leaf);
+            let Some(pages) = locations.get(leaf).filter(|l| !l.is_empty()) else {
+                estimate.page_bytes += chunk.compressed_size().max(0) as u64;
+                continue;
+            };
+            // The dictionary page, if any, sits before the first data page.
+            let (chunk_start, _) = chunk.byte_range();
+            let mut bytes = (pages[0].offset as u64).saturating_sub(chunk_start);
+            for (i, page) in pages.iter().enumerate() {
+                let (start, end) = (page.first_row_index, row_end(pages, i));
+                if kept_rows.iter().any(|&(from, to)| start < to && from < end) {
+                    bytes += page.compressed_page_size.max(0) as u64;
+                }
+            }
+            estimate.page_bytes += bytes;
+        }
+    }
+
+    Ok(estimate)
+}
@@ -0,0 +1,322 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use parquet2::metadata::{ColumnChunkMetaData, FileMetaData};
+use parquet_format_safe::{thrift::protocol::TCompactInputProtocol, ColumnMetaData};
+
+use super::bloom::read_bloom_filter_header;
+use crate::{
+    cache::read_footer_bytes,
+    parquet::{read_row_group_footers, RowGroupFooter},
+    source::FileSource,
+};
+
+/// Upper bound on the size of a copy of a chunk's column metadata, statistics included.
+const MAX_METADATA_COPY_SIZE: usize = 64 * 1024;
+
+/// Kind of a [LayoutIssue].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LayoutIssueKind {
+    /// The ordinal of a row group is not its index in the footer.
+    Ordinal,
+    /// The declared file offset of a row group is not where its first column chunk starts.
+    FileOffset,
+    /// A row group starts before the previous one.
+    Order,
+    /// A row group overlaps the previous one, or a column chunk overlaps another one.
+    Overlap,
+    /// Bytes between row groups, or before the footer, that no index or bloom filter accounts
+    /// for.
+    Gap,
+    /// A row group starts inside the leading magic bytes or ends past the start of the footer.
+    Bounds,
+}
+
+impl LayoutIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LayoutIssueKind::Ordinal => "ordinal",
+            LayoutIssueKind::FileOffset => "file_offset",
+            LayoutIssueKind::Order => "order",
+            LayoutIssueKind::Overlap => "overlap",
+            LayoutIssueKind::Gap => "gap",
+            LayoutIssueKind::Bounds => "bounds",
+        }
+    }
+}
+
+/// An anomaly in the placement of row groups, hinting at a writer bug or at bytes being spliced
+/// into or cut from the file after it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutIssue {
+    pub row_group: Option<usize>,
+    pub kind: LayoutIssueKind,
+    pub detail: String,
+}
+
+/// Byte range spanned by the column chunks of a row group stored in the file, with the
+/// placement fields its footer declares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowGroupExtent {
+    pub row_group: usize,
+    pub start: u64,
+    pub end: u64,
+    pub file_offset: Option<i64>,
+    pub ordinal: Option<i16>,
+}
+
+/// Bytes of `[start, end)` covered by `ranges`, which must not overlap each other.
+fn covered(ranges: &[(u64, u64)], start: u64, end: u64) -> u64 {
+    ranges
+        .iter()
+        .map(|&(from, to)| to.min(end).saturating_sub(from.max(start)))
+        .sum()
+}
+
+/// Size of the copy of a chunk's column metadata that some writers, e.g. parquet-rs, place right
+/// after the chunk, or `None` if the bytes at `offset` are not one.
+pub fn metadata_copy_size<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    chunk: &ColumnChunkMetaData,
+) -> Option<u64> {
+    reader.seek(SeekFrom::Start(offset)).ok()?;
+    let mut protocol = TCompactInputProtocol::new(&mut *reader, MAX_METADATA_COPY_SIZE);
+    let copy = ColumnMetaData::read_from_in_protocol(&mut protocol).ok()?;
+    (copy.path_in_schema == chunk.metadata().path_in_schema)
+        .then(|| reader.stream_position().ok())
+        .flatten()
+        .map(|end| end - offset)
+}
+
+/// Ranges of the structures writers legitimately place between or after row groups: copies of
+/// the column metadata, bloom filters, column indexes and offset indexes.
+fn auxiliary_ranges<R: Read + Seek>(reader: &mut R, metadata: &FileMetaData) -> Vec<(u64, u64)> {
+    let mut ranges = Vec::new();
+    for chunk in metadata
+        .row_groups
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+    {
+        let (start, length) = chunk.byte_range();
+        if let Some(size) = metadata_copy_size(reader, start + length, chunk) {
+            ranges.push((start + length, start + length + size));
+        }
+
+        let column_chunk = chunk.column_chunk();
+        for (offset, length) in [
+            (
+                column_chunk.column_index_offset,
+                column_chunk.column_index_length,
+            ),
+            (
+                column_chunk.offset_index_offset,
+                column_chunk.offset_index_length,
+            ),
+        ] {
+            if let (Some(offset), Some(length)) = (offset, length) {
+                let offset = offset.max(0) as u64;
+                ranges.push((offset, offset + length.max(0) as u64));
+            }
+        }
+        if let Some(offset) = chunk.metadata().bloom_filter_offset {
+            let offset = offset.max(0) as u64;
+            if let Ok((header, header_size)) = read_bloom_filter_header(reader, offset) {
+                ranges.push((
+                    offset,
+                    offset + header_size + header.num_bytes.max(0) as u64,
+                ));
+            }
+        }
+    }
+    ranges.sort_unstable();
+    ranges.dedup();
+    ranges
+}
+
+/// Byte ranges of the row groups, in footer order. Row groups whose chunks are all stored in
+/// external files are left out.
+pub fn row_group_extents(
+    metadata: &FileMetaData,
+    footers: &[RowGroupFooter],
+) -> Vec<RowGroupExtent> {
+    metadata
+        .row_groups
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row_group)| {
+            let ranges: Vec<(u64, u64)> = row_group
+                .columns()
+                .iter()
+                .filter(|chunk| chunk.file_path().is_none())
+                .map(|chunk| {
+                    let (start, length) = chunk.byte_range();
+                    (start, start + length)
+                })
+                .collect();
+            let footer = footers.get(index);
+            Some(RowGroupExtent {
+                row_group: index,
+                start: ranges.iter().map(|&(start, _)| start).min()?,
+                end: ranges.iter().map(|&(_, end)| end).max()?,
+                file_offset: footer.and_then(|footer| footer.file_offset),
+                ordinal: footer.and_then(|footer| footer.ordinal),
+            })
+        })
+        .collect()
+}
+
+/// Check that row groups are numbered by their ordinals, start at their declared file offsets,
+/// follow each other in file order without overlapping, and leave no gaps beyond the indexes
+/// and bloom filters written between them. Column chunks are checked for overlaps as well.
+pub fn check_row_group_layout(
+    path: &Path,
+    metadata: &FileMetaData,
+) -> color_eyre::Result<(Vec<RowGroupExtent>, Vec<LayoutIssue>)> {
+    let footers = read_row_group_footers(path)?;
+    let mut reader = FileSource::open(path)?.reader()?;
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let footer_start = file_size.saturating_sub(read_footer_bytes(&mut reader)?.len() as u64 + 8);
+    let auxiliary = auxiliary_ranges(&mut reader, metadata);
+
+    let extents = row_group_extents(metadata, &footers);
+    let mut issues = Vec::new();
+    let mut issue = |row_group, kind, detail: String| {
+        issues.push(LayoutIssue {
+            row_group,
+            kind,
+            detail,
+        })
+    };
+
+    for (index, footer) in footers.iter().enumerate() {
+        if let Some(ordinal) = footer.ordinal.filter(|&ordinal| ordinal as usize != index) {
+            issue(
+                Some(index),
+                LayoutIssueKind::Ordinal,
+                format!("ordinal {} at index {}", ordinal, index),
+            );
+        }
+    }
+
+    let mut previous: Option<&RowGroupExtent> = None;
+    for extent in &extents {
+        let row_group = Some(extent.row_group);
+        if let Some(file_offset) = extent
+            .file_offset
+            .filter(|&offset| offset.max(0) as u64 != extent.start)
+        {
+            issue(
+                row_group,
+                LayoutIssueKind::FileOffset,
+                format!(
+                    "file_offset {} but the first column chunk starts at {}",
+                    file_offset, extent.start
+                ),
+            );
+        }
+        if extent.start < 4 || extent.end > footer_start {
+            issue(
+                row_group,
+                LayoutIssueKind::Bounds,
+                format!(
+                    "spans {}..{}, outside of the data section 4..{}",
+                    extent.start, extent.end, footer_start
+                ),
+            );
+        }
+
+        let gap_start = previous.map_or(4, |previous| previous.end);
+        match previous {
+            Some(previous) if extent.start < previous.start => issue(
+                row_group,
+                LayoutIssueKind::Order,
+                format!(
+                    "starts at {}, before row group {} at {}",
+                    extent.start, previous.row_group, previous.start
+                ),
+            ),
+            Some(previous) if extent.start < previous.end => issue(
+                row_group,
+                LayoutIssueKind::Overlap,
+                format!(
+                    "overlaps the last {} bytes of row group {}",
+                    previous.end - extent.start,
+                    previous.row_group
+                ),
+            ),
+            _ => {
+                let gap = extent.start.saturating_sub(gap_start);
+                let unexplained = gap - covered(&auxiliary, gap_start, extent.start);
+                if unexplained > 0 {
+                    issue(
+                        row_group,
+                        LayoutIssueKind::Gap,
+                        format!(
+                            "{} unaccounted bytes before it, in {}..{}",
+                            unexplained, gap_start, extent.start
+                        ),
+                    );
+                }
+            }
+        }
+        previous = Some(extent);
+    }
+
+    let data_end = extents.iter().map(|extent| extent.end).max().unwrap_or(4);
+    let trailing = footer_start.saturating_sub(data_end);
+    let unexplained = trailing - covered(&auxiliary, data_end, footer_start);
+    if unexplained > 0 {
+        issue(
+            None,
+            LayoutIssueKind::Gap,
+            format!(
+                "{} unaccounted bytes before the footer, in {}..{}",
+                unexplained, data_end, footer_start
+            ),
+        );
+    }
+
+    // Column chunks in file order. Overlaps across row groups show as row group overlaps.
+    let mut chunks: Vec<(u64, u64, usize, String)> = metadata
+        .row_groups
+        .iter()
+        .enumerate()
+        .flat_map(|(index, row_group)| {
+            row_group
+                .columns()
+                .iter()
+                .filter(|chunk| chunk.file_path().is_none())
+                .map(move |chunk| {
+                    let (start, length) = chunk.byte_range();
+                    (
+                        start,
+                        start + length,
+                        index,
+                        chunk.descriptor().path_in_schema.join("."),
+                    )
+                })
+        })
+        .collect();
+    chunks.sort_unstable();
+    for pair in chunks.windows(2) {
+        let ((_, end, row_group, column), (start, _, other_row_group, other_column)) =
+            (&pair[0], &pair[1]);
+        if start < end && row_group == other_row_group {
+            issue(
+                Some(*other_row_group),
+                LayoutIssueKind::Overlap,
+                format!(
+                    "chunk {} overlaps the last {} bytes of chunk {}",
+                    other_column,
+                    end - start,
+                    column
+                ),
+            );
+        }
+    }
+
+    Ok((extents, issues))
+}
@@ -0,0 +1,69 @@
+use parquet2::metadata::FileMetaData;
+
+/// Row groups smaller than the median by this factor, or larger by it, are outliers.
+const OUTLIER_FACTOR: f64 = 2.0;
+
+/// Row count and compressed size of a row group, flagged when far from the median.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RowGroupSize {
+    pub rows: i64,
+    pub compressed_bytes: i64,
+    pub rows_outlier: bool,
+    pub bytes_outlier: bool,
+}
+
+impl RowGroupSize {
+    pub fn is_outlier(&self) -> bool {
+        self.rows_outlier || self.bytes_outlier
+    }
+}
+
+fn median(values: &[i64]) -> i64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+/// Flags the values that are more than [OUTLIER_FACTOR] times smaller or larger than the
+/// median. Nothing is flagged with fewer than two values.
+fn outliers(values: &[i64]) -> Vec<bool> {
+    if values.len() < 2 {
+        return vec![false; values.len()];
+    }
+    let median = median(values) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            let value = value as f64;
+            value * OUTLIER_FACTOR < median || value > median * OUTLIER_FACTOR
+        })
+        .collect()
+}
+
+/// Sizes of every row group, flagging outliers such as a tiny final row group.
+pub fn row_group_sizes(metadata: &FileMetaData) -> Vec<RowGroupSize> {
+    let rows: Vec<i64> = metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.num_rows() as i64)
+        .collect();
+    let bytes: Vec<i64> = metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.compressed_size() as i64)
+        .collect();
+
+    outliers(&rows)
+        .into_iter()
+        .zip(outliers(&bytes))
+        .zip(rows.iter().zip(&bytes))
+        .map(
+            |((rows_outlier, bytes_outlier), (&rows, &compressed_bytes))| RowGroupSize {
+                rows,
+                compressed_bytes,
+                rows_outlier,
+                bytes_outlier,
+            },
+        )
+        .collect()
+}
@@ -0,0 +1,140 @@
+use std::{collections::HashSet, path::Path, time::Instant};
+
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+
+use crate::parquet::{max_memory, ColumnChunkMetaDataExt, StatValue, ValueStream};
+
+/// Statistics computed by decoding every value of a column chunk, as opposed to the footer
+/// statistics written by the producer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnScan {
+    pub column: String,
+    /// Number of non-null values.
+    pub values: usize,
+    /// Number of null values, or empty lists for repeated columns.
+    pub nulls: usize,
+    pub distinct: usize,
+    /// False when distinct values outgrew the memory budget, making `distinct` a lower bound.
+    pub distinct_exact: bool,
+    /// Smallest and largest values, leaving out NaN.
+    pub min: Option<StatValue>,
+    pub max: Option<StatValue>,
+    /// Special values of FLOAT and DOUBLE columns, `None` for other types.
+    pub floats: Option<FloatSpecials>,
+    /// Whether the footer min or max of the chunk is NaN. Readers comparing against such
+    /// statistics prune row groups they should not, or keep every one.
+    pub footer_nan: bool,
+}
+
+/// Counts of the values of a FLOAT or DOUBLE column chunk that min/max statistics handle
+/// inconsistently: writers differ in whether they skip NaN, and in how they order -0 and +0.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FloatSpecials {
+    pub nan: usize,
+    pub positive_infinity: usize,
+    pub negative_infinity: usize,
+    pub negative_zero: usize,
+}
+
+impl FloatSpecials {
+    fn count(&mut self, value: f64) {
+        if value.is_nan() {
+            self.nan += 1;
+        } else if value == f64::INFINITY {
+            self.positive_infinity += 1;
+        } else if value == f64::NEG_INFINITY {
+            self.negative_infinity += 1;
+        } else if value == 0.0 && value.is_sign_negative() {
+            self.negative_zero += 1;
+        }
+    }
+}
+
+/// Approximate heap overhead of a distinct value entry, on top of its rendered bytes.
+const DISTINCT_ENTRY_OVERHEAD: usize = 48;
+
+/// Scan the given columns of a row group.
+///
+/// Values are streamed batch by batch. Distinct values are tracked until they would take
+/// half of the memory budget, after which the count is a lower bound.
+pub fn scan_columns<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    columns: &[usize],
+) -> color_eyre::Result<Vec<ColumnScan>> {
+    let distinct_budget = max_memory() / 2;
+
+    columns
+        .iter()
+        .map(|&column| {
+            let chunk = &metadata.row_groups[row_group].columns()[column];
+            let started = Instant::now();
+            let mut floats = matches!(
+                chunk.physical_type(),
+                PhysicalType::Float | PhysicalType::Double
+            )
+            .then(FloatSpecials::default);
+
+            let mut values = 0;
+            let mut distinct = HashSet::new();
+            let mut distinct_bytes = 0;
+            let mut distinct_exact = true;
+            let mut min: Option<StatValue> = None;
+            let mut max: Option<StatValue> = None;
+            for batch in ValueStream::open(path.as_ref(), row_group, column, usize::MAX)? {
+                for value in batch? {
+                    values += 1;
+                    if let (Some(floats), StatValue::Float(x)) = (&mut floats, &value) {
+                        floats.count(*x);
+                    }
+                    if distinct_exact {
+                        let key = format!("{:?}", value);
+                        let size = key.len() + DISTINCT_ENTRY_OVERHEAD;
+                        if !distinct.contains(&key) {
+                            if distinct_bytes + size > distinct_budget {
+                                distinct_exact = false;
+                            } else {
+                                distinct_bytes += size;
+                                distinct.insert(key);
+                            }
+                        }
+                    }
+                    // NaN compares false with everything, and would stick as the first value.
+                    if matches!(value, StatValue::Float(x) if x.is_nan()) {
+                        continue;
+                    }
+                    if min.as_ref().is_none_or(|min| value < *min) {
+                        min = Some(value.clone());
+                    }
+                    if max.as_ref().is_none_or(|max| value > *max) {
+                        max = Some(value);
+                    }
+                }
+            }
+            tracing::debug!(
+                "decoded {} values of column {} in row group {} in {:?}",
+                values,
+                column,
+                row_group,
+                started.elapsed()
+            );
+
+            Ok(ColumnScan {
+                column: chunk.descriptor().path_in_schema.join("."),
+                values,
+                nulls: (chunk.num_values().max(0) as usize).saturating_sub(values),
+                distinct: distinct.len(),
+                distinct_exact,
+                min,
+                max,
+                floats,
+                footer_nan: chunk.min_max().is_some_and(|(min, max)| {
+                    [min, max]
+                        .iter()
+                        .any(|value| matches!(value, StatValue::Float(x) if x.is_nan()))
+                }),
+            })
+        })
+        .collect()
+}
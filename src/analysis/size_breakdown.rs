@@ -0,0 +1,94 @@
+use std::{
+    io::{Seek, SeekFrom},
+    path::Path,
+};
+
+use parquet2::metadata::FileMetaData;
+
+use super::bloom::read_bloom_filter_header;
+use crate::{cache::read_footer_bytes, pages::read_page_headers, source::FileSource};
+
+/// Where the bytes of a file go, from page payloads to the footer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SizeBreakdown {
+    pub file_size: u64,
+    pub data_pages: u64,
+    pub dictionary_pages: u64,
+    pub page_headers: u64,
+    pub column_indexes: u64,
+    pub offset_indexes: u64,
+    pub bloom_filters: u64,
+    /// Serialized metadata, its length and the magic bytes at both ends of the file.
+    pub footer: u64,
+}
+
+impl SizeBreakdown {
+    /// Bytes not claimed by any of the other parts, e.g. copies of the column metadata that
+    /// some writers place after each chunk, padding or unreferenced data.
+    pub fn other(&self) -> u64 {
+        self.file_size.saturating_sub(
+            self.data_pages
+                + self.dictionary_pages
+                + self.page_headers
+                + self.column_indexes
+                + self.offset_indexes
+                + self.bloom_filters
+                + self.footer,
+        )
+    }
+
+    /// Named parts, in file order where possible.
+    pub fn parts(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("Data pages", self.data_pages),
+            ("Dictionary pages", self.dictionary_pages),
+            ("Page headers", self.page_headers),
+            ("Bloom filters", self.bloom_filters),
+            ("Column indexes", self.column_indexes),
+            ("Offset indexes", self.offset_indexes),
+            ("Footer", self.footer),
+            ("Other", self.other()),
+        ]
+    }
+}
+
+/// Break the size of a file down by scanning the page headers of every column chunk stored in
+/// it. Chunks stored in external files are not counted.
+pub fn size_breakdown(path: &Path, metadata: &FileMetaData) -> color_eyre::Result<SizeBreakdown> {
+    let mut reader = FileSource::open(path)?.reader()?;
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let footer = read_footer_bytes(&mut reader)?.len() as u64 + 12;
+
+    let mut breakdown = SizeBreakdown {
+        file_size,
+        footer,
+        ..Default::default()
+    };
+
+    for chunk in metadata
+        .row_groups
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+        .filter(|chunk| chunk.file_path().is_none())
+    {
+        for page in read_page_headers(&mut reader, chunk)? {
+            breakdown.page_headers += page.header_size;
+            if page.is_dictionary() {
+                breakdown.dictionary_pages += page.compressed_size();
+            } else {
+                breakdown.data_pages += page.compressed_size();
+            }
+        }
+
+        let column_chunk = chunk.column_chunk();
+        breakdown.column_indexes += column_chunk.column_index_length.unwrap_or(0).max(0) as u64;
+        breakdown.offset_indexes += column_chunk.offset_index_length.unwrap_or(0).max(0) as u64;
+        if let Some(offset) = chunk.metadata().bloom_filter_offset {
+            let (header, header_size) =
+                read_bloom_filter_header(&mut reader, offset.max(0) as u64)?;
+            breakdown.bloom_filters += header_size + header.num_bytes.max(0) as u64;
+        }
+    }
+
+    Ok(breakdown)
+}
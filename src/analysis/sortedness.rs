@@ -0,0 +1,221 @@
+use std::path::Path;
+
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+
+use crate::parquet::{ColumnChunkMetaDataExt, StatValue, ValueStream};
+
+/// Order of a column across row groups, inferred from the footer min/max statistics.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Order {
+    /// Each row group's min is at least the previous row group's max.
+    Ascending,
+    /// Each row group's max is at most the previous row group's min.
+    Descending,
+    /// Every row group holds the same single value.
+    Constant,
+    /// Row group ranges are not monotonic.
+    Unsorted,
+    /// Statistics are missing for at least one row group, or there is only one row group.
+    Unknown,
+}
+
+impl Order {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Order::Ascending => "ascending",
+            Order::Descending => "descending",
+            Order::Constant => "constant",
+            Order::Unsorted => "unsorted",
+            Order::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_sorted(&self) -> bool {
+        matches!(self, Order::Ascending | Order::Descending)
+    }
+}
+
+/// Sortedness of a single leaf column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSortedness {
+    pub column: String,
+    pub global_order: Order,
+    /// Average fraction of the other row groups whose min/max range overlaps each row group.
+    /// Lower is better for pruning. `None` when statistics are missing.
+    pub overlap: Option<f64>,
+    /// Whether the sampled values were sorted within every row group. `None` unless sampling
+    /// was requested.
+    pub locally_sorted: Option<bool>,
+}
+
+/// Analyze the sortedness of every leaf column. When `sample` is non-zero, up to that many
+/// values are read from each column chunk to check whether the data is sorted within row
+/// groups.
+pub fn analyze<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    sample: usize,
+) -> color_eyre::Result<Vec<ColumnSortedness>> {
+    let mut columns = Vec::new();
+
+    for (column, descriptor) in metadata.schema().columns().iter().enumerate() {
+        let ranges: Option<Vec<(StatValue, StatValue)>> = metadata
+            .row_groups
+            .iter()
+            .map(|row_group| row_group.columns()[column].min_max())
+            .collect();
+
+        let (global_order, overlap) = match &ranges {
+            Some(ranges) => (order_of(ranges), Some(overlap_of(ranges))),
+            None => (Order::Unknown, None),
+        };
+
+        let locally_sorted = if sample > 0 {
+            let mut sorted = true;
+            for row_group in 0..metadata.row_groups.len() {
+                let mut monotonic = Monotonic::default();
+                for batch in ValueStream::open(path.as_ref(), row_group, column, sample)? {
+                    batch?.into_iter().for_each(|value| monotonic.push(value));
+                    if !monotonic.is_monotonic() {
+                        break;
+                    }
+                }
+                sorted &= monotonic.is_monotonic();
+            }
+            Some(sorted)
+        } else {
+            None
+        };
+
+        columns.push(ColumnSortedness {
+            column: descriptor.path_in_schema.join("."),
+            global_order,
+            overlap,
+            locally_sorted,
+        });
+    }
+
+    Ok(columns)
+}
+
+/// Suggest up to `limit` columns to sort the file by, best candidate first.
+///
+/// Columns that are already globally sorted are not suggested. Candidates are columns whose
+/// row group ranges overlap the most, since sorting by them improves pruning the most;
+/// columns already sorted within row groups rank first because they are cheapest to
+/// merge. Boolean, constant and repeated columns are never suggested.
+pub fn suggest_sort_keys(
+    metadata: &FileMetaData,
+    columns: &[ColumnSortedness],
+    limit: usize,
+) -> Vec<String> {
+    let descriptors = metadata.schema().columns();
+    let mut candidates: Vec<&ColumnSortedness> = columns
+        .iter()
+        .zip(descriptors)
+        .filter(|(column, descriptor)| {
+            !column.global_order.is_sorted()
+                && column.global_order != Order::Constant
+                && column.overlap.is_some()
+                && descriptor.descriptor.primitive_type.physical_type != PhysicalType::Boolean
+                && descriptor.descriptor.max_rep_level == 0
+        })
+        .map(|(column, _)| column)
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        let a_key = (
+            a.locally_sorted == Some(true),
+            a.overlap.unwrap_or_default(),
+        );
+        let b_key = (
+            b.locally_sorted == Some(true),
+            b.overlap.unwrap_or_default(),
+        );
+        b_key
+            .partial_cmp(&a_key)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|column| column.column.clone())
+        .collect()
+}
+
+/// Order of consecutive min/max ranges, such as those of row groups or pages.
+pub(crate) fn order_of(ranges: &[(StatValue, StatValue)]) -> Order {
+    if ranges.len() < 2 {
+        return Order::Unknown;
+    }
+
+    if ranges
+        .iter()
+        .all(|(min, max)| min == max && *min == ranges[0].0)
+    {
+        return Order::Constant;
+    }
+    if ranges.windows(2).all(|pair| pair[0].1 <= pair[1].0) {
+        return Order::Ascending;
+    }
+    if ranges.windows(2).all(|pair| pair[0].0 >= pair[1].1) {
+        return Order::Descending;
+    }
+
+    Order::Unsorted
+}
+
+/// Average fraction of the other ranges that overlap each range.
+pub(crate) fn overlap_of(ranges: &[(StatValue, StatValue)]) -> f64 {
+    if ranges.len() < 2 {
+        return 0.0;
+    }
+
+    let overlapping_pairs = ranges
+        .iter()
+        .enumerate()
+        .map(|(i, (min, max))| {
+            ranges
+                .iter()
+                .enumerate()
+                .filter(|(j, (other_min, other_max))| {
+                    i != *j && min <= other_max && other_min <= max
+                })
+                .count()
+        })
+        .sum::<usize>();
+
+    overlapping_pairs as f64 / (ranges.len() * (ranges.len() - 1)) as f64
+}
+
+/// Incremental check that a sequence of values is either non-decreasing or non-increasing.
+struct Monotonic {
+    previous: Option<StatValue>,
+    ascending: bool,
+    descending: bool,
+}
+
+impl Default for Monotonic {
+    fn default() -> Self {
+        Self {
+            previous: None,
+            ascending: true,
+            descending: true,
+        }
+    }
+}
+
+impl Monotonic {
+    fn push(&mut self, value: StatValue) {
+        if let Some(previous) = &self.previous {
+            self.ascending &= *previous <= value;
+            self.descending &= *previous >= value;
+        }
+        self.previous = Some(value);
+    }
+
+    fn is_monotonic(&self) -> bool {
+        self.ascending || self.descending
+    }
+}
@@ -0,0 +1,73 @@
+use parquet2::metadata::FileMetaData;
+
+/// Which statistics the footer records for a column chunk.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct StatsPresence {
+    /// Both bounds, in the current `min_value`/`max_value` fields or the deprecated `min`/`max`.
+    pub min_max: bool,
+    pub null_count: bool,
+    pub distinct_count: bool,
+}
+
+/// Statistics presence of every column chunk of a file. Missing statistics silently keep
+/// readers from pruning row groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresenceMatrix {
+    pub columns: Vec<String>,
+    /// Presence by row group, then by column.
+    pub row_groups: Vec<Vec<StatsPresence>>,
+}
+
+impl PresenceMatrix {
+    fn chunks(&self) -> impl Iterator<Item = &StatsPresence> {
+        self.row_groups.iter().flatten()
+    }
+
+    pub fn num_chunks(&self) -> usize {
+        self.chunks().count()
+    }
+
+    /// Fraction of column chunks with min/max, null count and distinct count statistics.
+    pub fn coverage(&self) -> (f64, f64, f64) {
+        let total = self.num_chunks().max(1) as f64;
+        let share = |present: fn(&StatsPresence) -> bool| {
+            self.chunks().filter(|chunk| present(chunk)).count() as f64 / total
+        };
+        (
+            share(|chunk| chunk.min_max),
+            share(|chunk| chunk.null_count),
+            share(|chunk| chunk.distinct_count),
+        )
+    }
+}
+
+/// Read which statistics are present for every column chunk from the footer.
+pub fn stats_presence(metadata: &FileMetaData) -> PresenceMatrix {
+    PresenceMatrix {
+        columns: metadata
+            .schema()
+            .columns()
+            .iter()
+            .map(|column| column.path_in_schema.join("."))
+            .collect(),
+        row_groups: metadata
+            .row_groups
+            .iter()
+            .map(|row_group| {
+                row_group
+                    .columns()
+                    .iter()
+                    .map(|chunk| match &chunk.metadata().statistics {
+                        Some(stats) => StatsPresence {
+                            min_max: (stats.min_value.is_some() && stats.max_value.is_some())
+                                || (stats.min.is_some() && stats.max.is_some()),
+                            null_count: stats.null_count.is_some(),
+                            distinct_count: stats.distinct_count.is_some(),
+                        },
+                        None => StatsPresence::default(),
+                    })
+                    .collect()
+            })
+            .collect(),
+    }
+}
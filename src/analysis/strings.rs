@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+
+use crate::parquet::{StatValue, ValueStream};
+
+/// Profile of the values of a BYTE_ARRAY column, to catch binary data in string columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringProfile {
+    pub column: String,
+    /// Number of non-null values sampled across all row groups.
+    pub sampled: usize,
+    /// Value lengths in bytes. `None` when no value was sampled.
+    pub min_length: Option<usize>,
+    pub avg_length: Option<f64>,
+    pub max_length: Option<usize>,
+    /// Fraction of the sampled values that are valid UTF-8.
+    pub valid_utf8: Option<f64>,
+    /// Number of valid UTF-8 values containing control characters other than tab, CR and LF.
+    pub with_control_chars: usize,
+}
+
+/// Profile every BYTE_ARRAY column, sampling up to `sample` values from each column chunk.
+pub fn analyze<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    sample: usize,
+) -> color_eyre::Result<Vec<StringProfile>> {
+    let mut profiles = Vec::new();
+
+    for (column, descriptor) in metadata.schema().columns().iter().enumerate() {
+        if descriptor.descriptor.primitive_type.physical_type != PhysicalType::ByteArray {
+            continue;
+        }
+
+        let mut sampled = 0;
+        let mut min_length: Option<usize> = None;
+        let mut max_length: Option<usize> = None;
+        let mut total_length = 0;
+        let mut valid_utf8 = 0;
+        let mut with_control_chars = 0;
+        for row_group in 0..metadata.row_groups.len() {
+            for batch in ValueStream::open(path.as_ref(), row_group, column, sample)? {
+                for value in batch? {
+                    let StatValue::Bytes(bytes) = value else {
+                        continue;
+                    };
+                    sampled += 1;
+                    total_length += bytes.len();
+                    min_length = Some(min_length.map_or(bytes.len(), |min| min.min(bytes.len())));
+                    max_length = Some(max_length.map_or(bytes.len(), |max| max.max(bytes.len())));
+                    if let Ok(s) = std::str::from_utf8(&bytes) {
+                        valid_utf8 += 1;
+                        if s.chars()
+                            .any(|c| c.is_control() && !matches!(c, '\t' | '\r' | '\n'))
+                        {
+                            with_control_chars += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        profiles.push(StringProfile {
+            column: descriptor.path_in_schema.join("."),
+            sampled,
+            min_length,
+            avg_length: (sampled > 0).then(|| total_length as f64 / sampled as f64),
+            max_length,
+            valid_utf8: (sampled > 0).then(|| valid_utf8 as f64 / sampled as f64),
+            with_control_chars,
+        });
+    }
+
+    Ok(profiles)
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, path::Path};
+
+use color_eyre::eyre::eyre;
+use parquet2::{metadata::FileMetaData, schema::types::PhysicalType};
+
+use crate::parquet::{StatValue, ValueStream};
+
+/// Values of a column chunk whose byte lengths fall in a range, from `min` to `max` bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeBucket {
+    pub min: usize,
+    pub max: usize,
+    pub count: u64,
+}
+
+/// Distribution of the byte lengths of the values of a BYTE_ARRAY column chunk. A few
+/// oversized values, e.g. blobs in a column of short strings, blow up the memory of readers
+/// that size their buffers by them, and hide behind averages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueSizes {
+    pub column: String,
+    /// Number of non-null values, and their total length in bytes.
+    pub values: u64,
+    pub total_bytes: u64,
+    /// Length percentiles and the largest length, 0 when the chunk has no values.
+    pub p50: usize,
+    pub p95: usize,
+    pub p99: usize,
+    pub max: usize,
+    /// Lengths in power-of-two buckets, `0`, `1`, `2-3`, `4-7` and so on, up to the bucket
+    /// of the largest value. Empty buckets are kept, to show gaps in the distribution.
+    pub buckets: Vec<SizeBucket>,
+}
+
+/// Index of the power-of-two bucket of a length.
+fn bucket(length: usize) -> usize {
+    length.checked_ilog2().map_or(0, |log| log as usize + 1)
+}
+
+/// Smallest length such that at least `quantile` of the values are no longer, by the number of
+/// values of each length.
+fn percentile(lengths: &BTreeMap<usize, u64>, values: u64, quantile: f64) -> usize {
+    let rank = ((values as f64 * quantile).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (&length, &count) in lengths {
+        seen += count;
+        if seen >= rank {
+            return length;
+        }
+    }
+    0
+}
+
+/// Decode every value of a BYTE_ARRAY column chunk and measure its length.
+///
+/// Values are streamed batch by batch, and only the number of values of each distinct length
+/// is kept, so the percentiles are exact at any chunk size.
+pub fn value_sizes<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    column: usize,
+) -> color_eyre::Result<ValueSizes> {
+    let chunk = &metadata.row_groups[row_group].columns()[column];
+    let name = chunk.descriptor().path_in_schema.join(".");
+    let physical_type = chunk.descriptor().descriptor.primitive_type.physical_type;
+    if physical_type != PhysicalType::ByteArray {
+        return Err(eyre!("{} is not a BYTE_ARRAY column", name));
+    }
+
+    let mut lengths: BTreeMap<usize, u64> = BTreeMap::new();
+    for batch in ValueStream::open(path.as_ref(), row_group, column, usize::MAX)? {
+        for value in batch? {
+            if let StatValue::Bytes(bytes) = value {
+                *lengths.entry(bytes.len()).or_default() += 1;
+            }
+        }
+    }
+
+    let values = lengths.values().sum();
+    let total_bytes = lengths
+        .iter()
+        .map(|(&length, &count)| length as u64 * count)
+        .sum();
+    let max = lengths.keys().next_back().copied().unwrap_or_default();
+    let mut buckets: Vec<SizeBucket> = (0..=bucket(max))
+        .map(|index| match index {
+            0 => SizeBucket {
+                min: 0,
+                max: 0,
+                count: 0,
+            },
+            _ => SizeBucket {
+                min: 1 << (index - 1),
+                max: (1 << index) - 1,
+                count: 0,
+            },
+        })
+        .collect();
+    for (&length, &count) in &lengths {
+        buckets[bucket(length)].count += count;
+    }
+
+    Ok(ValueSizes {
+        column: name,
+        values,
+        total_bytes,
+        p50: percentile(&lengths, values, 0.5),
+        p95: percentile(&lengths, values, 0.95),
+        p99: percentile(&lengths, values, 0.99),
+        max,
+        buckets,
+    })
+}
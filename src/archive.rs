@@ -0,0 +1,539 @@
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use bytes::Bytes;
+use flate2::read::{DeflateDecoder, GzDecoder};
+
+use crate::source::{FileSource, ReadSeek};
+
+/// Separates an archive from the path of a member inside it, e.g.
+/// `dump.zip!part-0001.parquet`.
+pub const MEMBER_SEPARATOR: char = '!';
+
+/// Bytes of a tar header block, and the alignment of member data.
+const TAR_BLOCK: u64 = 512;
+
+/// Bytes searched back from the end of a zip archive for the end of central directory record,
+/// which ends with a comment of up to 64 KiB.
+const ZIP_EOCD_SEARCH: u64 = 22 + 0xffff;
+
+const ZIP_EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_EOCD_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP_CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+
+/// Archive formats Parquet files can be read from, by file extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    /// A gzipped tar, which is decompressed in memory as a whole when opened.
+    TarGz,
+}
+
+impl ArchiveKind {
+    pub fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Split a path such as `dump.zip!data/part-0001.parquet` into the archive and the name of
+/// the member, when the part before a separator is an existing archive.
+pub fn split_member(path: &Path) -> Option<(PathBuf, String)> {
+    let path = path.to_str()?;
+    path.match_indices(MEMBER_SEPARATOR).find_map(|(i, _)| {
+        let archive = Path::new(&path[..i]);
+        (ArchiveKind::of(archive).is_some() && archive.is_file())
+            .then(|| (archive.to_path_buf(), path[i + 1..].to_string()))
+    })
+}
+
+/// Path of a member inside an archive, as understood by [split_member].
+pub fn member_path(archive: &Path, name: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}{}", archive.display(), MEMBER_SEPARATOR, name))
+}
+
+/// How the bytes of a member are stored in the archive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Storage {
+    /// As is, from `offset`.
+    Stored { offset: u64 },
+    /// Deflated, from the local header of a zip member at `header`.
+    Deflated { header: u64, compressed: u64 },
+    /// Deflated in a way this build cannot read, e.g. encrypted or in another zip method.
+    Unsupported(&'static str),
+}
+
+/// A regular file inside an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub name: String,
+    pub size: u64,
+    storage: Storage,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn read_at<R: Read + Seek + ?Sized>(
+    reader: &mut R,
+    offset: u64,
+    len: usize,
+) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// List the members of a zip archive from its central directory, with zip64 sizes and offsets.
+fn zip_members<R: Read + Seek + ?Sized>(reader: &mut R) -> io::Result<Vec<Member>> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let search = file_size.min(ZIP_EOCD_SEARCH);
+    let tail = read_at(reader, file_size - search, search as usize)?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| read_u32(&tail, i) == ZIP_EOCD_SIGNATURE)
+        .ok_or_else(|| invalid("not a zip archive: no end of central directory record"))?;
+    let mut entries = u64::from(read_u16(&tail, eocd + 10));
+    let mut directory_size = u64::from(read_u32(&tail, eocd + 12));
+    let mut directory_offset = u64::from(read_u32(&tail, eocd + 16));
+
+    // Archives over 4 GiB or 65535 members keep the real values in a zip64 record.
+    if eocd >= 20 && read_u32(&tail, eocd - 20) == ZIP64_EOCD_LOCATOR_SIGNATURE {
+        let record = read_at(reader, read_u64(&tail, eocd - 20 + 8), 56)?;
+        if read_u32(&record, 0) != ZIP64_EOCD_SIGNATURE {
+            return Err(invalid("corrupt zip64 end of central directory record"));
+        }
+        entries = read_u64(&record, 32);
+        directory_size = read_u64(&record, 40);
+        directory_offset = read_u64(&record, 48);
+    }
+
+    let directory = read_at(
+        reader,
+        directory_offset,
+        usize::try_from(directory_size).map_err(|_| invalid("zip central directory too large"))?,
+    )?;
+    let mut members = Vec::new();
+    let mut at = 0;
+    for _ in 0..entries {
+        if at + 46 > directory.len() || read_u32(&directory, at) != ZIP_CENTRAL_HEADER_SIGNATURE {
+            return Err(invalid("corrupt zip central directory"));
+        }
+        let flags = read_u16(&directory, at + 8);
+        let method = read_u16(&directory, at + 10);
+        let mut compressed = u64::from(read_u32(&directory, at + 20));
+        let mut size = u64::from(read_u32(&directory, at + 24));
+        let name_len = usize::from(read_u16(&directory, at + 28));
+        let extra_len = usize::from(read_u16(&directory, at + 30));
+        let comment_len = usize::from(read_u16(&directory, at + 32));
+        let mut header = u64::from(read_u32(&directory, at + 42));
+        let end = at + 46 + name_len + extra_len + comment_len;
+        if end > directory.len() {
+            return Err(invalid("corrupt zip central directory"));
+        }
+        let name = String::from_utf8_lossy(&directory[at + 46..at + 46 + name_len]).into_owned();
+
+        // The zip64 extra field holds the 64-bit values of the fields set to 0xffffffff.
+        let mut extra = &directory[at + 46 + name_len..at + 46 + name_len + extra_len];
+        while extra.len() >= 4 {
+            let (id, len) = (read_u16(extra, 0), usize::from(read_u16(extra, 2)));
+            let data = &extra[4..(4 + len).min(extra.len())];
+            if id == 0x0001 {
+                let mut values = data.chunks_exact(8).map(|value| read_u64(value, 0));
+                for field in [&mut size, &mut compressed, &mut header] {
+                    if *field == u64::from(u32::MAX) {
+                        *field = values.next().unwrap_or(*field);
+                    }
+                }
+            }
+            extra = &extra[(4 + len).min(extra.len())..];
+        }
+
+        if !name.ends_with('/') {
+            members.push(Member {
+                name,
+                size,
+                storage: match (flags & 1, method) {
+                    (1, _) => Storage::Unsupported("encrypted"),
+                    (_, 0) => Storage::Stored { offset: header },
+                    (_, 8) => Storage::Deflated { header, compressed },
+                    _ => Storage::Unsupported("compressed with a zip method other than deflate"),
+                },
+            });
+        }
+        at = end;
+    }
+    Ok(members)
+}
+
+/// Offset of the data of a zip member, after its local header.
+fn zip_data_offset<R: Read + Seek + ?Sized>(reader: &mut R, header: u64) -> io::Result<u64> {
+    let local = read_at(reader, header, 30)?;
+    if read_u32(&local, 0) != ZIP_LOCAL_HEADER_SIGNATURE {
+        return Err(invalid("corrupt zip local header"));
+    }
+    Ok(header + 30 + u64::from(read_u16(&local, 26)) + u64::from(read_u16(&local, 28)))
+}
+
+/// Parse a numeric tar header field: octal digits, or big-endian base-256 when its first byte
+/// has the high bit set.
+fn tar_number(field: &[u8]) -> io::Result<u64> {
+    if field.first().is_some_and(|byte| byte & 0x80 != 0) {
+        return Ok(field[1..]
+            .iter()
+            .fold(u64::from(field[0] & 0x7f), |n, &byte| {
+                n << 8 | u64::from(byte)
+            }));
+    }
+    let digits = String::from_utf8_lossy(field);
+    let digits = digits.trim_matches(|c: char| c == '\0' || c == ' ');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(digits, 8).map_err(|_| invalid("corrupt tar header"))
+}
+
+fn tar_string(field: &[u8]) -> String {
+    let end = field
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// List the regular files of a tar archive, with the long names of GNU and PAX headers.
+fn tar_members<R: Read + Seek + ?Sized>(reader: &mut R) -> io::Result<Vec<Member>> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let mut members = Vec::new();
+    let mut long_name: Option<String> = None;
+    let mut offset = 0;
+    while offset + TAR_BLOCK <= file_size {
+        let header = read_at(reader, offset, TAR_BLOCK as usize)?;
+        if header.iter().all(|&byte| byte == 0) {
+            break;
+        }
+        let size = tar_number(&header[124..136])?;
+        let data = offset + TAR_BLOCK;
+        if size > file_size - data {
+            return Err(invalid(format!(
+                "tar entry at offset {} of {} bytes extends past the end of the archive",
+                offset, size
+            )));
+        }
+        match header[156] {
+            // A GNU long name, for the next header.
+            b'L' => long_name = Some(tar_string(&read_at(reader, data, size as usize)?)),
+            // PAX extended attributes, of which only the path matters.
+            b'x' => {
+                let attributes = read_at(reader, data, size as usize)?;
+                for record in String::from_utf8_lossy(&attributes).lines() {
+                    if let Some((_, path)) = record.split_once(" path=") {
+                        long_name = Some(path.to_string());
+                    }
+                }
+            }
+            kind => {
+                let name = long_name.take().unwrap_or_else(|| {
+                    let name = tar_string(&header[..100]);
+                    match tar_string(&header[345..500]) {
+                        prefix if header[257..262] == *b"ustar" && !prefix.is_empty() => {
+                            format!("{}/{}", prefix, name)
+                        }
+                        _ => name,
+                    }
+                });
+                if kind == b'0' || kind == 0 {
+                    members.push(Member {
+                        name,
+                        size,
+                        storage: Storage::Stored { offset: data },
+                    });
+                }
+            }
+        }
+        offset = data + size.div_ceil(TAR_BLOCK) * TAR_BLOCK;
+    }
+    Ok(members)
+}
+
+/// An archive opened for reading its members.
+enum Archive {
+    File(File),
+    /// A decompressed tar.gz.
+    Memory(Bytes),
+}
+
+impl Archive {
+    fn open(path: &Path, kind: ArchiveKind) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(match kind {
+            ArchiveKind::Zip | ArchiveKind::Tar => Archive::File(file),
+            ArchiveKind::TarGz => {
+                let mut tar = Vec::new();
+                GzDecoder::new(io::BufReader::new(file)).read_to_end(&mut tar)?;
+                Archive::Memory(Bytes::from(tar))
+            }
+        })
+    }
+
+    fn reader(&self) -> io::Result<Box<dyn ReadSeek>> {
+        Ok(match self {
+            Archive::File(file) => Box::new(file.try_clone()?),
+            Archive::Memory(bytes) => Box::new(Cursor::new(bytes.clone())),
+        })
+    }
+}
+
+fn members_of(archive: &Archive, kind: ArchiveKind) -> io::Result<Vec<Member>> {
+    let mut reader = archive.reader()?;
+    match kind {
+        ArchiveKind::Zip => zip_members(&mut reader),
+        ArchiveKind::Tar | ArchiveKind::TarGz => tar_members(&mut reader),
+    }
+}
+
+/// List the regular files of an archive.
+pub fn members(path: &Path) -> io::Result<Vec<Member>> {
+    let kind = ArchiveKind::of(path)
+        .ok_or_else(|| invalid(format!("{} is not a zip or tar archive", path.display())))?;
+    members_of(&Archive::open(path, kind)?, kind)
+}
+
+/// Paths of the Parquet files of an archive, by their `.parquet` extension.
+pub fn parquet_members(path: &Path) -> io::Result<Vec<PathBuf>> {
+    Ok(members(path)?
+        .into_iter()
+        .filter(|member| member.name.to_ascii_lowercase().ends_with(".parquet"))
+        .map(|member| member_path(path, &member.name))
+        .collect())
+}
+
+/// Open a member of an archive for reading. Stored members are read in place, as a byte range
+/// of the archive, while deflated ones and members of a tar.gz are decompressed in memory.
+pub fn open_member(archive_path: &Path, name: &str) -> io::Result<FileSource> {
+    let kind = ArchiveKind::of(archive_path).ok_or_else(|| {
+        invalid(format!(
+            "{} is not a zip or tar archive",
+            archive_path.display()
+        ))
+    })?;
+    let archive = Archive::open(archive_path, kind)?;
+    let member = members_of(&archive, kind)?
+        .into_iter()
+        .find(|member| member.name == name)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} has no member {}", archive_path.display(), name),
+            )
+        })?;
+    let path = member_path(archive_path, name);
+
+    match (archive, member.storage) {
+        (_, Storage::Unsupported(reason)) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} is {}", path.display(), reason),
+        )),
+        (Archive::Memory(bytes), Storage::Stored { offset }) => {
+            let end = offset
+                .checked_add(member.size)
+                .filter(|&end| end <= bytes.len() as u64)
+                .ok_or_else(|| invalid(format!("{} is truncated", path.display())))?;
+            Ok(FileSource::Mapped(
+                bytes.slice(offset as usize..end as usize),
+            ))
+        }
+        (Archive::File(mut file), Storage::Stored { offset }) => {
+            // Zip members start after their local header.
+            let offset = match kind {
+                ArchiveKind::Zip => zip_data_offset(&mut file, offset)?,
+                _ => offset,
+            };
+            FileSource::range(file, path, offset, member.size)
+        }
+        (archive, Storage::Deflated { header, compressed }) => {
+            let mut reader = archive.reader()?;
+            let offset = zip_data_offset(&mut reader, header)?;
+            reader.seek(SeekFrom::Start(offset))?;
+            let mut data = Vec::with_capacity(member.size.min(1 << 30) as usize);
+            DeflateDecoder::new(reader.take(compressed)).read_to_end(&mut data)?;
+            if data.len() as u64 != member.size {
+                return Err(invalid(format!(
+                    "{} inflated to {} bytes instead of {}",
+                    path.display(),
+                    data.len(),
+                    member.size
+                )));
+            }
+            Ok(FileSource::Mapped(Bytes::from(data)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    /// A tar header block of a member, with only the fields the reader uses.
+    fn tar_header(name: &str, size: u64, kind: u8) -> Vec<u8> {
+        let mut header = vec![0; TAR_BLOCK as usize];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[124..135].copy_from_slice(format!("{:011o}", size).as_bytes());
+        header[156] = kind;
+        header
+    }
+
+    /// A tar archive of the given members, the last one under a GNU long name.
+    fn tar(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (i, (name, data)) in members.iter().enumerate() {
+            if i + 1 == members.len() {
+                tar.extend(tar_header("././@LongLink", name.len() as u64, b'L'));
+                tar.extend(name.as_bytes());
+                tar.resize(tar.len().next_multiple_of(TAR_BLOCK as usize), 0);
+                tar.extend(tar_header("truncated", data.len() as u64, b'0'));
+            } else {
+                tar.extend(tar_header(name, data.len() as u64, b'0'));
+            }
+            tar.extend(*data);
+            tar.resize(tar.len().next_multiple_of(TAR_BLOCK as usize), 0);
+        }
+        tar.extend([0; 2 * TAR_BLOCK as usize]);
+        tar
+    }
+
+    /// A path in a fresh temporary directory, removed with its contents when dropped.
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(test: &str, name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "parquet_console_{}_{}",
+                test,
+                std::process::id()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            TempPath(dir.join(name))
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(self.0.parent().unwrap());
+        }
+    }
+
+    fn read_member(archive: &Path, name: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        open_member(archive, name)
+            .unwrap()
+            .reader()
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        data
+    }
+
+    const LONG_NAME: &str = "a/very/long/directory/name/that/does/not/fit/in/the/hundred/bytes/\
+                             of/a/tar/header/part-0001.parquet";
+
+    #[test]
+    fn splits_members_of_existing_archives() {
+        let archive = TempPath::new("split", "dump.tar");
+        fs::write(&archive.0, tar(&[("x", b"x")])).unwrap();
+
+        let path = member_path(&archive.0, "data/part-0.parquet");
+        assert_eq!(
+            split_member(&path),
+            Some((archive.0.clone(), "data/part-0.parquet".to_string()))
+        );
+        assert_eq!(split_member(&archive.0), None);
+        assert_eq!(split_member(Path::new("missing.tar!part-0.parquet")), None);
+        assert_eq!(split_member(Path::new("notes.txt!part-0.parquet")), None);
+    }
+
+    #[test]
+    fn lists_and_reads_tar_members() {
+        let archive = TempPath::new("tar", "dump.tar");
+        fs::write(
+            &archive.0,
+            tar(&[("first.parquet", b"first"), (LONG_NAME, b"second")]),
+        )
+        .unwrap();
+
+        let members = members(&archive.0).unwrap();
+        let names: Vec<_> = members.iter().map(|member| member.name.as_str()).collect();
+        assert_eq!(names, ["first.parquet", LONG_NAME]);
+        assert_eq!(members[1].size, 6);
+        assert_eq!(
+            parquet_members(&archive.0).unwrap(),
+            [
+                member_path(&archive.0, "first.parquet"),
+                member_path(&archive.0, LONG_NAME)
+            ]
+        );
+        assert_eq!(read_member(&archive.0, LONG_NAME), b"second");
+        assert_eq!(
+            open_member(&archive.0, "missing.parquet")
+                .err()
+                .unwrap()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn reads_tar_gz_members() {
+        let archive = TempPath::new("tar_gz", "dump.tar.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar(&[("a.parquet", b"data")])).unwrap();
+        fs::write(&archive.0, encoder.finish().unwrap()).unwrap();
+
+        assert_eq!(read_member(&archive.0, "a.parquet"), b"data");
+    }
+
+    #[test]
+    fn rejects_tar_members_past_the_end() {
+        let archive = TempPath::new("oversized", "dump.tar");
+        let mut tar = tar_header("huge.parquet", 1 << 32, b'0');
+        tar.extend([0; TAR_BLOCK as usize]);
+        fs::write(&archive.0, tar).unwrap();
+
+        assert_eq!(
+            members(&archive.0).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{goto::column_paths, ActivePane, App, Overlay, StatField};
+
+/// Segment of the navigation path shown above the browsers, e.g.
+/// `events.parquet ▸ row group 4 ▸ column user.address.zip ▸ page 12`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Crumb {
+    File(String),
+    RowGroup(usize),
+    Column(String),
+    Stat(StatField),
+    /// The view shown over the browsers.
+    View(String),
+}
+
+impl Crumb {
+    pub fn label(&self) -> String {
+        match self {
+            Crumb::File(name) => name.clone(),
+            Crumb::RowGroup(row_group) => format!("row group {}", row_group),
+            Crumb::Column(path) => format!("column {}", path),
+            Crumb::Stat(field) => field.label().to_string(),
+            Crumb::View(label) => label.clone(),
+        }
+    }
+}
+
+/// Navigation path of the app, from the file down to the focused pane or view.
+pub fn breadcrumbs(app: &App) -> Vec<Crumb> {
+    let mut crumbs = vec![
+        Crumb::File(app.file_name.clone()),
+        Crumb::RowGroup(app.row_group_view_state.selected().unwrap_or_default()),
+    ];
+    if app.active_pane != ActivePane::RowGroupBrowser {
+        let column = app.column_chunk_view_state.selected().unwrap_or_default();
+        if let Some(path) = column_paths(&app.parquet_metadata).get(column) {
+            crumbs.push(Crumb::Column(path.clone()));
+        }
+    }
+    if app.active_pane == ActivePane::ColumnDetail {
+        crumbs.push(Crumb::Stat(app.stat_field));
+    }
+    if let Some(overlay) = &app.overlay {
+        crumbs.push(Crumb::View(view_label(overlay)));
+    }
+    crumbs
+}
+
+/// Breadcrumb label of a view, naming the page or record it is focused on when it has one.
+fn view_label(overlay: &Overlay) -> String {
+    match overlay {
+        Overlay::PageOverhead { .. } => "page overhead".to_string(),
+        Overlay::SizeCharts { .. } => "row group sizes".to_string(),
+        Overlay::SizeBreakdown { .. } => "size breakdown".to_string(),
+        Overlay::RemoteReads { .. } => "remote reads".to_string(),
+        Overlay::PageRanges { state, .. } => match state.selected() {
+            Some(page) => format!("page {}", page),
+            None => "page ranges".to_string(),
+        },
+        Overlay::Levels { .. } => "levels".to_string(),
+        Overlay::ValueSizes { .. } => "value sizes".to_string(),
+        Overlay::Decompression { profile, state, .. } => match state.selected() {
+            Some(page) if page < profile.pages.len() => format!("page {}", page),
+            _ => "decompression".to_string(),
+        },
+        Overlay::StatsPresence { .. } => "statistics presence".to_string(),
+        Overlay::ScanStats { .. } => "scan".to_string(),
+        Overlay::ColumnRollup { .. } => "column statistics".to_string(),
+        Overlay::Preview(_) => "preview".to_string(),
+        Overlay::Record { row, .. } => format!("record {}", row),
+        Overlay::Compare { .. } => "compare".to_string(),
+        Overlay::Files { .. } => "files".to_string(),
+        Overlay::Yank { .. } => "copy".to_string(),
+        Overlay::Bookmarks { .. } => "bookmarks".to_string(),
+        Overlay::TypeFilter { .. } => "type filter".to_string(),
+        Overlay::Ddl { dialect, .. } => format!("{} DDL", dialect.name()),
+        Overlay::Value { title, .. } => title.clone(),
+    }
+}
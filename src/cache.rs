@@ -0,0 +1,272 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::eyre::{eyre, Context};
+use parquet2::metadata::FileMetaData;
+use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
+
+use crate::{
+    lazy_footer::{self, FooterSummary, LazyFooter},
+    source::{self, FileIdentity, FileSource},
+};
+
+/// Footers smaller than this are cheap to re-read and are never cached.
+const MIN_CACHED_FOOTER_SIZE: u64 = 64 * 1024;
+
+/// Upper bound on the size of a footer, to protect against corrupt files.
+pub const MAX_FOOTER_SIZE: usize = 1 << 30;
+
+/// Total size of the cached footers above which the least recently used ones are removed.
+const MAX_CACHE_SIZE: u64 = 1 << 30;
+
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+static CACHE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Bypass the footer cache for the rest of the process, e.g. for `--no-cache`.
+pub fn disable() {
+    CACHE_ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    CACHE_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Directory holding cached footers, `$XDG_CACHE_HOME/parquet_console/footers` or
+/// `~/.cache/parquet_console/footers`.
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(cache_home) => PathBuf::from(cache_home),
+        None => {
+            PathBuf::from(std::env::var_os("HOME").filter(|dir| !dir.is_empty())?).join(".cache")
+        }
+    };
+    Some(base.join("parquet_console").join("footers"))
+}
+
+/// Identity of a file version: its canonical path, size and modification time. A change to
+/// any of them invalidates the cached footer. Objects of a bucket are keyed by their URI.
+fn cache_key(path: &Path) -> color_eyre::Result<String> {
+    let (name, identity) = match source::remote_scheme(path) {
+        Some(_) => (path.to_path_buf(), FileIdentity::of(path)?),
+        None => {
+            let canonical = fs::canonicalize(path)?;
+            let identity = FileIdentity::from(&fs::metadata(&canonical)?);
+            (canonical, identity)
+        }
+    };
+    let modified = identity
+        .modified
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    Ok(format!(
+        "{}\0{}\0{}",
+        name.display(),
+        identity.len,
+        modified
+    ))
+}
+
+fn cache_entry(key: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    Some(cache_dir()?.join(format!("{:016x}.footer", hasher.finish())))
+}
+
+/// Read the serialized footer of a file, without decoding it.
+pub fn read_footer_bytes<R: Read + Seek>(reader: &mut R) -> color_eyre::Result<Vec<u8>> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    if file_size < 12 {
+        return Err(eyre!("file is too small to be a Parquet file"));
+    }
+
+    let mut trailer = [0u8; 8];
+    reader.seek(SeekFrom::End(-8))?;
+    reader.read_exact(&mut trailer)?;
+    if &trailer[4..] != PARQUET_MAGIC {
+        return Err(eyre!("file does not end with the Parquet magic bytes"));
+    }
+
+    let footer_len = u64::from(u32::from_le_bytes(trailer[..4].try_into().unwrap()));
+    if footer_len + 12 > file_size {
+        return Err(eyre!("footer length {} exceeds the file size", footer_len));
+    }
+
+    let mut footer = vec![0; footer_len as usize];
+    reader.seek(SeekFrom::End(-8 - footer_len as i64))?;
+    reader.read_exact(&mut footer)?;
+
+    Ok(footer)
+}
+
+/// Decode a serialized footer.
+pub fn decode_footer(footer: &[u8]) -> color_eyre::Result<FileMetaData> {
+    let mut protocol = TCompactInputProtocol::new(footer, MAX_FOOTER_SIZE);
+    let metadata = parquet_format_safe::FileMetaData::read_from_in_protocol(&mut protocol)?;
+    Ok(FileMetaData::try_from_thrift(metadata)?)
+}
+
+/// Read the footer metadata of a file, going through the footer cache when it is enabled.
+/// Every row group is decoded, cached or not.
+pub fn read_metadata_cached(path: &Path) -> color_eyre::Result<FileMetaData> {
+    let entry = CacheEntry::of(path);
+    if let Some((footer, _)) = entry.as_ref().and_then(CacheEntry::load) {
+        tracing::debug!("footer cache hit for {}", path.display());
+        return decode_footer(&footer);
+    }
+
+    let footer = read_footer_from_file(path)?;
+    let metadata = decode_footer(&footer)?;
+    if let Some(entry) = entry {
+        if footer.len() as u64 >= MIN_CACHED_FOOTER_SIZE {
+            if let Ok(summary) = FooterSummary::scan(&footer) {
+                entry.store(&footer, &summary);
+            }
+        }
+    }
+    Ok(metadata)
+}
+
+/// Read the footer of a file for the TUI, going through the footer cache when it is enabled.
+///
+/// Footers read from the cache come with the summary of their row groups, so they are decoded
+/// lazily whatever their size: opening a cached file decodes its schema and the row groups
+/// shown, not every column chunk. See [lazy_footer::decode] for the others.
+pub fn read_footer_cached(path: &Path) -> color_eyre::Result<(FileMetaData, Option<LazyFooter>)> {
+    let entry = CacheEntry::of(path);
+    if let Some((footer, summary)) = entry.as_ref().and_then(CacheEntry::load) {
+        match lazy_footer::decode_lazily(footer, summary) {
+            Ok((metadata, lazy_footer)) => {
+                tracing::debug!("footer cache hit for {}", path.display());
+                return Ok((metadata, Some(lazy_footer)));
+            }
+            Err(err) => tracing::warn!("ignoring corrupt footer cache entry: {}", err),
+        }
+    }
+
+    let footer = read_footer_from_file(path)?;
+    let summary = FooterSummary::scan(&footer)?;
+    if let Some(entry) = entry {
+        if footer.len() as u64 >= MIN_CACHED_FOOTER_SIZE {
+            entry.store(&footer, &summary);
+        }
+    }
+    lazy_footer::decode(footer, summary)
+}
+
+fn read_footer_from_file(path: &Path) -> color_eyre::Result<Vec<u8>> {
+    let mut reader = FileSource::open(path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    read_footer_bytes(&mut reader)
+}
+
+/// The cache file of a file version.
+///
+/// It holds the key of the file version, the [FooterSummary] of its row groups and the
+/// serialized footer, each starting on a new line. Failing to read or write it is not an
+/// error: the footer is read from the file instead.
+struct CacheEntry {
+    key: String,
+    file: PathBuf,
+}
+
+impl CacheEntry {
+    fn of(path: &Path) -> Option<Self> {
+        if !is_enabled() {
+            return None;
+        }
+        let key = cache_key(path).ok()?;
+        let file = cache_entry(&key)?;
+        Some(CacheEntry { key, file })
+    }
+
+    /// The footer and the summary cached for this file version, if any. Marks the entry as
+    /// used, so that it is removed last when the cache is pruned.
+    fn load(&self) -> Option<(Vec<u8>, FooterSummary)> {
+        let mut contents = fs::read(&self.file).ok()?;
+        let prefix = self.key.len() + 1;
+        if contents.len() <= prefix
+            || contents[..self.key.len()] != *self.key.as_bytes()
+            || contents[self.key.len()] != b'\n'
+        {
+            return None;
+        }
+        let Some((summary, len)) = FooterSummary::parse(&contents[prefix..]) else {
+            tracing::warn!(
+                "ignoring corrupt footer cache entry {}",
+                self.file.display()
+            );
+            return None;
+        };
+        if let Err(err) = File::options()
+            .write(true)
+            .open(&self.file)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            tracing::debug!("could not touch {}: {}", self.file.display(), err);
+        }
+        Some((contents.split_off(prefix + len), summary))
+    }
+
+    fn store(&self, footer: &[u8], summary: &FooterSummary) {
+        let summary = summary.encode();
+        let mut contents = Vec::with_capacity(self.key.len() + 1 + summary.len() + footer.len());
+        contents.extend_from_slice(self.key.as_bytes());
+        contents.push(b'\n');
+        contents.extend_from_slice(summary.as_bytes());
+        contents.extend_from_slice(footer);
+        let Some(dir) = self.file.parent() else {
+            return;
+        };
+        if let Err(err) = fs::create_dir_all(dir).and_then(|_| fs::write(&self.file, contents)) {
+            tracing::debug!("could not cache footer in {}: {}", self.file.display(), err);
+            return;
+        }
+        if let Err(err) = prune(dir, MAX_CACHE_SIZE) {
+            tracing::debug!(
+                "could not prune the footer cache {}: {}",
+                dir.display(),
+                err
+            );
+        }
+    }
+}
+
+/// Remove the least recently used footers of the cache in `dir` until the rest add up to
+/// `max_size` bytes at most.
+fn prune(dir: &Path, max_size: u64) -> std::io::Result<()> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.path().extension() != Some("footer".as_ref()) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        entries.push((
+            metadata.modified().unwrap_or(UNIX_EPOCH),
+            metadata.len(),
+            entry.path(),
+        ));
+    }
+
+    let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+    entries.sort();
+    for (_, len, path) in entries {
+        if total <= max_size {
+            break;
+        }
+        fs::remove_file(&path)?;
+        total -= len;
+    }
+    Ok(())
+}
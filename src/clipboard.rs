@@ -0,0 +1,20 @@
+use arboard::Clipboard;
+
+/// Access to the system clipboard, opened on first use.
+///
+/// The clipboard is kept open for the lifetime of the app since on X11 the copied text is
+/// only available while its owner is alive.
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn copy(&mut self, text: &str) -> Result<(), arboard::Error> {
+        let clipboard = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(Clipboard::new()?),
+        };
+        clipboard.set_text(text)
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    fmt,
+    io::{Read, Seek},
+};
+
+use parquet2::{
+    compression::Compression,
+    error::{Error, Feature},
+    metadata::ColumnChunkMetaData,
+};
+
+use crate::pages::{read_page_headers, read_page_payload};
+
+/// Whether this build can decompress pages of a codec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecSupport {
+    Supported,
+    /// The codec needs a Cargo feature of the Parquet crates that this build was compiled
+    /// without, named as in parquet2, e.g. `zstd`.
+    MissingFeature(String),
+    /// No build of the console can decompress the codec, e.g. LZO.
+    Unsupported,
+}
+
+impl CodecSupport {
+    pub fn is_supported(&self) -> bool {
+        *self == CodecSupport::Supported
+    }
+}
+
+impl fmt::Display for CodecSupport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecSupport::Supported => write!(f, "supported"),
+            CodecSupport::MissingFeature(feature) => write!(
+                f,
+                "not built in: rebuild with the `{}` feature of the Parquet crates",
+                feature
+            ),
+            CodecSupport::Unsupported => write!(f, "not supported by parquet_console"),
+        }
+    }
+}
+
+/// Probe whether the codec can be decompressed, by decompressing an empty buffer: codecs
+/// compiled out fail with a feature error before looking at the input.
+pub fn codec_support(compression: Compression) -> CodecSupport {
+    if compression == Compression::Uncompressed {
+        return CodecSupport::Supported;
+    }
+    match parquet2::compression::decompress(compression, &[], &mut []) {
+        Err(Error::FeatureNotActive(feature, _)) => CodecSupport::MissingFeature(
+            match feature {
+                Feature::Snappy => "snappy",
+                Feature::Brotli => "brotli",
+                Feature::Gzip => "gzip",
+                Feature::Lz4 => "lz4",
+                Feature::Zstd => "zstd",
+                _ => "default",
+            }
+            .to_string(),
+        ),
+        Err(Error::FeatureNotSupported(_)) => CodecSupport::Unsupported,
+        _ => CodecSupport::Supported,
+    }
+}
+
+/// Human-readable name of a codec, as in the Parquet specification.
+pub fn codec_name(compression: Compression) -> &'static str {
+    match compression {
+        Compression::Uncompressed => "UNCOMPRESSED",
+        Compression::Snappy => "SNAPPY",
+        Compression::Gzip => "GZIP",
+        Compression::Lzo => "LZO",
+        Compression::Brotli => "BROTLI",
+        Compression::Lz4 => "LZ4",
+        Compression::Zstd => "ZSTD",
+        Compression::Lz4Raw => "LZ4_RAW",
+    }
+}
+
+/// Compression level of a column chunk, where the codec embeds a hint of it in its output.
+///
+/// Only gzip does, through the XFL byte of its header: writers set it for the fastest and
+/// the best compression levels. Other codecs with levels do not record them, and SNAPPY and
+/// LZ4 have none.
+pub fn level_hint<R: Read + Seek>(
+    reader: &mut R,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<String> {
+    Ok(match chunk.compression() {
+        Compression::Gzip => {
+            let pages = read_page_headers(reader, chunk)?;
+            let Some(page) = pages.first() else {
+                return Ok("unknown, no pages".to_string());
+            };
+            let payload = read_page_payload(reader, page)?;
+            // Levels at the start of v2 data pages are not compressed.
+            let prefix = page
+                .header
+                .data_page_header_v2
+                .as_ref()
+                .map(|header| {
+                    (header.definition_levels_byte_length + header.repetition_levels_byte_length)
+                        .max(0) as usize
+                })
+                .unwrap_or_default();
+            match payload.get(prefix..prefix + 10) {
+                Some([0x1f, 0x8b, _, _, _, _, _, _, 2, _]) => "9 (best compression)".to_string(),
+                Some([0x1f, 0x8b, _, _, _, _, _, _, 4, _]) => "1 (fastest)".to_string(),
+                Some([0x1f, 0x8b, ..]) => "between 2 and 8, not recorded".to_string(),
+                _ => "unknown, no gzip header".to_string(),
+            }
+        }
+        Compression::Zstd | Compression::Brotli => "not recorded by the codec".to_string(),
+        Compression::Uncompressed
+        | Compression::Snappy
+        | Compression::Lz4
+        | Compression::Lz4Raw
+        | Compression::Lzo => "n/a".to_string(),
+    })
+}
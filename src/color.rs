@@ -0,0 +1,51 @@
+use std::{
+    io::{stderr, stdout, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Render without colors for the rest of the process, e.g. for `--no-color`.
+pub fn disable() {
+    COLOR_ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    COLOR_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether the environment asks for output without colors: `NO_COLOR` is set to a non-empty
+/// value or the terminal is dumb.
+pub fn disabled_by_env() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+        || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// Whether log lines printed to stderr may be colored: colors are enabled and stderr is a
+/// terminal rather than a file or pipe such as a CI log.
+pub fn stderr_colored() -> bool {
+    is_enabled() && stderr().is_terminal()
+}
+
+/// Whether standard output cannot show the TUI: it is not a terminal, or `TERM=dumb` lacks
+/// the cursor movements the TUI draws with.
+pub fn is_dumb_terminal() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb") || !stdout().is_terminal()
+}
+
+/// Strip the colors of a rendered screen. Highlights, drawn as dark text on a light
+/// background, are kept as reverse video.
+pub fn monochrome(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        if !matches!(cell.bg, Color::Reset | Color::Black) {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
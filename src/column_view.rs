@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use parquet2::{
+    metadata::FileMetaData,
+    schema::types::{PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType},
+};
+
+use crate::parquet::PhysicalTypeExt;
+
+/// Type the column browser can be filtered by, named as in the browser.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TypeFilter {
+    Physical(&'static str),
+    /// A logical type, or the converted type older writers annotate columns with, ignoring
+    /// its parameters: every timestamp unit is a `TIMESTAMP`.
+    Logical(&'static str),
+}
+
+impl TypeFilter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypeFilter::Physical(name) | TypeFilter::Logical(name) => name,
+        }
+    }
+
+    /// Name of the logical type of a column, if it has one.
+    fn logical_name(primitive_type: &PrimitiveType) -> Option<&'static str> {
+        if let Some(logical) = &primitive_type.logical_type {
+            return Some(match logical {
+                PrimitiveLogicalType::String => "STRING",
+                PrimitiveLogicalType::Enum => "ENUM",
+                PrimitiveLogicalType::Decimal(..) => "DECIMAL",
+                PrimitiveLogicalType::Date => "DATE",
+                PrimitiveLogicalType::Time { .. } => "TIME",
+                PrimitiveLogicalType::Timestamp { .. } => "TIMESTAMP",
+                PrimitiveLogicalType::Integer(_) => "INTEGER",
+                PrimitiveLogicalType::Unknown => "UNKNOWN",
+                PrimitiveLogicalType::Json => "JSON",
+                PrimitiveLogicalType::Bson => "BSON",
+                PrimitiveLogicalType::Uuid => "UUID",
+            });
+        }
+        Some(match primitive_type.converted_type? {
+            PrimitiveConvertedType::Utf8 => "STRING",
+            PrimitiveConvertedType::Enum => "ENUM",
+            PrimitiveConvertedType::Decimal(..) => "DECIMAL",
+            PrimitiveConvertedType::Date => "DATE",
+            PrimitiveConvertedType::TimeMillis | PrimitiveConvertedType::TimeMicros => "TIME",
+            PrimitiveConvertedType::TimestampMillis | PrimitiveConvertedType::TimestampMicros => {
+                "TIMESTAMP"
+            }
+            PrimitiveConvertedType::Uint8
+            | PrimitiveConvertedType::Uint16
+            | PrimitiveConvertedType::Uint32
+            | PrimitiveConvertedType::Uint64
+            | PrimitiveConvertedType::Int8
+            | PrimitiveConvertedType::Int16
+            | PrimitiveConvertedType::Int32
+            | PrimitiveConvertedType::Int64 => "INTEGER",
+            PrimitiveConvertedType::Json => "JSON",
+            PrimitiveConvertedType::Bson => "BSON",
+            PrimitiveConvertedType::Interval => "INTERVAL",
+        })
+    }
+
+    /// The physical type of a column, and its logical type if it has one.
+    pub fn of(primitive_type: &PrimitiveType) -> Vec<TypeFilter> {
+        let mut types = vec![TypeFilter::Physical(
+            primitive_type.physical_type.human_readable(),
+        )];
+        types.extend(Self::logical_name(primitive_type).map(TypeFilter::Logical));
+        types
+    }
+
+    pub fn matches(&self, primitive_type: &PrimitiveType) -> bool {
+        Self::of(primitive_type).contains(self)
+    }
+
+    /// Types of the leaf columns of a file, physical types first, with their column counts.
+    pub fn available(metadata: &FileMetaData) -> Vec<(TypeFilter, usize)> {
+        let mut counts = BTreeMap::new();
+        for column in metadata.schema().columns() {
+            for type_filter in Self::of(&column.descriptor.primitive_type) {
+                *counts.entry(type_filter).or_insert(0) += 1;
+            }
+        }
+        counts.into_iter().collect()
+    }
+}
+
+/// A row of the column browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRow {
+    /// Header of the leaf columns nested in a top-level group field, e.g. `event` for
+    /// `event.*`, listing those shown by the type filter.
+    Group {
+        name: String,
+        columns: Vec<usize>,
+        collapsed: bool,
+    },
+    /// A leaf column, by index.
+    Column(usize),
+}
+
+impl ColumnRow {
+    /// Column selected on the row: the leaf itself, or the first leaf of a collapsed group.
+    /// Headers of expanded groups cannot be selected.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            ColumnRow::Group {
+                columns, collapsed, ..
+            } => columns.first().filter(|_| *collapsed).copied(),
+            ColumnRow::Column(column) => Some(*column),
+        }
+    }
+}
+
+/// How the column browser lays out the leaf columns: grouped under a header per top-level
+/// group field, so that deeply nested schemas stay navigable, with some groups collapsed, and
+/// possibly limited to the columns of a type.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnView {
+    /// Names of the collapsed top-level fields.
+    pub collapsed: BTreeSet<String>,
+    pub type_filter: Option<TypeFilter>,
+}
+
+impl ColumnView {
+    /// Whether the type filter lets a column through.
+    pub fn shows(&self, metadata: &FileMetaData, column: usize) -> bool {
+        self.type_filter.is_none_or(|type_filter| {
+            metadata
+                .schema()
+                .columns()
+                .get(column)
+                .is_some_and(|column| type_filter.matches(&column.descriptor.primitive_type))
+        })
+    }
+
+    /// Rows of the browser, in schema order. Leaves of a collapsed group, columns the type
+    /// filter hides and groups left without columns are left out.
+    pub fn rows(&self, metadata: &FileMetaData) -> Vec<ColumnRow> {
+        let columns = metadata.schema().columns();
+        let mut rows = Vec::new();
+        let mut start = 0;
+        while start < columns.len() {
+            let path = &columns[start].path_in_schema;
+            if path.len() == 1 {
+                if self.shows(metadata, start) {
+                    rows.push(ColumnRow::Column(start));
+                }
+                start += 1;
+                continue;
+            }
+
+            let name = &path[0];
+            let end = start
+                + columns[start..]
+                    .iter()
+                    .take_while(|column| column.path_in_schema.len() > 1)
+                    .take_while(|column| &column.path_in_schema[0] == name)
+                    .count();
+            let shown: Vec<usize> = (start..end)
+                .filter(|&column| self.shows(metadata, column))
+                .collect();
+            start = end;
+            if shown.is_empty() {
+                continue;
+            }
+
+            let collapsed = self.collapsed.contains(name);
+            rows.push(ColumnRow::Group {
+                name: name.clone(),
+                columns: shown.clone(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(shown.into_iter().map(ColumnRow::Column));
+            }
+        }
+        rows
+    }
+
+    /// Index of the row showing `column`: its own row, or the header of its collapsed group.
+    pub fn row_of(rows: &[ColumnRow], column: usize) -> Option<usize> {
+        rows.iter().position(|row| match row {
+            ColumnRow::Group {
+                columns, collapsed, ..
+            } => *collapsed && columns.contains(&column),
+            ColumnRow::Column(other) => *other == column,
+        })
+    }
+
+    /// Column selected `delta` selectable rows away from the one showing `column`, wrapping
+    /// around.
+    pub fn step(&self, metadata: &FileMetaData, column: usize, delta: isize) -> usize {
+        let rows = self.rows(metadata);
+        let selectable: Vec<usize> = rows.iter().filter_map(ColumnRow::column).collect();
+        let current = Self::row_of(&rows, column)
+            .and_then(|row| rows[row].column())
+            .and_then(|column| selectable.iter().position(|&other| other == column));
+        match current {
+            Some(index) => {
+                let len = selectable.len() as isize;
+                selectable[(index as isize + delta).rem_euclid(len) as usize]
+            }
+            None => selectable.first().copied().unwrap_or(column),
+        }
+    }
+
+    /// Name of the top-level group field `column` is nested in, if any.
+    fn group_of(metadata: &FileMetaData, column: usize) -> Option<String> {
+        let path = &metadata.schema().columns().get(column)?.path_in_schema;
+        (path.len() > 1).then(|| path[0].clone())
+    }
+
+    /// Collapse or expand the group `column` is nested in. Returns the column to select
+    /// afterwards, the first shown of the group once collapsed, or `None` if `column` is not
+    /// nested.
+    pub fn toggle(&mut self, metadata: &FileMetaData, column: usize) -> Option<usize> {
+        let name = Self::group_of(metadata, column)?;
+        if self.collapsed.remove(&name) {
+            return Some(column);
+        }
+        self.collapsed.insert(name.clone());
+        (0..=column)
+            .find(|&first| {
+                Self::group_of(metadata, first).as_ref() == Some(&name)
+                    && self.shows(metadata, first)
+            })
+            .or(Some(column))
+    }
+
+    /// Collapse every group, or expand them all.
+    pub fn set_all_collapsed(&mut self, metadata: &FileMetaData, collapsed: bool) {
+        self.collapsed = if collapsed {
+            (0..metadata.schema().columns().len())
+                .filter_map(|column| Self::group_of(metadata, column))
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+    }
+}
@@ -0,0 +1,32 @@
+use clap::Args;
+
+pub mod audit;
+pub mod bloom;
+pub mod canvas;
+pub mod chunk_bytes;
+pub mod count;
+pub mod dataset_stats;
+pub mod diff_column;
+pub mod encodings;
+pub mod export;
+pub mod generate;
+pub mod layout;
+pub mod layout_check;
+pub mod memory;
+pub mod meta;
+pub mod offset;
+pub mod pushdown;
+pub mod query;
+pub mod recover;
+pub mod schema;
+pub mod schema_check;
+pub mod serve;
+pub mod sortedness;
+pub mod strings;
+
+/// Arguments of the `completions` subcommand.
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    #[arg(value_enum, value_name = "SHELL")]
+    pub shell: clap_complete::Shell,
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::pii::audit,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `audit` subcommand.
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values sampled from every BYTE_ARRAY column, across its row groups.
+    #[arg(long, default_value_t = 1000)]
+    pub sample: usize,
+}
+
+/// Flag the columns likely to contain personal data, with a confidence level and the evidence
+/// for it.
+pub fn run(args: &AuditArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let findings = audit(&args.file, &metadata, args.sample);
+
+    let rows = findings
+        .into_iter()
+        .map(|finding| {
+            vec![
+                Value::from(finding.column),
+                finding.kind.as_str().into(),
+                finding.confidence.as_str().into(),
+                finding.evidence.join("; ").into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new().table(
+        "findings",
+        &["column", "kind", "confidence", "evidence"],
+        rows,
+    ))
+}
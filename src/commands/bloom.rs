@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::bloom::bloom_coverage,
+    output::{Report, Value},
+    parquet::{read_file_metadata, PhysicalTypeExt},
+};
+
+/// Arguments of the `bloom` subcommand.
+#[derive(Args, Debug)]
+pub struct BloomArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values sampled from a column chunk to estimate its distinct values, when its
+    /// statistics have no distinct count.
+    #[arg(long, default_value_t = 1000)]
+    pub sample: usize,
+}
+
+/// List the bloom filters of every column chunk with their size and estimated false positive
+/// probability, and flag high-cardinality string columns without them.
+pub fn run(args: &BloomArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let coverage = bloom_coverage(&args.file, &metadata, args.sample)?;
+
+    let filters = coverage
+        .filters
+        .into_iter()
+        .map(|filter| {
+            vec![
+                Value::from(filter.row_group),
+                filter.column.into(),
+                filter.offset.into(),
+                filter.size.into(),
+                filter.distinct.map(|(distinct, _)| distinct).into(),
+                filter
+                    .distinct
+                    .map(|(_, source)| source.as_str().to_string())
+                    .into(),
+                filter.fpp.map(|fpp| format!("{:.4}%", fpp * 100.0)).into(),
+            ]
+        })
+        .collect();
+
+    let columns = coverage
+        .columns
+        .into_iter()
+        .map(|column| {
+            vec![
+                Value::from(column.column.clone()),
+                column.physical_type.human_readable().into(),
+                format!("{} of {}", column.row_groups_with_filter, column.row_groups).into(),
+                column.bytes.into(),
+                column
+                    .distinct_ratio
+                    .map(|ratio| format!("{:.1}%", ratio * 100.0))
+                    .into(),
+                column
+                    .lacks_filter()
+                    .then(|| "high-cardinality string column, consider a bloom filter".to_string())
+                    .into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new()
+        .table(
+            "bloom_filters",
+            &[
+                "row_group",
+                "column",
+                "offset",
+                "bytes",
+                "distinct",
+                "distinct_from",
+                "estimated_fpp",
+            ],
+            filters,
+        )
+        .table(
+            "columns",
+            &[
+                "column",
+                "type",
+                "row_groups_with_filter",
+                "bytes",
+                "sampled_distinct",
+                "suggestion",
+            ],
+            columns,
+        ))
+}
@@ -0,0 +1,42 @@
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use color_eyre::eyre::Context;
+
+use crate::{
+    layout_image::row_group_svg,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `canvas` subcommand.
+#[derive(Args, Debug)]
+pub struct CanvasArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Path of the SVG image to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// Draw the row groups of a file, split into their column chunks and scaled by size, as an
+/// SVG image, e.g. for design docs about file layout problems.
+pub fn run(args: &CanvasArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let file_name = args
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| args.file.display().to_string());
+    fs::write(&args.output, row_group_svg(&file_name, &metadata))
+        .wrap_err_with(|| format!("failed to write {}", args.output.display()))?;
+
+    Ok(Report::new().record(
+        "canvas",
+        [
+            ("output", Value::from(args.output.display().to_string())),
+            ("row_groups", Value::from(metadata.row_groups.len())),
+        ],
+    ))
+}
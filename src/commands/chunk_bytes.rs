@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use color_eyre::eyre::eyre;
+
+use crate::{
+    export::export_chunk_bytes,
+    goto::column_paths,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `chunk-bytes` subcommand.
+#[derive(Args, Debug)]
+pub struct ChunkBytesArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    #[arg(long)]
+    pub row_group: usize,
+
+    /// Dotted path of the column, e.g. `address.zip`.
+    #[arg(long)]
+    pub column: String,
+
+    /// Path of the binary file to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// Copy the raw byte range of a column chunk into a binary file.
+pub fn run(args: &ChunkBytesArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let column = column_paths(&metadata)
+        .iter()
+        .position(|path| *path == args.column)
+        .ok_or_else(|| eyre!("no column named {:?}", args.column))?;
+
+    let (offset, length) =
+        export_chunk_bytes(&args.file, &metadata, args.row_group, column, &args.output)?;
+
+    Ok(Report::new().record(
+        "chunk_bytes",
+        [
+            ("output", Value::from(args.output.display().to_string())),
+            ("offset", Value::from(offset)),
+            ("length", Value::from(length)),
+        ],
+    ))
+}
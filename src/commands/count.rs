@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    output::{Report, Value},
+    parquet::read_file_metadata,
+    preview::{count_matches, Predicate},
+};
+
+/// Arguments of the `count` subcommand.
+#[derive(Args, Debug)]
+pub struct CountArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Only count rows matching a filter, e.g. `'score > 5'` or `'name is null'`.
+    #[arg(long = "where", value_name = "PREDICATE")]
+    pub predicate: Option<Predicate>,
+}
+
+/// Count the rows of a file: from the footer alone, or by streaming the filtered column when
+/// a predicate is given.
+pub fn run(args: &CountArgs) -> color_eyre::Result<Report> {
+    let fields = match &args.predicate {
+        None => {
+            let metadata = read_file_metadata(&args.file)?;
+            vec![
+                ("rows", Value::from(metadata.num_rows)),
+                ("source", "footer".into()),
+            ]
+        }
+        Some(predicate) => {
+            predicate.decimal_literal(&read_file_metadata(&args.file)?)?;
+            let (matched, scanned) = count_matches(&args.file, predicate)?;
+            vec![
+                ("rows", Value::from(matched)),
+                ("scanned", scanned.into()),
+                ("predicate", predicate.to_string().into()),
+                ("source", "scan".into()),
+            ]
+        }
+    };
+
+    Ok(Report::new().record("count", fields))
+}
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    dataset::{aggregate, default_threads, find_files, read_footers},
+    output::{Report, Value},
+};
+
+/// Arguments of the `dataset-stats` subcommand.
+#[derive(Args, Debug)]
+pub struct DatasetStatsArgs {
+    /// Directory holding the Parquet files of the dataset, searched recursively.
+    #[arg(value_name = "DIR")]
+    pub dir: PathBuf,
+
+    /// Number of footers read concurrently. Defaults to the number of CPUs.
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+/// Aggregate the footers of every file of a dataset: totals, per-column ranges, partitions and
+/// schema consistency. Files whose footer cannot be read are listed and left out.
+pub fn run(args: &DatasetStatsArgs) -> color_eyre::Result<Report> {
+    let files = find_files(&args.dir)?;
+    let results = read_footers(
+        &files,
+        args.threads.unwrap_or_else(default_threads),
+        |_, _| {},
+    );
+
+    let mut footers = Vec::new();
+    let mut failures = Vec::new();
+    for (path, result) in files.into_iter().zip(results) {
+        match result {
+            Ok(metadata) => footers.push((path, metadata)),
+            Err(err) => failures.push(vec![
+                Value::from(path.display().to_string()),
+                format!("{:#}", err).into(),
+            ]),
+        }
+    }
+
+    let stats = aggregate(&args.dir, &footers);
+
+    let columns = stats
+        .columns
+        .iter()
+        .map(|column| {
+            vec![
+                Value::from(column.column.clone()),
+                column.files.into(),
+                column.min.clone().into(),
+                column.max.clone().into(),
+                column.nulls.into(),
+                column.compressed_bytes.into(),
+            ]
+        })
+        .collect();
+
+    let partitions = stats
+        .partitions
+        .iter()
+        .map(|(partition, (files, rows))| {
+            vec![
+                Value::from(if partition.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    partition.clone()
+                }),
+                (*files).into(),
+                (*rows).into(),
+            ]
+        })
+        .collect();
+
+    let schemas = stats
+        .schemas
+        .iter()
+        .enumerate()
+        .map(|(index, version)| {
+            vec![
+                Value::from(index + 1),
+                version.files.len().into(),
+                version.columns.len().into(),
+                version.files[0].display().to_string().into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new()
+        .record(
+            "dataset",
+            [
+                ("dir", Value::from(args.dir.display().to_string())),
+                ("files", stats.files.into()),
+                ("unreadable_files", failures.len().into()),
+                ("rows", stats.rows.into()),
+                ("row_groups", stats.row_groups.into()),
+                ("file_bytes", stats.file_bytes.into()),
+                ("compressed_bytes", stats.compressed_bytes.into()),
+                ("uncompressed_bytes", stats.uncompressed_bytes.into()),
+                (
+                    "schema",
+                    if stats.is_schema_consistent() {
+                        "consistent".to_string()
+                    } else {
+                        format!("{} distinct schemas", stats.schemas.len())
+                    }
+                    .into(),
+                ),
+            ],
+        )
+        .table(
+            "columns",
+            &["column", "files", "min", "max", "nulls", "compressed_bytes"],
+            columns,
+        )
+        .table("partitions", &["partition", "files", "rows"], partitions)
+        .table(
+            "schemas",
+            &["schema", "files", "columns", "first_file"],
+            schemas,
+        )
+        .table("unreadable_files", &["file", "error"], failures))
+}
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use color_eyre::eyre::eyre;
+use parquet2::metadata::{ColumnChunkMetaData, FileMetaData};
+
+use crate::{
+    goto::column_paths,
+    output::{Report, Value},
+    pages::encoding_name,
+    parquet::{read_file_metadata, ColumnChunkMetaDataExt, HumanFriendlyStats, PhysicalTypeExt},
+};
+
+/// Arguments of the `diff-column` subcommand.
+#[derive(Args, Debug)]
+pub struct DiffColumnArgs {
+    #[arg(value_name = "FILE_A")]
+    pub file_a: PathBuf,
+
+    #[arg(value_name = "FILE_B")]
+    pub file_b: PathBuf,
+
+    /// Dotted path of the column to compare, e.g. `address.zip`.
+    #[arg(long)]
+    pub column: String,
+}
+
+/// Chunk attributes compared between the two files. Sizes are reported but not compared, since
+/// they change with any difference in the writer.
+struct ChunkSummary {
+    rows: i64,
+    physical_type: &'static str,
+    codec: String,
+    encodings: String,
+    compressed_size: i64,
+    stats: HumanFriendlyStats,
+}
+
+impl ChunkSummary {
+    fn new(rows: usize, chunk: &ColumnChunkMetaData) -> Self {
+        Self {
+            rows: rows as i64,
+            physical_type: chunk.physical_type().human_readable(),
+            codec: format!("{:?}", chunk.compression()),
+            encodings: chunk
+                .column_encoding()
+                .iter()
+                .map(|encoding| encoding_name(*encoding))
+                .collect::<Vec<_>>()
+                .join(","),
+            compressed_size: chunk.compressed_size(),
+            stats: chunk.stats(),
+        }
+    }
+
+    /// Names of the attributes that differ from `other`.
+    fn divergence(&self, other: &Self) -> Vec<&'static str> {
+        [
+            ("rows", self.rows != other.rows),
+            ("type", self.physical_type != other.physical_type),
+            ("codec", self.codec != other.codec),
+            ("encodings", self.encodings != other.encodings),
+            ("min", self.stats.min != other.stats.min),
+            ("max", self.stats.max != other.stats.max),
+            ("nulls", self.stats.null_count != other.stats.null_count),
+        ]
+        .into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+fn summaries(metadata: &FileMetaData, column: &str) -> color_eyre::Result<Vec<ChunkSummary>> {
+    let index = column_paths(metadata)
+        .iter()
+        .position(|path| path == column)
+        .ok_or_else(|| eyre!("no column named {:?}", column))?;
+
+    Ok(metadata
+        .row_groups
+        .iter()
+        .map(|row_group| ChunkSummary::new(row_group.num_rows(), &row_group.columns()[index]))
+        .collect())
+}
+
+/// Align the row group statistics of a column in two files and flag the row groups where they
+/// diverge.
+pub fn run(args: &DiffColumnArgs) -> color_eyre::Result<Report> {
+    let a = summaries(&read_file_metadata(&args.file_a)?, &args.column)
+        .map_err(|err| eyre!("{}: {}", args.file_a.display(), err))?;
+    let b = summaries(&read_file_metadata(&args.file_b)?, &args.column)
+        .map_err(|err| eyre!("{}: {}", args.file_b.display(), err))?;
+
+    let mut diverging = 0;
+    let rows = (0..a.len().max(b.len()))
+        .map(|row_group| {
+            let (a, b) = (a.get(row_group), b.get(row_group));
+            let divergence = match (a, b) {
+                (Some(a), Some(b)) => a.divergence(b).join(","),
+                (Some(_), None) => "missing in B".to_string(),
+                _ => "missing in A".to_string(),
+            };
+            if !divergence.is_empty() {
+                diverging += 1;
+            }
+
+            vec![
+                Value::from(row_group),
+                a.map(|a| a.rows).into(),
+                b.map(|b| b.rows).into(),
+                a.and_then(|a| a.stats.min.clone()).into(),
+                b.and_then(|b| b.stats.min.clone()).into(),
+                a.and_then(|a| a.stats.max.clone()).into(),
+                b.and_then(|b| b.stats.max.clone()).into(),
+                a.and_then(|a| a.stats.null_count).into(),
+                b.and_then(|b| b.stats.null_count).into(),
+                a.map(|a| a.compressed_size).into(),
+                b.map(|b| b.compressed_size).into(),
+                divergence.into(),
+            ]
+        })
+        .collect();
+
+    let type_and_codecs = |side: &[ChunkSummary]| {
+        side.first()
+            .map(|chunk| {
+                format!(
+                    "{} {} [{}]",
+                    chunk.physical_type, chunk.codec, chunk.encodings
+                )
+            })
+            .unwrap_or_default()
+    };
+
+    Ok(Report::new()
+        .record(
+            "diff_column",
+            [
+                ("column", Value::from(args.column.clone())),
+                ("file_a", Value::from(args.file_a.display().to_string())),
+                ("file_b", Value::from(args.file_b.display().to_string())),
+                ("row_groups_a", Value::from(a.len())),
+                ("row_groups_b", Value::from(b.len())),
+                ("chunk_a", Value::from(type_and_codecs(&a))),
+                ("chunk_b", Value::from(type_and_codecs(&b))),
+                ("diverging_row_groups", Value::from(diverging)),
+            ],
+        )
+        .table(
+            "row_groups",
+            &[
+                "row_group",
+                "rows_a",
+                "rows_b",
+                "min_a",
+                "min_b",
+                "max_a",
+                "max_b",
+                "nulls_a",
+                "nulls_b",
+                "compressed_a",
+                "compressed_b",
+                "diverges",
+            ],
+            rows,
+        ))
+}
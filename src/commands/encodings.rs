@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::encodings::analyze,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Candidates must save at least this fraction of a column's size to be suggested.
+const SUGGESTION_THRESHOLD: f64 = 0.1;
+
+/// Arguments of the `encodings` subcommand.
+#[derive(Args, Debug)]
+pub struct EncodingsArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values to read from every column chunk.
+    #[arg(long, default_value_t = 10000)]
+    pub sample: usize,
+}
+
+/// Estimate the size of every column under alternative encodings, and suggest the ones that
+/// would shrink it.
+pub fn run(args: &EncodingsArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let columns = analyze(&args.file, &metadata, args.sample)?;
+
+    let suggestions = columns
+        .iter()
+        .map(|column| {
+            let suggestion = column.suggestion(SUGGESTION_THRESHOLD);
+            vec![
+                Value::from(column.column.as_str()),
+                column.current_encodings.join(", ").into(),
+                column.sampled.into(),
+                format!("{:?}", column.current).into(),
+                column.current_bytes.into(),
+                suggestion
+                    .map(|candidate| format!("{:?}", candidate.encoding))
+                    .into(),
+                suggestion
+                    .map(|candidate| format!("{:.1}%", candidate.savings * 100.0))
+                    .into(),
+            ]
+        })
+        .collect();
+
+    let estimates = columns
+        .iter()
+        .flat_map(|column| {
+            column.candidates.iter().map(|candidate| {
+                vec![
+                    Value::from(column.column.as_str()),
+                    format!("{:?}", candidate.encoding).into(),
+                    candidate.bytes.into(),
+                    format!("{:.1}%", candidate.savings * 100.0).into(),
+                ]
+            })
+        })
+        .collect();
+
+    Ok(Report::new()
+        .table(
+            "suggestions",
+            &[
+                "column",
+                "current_encodings",
+                "sampled",
+                "compared_with",
+                "estimated_bytes",
+                "suggested",
+                "savings",
+            ],
+            suggestions,
+        )
+        .table(
+            "estimates",
+            &["column", "encoding", "estimated_bytes", "savings"],
+            estimates,
+        ))
+}
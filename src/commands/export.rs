@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+use crate::{
+    export::{export_arrow_ipc, export_parquet, IpcFormat},
+    output::{Report, Value},
+};
+
+/// File format written by the `export` subcommand.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, ValueEnum)]
+pub enum ExportFormat {
+    /// A standalone Parquet file.
+    #[default]
+    Parquet,
+    /// An Arrow IPC file, also known as Feather v2.
+    ArrowIpc,
+    /// An Arrow IPC stream.
+    ArrowStream,
+}
+
+/// Arguments of the `export` subcommand.
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Row group to export. Exports the whole file when omitted.
+    #[arg(long)]
+    pub row_group: Option<usize>,
+
+    /// Comma-separated columns to keep. A group selects all of its nested columns.
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Format of the written file. Named apart from `--format`, which selects how the summary
+    /// is printed.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Parquet)]
+    pub to: ExportFormat,
+
+    /// Path of the file to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// Extract a row group and/or a subset of columns into a standalone Parquet file, or convert
+/// them into Arrow IPC.
+pub fn run(args: &ExportArgs) -> color_eyre::Result<Report> {
+    let summary = match args.to {
+        ExportFormat::Parquet => {
+            export_parquet(&args.file, args.row_group, &args.columns, &args.output)?
+        }
+        ExportFormat::ArrowIpc => export_arrow_ipc(
+            &args.file,
+            args.row_group,
+            &args.columns,
+            &args.output,
+            IpcFormat::File,
+        )?,
+        ExportFormat::ArrowStream => export_arrow_ipc(
+            &args.file,
+            args.row_group,
+            &args.columns,
+            &args.output,
+            IpcFormat::Stream,
+        )?,
+    };
+
+    Ok(Report::new().record(
+        "export",
+        [
+            ("output", Value::from(args.output.display().to_string())),
+            ("rows", Value::from(summary.rows)),
+            ("columns", Value::from(summary.columns)),
+        ],
+    ))
+}
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use parquet::basic::Encoding;
+
+use crate::{
+    generate::{generate, parse_encoding, Codec, ColumnSpec, GenerateOptions, DEFAULT_SCHEMA},
+    output::{Report, Value},
+};
+
+/// Arguments of the `generate` subcommand.
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Path of the Parquet file to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Comma-separated columns as `NAME:TYPE[:NULL_RATIO]`, with types bool, int32, int64,
+    /// float, double, string, date and timestamp.
+    #[arg(long, value_delimiter = ',', default_value = DEFAULT_SCHEMA)]
+    pub schema: Vec<ColumnSpec>,
+
+    /// Number of rows to write.
+    #[arg(long, default_value_t = 10_000)]
+    pub rows: usize,
+
+    /// Largest number of rows of a row group.
+    #[arg(long, value_name = "ROWS", default_value_t = 1024 * 1024)]
+    pub row_group_size: usize,
+
+    /// Compression codec of every column chunk.
+    #[arg(long, value_enum, default_value_t = Codec::Snappy)]
+    pub codec: Codec,
+
+    /// Encoding of the columns that support it, e.g. `plain`, `delta_binary_packed` or
+    /// `byte_stream_split`, instead of dictionary encoding.
+    #[arg(long, value_parser = parse_encoding)]
+    pub encoding: Option<Encoding>,
+
+    /// Fraction of null values in every column without its own ratio.
+    #[arg(long, default_value_t = 0.0)]
+    pub null_ratio: f64,
+
+    /// Number of distinct values of the integer, string, date and timestamp columns.
+    #[arg(long, default_value_t = 1000)]
+    pub cardinality: u64,
+
+    /// Anomaly: write no statistics, neither per column chunk nor in a page index.
+    #[arg(long)]
+    pub no_stats: bool,
+
+    /// Anomaly: write chunk statistics but no page index.
+    #[arg(long)]
+    pub no_page_index: bool,
+
+    /// Anomaly: cap the rows of every data page, e.g. `--page-rows 1` for tiny pages.
+    #[arg(long, value_name = "ROWS")]
+    pub page_rows: Option<usize>,
+
+    /// Seed of the random values. The same arguments and seed always write the same rows.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+}
+
+/// Write a synthetic Parquet file, for testing the console and downstream readers.
+pub fn run(args: &GenerateArgs) -> color_eyre::Result<Report> {
+    let options = GenerateOptions {
+        columns: args.schema.clone(),
+        rows: args.rows,
+        row_group_size: args.row_group_size,
+        codec: args.codec,
+        encoding: args.encoding,
+        null_ratio: args.null_ratio,
+        cardinality: args.cardinality,
+        no_stats: args.no_stats,
+        no_page_index: args.no_page_index,
+        page_rows: args.page_rows,
+        seed: args.seed,
+    };
+    let summary = generate(&args.output, &options)?;
+
+    Ok(Report::new().record(
+        "generate",
+        [
+            ("output", Value::from(args.output.display().to_string())),
+            ("rows", Value::from(summary.rows)),
+            ("row_groups", Value::from(summary.row_groups)),
+            ("columns", Value::from(summary.columns)),
+            ("bytes", Value::from(summary.bytes)),
+        ],
+    ))
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::byte_map::byte_map,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `layout` subcommand.
+#[derive(Args, Debug)]
+pub struct LayoutArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+}
+
+/// Map the bytes of a file: every row group, column chunk, page, index, bloom filter and the
+/// footer, with where it starts, its length and its share of the file, sorted by offset.
+pub fn run(args: &LayoutArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let (elements, file_size) = byte_map(&args.file, &metadata)?;
+
+    let rows = elements
+        .into_iter()
+        .map(|element| {
+            vec![
+                Value::from(element.kind.as_str()),
+                element.row_group.into(),
+                element.column.into(),
+                element.start.into(),
+                element.length.into(),
+                format!(
+                    "{:.1}%",
+                    element.length as f64 / file_size.max(1) as f64 * 100.0
+                )
+                .into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new().table(
+        "elements",
+        &["kind", "row_group", "column", "start", "length", "percent"],
+        rows,
+    ))
+}
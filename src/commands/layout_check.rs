@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::row_group_layout::check_row_group_layout,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `layout-check` subcommand.
+#[derive(Args, Debug)]
+pub struct LayoutCheckArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+}
+
+/// Validate the ordinals and file offsets of the row groups and check that they follow each
+/// other without overlaps or unexplained gaps.
+pub fn run(args: &LayoutCheckArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let (extents, issues) = check_row_group_layout(&args.file, &metadata)?;
+
+    let row_groups = extents
+        .into_iter()
+        .map(|extent| {
+            vec![
+                Value::from(extent.row_group),
+                extent.ordinal.map(i64::from).into(),
+                extent.file_offset.into(),
+                extent.start.into(),
+                extent.end.into(),
+            ]
+        })
+        .collect();
+    let issues = issues
+        .into_iter()
+        .map(|issue| {
+            vec![
+                Value::from(issue.row_group),
+                issue.kind.as_str().into(),
+                issue.detail.into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new()
+        .table(
+            "row_groups",
+            &["row_group", "ordinal", "file_offset", "start", "end"],
+            row_groups,
+        )
+        .table("issues", &["row_group", "kind", "detail"], issues))
+}
@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::memory::{estimate_row_group, DEFAULT_SAMPLE},
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `memory` subcommand.
+#[derive(Args, Debug)]
+pub struct MemoryArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values sampled from every BYTE_ARRAY column chunk to estimate value lengths.
+    #[arg(long, default_value_t = DEFAULT_SAMPLE)]
+    pub sample: usize,
+}
+
+/// Estimate the decoded, in-memory (Arrow) size of every row group and column, next to their
+/// on-disk sizes.
+pub fn run(args: &MemoryArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+
+    // Compressed, uncompressed and in-memory bytes per column, summed over row groups.
+    let mut columns: Vec<(String, i64, i64, u64)> = Vec::new();
+    let mut row_groups = Vec::new();
+    for row_group in 0..metadata.row_groups.len() {
+        let estimates = estimate_row_group(&args.file, &metadata, row_group, args.sample);
+        if columns.is_empty() {
+            columns = estimates
+                .iter()
+                .map(|estimate| (estimate.column.clone(), 0, 0, 0))
+                .collect();
+        }
+        for (column, estimate) in columns.iter_mut().zip(&estimates) {
+            column.1 += estimate.compressed_bytes;
+            column.2 += estimate.uncompressed_bytes;
+            column.3 += estimate.in_memory_bytes;
+        }
+
+        row_groups.push(vec![
+            Value::from(row_group),
+            metadata.row_groups[row_group].num_rows().into(),
+            metadata.row_groups[row_group].compressed_size().into(),
+            metadata.row_groups[row_group].total_byte_size().into(),
+            estimates
+                .iter()
+                .map(|estimate| estimate.in_memory_bytes)
+                .sum::<u64>()
+                .into(),
+        ]);
+    }
+
+    let columns = columns
+        .into_iter()
+        .map(|(column, compressed, uncompressed, in_memory)| {
+            vec![
+                Value::from(column),
+                compressed.into(),
+                uncompressed.into(),
+                in_memory.into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new()
+        .table(
+            "row_groups",
+            &[
+                "row_group",
+                "rows",
+                "compressed",
+                "uncompressed",
+                "in_memory",
+            ],
+            row_groups,
+        )
+        .table(
+            "columns",
+            &["column", "compressed", "uncompressed", "in_memory"],
+            columns,
+        ))
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use parquet2::compression::Compression;
+
+use crate::{
+    codecs::{codec_name, codec_support},
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `meta` subcommand.
+#[derive(Args, Debug)]
+pub struct MetaArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+}
+
+/// Summarize the footer metadata of a file: file-level fields, one row per row group and one
+/// row per compression codec.
+pub fn run(args: &MetaArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+
+    let file = vec![
+        ("path", Value::from(args.file.display().to_string())),
+        ("version", metadata.version.into()),
+        ("num_rows", metadata.num_rows.into()),
+        ("num_row_groups", metadata.row_groups.len().into()),
+        ("num_columns", metadata.schema().columns().len().into()),
+        ("created_by", metadata.created_by.clone().into()),
+    ];
+
+    let row_groups = metadata
+        .row_groups
+        .iter()
+        .enumerate()
+        .map(|(index, row_group)| {
+            vec![
+                index.into(),
+                row_group.num_rows().into(),
+                row_group.total_byte_size().into(),
+                row_group.compressed_size().into(),
+                row_group.columns().len().into(),
+            ]
+        })
+        .collect();
+
+    // Chunks and compressed bytes per codec, in order of first use.
+    let mut codecs: Vec<(Compression, usize, i64)> = Vec::new();
+    for chunk in metadata
+        .row_groups
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+    {
+        let compression = chunk.compression();
+        match codecs
+            .iter_mut()
+            .find(|(codec, _, _)| *codec == compression)
+        {
+            Some((_, chunks, bytes)) => {
+                *chunks += 1;
+                *bytes += chunk.compressed_size();
+            }
+            None => codecs.push((compression, 1, chunk.compressed_size())),
+        }
+    }
+    let codecs = codecs
+        .into_iter()
+        .map(|(compression, chunks, bytes)| {
+            vec![
+                codec_name(compression).into(),
+                chunks.into(),
+                bytes.into(),
+                codec_support(compression).to_string().into(),
+            ]
+        })
+        .collect();
+
+    let key_values = metadata
+        .key_value_metadata()
+        .iter()
+        .flatten()
+        .map(|kv| vec![kv.key.clone().into(), kv.value.clone().into()])
+        .collect();
+
+    Ok(Report::new()
+        .record("file", file)
+        .table(
+            "row_groups",
+            &[
+                "index",
+                "num_rows",
+                "total_byte_size",
+                "compressed_size",
+                "num_columns",
+            ],
+            row_groups,
+        )
+        .table(
+            "codecs",
+            &["codec", "chunks", "compressed_size", "decompression"],
+            codecs,
+        )
+        .table("key_value_metadata", &["key", "value"], key_values))
+}
@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::offsets::{locate_offset, OffsetLocation},
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `offset` subcommand.
+#[derive(Args, Debug)]
+pub struct OffsetArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Byte offset in the file, e.g. from a failed range request.
+    #[arg(value_name = "OFFSET")]
+    pub offset: u64,
+}
+
+/// Report the row group, column chunk and page covering a byte offset, and their rows.
+pub fn run(args: &OffsetArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let location = locate_offset(&args.file, &metadata, args.offset)?;
+
+    let mut fields = vec![("offset", Value::from(args.offset))];
+    match location {
+        OffsetLocation::LeadingMagic => fields.push(("region", "leading magic bytes".into())),
+        OffsetLocation::Footer => fields.push(("region", "footer".into())),
+        OffsetLocation::Unclaimed => fields.push((
+            "region",
+            "between column chunks, e.g. a page index or bloom filter".into(),
+        )),
+        OffsetLocation::PastEnd => fields.push(("region", "past the end of the file".into())),
+        OffsetLocation::Chunk(hit) => {
+            fields.extend([
+                ("region", Value::from("column chunk")),
+                ("row_group", hit.row_group.into()),
+                ("column", hit.column_path.into()),
+                (
+                    "chunk_range",
+                    format!("{}..{}", hit.chunk_start, hit.chunk_end).into(),
+                ),
+            ]);
+            if let Some(page) = &hit.page {
+                fields.extend([
+                    ("page", Value::from(page.index)),
+                    ("page_type", page.page_type.into()),
+                    (
+                        "page_range",
+                        format!(
+                            "{}..{}",
+                            page.offset,
+                            page.offset + page.header_size + page.compressed_size
+                        )
+                        .into(),
+                    ),
+                    ("page_part", page.part().into()),
+                ]);
+            }
+            fields.extend([
+                ("rows", format!("{}..{}", hit.rows.0, hit.rows.1).into()),
+                (
+                    "rows_of",
+                    if hit.page_rows { "page" } else { "row group" }.into(),
+                ),
+            ]);
+        }
+    }
+
+    Ok(Report::new().record("location", fields))
+}
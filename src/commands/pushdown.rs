@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::pushdown::estimate_scan,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+    preview::Predicate,
+};
+
+/// Arguments of the `pushdown` subcommand.
+#[derive(Args, Debug)]
+pub struct PushdownArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Filter to push down, e.g. `'id > 100'` or `'address.city = nyc'`.
+    #[arg(long = "where", value_name = "PREDICATE")]
+    pub predicate: Predicate,
+
+    /// Comma-separated columns the scan reads besides the filter column. A group selects all
+    /// of its nested columns. Reads every column when omitted.
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+}
+
+/// Estimate the bytes a filtered scan reads after row group and page pruning.
+pub fn run(args: &PushdownArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let estimate = estimate_scan(&args.file, &metadata, &args.predicate, &args.columns)?;
+
+    Ok(Report::new().record(
+        "pushdown",
+        [
+            ("predicate", Value::from(args.predicate.to_string())),
+            ("row_groups", estimate.row_groups.into()),
+            ("row_groups_kept", estimate.row_groups_kept.into()),
+            ("pages", estimate.pages.into()),
+            ("pages_kept", estimate.pages_kept.into()),
+            ("total_bytes", estimate.total_bytes.into()),
+            ("after_row_group_pruning", estimate.row_group_bytes.into()),
+            ("after_page_pruning", estimate.page_bytes.into()),
+            (
+                "scan_ratio",
+                format!("{:.1}%", estimate.scan_ratio() * 100.0).into(),
+            ),
+        ],
+    ))
+}
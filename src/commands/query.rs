@@ -0,0 +1,286 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use color_eyre::eyre::eyre;
+
+use crate::{
+    output::{Report, Value},
+    parquet::read_file_metadata,
+    preview::{load_preview, Op, Predicate},
+    source,
+};
+
+/// Engine reading the rows of the `query` subcommand.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum Engine {
+    /// The console's own reader, as used by the data preview and `count`.
+    #[default]
+    Builtin,
+    /// DuckDB's Parquet reader, to validate results against its semantics. Only in builds with
+    /// the `duckdb` feature.
+    Duckdb,
+}
+
+/// Arguments of the `query` subcommand.
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Only print rows matching a filter, e.g. `'score > 5'` or `'name is null'`.
+    #[arg(long = "where", value_name = "PREDICATE")]
+    pub predicate: Option<Predicate>,
+
+    /// Comma-separated top-level columns to print. Prints every column when omitted.
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Most rows printed.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Engine reading the rows: `builtin`, or `duckdb` in builds with the `duckdb` feature.
+    #[arg(long, value_enum, default_value_t = Engine::Builtin)]
+    pub engine: Engine,
+}
+
+/// Columns and rows of a query, values as the data preview shows them.
+type Rows = (Vec<String>, Vec<Vec<Value>>);
+
+/// Print the first rows of a file matching a filter, read by the console or by DuckDB.
+pub fn run(args: &QueryArgs) -> color_eyre::Result<Report> {
+    let (columns, rows) = match args.engine {
+        Engine::Builtin => query_builtin(args)?,
+        Engine::Duckdb => query_duckdb(args)?,
+    };
+    let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+    Ok(Report::new().table("rows", &columns, rows))
+}
+
+/// Read the rows with the data preview, one row group at a time until enough match.
+fn query_builtin(args: &QueryArgs) -> color_eyre::Result<Rows> {
+    let metadata = read_file_metadata(&args.file)?;
+    if let Some(predicate) = &args.predicate {
+        predicate.decimal_literal(&metadata)?;
+    }
+    let projection = (!args.columns.is_empty()).then_some(args.columns.as_slice());
+
+    let mut columns = Vec::new();
+    let mut rows = Vec::new();
+    for row_group in 0..metadata.row_groups.len() {
+        let preview = load_preview(
+            &args.file,
+            &metadata,
+            row_group,
+            args.predicate.as_ref(),
+            projection,
+            args.limit - rows.len(),
+        )?;
+        columns = preview.columns;
+        rows.extend(
+            preview
+                .rows
+                .into_iter()
+                .map(|row| row.into_iter().map(Value::Str).collect()),
+        );
+        if rows.len() == args.limit {
+            break;
+        }
+    }
+    if let Some(missing) = args.columns.iter().find(|name| !columns.contains(name)) {
+        return Err(eyre!("no column named {:?}", missing));
+    }
+    Ok((columns, rows))
+}
+
+#[cfg(feature = "duckdb")]
+fn query_duckdb(args: &QueryArgs) -> color_eyre::Result<Rows> {
+    let file = duckdb_file(&args.file)?;
+    let connection = duckdb::Connection::open_in_memory()?;
+    let mut statement = connection.prepare(&sql(&file, args))?;
+    let mut result = statement.query([])?;
+    let columns = result
+        .as_ref()
+        .map(|statement| statement.column_names())
+        .unwrap_or_default();
+
+    let mut rows = Vec::new();
+    while let Some(row) = result.next()? {
+        let values = (0..columns.len())
+            .map(|i| {
+                let value: Option<String> = row.get(i)?;
+                Ok(Value::Str(value.unwrap_or_else(|| "null".to_string())))
+            })
+            .collect::<duckdb::Result<_>>()?;
+        rows.push(values);
+    }
+    Ok((columns, rows))
+}
+
+#[cfg(not(feature = "duckdb"))]
+fn query_duckdb(args: &QueryArgs) -> color_eyre::Result<Rows> {
+    duckdb_file(&args.file)?;
+    Err(eyre!(
+        "this build has no DuckDB engine: rebuild with `cargo install parquet-console \
+         --features duckdb`"
+    ))
+}
+
+/// The path DuckDB reads the file from. DuckDB reads local files itself, so files read from
+/// S3 or from archives cannot be queried with it.
+fn duckdb_file(path: &Path) -> color_eyre::Result<String> {
+    if source::remote_scheme(path).is_some() || !path.is_file() {
+        return Err(eyre!(
+            "the DuckDB engine only reads local files, not {}: use --engine builtin",
+            path.display()
+        ));
+    }
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("{} is not valid UTF-8", path.display()))
+}
+
+/// The DuckDB query of a `query` invocation, casting every value to text so that the rows
+/// print as those of the builtin engine do.
+#[cfg_attr(not(feature = "duckdb"), allow(dead_code))]
+fn sql(file: &str, args: &QueryArgs) -> String {
+    let projection = match args.columns.as_slice() {
+        [] => "*".to_string(),
+        columns => columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    let filter = match &args.predicate {
+        Some(predicate) => format!(" WHERE {}", condition(predicate)),
+        None => String::new(),
+    };
+    format!(
+        "SELECT COLUMNS(*)::VARCHAR FROM (SELECT {} FROM read_parquet({}){} LIMIT {})",
+        projection,
+        quote_literal(file),
+        filter,
+        args.limit
+    )
+}
+
+/// A predicate as a SQL condition. The value is a string literal, which DuckDB casts to the
+/// type of the column, e.g. exactly to the scale of a DECIMAL column.
+fn condition(predicate: &Predicate) -> String {
+    let column = predicate
+        .column
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".");
+    let op = match predicate.op {
+        Op::Eq => "=",
+        Op::Ne => "<>",
+        Op::Lt => "<",
+        Op::Le => "<=",
+        Op::Gt => ">",
+        Op::Ge => ">=",
+        Op::IsNull => return format!("{} IS NULL", column),
+        Op::IsNotNull => return format!("{} IS NOT NULL", column),
+    };
+    format!("{} {} {}", column, op, quote_literal(&predicate.value))
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(predicate: Option<&str>, columns: &[&str]) -> QueryArgs {
+        QueryArgs {
+            file: PathBuf::from("data.parquet"),
+            predicate: predicate.map(|predicate| predicate.parse().unwrap()),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            limit: 10,
+            engine: Engine::Duckdb,
+        }
+    }
+
+    #[test]
+    fn builds_duckdb_queries() {
+        assert_eq!(
+            sql("data.parquet", &args(None, &[])),
+            "SELECT COLUMNS(*)::VARCHAR FROM (SELECT * FROM read_parquet('data.parquet') \
+             LIMIT 10)"
+        );
+        assert_eq!(
+            sql(
+                "it's.parquet",
+                &args(Some("address.city = O'Hare"), &["id", "na\"me"])
+            ),
+            "SELECT COLUMNS(*)::VARCHAR FROM (SELECT \"id\", \"na\"\"me\" FROM \
+             read_parquet('it''s.parquet') WHERE \"address\".\"city\" = 'O''Hare' LIMIT 10)"
+        );
+    }
+
+    #[test]
+    fn translates_predicates() {
+        let condition = |predicate: &str| condition(&predicate.parse().unwrap());
+        assert_eq!(condition("score >= 1.5"), "\"score\" >= '1.5'");
+        assert_eq!(condition("name != x"), "\"name\" <> 'x'");
+        assert_eq!(condition("name is not null"), "\"name\" IS NOT NULL");
+    }
+
+    #[cfg(feature = "duckdb")]
+    #[test]
+    fn reads_the_rows_of_the_builtin_engine_with_duckdb() {
+        use crate::generate::{generate, Codec, GenerateOptions};
+
+        let dir =
+            std::env::temp_dir().join(format!("parquet_console_query_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("scores.parquet");
+        let options = GenerateOptions {
+            columns: vec![
+                "id:int64".parse().unwrap(),
+                "name:string".parse().unwrap(),
+                "rank:int32".parse().unwrap(),
+            ],
+            rows: 300,
+            row_group_size: 100,
+            codec: Codec::Snappy,
+            encoding: None,
+            null_ratio: 0.1,
+            cardinality: 50,
+            no_stats: false,
+            no_page_index: false,
+            page_rows: None,
+            seed: 3,
+        };
+        generate(&file, &options).unwrap();
+
+        for (predicate, columns) in [
+            (Some("rank > 25"), vec!["id", "name"]),
+            (Some("name is null"), vec![]),
+            (None, vec!["rank"]),
+        ] {
+            let args = QueryArgs {
+                file: file.clone(),
+                limit: 150,
+                ..args(predicate, &columns)
+            };
+            let (columns, rows) = query_duckdb(&args).unwrap();
+            assert!(!rows.is_empty(), "{:?}", predicate);
+            assert_eq!(
+                (columns, rows),
+                query_builtin(&args).unwrap(),
+                "{:?}",
+                predicate
+            );
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
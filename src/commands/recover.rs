@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use color_eyre::eyre::eyre;
+
+use crate::{
+    output::{Report, Value},
+    parquet::read_file_metadata,
+    recovery::{export_recovered, group_row_groups, scan_pages, Reference, RowGroupStatus},
+};
+
+/// Arguments of the `recover` subcommand.
+#[derive(Args, Debug)]
+pub struct RecoverArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Healthy file with the same schema, e.g. another file of the dataset, used to name the
+    /// recovered chunks and group them into row groups.
+    #[arg(long, value_name = "FILE")]
+    pub schema_from: Option<PathBuf>,
+
+    /// Write the complete recovered row groups into a new Parquet file. Requires
+    /// `--schema-from`.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Scan a truncated or corrupt file for page headers and list the column chunks and row groups
+/// that can be recovered, optionally salvaging the complete row groups into a new file.
+pub fn run(args: &RecoverArgs) -> color_eyre::Result<Report> {
+    if args.output.is_some() && args.schema_from.is_none() {
+        return Err(eyre!(
+            "--output requires --schema-from to rebuild the footer"
+        ));
+    }
+
+    let footer = match read_file_metadata(&args.file) {
+        Ok(_) => "readable".to_string(),
+        Err(err) => format!("unreadable: {}", err.root_cause()),
+    };
+    let recovery = scan_pages(&args.file)?;
+    let reference = args
+        .schema_from
+        .as_deref()
+        .map(Reference::read)
+        .transpose()?;
+    let row_groups = reference
+        .as_ref()
+        .map(|reference| group_row_groups(&recovery, reference))
+        .unwrap_or_default();
+
+    // Row group and column of each chunk, when a reference schema is known.
+    let mut placement = vec![(None, None); recovery.chunks.len()];
+    if let Some(reference) = &reference {
+        for (index, row_group) in row_groups.iter().enumerate() {
+            for (&chunk, column) in row_group.chunks.iter().zip(&reference.columns) {
+                placement[chunk] = (Some(index), Some(column.path.clone()));
+            }
+        }
+    }
+
+    let chunks = recovery
+        .chunks
+        .iter()
+        .zip(placement)
+        .enumerate()
+        .map(|(index, (chunk, (row_group, column)))| {
+            vec![
+                index.into(),
+                row_group.into(),
+                column.into(),
+                chunk.start.into(),
+                (chunk.end - chunk.start).into(),
+                chunk.pages.len().into(),
+                chunk.num_values.into(),
+                chunk.has_dictionary().into(),
+                chunk.truncated.into(),
+            ]
+        })
+        .collect();
+
+    let mut summary = vec![
+        ("file_size", Value::from(recovery.file_size)),
+        ("footer", footer.into()),
+        ("leading_magic", recovery.has_leading_magic.into()),
+        ("pages", recovery.num_pages().into()),
+        ("chunks", recovery.chunks.len().into()),
+        ("skipped_bytes", recovery.skipped_bytes.into()),
+    ];
+    let mut report = Report::new();
+
+    if let Some(reference) = &reference {
+        let complete: Vec<_> = row_groups
+            .iter()
+            .filter(|row_group| row_group.status == RowGroupStatus::Complete)
+            .collect();
+        summary.extend([
+            ("row_groups", Value::from(row_groups.len())),
+            ("complete_row_groups", complete.len().into()),
+            (
+                "recoverable_rows",
+                complete
+                    .iter()
+                    .filter_map(|row_group| row_group.num_rows)
+                    .sum::<i64>()
+                    .into(),
+            ),
+        ]);
+        if let Some(output) = &args.output {
+            let rows = export_recovered(&args.file, &recovery, reference, &row_groups, output)?;
+            summary.extend([
+                ("output", Value::from(output.display().to_string())),
+                ("written_rows", rows.into()),
+            ]);
+        }
+        report = report.record("recovery", summary).table(
+            "row_groups",
+            &["row_group", "chunks", "rows", "status"],
+            row_groups
+                .iter()
+                .enumerate()
+                .map(|(index, row_group)| {
+                    vec![
+                        index.into(),
+                        row_group.chunks.len().into(),
+                        row_group.num_rows.into(),
+                        row_group.status.name().into(),
+                    ]
+                })
+                .collect(),
+        );
+    } else {
+        report = report.record("recovery", summary);
+    }
+
+    Ok(report.table(
+        "chunks",
+        &[
+            "chunk",
+            "row_group",
+            "column",
+            "offset",
+            "bytes",
+            "pages",
+            "values",
+            "dictionary",
+            "truncated",
+        ],
+        chunks,
+    ))
+}
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+
+use crate::{
+    dataset::type_name,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+    schema_export::{avro_schema, json_schema, protobuf_schema},
+};
+
+/// Schema languages the `schema` subcommand converts into.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum SchemaFormat {
+    /// A draft-07 JSON Schema of the records.
+    #[value(name = "jsonschema")]
+    JsonSchema,
+    /// An Avro record schema (`.avsc`).
+    Avro,
+    /// A proto3 message (`.proto`).
+    Protobuf,
+}
+
+/// Arguments of the `schema` subcommand.
+#[derive(Args, Debug)]
+pub struct SchemaArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Print the schema converted into another schema language instead of its columns. Named
+    /// apart from `--format`, which selects how the columns are printed.
+    #[arg(long)]
+    pub to: Option<SchemaFormat>,
+}
+
+/// List the leaf columns of a file with their types, repetition and maximum levels.
+pub fn run(args: &SchemaArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let rows = metadata
+        .schema()
+        .columns()
+        .iter()
+        .map(|column| {
+            let primitive_type = &column.descriptor.primitive_type;
+            vec![
+                Value::from(column.path_in_schema.join(".")),
+                type_name(primitive_type).into(),
+                format!("{:?}", primitive_type.field_info.repetition)
+                    .to_lowercase()
+                    .into(),
+                column.descriptor.max_def_level.into(),
+                column.descriptor.max_rep_level.into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new()
+        .record(
+            "schema",
+            [
+                ("name", Value::from(metadata.schema().name())),
+                ("num_columns", metadata.schema().columns().len().into()),
+            ],
+        )
+        .table(
+            "columns",
+            &[
+                "column",
+                "type",
+                "repetition",
+                "max_def_level",
+                "max_rep_level",
+            ],
+            rows,
+        ))
+}
+
+/// Convert the schema of a file into another schema language.
+pub fn convert(file: &Path, to: SchemaFormat) -> color_eyre::Result<String> {
+    let metadata = read_file_metadata(file)?;
+    Ok(match to {
+        SchemaFormat::JsonSchema => json_schema(metadata.schema()),
+        SchemaFormat::Avro => avro_schema(metadata.schema()),
+        SchemaFormat::Protobuf => protobuf_schema(metadata.schema()),
+    })
+}
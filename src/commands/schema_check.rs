@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    dataset::{default_threads, diff_schemas, find_files, read_footers, schema_versions},
+    files::FileOrder,
+    output::{Report, Value},
+};
+
+/// Arguments of the `schema-check` subcommand.
+#[derive(Args, Debug)]
+pub struct SchemaCheckArgs {
+    /// Directory holding the Parquet files of the dataset, searched recursively.
+    #[arg(value_name = "DIR")]
+    pub dir: PathBuf,
+
+    /// Order of the files in the timeline. With `modified`, versions appear in the order they
+    /// were written.
+    #[arg(long, value_enum, default_value_t = FileOrder::Name)]
+    pub order: FileOrder,
+
+    /// Number of footers read concurrently. Defaults to the number of CPUs.
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+/// Compare the schemas of every file of a dataset and print the timeline of schema versions,
+/// with the changes introduced by each.
+pub fn run(args: &SchemaCheckArgs) -> color_eyre::Result<Report> {
+    let files = crate::files::expand(&find_files(&args.dir)?, args.order)?;
+    let results = read_footers(
+        &files,
+        args.threads.unwrap_or_else(default_threads),
+        |_, _| {},
+    );
+
+    let mut footers = Vec::new();
+    let mut unreadable = 0;
+    for (path, result) in files.into_iter().zip(results) {
+        match result {
+            Ok(metadata) => footers.push((path, metadata)),
+            Err(err) => {
+                tracing::warn!("skipping {}: {:#}", path.display(), err);
+                unreadable += 1;
+            }
+        }
+    }
+
+    let versions = schema_versions(&footers);
+
+    let mut version_rows = Vec::new();
+    let mut change_rows = Vec::new();
+    let mut compatible = true;
+    for (index, version) in versions.iter().enumerate() {
+        let changes = match index {
+            0 => Vec::new(),
+            _ => diff_schemas(&versions[index - 1].columns, &version.columns),
+        };
+        compatible &= changes.iter().all(|change| change.is_compatible());
+
+        version_rows.push(vec![
+            Value::from(index + 1),
+            version.files[0].display().to_string().into(),
+            version.files.len().into(),
+            version.columns.len().into(),
+            changes
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+                .into(),
+        ]);
+        change_rows.extend(changes.iter().map(|change| {
+            vec![
+                Value::from(index + 1),
+                change.kind().into(),
+                change.to_string().into(),
+            ]
+        }));
+    }
+
+    let status = if versions.len() <= 1 {
+        "consistent"
+    } else if compatible {
+        "compatible (columns added only)"
+    } else {
+        "incompatible"
+    };
+
+    Ok(Report::new()
+        .record(
+            "schema_check",
+            [
+                ("dir", Value::from(args.dir.display().to_string())),
+                ("files", footers.len().into()),
+                ("unreadable_files", unreadable.into()),
+                ("versions", versions.len().into()),
+                ("status", status.into()),
+            ],
+        )
+        .table(
+            "versions",
+            &["version", "first_file", "files", "columns", "changes"],
+            version_rows,
+        )
+        .table("changes", &["version", "kind", "change"], change_rows))
+}
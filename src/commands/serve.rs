@@ -0,0 +1,38 @@
+use std::{net::TcpListener, path::PathBuf};
+
+use clap::Args;
+use color_eyre::eyre::Context;
+
+use crate::parquet::read_file_metadata;
+
+/// Arguments of the `serve` subcommand.
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Address to listen on. Use `0.0.0.0` to accept connections from other hosts.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+}
+
+/// Serve the metadata of a file as JSON endpoints until interrupted: `/metadata`,
+/// `/row-groups/{i}`, `/columns/{path}/stats` and
+/// `/sample?column=PATH&row_group=N&limit=N&strategy=S`.
+pub fn run(args: &ServeArgs) -> color_eyre::Result<()> {
+    // Fail early on files that are not Parquet rather than on the first request.
+    read_file_metadata(&args.file)?;
+
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .wrap_err_with(|| format!("failed to listen on {}:{}", args.host, args.port))?;
+    eprintln!(
+        "Serving {} on http://{}",
+        args.file.display(),
+        listener.local_addr()?
+    );
+
+    crate::serve::serve(&args.file, listener)
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::sortedness::{analyze, suggest_sort_keys},
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `sortedness` subcommand.
+#[derive(Args, Debug)]
+pub struct SortednessArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values to read from every column chunk to check whether it is sorted within
+    /// its row group. 0 only uses footer statistics.
+    #[arg(long, default_value_t = 0)]
+    pub sample: usize,
+
+    /// Maximum number of sort keys to suggest.
+    #[arg(long, default_value_t = 3)]
+    pub suggest: usize,
+}
+
+/// Report the inferred sort order of every column and suggest sort keys for rewriting the file.
+pub fn run(args: &SortednessArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let columns = analyze(&args.file, &metadata, args.sample)?;
+    let suggestions = suggest_sort_keys(&metadata, &columns, args.suggest);
+
+    let rows = columns
+        .iter()
+        .map(|column| {
+            vec![
+                column.column.clone().into(),
+                column.global_order.as_str().into(),
+                column
+                    .overlap
+                    .map(|overlap| format!("{:.1}%", overlap * 100.0))
+                    .into(),
+                column.locally_sorted.into(),
+            ]
+        })
+        .collect();
+
+    let suggestion_rows = suggestions
+        .into_iter()
+        .enumerate()
+        .map(|(rank, column)| vec![Value::from(rank + 1), column.into()])
+        .collect();
+
+    Ok(Report::new()
+        .table(
+            "columns",
+            &["column", "global_order", "overlap", "locally_sorted"],
+            rows,
+        )
+        .table("suggested_sort_keys", &["rank", "column"], suggestion_rows))
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{
+    analysis::strings::analyze,
+    output::{Report, Value},
+    parquet::read_file_metadata,
+};
+
+/// Arguments of the `strings` subcommand.
+#[derive(Args, Debug)]
+pub struct StringsArgs {
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Number of values to read from every column chunk.
+    #[arg(long, default_value_t = 1000)]
+    pub sample: usize,
+}
+
+/// Report value lengths, UTF-8 validity and control characters of every BYTE_ARRAY column.
+pub fn run(args: &StringsArgs) -> color_eyre::Result<Report> {
+    let metadata = read_file_metadata(&args.file)?;
+    let profiles = analyze(&args.file, &metadata, args.sample)?;
+
+    let rows = profiles
+        .into_iter()
+        .map(|profile| {
+            vec![
+                Value::from(profile.column),
+                profile.sampled.into(),
+                profile.min_length.into(),
+                profile.avg_length.map(|avg| format!("{:.1}", avg)).into(),
+                profile.max_length.into(),
+                profile
+                    .valid_utf8
+                    .map(|valid| format!("{:.1}%", valid * 100.0))
+                    .into(),
+                profile.with_control_chars.into(),
+            ]
+        })
+        .collect();
+
+    Ok(Report::new().table(
+        "byte_array_columns",
+        &[
+            "column",
+            "sampled",
+            "min_length",
+            "avg_length",
+            "max_length",
+            "valid_utf8",
+            "with_control_chars",
+        ],
+        rows,
+    ))
+}
@@ -0,0 +1,564 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use parquet2::{metadata::FileMetaData, schema::types::PrimitiveType};
+
+use crate::parquet::{read_file_metadata, ColumnChunkMetaDataExt, PhysicalTypeExt, StatValue};
+
+/// Find the Parquet files under a directory, recursively, ordered by path.
+///
+/// Hidden files and files starting with `_`, such as `_SUCCESS` markers and `_metadata`
+/// summaries, are skipped.
+pub fn find_files<P: AsRef<Path>>(dir: P) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.as_ref().to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name.starts_with('_') {
+                continue;
+            }
+
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "parquet") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Default number of threads reading footers concurrently.
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(4)
+}
+
+/// Read the footers of many files concurrently on at most `threads` threads. `on_read` is
+/// called from the reading threads as each footer is read, in completion order.
+///
+/// Results are returned in the order of `files`.
+pub fn read_footers<F>(
+    files: &[PathBuf],
+    threads: usize,
+    on_read: F,
+) -> Vec<color_eyre::Result<FileMetaData>>
+where
+    F: Fn(usize, &color_eyre::Result<FileMetaData>) + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<color_eyre::Result<FileMetaData>>>> =
+        Mutex::new(files.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, files.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(index) else {
+                    break;
+                };
+                let result = read_file_metadata(path);
+                on_read(index, &result);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every file is read"))
+        .collect()
+}
+
+/// Footer read by a [FooterLoader], or the error that prevented reading it.
+pub type LoadedFooter = Result<FileMetaData, String>;
+
+/// Footers of a list of files, read on background threads so that opening a directory with
+/// many files does not block on reading them all.
+#[derive(Debug, Clone)]
+pub struct FooterLoader {
+    footers: Arc<Mutex<Vec<Option<LoadedFooter>>>>,
+    loaded: Arc<AtomicUsize>,
+}
+
+impl FooterLoader {
+    /// Start reading the footers of `files` on at most `threads` threads.
+    pub fn spawn(files: Vec<PathBuf>, threads: usize) -> Self {
+        let loader = Self {
+            footers: Arc::new(Mutex::new(vec![None; files.len()])),
+            loaded: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let background = loader.clone();
+        thread::spawn(move || {
+            read_footers(&files, threads, |index, result| {
+                let footer = match result {
+                    Ok(metadata) => Ok(metadata.clone()),
+                    Err(err) => Err(format!("{:#}", err)),
+                };
+                background.footers.lock().unwrap()[index] = Some(footer);
+                background.loaded.fetch_add(1, Ordering::Relaxed);
+            });
+        });
+
+        loader
+    }
+
+    pub fn len(&self) -> usize {
+        self.footers.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of footers read so far, successfully or not.
+    pub fn loaded(&self) -> usize {
+        self.loaded.load(Ordering::Relaxed)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.loaded() == self.len()
+    }
+
+    /// Footer of the file at `index`, or `None` while it is still being read.
+    pub fn get(&self, index: usize) -> Option<LoadedFooter> {
+        self.footers.lock().unwrap().get(index).cloned().flatten()
+    }
+
+    /// Replace the footer of the file at `index`, e.g. after it was re-read from disk.
+    pub fn replace(&self, index: usize, footer: LoadedFooter) {
+        if let Some(slot) = self.footers.lock().unwrap().get_mut(index) {
+            *slot = Some(footer);
+        }
+    }
+
+    /// Apply `f` to every footer read so far, without cloning them.
+    pub fn with_footers<T>(
+        &self,
+        f: impl FnOnce(&[Option<Result<FileMetaData, String>>]) -> T,
+    ) -> T {
+        f(&self.footers.lock().unwrap())
+    }
+}
+
+/// Hive-style partition of a file: the `key=value` directories between the dataset root and
+/// the file, e.g. `date=2024-01-01/region=eu`. Empty for unpartitioned files.
+pub fn partition_of(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .parent()
+        .map(|dir| {
+            dir.components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .filter(|component| component.contains('='))
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+/// Leaf columns of a schema, as `(dotted path, type)` pairs. Two files share a schema when
+/// these are equal.
+pub fn schema_columns(metadata: &FileMetaData) -> Vec<(String, String)> {
+    metadata
+        .schema()
+        .columns()
+        .iter()
+        .map(|column| {
+            (
+                column.path_in_schema.join("."),
+                type_name(&column.descriptor.primitive_type),
+            )
+        })
+        .collect()
+}
+
+/// Physical type of a column, followed by its logical or converted type if any.
+pub fn type_name(primitive_type: &PrimitiveType) -> String {
+    let physical = primitive_type.physical_type.human_readable();
+    match (&primitive_type.logical_type, &primitive_type.converted_type) {
+        (Some(logical), _) => format!("{} ({:?})", physical, logical),
+        (None, Some(converted)) => format!("{} ({:?})", physical, converted),
+        (None, None) => physical.to_string(),
+    }
+}
+
+/// A schema shared by some files of a dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaVersion {
+    /// Leaf columns, as returned by [schema_columns].
+    pub columns: Vec<(String, String)>,
+    /// Files with this schema, in dataset order.
+    pub files: Vec<PathBuf>,
+}
+
+/// Group files by schema. Versions are ordered by the first file exhibiting them, so with
+/// files ordered by time they form a timeline of the dataset's schema.
+pub fn schema_versions(footers: &[(PathBuf, FileMetaData)]) -> Vec<SchemaVersion> {
+    let mut versions: Vec<SchemaVersion> = Vec::new();
+    for (path, metadata) in footers {
+        let columns = schema_columns(metadata);
+        match versions
+            .iter_mut()
+            .find(|version| version.columns == columns)
+        {
+            Some(version) => version.files.push(path.clone()),
+            None => versions.push(SchemaVersion {
+                columns,
+                files: vec![path.clone()],
+            }),
+        }
+    }
+    versions
+}
+
+/// Difference in a single column between two schema versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    Added {
+        column: String,
+        type_name: String,
+    },
+    Removed {
+        column: String,
+        type_name: String,
+    },
+    /// A column removed and another added at the same position with the same type.
+    Renamed {
+        from: String,
+        to: String,
+    },
+    TypeChanged {
+        column: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl SchemaChange {
+    /// Whether readers of the old schema can still read files with the new one. Only
+    /// additions are compatible.
+    pub fn is_compatible(&self) -> bool {
+        matches!(self, SchemaChange::Added { .. })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchemaChange::Added { .. } => "added",
+            SchemaChange::Removed { .. } => "removed",
+            SchemaChange::Renamed { .. } => "renamed",
+            SchemaChange::TypeChanged { .. } => "type_changed",
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaChange::Added { column, type_name } => write!(f, "+{} {}", column, type_name),
+            SchemaChange::Removed { column, type_name } => {
+                write!(f, "-{} {}", column, type_name)
+            }
+            SchemaChange::Renamed { from, to } => write!(f, "{} -> {}", from, to),
+            SchemaChange::TypeChanged { column, from, to } => {
+                write!(f, "{}: {} -> {}", column, from, to)
+            }
+        }
+    }
+}
+
+/// Changes from one schema version to the next.
+///
+/// Renames cannot be told apart from a removal and an addition, so they are inferred: a
+/// removed column is considered renamed when the new schema has an added column of the same
+/// type at the same position.
+pub fn diff_schemas(old: &[(String, String)], new: &[(String, String)]) -> Vec<SchemaChange> {
+    let type_in = |schema: &[(String, String)], column: &str| {
+        schema
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, type_name)| type_name.clone())
+    };
+
+    let mut changes = Vec::new();
+    let mut added: Vec<(usize, &(String, String))> = new
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, _))| type_in(old, name).is_none())
+        .collect();
+
+    for (position, (column, type_name)) in old.iter().enumerate() {
+        match type_in(new, column) {
+            Some(new_type) if new_type != *type_name => changes.push(SchemaChange::TypeChanged {
+                column: column.clone(),
+                from: type_name.clone(),
+                to: new_type,
+            }),
+            Some(_) => {}
+            None => {
+                let renamed = added.iter().position(|(new_position, (_, new_type))| {
+                    *new_position == position && new_type == type_name
+                });
+                match renamed {
+                    Some(index) => {
+                        let (_, (to, _)) = added.remove(index);
+                        changes.push(SchemaChange::Renamed {
+                            from: column.clone(),
+                            to: to.clone(),
+                        });
+                    }
+                    None => changes.push(SchemaChange::Removed {
+                        column: column.clone(),
+                        type_name: type_name.clone(),
+                    }),
+                }
+            }
+        }
+    }
+
+    changes.extend(
+        added
+            .into_iter()
+            .map(|(_, (column, type_name))| SchemaChange::Added {
+                column: column.clone(),
+                type_name: type_name.clone(),
+            }),
+    );
+    changes
+}
+
+/// Statistics of one column combined across every file of a dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnRollup {
+    pub column: String,
+    /// Number of files containing the column.
+    pub files: usize,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Sum of the null counts, `None` if any chunk lacks one.
+    pub nulls: Option<i64>,
+    pub compressed_bytes: i64,
+}
+
+/// Aggregate numbers of a dataset.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DatasetStats {
+    pub files: usize,
+    pub rows: i64,
+    pub row_groups: usize,
+    /// Size of the files on disk.
+    pub file_bytes: u64,
+    pub compressed_bytes: i64,
+    pub uncompressed_bytes: i64,
+    pub columns: Vec<ColumnRollup>,
+    /// Files and rows per partition, by partition path.
+    pub partitions: BTreeMap<String, (usize, i64)>,
+    /// Distinct schemas, in order of first appearance, with the files exhibiting each.
+    pub schemas: Vec<SchemaVersion>,
+}
+
+impl DatasetStats {
+    pub fn is_schema_consistent(&self) -> bool {
+        self.schemas.len() <= 1
+    }
+}
+
+/// Combine the footers of the files of a dataset rooted at `root`. Columns are matched by
+/// path across files, in order of first appearance.
+pub fn aggregate(root: &Path, footers: &[(PathBuf, FileMetaData)]) -> DatasetStats {
+    let mut stats = DatasetStats {
+        files: footers.len(),
+        ..Default::default()
+    };
+
+    // Typed min and max of each column, along with the type used to render them.
+    let mut ranges: Vec<Option<(StatValue, StatValue, PrimitiveType)>> = Vec::new();
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+
+    for (path, metadata) in footers {
+        stats.rows += metadata.num_rows as i64;
+        stats.row_groups += metadata.row_groups.len();
+        stats.file_bytes += crate::source::file_size(path).unwrap_or_default();
+
+        let partition = stats
+            .partitions
+            .entry(partition_of(root, path))
+            .or_default();
+        partition.0 += 1;
+        partition.1 += metadata.num_rows as i64;
+
+        let schema = schema_columns(metadata);
+
+        for (column, (name, _)) in schema.iter().enumerate() {
+            let position = *positions.entry(name.clone()).or_insert_with(|| {
+                stats.columns.push(ColumnRollup {
+                    column: name.clone(),
+                    files: 0,
+                    min: None,
+                    max: None,
+                    nulls: Some(0),
+                    compressed_bytes: 0,
+                });
+                ranges.push(None);
+                stats.columns.len() - 1
+            });
+            let rollup = &mut stats.columns[position];
+            rollup.files += 1;
+
+            for row_group in &metadata.row_groups {
+                let chunk = &row_group.columns()[column];
+                stats.compressed_bytes += chunk.compressed_size();
+                stats.uncompressed_bytes += chunk.uncompressed_size();
+                rollup.compressed_bytes += chunk.compressed_size();
+                rollup.nulls = rollup
+                    .nulls
+                    .zip(chunk.stats().null_count)
+                    .map(|(a, b)| a + b);
+
+                let Some((min, max)) = chunk.min_max() else {
+                    continue;
+                };
+                let primitive_type = &chunk.descriptor().descriptor.primitive_type;
+                ranges[position] = match ranges[position].take() {
+                    // Files whose types disagree cannot be compared, keep the first range.
+                    Some((known_min, known_max, known_type))
+                        if known_type.physical_type == primitive_type.physical_type =>
+                    {
+                        Some((
+                            if min < known_min { min } else { known_min },
+                            if max > known_max { max } else { known_max },
+                            known_type,
+                        ))
+                    }
+                    Some(range) => Some(range),
+                    None => Some((min, max, primitive_type.clone())),
+                };
+            }
+        }
+    }
+
+    stats.schemas = schema_versions(footers);
+
+    for (rollup, range) in stats.columns.iter_mut().zip(ranges) {
+        if let Some((min, max, primitive_type)) = range {
+            let render = |value: &StatValue| {
+                crate::renderers::registry()
+                    .render(&primitive_type, value)
+                    .unwrap_or_else(|| value.to_string())
+            };
+            rollup.min = Some(render(&min));
+            rollup.max = Some(render(&max));
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(columns: &[(&str, &str)]) -> Vec<(String, String)> {
+        columns
+            .iter()
+            .map(|(name, type_name)| (name.to_string(), type_name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_added_and_removed_columns() {
+        let old = schema(&[("id", "INT64"), ("name", "BYTE_ARRAY")]);
+        let new = schema(&[("id", "INT64"), ("name", "BYTE_ARRAY"), ("score", "DOUBLE")]);
+        let added = SchemaChange::Added {
+            column: "score".to_string(),
+            type_name: "DOUBLE".to_string(),
+        };
+        assert_eq!(diff_schemas(&old, &new), vec![added.clone()]);
+        assert!(added.is_compatible());
+
+        let removed = SchemaChange::Removed {
+            column: "score".to_string(),
+            type_name: "DOUBLE".to_string(),
+        };
+        assert_eq!(diff_schemas(&new, &old), vec![removed.clone()]);
+        assert!(!removed.is_compatible());
+    }
+
+    #[test]
+    fn infers_renames_at_the_same_position_and_type() {
+        let old = schema(&[("id", "INT64"), ("name", "BYTE_ARRAY")]);
+        let new = schema(&[("id", "INT64"), ("full_name", "BYTE_ARRAY")]);
+        assert_eq!(
+            diff_schemas(&old, &new),
+            vec![SchemaChange::Renamed {
+                from: "name".to_string(),
+                to: "full_name".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn does_not_rename_columns_of_another_type() {
+        let old = schema(&[("id", "INT64"), ("score", "INT32")]);
+        let new = schema(&[("id", "INT64"), ("rating", "DOUBLE")]);
+        assert_eq!(
+            diff_schemas(&old, &new),
+            vec![
+                SchemaChange::Removed {
+                    column: "score".to_string(),
+                    type_name: "INT32".to_string(),
+                },
+                SchemaChange::Added {
+                    column: "rating".to_string(),
+                    type_name: "DOUBLE".to_string(),
+                },
+            ]
+        );
+
+        let new = schema(&[("id", "INT64"), ("score", "DOUBLE")]);
+        assert_eq!(
+            diff_schemas(&old, &new),
+            vec![SchemaChange::TypeChanged {
+                column: "score".to_string(),
+                from: "INT32".to_string(),
+                to: "DOUBLE".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_reordered_columns() {
+        let old = schema(&[("id", "INT64"), ("name", "BYTE_ARRAY"), ("score", "DOUBLE")]);
+        let new = schema(&[("score", "DOUBLE"), ("id", "INT64"), ("name", "BYTE_ARRAY")]);
+        assert_eq!(diff_schemas(&old, &new), vec![]);
+
+        // Renames are still inferred among reordered columns.
+        let new = schema(&[("name", "BYTE_ARRAY"), ("id", "INT64"), ("rank", "DOUBLE")]);
+        assert_eq!(
+            diff_schemas(&old, &new),
+            vec![SchemaChange::Renamed {
+                from: "score".to_string(),
+                to: "rank".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,523 @@
+use parquet2::{
+    metadata::SchemaDescriptor,
+    schema::types::{
+        IntegerType, PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType,
+        TimeUnit,
+    },
+};
+
+use crate::schema_export::{fields, logical_type, Field, FieldKind};
+
+/// SQL dialect `CREATE TABLE` statements are generated for.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Dialect {
+    #[default]
+    Trino,
+    SparkSql,
+    Snowflake,
+    BigQuery,
+    DuckDb,
+}
+
+impl Dialect {
+    pub const ALL: [Dialect; 5] = [
+        Dialect::Trino,
+        Dialect::SparkSql,
+        Dialect::Snowflake,
+        Dialect::BigQuery,
+        Dialect::DuckDb,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::Trino => "Trino",
+            Dialect::SparkSql => "Spark SQL",
+            Dialect::Snowflake => "Snowflake",
+            Dialect::BigQuery => "BigQuery",
+            Dialect::DuckDb => "DuckDB",
+        }
+    }
+
+    /// The dialect `delta` places away, wrapping around.
+    pub fn step(self, delta: isize) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|dialect| *dialect == self)
+            .unwrap() as isize;
+        Self::ALL[(index + delta).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Quote an identifier, keeping the case of Parquet field names.
+    fn quote(&self, name: &str) -> String {
+        match self {
+            Dialect::SparkSql | Dialect::BigQuery => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+}
+
+/// A `CREATE TABLE` statement, with the columns whose type the dialect cannot represent
+/// exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ddl {
+    pub statement: String,
+    /// Lossy conversions, as `column: what is lost`.
+    pub warnings: Vec<String>,
+}
+
+/// Precision of a time unit, in fractional digits of a second.
+fn precision(unit: TimeUnit) -> usize {
+    match unit {
+        TimeUnit::Milliseconds => 3,
+        TimeUnit::Microseconds => 6,
+        TimeUnit::Nanoseconds => 9,
+    }
+}
+
+/// Maps Parquet types to the types of a dialect, collecting the lossy conversions.
+struct TypeMapper {
+    dialect: Dialect,
+    warnings: Vec<String>,
+}
+
+impl TypeMapper {
+    fn warn(&mut self, column: &str, message: &str) {
+        self.warnings.push(format!("{}: {}", column, message));
+    }
+
+    /// Type of a timestamp of `unit`, falling back to microseconds where nanoseconds are not
+    /// supported.
+    fn timestamp(&mut self, column: &str, unit: TimeUnit, utc: bool) -> String {
+        let digits = precision(unit);
+        let truncated = |mapper: &mut Self| {
+            if unit == TimeUnit::Nanoseconds {
+                mapper.warn(column, "nanoseconds are truncated to microseconds");
+            }
+        };
+        match (self.dialect, utc) {
+            (Dialect::Trino, false) => format!("TIMESTAMP({})", digits),
+            (Dialect::Trino, true) => format!("TIMESTAMP({}) WITH TIME ZONE", digits),
+            (Dialect::Snowflake, false) => format!("TIMESTAMP_NTZ({})", digits),
+            (Dialect::Snowflake, true) => format!("TIMESTAMP_TZ({})", digits),
+            (Dialect::DuckDb, false) => match unit {
+                TimeUnit::Milliseconds => "TIMESTAMP_MS".to_string(),
+                TimeUnit::Microseconds => "TIMESTAMP".to_string(),
+                TimeUnit::Nanoseconds => "TIMESTAMP_NS".to_string(),
+            },
+            (Dialect::DuckDb, true) => {
+                truncated(self);
+                "TIMESTAMPTZ".to_string()
+            }
+            (Dialect::SparkSql, false) => {
+                truncated(self);
+                "TIMESTAMP_NTZ".to_string()
+            }
+            (Dialect::SparkSql, true) => {
+                truncated(self);
+                "TIMESTAMP".to_string()
+            }
+            (Dialect::BigQuery, false) => {
+                truncated(self);
+                "DATETIME".to_string()
+            }
+            (Dialect::BigQuery, true) => {
+                truncated(self);
+                "TIMESTAMP".to_string()
+            }
+        }
+    }
+
+    fn integer(&mut self, column: &str, integer_type: IntegerType) -> String {
+        let name = match (self.dialect, integer_type) {
+            (Dialect::BigQuery, IntegerType::UInt64) => "NUMERIC",
+            (Dialect::BigQuery, _) => "INT64",
+            (Dialect::DuckDb, IntegerType::UInt8) => "UTINYINT",
+            (Dialect::DuckDb, IntegerType::UInt16) => "USMALLINT",
+            (Dialect::DuckDb, IntegerType::UInt32) => "UINTEGER",
+            (Dialect::DuckDb, IntegerType::UInt64) => "UBIGINT",
+            (Dialect::Snowflake, IntegerType::UInt64) => "NUMBER(20, 0)",
+            (_, IntegerType::UInt64) => {
+                self.warn(column, "UINT64 is widened to DECIMAL(20, 0)");
+                "DECIMAL(20, 0)"
+            }
+            (_, IntegerType::Int8) => "TINYINT",
+            (_, IntegerType::Int16 | IntegerType::UInt8) => "SMALLINT",
+            (Dialect::SparkSql, IntegerType::Int32 | IntegerType::UInt16) => "INT",
+            (_, IntegerType::Int32 | IntegerType::UInt16) => "INTEGER",
+            (_, IntegerType::Int64 | IntegerType::UInt32) => "BIGINT",
+        };
+        name.to_string()
+    }
+
+    fn decimal(&mut self, column: &str, precision: usize, scale: usize) -> String {
+        match self.dialect {
+            Dialect::BigQuery if precision - scale.min(precision) <= 29 && scale <= 9 => {
+                format!("NUMERIC({}, {})", precision, scale)
+            }
+            Dialect::BigQuery if precision <= 76 && scale <= 38 => {
+                format!("BIGNUMERIC({}, {})", precision, scale)
+            }
+            Dialect::BigQuery => {
+                self.warn(
+                    column,
+                    "the precision exceeds BIGNUMERIC, stored as FLOAT64",
+                );
+                "FLOAT64".to_string()
+            }
+            _ if precision > 38 => {
+                self.warn(column, "precisions above 38 are stored as DOUBLE");
+                self.binary_float(true)
+            }
+            Dialect::Snowflake => format!("NUMBER({}, {})", precision, scale),
+            _ => format!("DECIMAL({}, {})", precision, scale),
+        }
+    }
+
+    fn binary_float(&self, double: bool) -> String {
+        match (self.dialect, double) {
+            (Dialect::BigQuery, _) => "FLOAT64",
+            (Dialect::Snowflake, _) => "FLOAT",
+            (Dialect::Trino, false) => "REAL",
+            (_, false) => "FLOAT",
+            (_, true) => "DOUBLE",
+        }
+        .to_string()
+    }
+
+    fn string(&self) -> String {
+        match self.dialect {
+            Dialect::Trino | Dialect::Snowflake | Dialect::DuckDb => "VARCHAR",
+            Dialect::SparkSql | Dialect::BigQuery => "STRING",
+        }
+        .to_string()
+    }
+
+    fn binary(&self) -> String {
+        match self.dialect {
+            Dialect::Trino => "VARBINARY",
+            Dialect::SparkSql | Dialect::Snowflake => "BINARY",
+            Dialect::BigQuery => "BYTES",
+            Dialect::DuckDb => "BLOB",
+        }
+        .to_string()
+    }
+
+    fn primitive(&mut self, column: &str, primitive_type: &PrimitiveType) -> String {
+        match logical_type(primitive_type) {
+            Some(PrimitiveLogicalType::String | PrimitiveLogicalType::Enum) => self.string(),
+            Some(PrimitiveLogicalType::Json) => match self.dialect {
+                Dialect::Trino | Dialect::BigQuery | Dialect::DuckDb => "JSON".to_string(),
+                Dialect::Snowflake => "VARIANT".to_string(),
+                Dialect::SparkSql => self.string(),
+            },
+            Some(PrimitiveLogicalType::Uuid) => match self.dialect {
+                Dialect::Trino | Dialect::DuckDb => "UUID".to_string(),
+                _ => {
+                    self.warn(column, "UUID has no native type, stored as a string");
+                    self.string()
+                }
+            },
+            Some(PrimitiveLogicalType::Date) => "DATE".to_string(),
+            Some(PrimitiveLogicalType::Time { unit, .. }) => match self.dialect {
+                Dialect::SparkSql => {
+                    self.warn(column, "TIME is not supported, stored as BIGINT");
+                    "BIGINT".to_string()
+                }
+                Dialect::Trino | Dialect::Snowflake => format!("TIME({})", precision(unit)),
+                Dialect::BigQuery | Dialect::DuckDb => {
+                    if unit == TimeUnit::Nanoseconds {
+                        self.warn(column, "nanoseconds are truncated to microseconds");
+                    }
+                    "TIME".to_string()
+                }
+            },
+            Some(PrimitiveLogicalType::Timestamp {
+                unit,
+                is_adjusted_to_utc,
+            }) => self.timestamp(column, unit, is_adjusted_to_utc),
+            Some(PrimitiveLogicalType::Decimal(precision, scale)) => {
+                self.decimal(column, precision, scale)
+            }
+            Some(PrimitiveLogicalType::Integer(integer_type)) => self.integer(column, integer_type),
+            Some(PrimitiveLogicalType::Bson | PrimitiveLogicalType::Unknown) | None => {
+                if primitive_type.converted_type == Some(PrimitiveConvertedType::Interval) {
+                    self.warn(column, "INTERVAL is stored as its 12 raw bytes");
+                }
+                match primitive_type.physical_type {
+                    PhysicalType::Boolean if self.dialect == Dialect::BigQuery => {
+                        "BOOL".to_string()
+                    }
+                    PhysicalType::Boolean => "BOOLEAN".to_string(),
+                    PhysicalType::Int32 => self.integer(column, IntegerType::Int32),
+                    PhysicalType::Int64 => self.integer(column, IntegerType::Int64),
+                    PhysicalType::Float => self.binary_float(false),
+                    PhysicalType::Double => self.binary_float(true),
+                    // INT96 is the legacy encoding of nanosecond timestamps.
+                    PhysicalType::Int96 => self.timestamp(column, TimeUnit::Nanoseconds, false),
+                    PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => self.binary(),
+                }
+            }
+        }
+    }
+
+    fn field_type(&mut self, column: &str, field: &Field) -> String {
+        match &field.kind {
+            FieldKind::Primitive(primitive_type) => self.primitive(column, primitive_type),
+            FieldKind::Struct(fields) => {
+                let members: Vec<String> = fields
+                    .iter()
+                    .map(|member| {
+                        let path = format!("{}.{}", column, member.name);
+                        let member_type = self.field_type(&path, member);
+                        let name = self.dialect.quote(&member.name);
+                        match self.dialect {
+                            Dialect::SparkSql => format!("{}: {}", name, member_type),
+                            _ => format!("{} {}", name, member_type),
+                        }
+                    })
+                    .collect();
+                match self.dialect {
+                    Dialect::Trino => format!("ROW({})", members.join(", ")),
+                    Dialect::SparkSql | Dialect::BigQuery => {
+                        format!("STRUCT<{}>", members.join(", "))
+                    }
+                    Dialect::DuckDb => format!("STRUCT({})", members.join(", ")),
+                    Dialect::Snowflake => {
+                        self.warn(column, "nested fields are stored as an untyped OBJECT");
+                        "OBJECT".to_string()
+                    }
+                }
+            }
+            FieldKind::List(element) => {
+                if self.dialect == Dialect::Snowflake {
+                    self.warn(column, "elements are stored in an untyped ARRAY");
+                    return "ARRAY".to_string();
+                }
+                if self.dialect == Dialect::BigQuery {
+                    if matches!(element.kind, FieldKind::List(_)) {
+                        self.warn(column, "arrays of arrays are not supported");
+                    }
+                    if element.nullable {
+                        self.warn(column, "arrays cannot hold NULL elements");
+                    }
+                }
+                let element_type = self.field_type(&format!("{}[]", column), element);
+                match self.dialect {
+                    Dialect::Trino => format!("ARRAY({})", element_type),
+                    Dialect::DuckDb => format!("{}[]", element_type),
+                    _ => format!("ARRAY<{}>", element_type),
+                }
+            }
+            FieldKind::Map(key, value) => {
+                if self.dialect == Dialect::Snowflake {
+                    self.warn(column, "entries are stored in an untyped OBJECT");
+                    return "OBJECT".to_string();
+                }
+                let key_type = self.field_type(&format!("{}.key", column), key);
+                let value_type = self.field_type(&format!("{}.value", column), value);
+                match self.dialect {
+                    Dialect::Trino | Dialect::DuckDb => {
+                        format!("MAP({}, {})", key_type, value_type)
+                    }
+                    Dialect::SparkSql => format!("MAP<{}, {}>", key_type, value_type),
+                    _ => {
+                        self.warn(column, "MAP is not supported, stored as key/value pairs");
+                        format!("ARRAY<STRUCT<key {}, value {}>>", key_type, value_type)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generate the `CREATE TABLE` statement of a table with the given schema. Required
+/// top-level columns are `NOT NULL`; the nullability of nested fields is not declared.
+pub fn create_table(schema: &SchemaDescriptor, table: &str, dialect: Dialect) -> Ddl {
+    let mut mapper = TypeMapper {
+        dialect,
+        warnings: Vec::new(),
+    };
+    let columns: Vec<String> = fields(schema)
+        .iter()
+        .map(|field| {
+            let column_type = mapper.field_type(&field.name, field);
+            let not_null = if field.nullable { "" } else { " NOT NULL" };
+            format!(
+                "  {} {}{}",
+                dialect.quote(&field.name),
+                column_type,
+                not_null
+            )
+        })
+        .collect();
+    Ddl {
+        statement: format!(
+            "CREATE TABLE {} (\n{}\n);",
+            dialect.quote(table),
+            columns.join(",\n")
+        ),
+        warnings: mapper.warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet2::schema::{io_message::from_message, types::ParquetType};
+
+    use super::*;
+
+    fn schema(message: &str) -> SchemaDescriptor {
+        match from_message(message).unwrap() {
+            ParquetType::GroupType {
+                field_info, fields, ..
+            } => SchemaDescriptor::new(field_info.name, fields),
+            ParquetType::PrimitiveType(_) => unreachable!(),
+        }
+    }
+
+    fn events() -> SchemaDescriptor {
+        schema(
+            "message events {
+                required int64 id;
+                optional binary name (UTF8);
+                optional int64 amount (DECIMAL(12, 2));
+                optional int64 created (TIMESTAMP(NANOS, true));
+                optional int64 visits (INTEGER(64, false));
+                optional group tags (LIST) {
+                    repeated group list {
+                        optional binary element (UTF8);
+                    }
+                }
+                optional group address {
+                    required binary city (UTF8);
+                    optional int32 zip;
+                }
+                optional group attributes (MAP) {
+                    repeated group key_value {
+                        required binary key (UTF8);
+                        optional double value;
+                    }
+                }
+            }",
+        )
+    }
+
+    #[test]
+    fn maps_nested_types_to_trino() {
+        let ddl = create_table(&events(), "events", Dialect::Trino);
+        assert_eq!(
+            ddl.statement,
+            r#"CREATE TABLE "events" (
+  "id" BIGINT NOT NULL,
+  "name" VARCHAR,
+  "amount" DECIMAL(12, 2),
+  "created" TIMESTAMP(9) WITH TIME ZONE,
+  "visits" DECIMAL(20, 0),
+  "tags" ARRAY(VARCHAR),
+  "address" ROW("city" VARCHAR, "zip" INTEGER),
+  "attributes" MAP(VARCHAR, DOUBLE)
+);"#
+        );
+        assert_eq!(
+            ddl.warnings,
+            ["visits: UINT64 is widened to DECIMAL(20, 0)"]
+        );
+    }
+
+    #[test]
+    fn maps_nested_types_to_spark_sql() {
+        let ddl = create_table(&events(), "events", Dialect::SparkSql);
+        assert_eq!(
+            ddl.statement,
+            "CREATE TABLE `events` (
+  `id` BIGINT NOT NULL,
+  `name` STRING,
+  `amount` DECIMAL(12, 2),
+  `created` TIMESTAMP,
+  `visits` DECIMAL(20, 0),
+  `tags` ARRAY<STRING>,
+  `address` STRUCT<`city`: STRING, `zip`: INT>,
+  `attributes` MAP<STRING, DOUBLE>
+);"
+        );
+        assert_eq!(
+            ddl.warnings,
+            [
+                "created: nanoseconds are truncated to microseconds",
+                "visits: UINT64 is widened to DECIMAL(20, 0)",
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_of_lossy_conversions() {
+        let column_types = |dialect| {
+            let ddl = create_table(&events(), "events", dialect);
+            let lines: Vec<String> = ddl.statement.lines().map(str::to_string).collect();
+            (lines, ddl.warnings)
+        };
+
+        let (lines, warnings) = column_types(Dialect::Snowflake);
+        assert_eq!(lines[4], "  \"created\" TIMESTAMP_TZ(9),");
+        assert_eq!(lines[7], "  \"address\" OBJECT,");
+        assert_eq!(
+            warnings,
+            [
+                "tags: elements are stored in an untyped ARRAY",
+                "address: nested fields are stored as an untyped OBJECT",
+                "attributes: entries are stored in an untyped OBJECT",
+            ]
+        );
+
+        let (lines, warnings) = column_types(Dialect::BigQuery);
+        assert_eq!(lines[1], "  `id` INT64 NOT NULL,");
+        assert_eq!(lines[5], "  `visits` NUMERIC,");
+        assert_eq!(
+            lines[8],
+            "  `attributes` ARRAY<STRUCT<key STRING, value FLOAT64>>"
+        );
+        assert_eq!(
+            warnings,
+            [
+                "created: nanoseconds are truncated to microseconds",
+                "tags: arrays cannot hold NULL elements",
+                "attributes: MAP is not supported, stored as key/value pairs",
+            ]
+        );
+
+        let (lines, warnings) = column_types(Dialect::DuckDb);
+        assert_eq!(lines[5], "  \"visits\" UBIGINT,");
+        assert_eq!(lines[6], "  \"tags\" VARCHAR[],");
+        assert_eq!(
+            warnings,
+            ["created: nanoseconds are truncated to microseconds"]
+        );
+    }
+
+    #[test]
+    fn maps_decimals_by_precision() {
+        let schema = schema(
+            "message prices {
+                optional fixed_len_byte_array(16) small (DECIMAL(31, 10));
+                optional fixed_len_byte_array(32) wide (DECIMAL(60, 2));
+            }",
+        );
+        let ddl = create_table(&schema, "prices", Dialect::BigQuery);
+        assert!(ddl.statement.contains("`small` BIGNUMERIC(31, 10)"));
+        assert!(ddl.statement.contains("`wide` BIGNUMERIC(60, 2)"));
+        let ddl = create_table(&schema, "prices", Dialect::Trino);
+        assert!(ddl.statement.contains("\"small\" DECIMAL(31, 10)"));
+        assert!(ddl.statement.contains("\"wide\" DOUBLE"));
+        assert_eq!(
+            ddl.warnings,
+            ["wide: precisions above 38 are stored as DOUBLE"]
+        );
+    }
+
+    #[test]
+    fn steps_through_dialects() {
+        assert_eq!(Dialect::Trino.step(1), Dialect::SparkSql);
+        assert_eq!(Dialect::Trino.step(-1), Dialect::DuckDb);
+        assert_eq!(Dialect::DuckDb.step(1), Dialect::Trino);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use color_eyre::eyre::eyre;
+use parquet2::schema::types::{PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType};
+
+use crate::parquet::StatValue;
+
+/// Precision and scale of a DECIMAL column, from its logical or converted type.
+pub fn decimal_type(primitive_type: &PrimitiveType) -> Option<(usize, usize)> {
+    match (primitive_type.logical_type, primitive_type.converted_type) {
+        (Some(PrimitiveLogicalType::Decimal(precision, scale)), _) => Some((precision, scale)),
+        (_, Some(PrimitiveConvertedType::Decimal(precision, scale))) => Some((precision, scale)),
+        _ => None,
+    }
+}
+
+/// An exact decimal number, as its unscaled integer and the number of digits after the
+/// decimal point, e.g. `12.340` is `12340` with scale 3.
+///
+/// Filters on DECIMAL columns compare values as decimals instead of floats or their stored
+/// integers, so that `price > 12.34` prunes and matches by the column's scale.
+#[derive(Debug, Copy, Clone)]
+pub struct Decimal {
+    pub unscaled: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// A DECIMAL statistic or value of a column with `scale`: stored as INT32 or INT64, or as
+    /// big-endian two's complement bytes.
+    pub fn from_stat(value: &StatValue, scale: usize) -> Option<Self> {
+        let unscaled = match value {
+            StatValue::Int(v) => i128::from(*v),
+            StatValue::Bytes(bytes) if !bytes.is_empty() && bytes.len() <= 16 => {
+                // Sign-extend the big-endian bytes to 128 bits.
+                let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+                let mut buf = [fill; 16];
+                buf[16 - bytes.len()..].copy_from_slice(bytes);
+                i128::from_be_bytes(buf)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            unscaled,
+            scale: scale.try_into().ok()?,
+        })
+    }
+
+    /// The unscaled value at a scale at least the current one, or `None` when it does not fit.
+    fn rescale(&self, scale: u32) -> Option<i128> {
+        self.unscaled
+            .checked_mul(10i128.checked_pow(scale - self.scale)?)
+    }
+
+    /// Number of digits before the decimal point, for checking literals against a precision.
+    fn integer_digits(&self) -> u32 {
+        let Some(divisor) = 10u128.checked_pow(self.scale) else {
+            return 0;
+        };
+        let integer = self.unscaled.unsigned_abs() / divisor;
+        integer.checked_ilog10().map_or(0, |digits| digits + 1)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            // Beyond 38 digits, which DECIMAL columns cannot hold anyway.
+            _ => {
+                let float = |d: &Decimal| d.unscaled as f64 / 10f64.powi(d.scale as i32);
+                float(self).total_cmp(&float(other))
+            }
+        }
+    }
+}
+
+/// Parsed from plain decimal notation, e.g. `-12.34`, `+5` or `.5`.
+impl FromStr for Decimal {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || eyre!("`{}` is not a decimal number, e.g. 12.34", s);
+        let (negative, digits) = match s.trim().strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.trim().strip_prefix('+').unwrap_or(s.trim())),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let unscaled: i128 = format!("0{}{}", integer, fraction)
+            .parse()
+            .map_err(|_| invalid())?;
+        Ok(Self {
+            unscaled: if negative { -unscaled } else { unscaled },
+            scale: fraction.len().try_into().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Parse a filter literal for a DECIMAL(`precision`, `scale`) column. Literals with more
+/// digits after the point than the scale still compare exactly, but ones with more digits
+/// before it than the precision allows are rejected: no value of the column comes near them,
+/// so the filter is likely a typo.
+pub fn parse_literal(literal: &str, precision: usize, scale: usize) -> color_eyre::Result<Decimal> {
+    let decimal: Decimal = literal.parse()?;
+    let max_digits = precision.saturating_sub(scale) as u32;
+    if decimal.integer_digits() > max_digits {
+        return Err(eyre!(
+            "{} has more than {} digits before the decimal point of DECIMAL({}, {})",
+            literal,
+            max_digits,
+            precision,
+            scale
+        ));
+    }
+    Ok(decimal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_signs_and_fractions() {
+        let parsed = decimal("-12.340");
+        assert_eq!((parsed.unscaled, parsed.scale), (-12340, 3));
+        let parsed = decimal("+5");
+        assert_eq!((parsed.unscaled, parsed.scale), (5, 0));
+        let parsed = decimal(".5");
+        assert_eq!((parsed.unscaled, parsed.scale), (5, 1));
+        let parsed = decimal("-0.05");
+        assert_eq!((parsed.unscaled, parsed.scale), (-5, 2));
+        for invalid in ["", "-", ".", "1.2.3", "1e5", "12,5", "--1"] {
+            assert!(invalid.parse::<Decimal>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn compares_across_scales() {
+        assert_eq!(decimal("12.34"), decimal("12.340"));
+        assert!(decimal("12.345") > decimal("12.34"));
+        assert!(decimal("-12.345") < decimal("-12.34"));
+        assert!(decimal("-0.1") < decimal("0"));
+        assert!(decimal("1") > decimal("0.999999"));
+    }
+
+    #[test]
+    fn rescales_unless_it_overflows() {
+        assert_eq!(decimal("-1.5").rescale(3), Some(-1500));
+        assert_eq!(decimal("1").rescale(38), Some(10i128.pow(38)));
+        assert_eq!(decimal("100").rescale(38), None);
+        // Beyond i128, comparisons fall back to floats.
+        assert!(decimal("100") > decimal("1.00000000000000000000000000000000000001"));
+    }
+
+    #[test]
+    fn reads_statistics_at_the_column_scale() {
+        let stat = Decimal::from_stat(&StatValue::Int(-1234), 2).unwrap();
+        assert_eq!(stat, decimal("-12.34"));
+        // -1234 as big-endian two's complement bytes.
+        let stat = Decimal::from_stat(&StatValue::Bytes(vec![0xfb, 0x2e]), 2).unwrap();
+        assert_eq!(stat, decimal("-12.34"));
+        assert!(Decimal::from_stat(&StatValue::Bytes(vec![]), 2).is_none());
+    }
+
+    #[test]
+    fn checks_literals_against_the_precision() {
+        // Excess digits after the point still compare exactly at the literal's scale.
+        let literal = parse_literal("12.345", 5, 2).unwrap();
+        assert!(literal > decimal("12.34"));
+        assert!(literal < decimal("12.35"));
+        assert!(parse_literal("-999.99", 5, 2).is_ok());
+        assert!(parse_literal("1000", 5, 2).is_err());
+        assert!(parse_literal("-1000.5", 5, 2).is_err());
+        assert!(parse_literal("0.123", 3, 3).is_ok());
+        assert!(parse_literal("1.5", 3, 3).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use arrow_array::RecordBatchReader;
+use arrow_ipc::writer::{FileWriter, StreamWriter};
+use color_eyre::eyre::{eyre, Context};
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter, ProjectionMask},
+    file::properties::WriterProperties,
+    schema::types::SchemaDescriptor,
+};
+use parquet2::metadata::FileMetaData;
+
+use crate::source::FileSource;
+
+/// Outcome of an export.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ExportSummary {
+    pub rows: usize,
+    pub columns: usize,
+}
+
+/// Resolve column selectors to leaf column indices. A selector matches a leaf whose dotted
+/// path equals it, or every leaf nested under it (e.g. `address` selects `address.zip`).
+/// An empty list selects every leaf.
+pub fn resolve_leaves(
+    schema: &SchemaDescriptor,
+    selectors: &[String],
+) -> color_eyre::Result<Vec<usize>> {
+    let paths: Vec<String> = schema
+        .columns()
+        .iter()
+        .map(|column| column.path().string())
+        .collect();
+
+    if selectors.is_empty() {
+        return Ok((0..paths.len()).collect());
+    }
+
+    let mut leaves = Vec::new();
+    for selector in selectors {
+        let prefix = format!("{}.", selector);
+        let matched: Vec<usize> = paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| *path == selector || path.starts_with(&prefix))
+            .map(|(i, _)| i)
+            .collect();
+        if matched.is_empty() {
+            return Err(eyre!("no column matches {:?}", selector));
+        }
+        leaves.extend(matched);
+    }
+    leaves.sort_unstable();
+    leaves.dedup();
+
+    Ok(leaves)
+}
+
+/// Container written by [export_arrow_ipc].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IpcFormat {
+    /// The Arrow IPC file format, also known as Feather v2.
+    File,
+    /// The Arrow IPC streaming format.
+    Stream,
+}
+
+/// Open a reader over a row group (or the whole file when `row_group` is `None`), projected to
+/// the selected columns. Returns it along with the number of selected leaf columns.
+fn open_reader(
+    path: &Path,
+    row_group: Option<usize>,
+    columns: &[String],
+) -> color_eyre::Result<(ParquetRecordBatchReaderBuilder<FileSource>, usize)> {
+    let input =
+        FileSource::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let mut builder = ParquetRecordBatchReaderBuilder::try_new(input)?;
+
+    let num_row_groups = builder.metadata().num_row_groups();
+    if let Some(row_group) = row_group {
+        if row_group >= num_row_groups {
+            return Err(eyre!(
+                "row group {} out of range, the file has {}",
+                row_group,
+                num_row_groups
+            ));
+        }
+        builder = builder.with_row_groups(vec![row_group]);
+    }
+
+    let leaves = resolve_leaves(builder.parquet_schema(), columns)?;
+    let mask = ProjectionMask::leaves(builder.parquet_schema(), leaves.iter().cloned());
+    Ok((builder.with_projection(mask), leaves.len()))
+}
+
+/// Copy a row group (or the whole file when `row_group` is `None`) into a new standalone
+/// Parquet file, keeping only the selected columns. The data is re-encoded, using the
+/// compression codec of the source's first column chunk.
+pub fn export_parquet<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    row_group: Option<usize>,
+    columns: &[String],
+    output: Q,
+) -> color_eyre::Result<ExportSummary> {
+    let (builder, num_columns) = open_reader(path.as_ref(), row_group, columns)?;
+    let metadata = builder.metadata().clone();
+    let reader = builder.build()?;
+
+    let compression = metadata
+        .row_groups()
+        .first()
+        .and_then(|row_group| row_group.columns().first())
+        .map(|column| column.compression())
+        .unwrap_or(parquet::basic::Compression::UNCOMPRESSED);
+    let properties = WriterProperties::builder()
+        .set_compression(compression)
+        .build();
+
+    let output = File::create(output.as_ref())
+        .wrap_err_with(|| format!("failed to create {}", output.as_ref().display()))?;
+    let mut writer = ArrowWriter::try_new(output, reader.schema(), Some(properties))?;
+
+    let mut rows = 0;
+    for batch in reader {
+        let batch = batch?;
+        rows += batch.num_rows();
+        writer.write(&batch)?;
+    }
+    writer.close()?;
+
+    Ok(ExportSummary {
+        rows,
+        columns: num_columns,
+    })
+}
+
+/// Convert a row group (or the whole file when `row_group` is `None`) into an uncompressed
+/// Arrow IPC file or stream, keeping only the selected columns, for tools that read Arrow but
+/// not Parquet.
+pub fn export_arrow_ipc<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    row_group: Option<usize>,
+    columns: &[String],
+    output: Q,
+    format: IpcFormat,
+) -> color_eyre::Result<ExportSummary> {
+    let (builder, num_columns) = open_reader(path.as_ref(), row_group, columns)?;
+    let reader = builder.build()?;
+    let schema = reader.schema();
+
+    let output = BufWriter::new(
+        File::create(output.as_ref())
+            .wrap_err_with(|| format!("failed to create {}", output.as_ref().display()))?,
+    );
+    let mut rows = 0;
+    match format {
+        IpcFormat::File => {
+            let mut writer = FileWriter::try_new(output, &schema)?;
+            for batch in reader {
+                let batch = batch?;
+                rows += batch.num_rows();
+                writer.write(&batch)?;
+            }
+            writer.finish()?;
+        }
+        IpcFormat::Stream => {
+            let mut writer = StreamWriter::try_new(output, &schema)?;
+            for batch in reader {
+                let batch = batch?;
+                rows += batch.num_rows();
+                writer.write(&batch)?;
+            }
+            writer.finish()?;
+        }
+    }
+
+    Ok(ExportSummary {
+        rows,
+        columns: num_columns,
+    })
+}
+
+/// Copy the raw bytes of a column chunk, exactly as stored in the file, into `output`.
+/// Returns the file offset and length of the copied range.
+pub fn export_chunk_bytes<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    column: usize,
+    output: Q,
+) -> color_eyre::Result<(u64, u64)> {
+    let chunk = metadata
+        .row_groups
+        .get(row_group)
+        .ok_or_else(|| eyre!("row group {} out of range", row_group))?
+        .columns()
+        .get(column)
+        .ok_or_else(|| eyre!("column {} out of range", column))?;
+    let (offset, length) = chunk.byte_range();
+
+    let data_path = crate::parquet::chunk_data_path(path.as_ref(), chunk.file_path().as_deref());
+    let mut input = FileSource::open(&data_path)
+        .and_then(|source| source.reader())
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    input.seek(SeekFrom::Start(offset))?;
+
+    let mut writer = BufWriter::new(
+        File::create(output.as_ref())
+            .wrap_err_with(|| format!("failed to create {}", output.as_ref().display()))?,
+    );
+    let copied = io::copy(&mut input.take(length), &mut writer)?;
+    if copied != length {
+        return Err(eyre!(
+            "file ended after {} of {} bytes of the column chunk",
+            copied,
+            length
+        ));
+    }
+
+    Ok((offset, length))
+}
@@ -0,0 +1,75 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use clap::ValueEnum;
+use color_eyre::eyre::{eyre, Context};
+
+use crate::archive::{self, ArchiveKind};
+
+/// Order of the files given to a command, after expanding globs.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, ValueEnum)]
+pub enum FileOrder {
+    /// By path, e.g. `part-00000` before `part-00001`.
+    #[default]
+    Name,
+    /// Oldest modification time first.
+    Modified,
+}
+
+/// Whether a file argument is a glob pattern rather than a plain path.
+pub fn is_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// Expand the glob patterns among file arguments and order the resulting files.
+///
+/// Arguments naming an existing file are kept as-is, even if they contain glob characters.
+/// A pattern that matches no file is an error, so a typo does not silently shrink a dataset.
+/// Zip and tar archives expand to the Parquet files they hold, e.g. `dump.zip!part-0.parquet`.
+pub fn expand(args: &[PathBuf], order: FileOrder) -> color_eyre::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for arg in args {
+        if ArchiveKind::of(arg).is_some() && arg.is_file() {
+            let members = archive::parquet_members(arg)
+                .wrap_err_with(|| format!("failed to list the members of {}", arg.display()))?;
+            if members.is_empty() {
+                return Err(eyre!("no Parquet file in {}", arg.display()));
+            }
+            files.extend(members);
+            continue;
+        }
+        if !is_pattern(arg) || arg.exists() {
+            files.push(arg.clone());
+            continue;
+        }
+
+        let pattern = arg.to_string_lossy();
+        let matches = glob::glob(&pattern)
+            .wrap_err_with(|| format!("invalid glob pattern {:?}", pattern))?
+            .filter_map(|entry| entry.ok())
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            return Err(eyre!("no file matches {:?}", pattern));
+        }
+        files.extend(matches);
+    }
+
+    match order {
+        FileOrder::Name => files.sort(),
+        FileOrder::Modified => files.sort_by_cached_key(|path| {
+            (
+                fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH),
+                path.clone(),
+            )
+        }),
+    }
+    files.dedup();
+
+    Ok(files)
+}
@@ -0,0 +1,394 @@
+use std::{fs::File, path::Path, str::FromStr, sync::Arc};
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int32Array, Int64Array,
+    RecordBatch, StringArray, TimestampMicrosecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use clap::ValueEnum;
+use color_eyre::eyre::{eyre, Context};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{BrotliLevel, Compression, Encoding, GzipLevel, ZstdLevel},
+    file::properties::{EnabledStatistics, WriterProperties},
+    schema::types::ColumnPath,
+};
+
+/// Schema of generated files when none is given.
+pub const DEFAULT_SCHEMA: &str = "id:int64,name:string,score:double,active:bool,created:timestamp";
+
+/// Rows built in memory at once before being handed to the writer.
+const BATCH_SIZE: usize = 8192;
+
+/// Microseconds in a day, used to spread generated timestamps over days.
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Earliest generated timestamp, 2019-04-14T00:00:00Z in microseconds since the epoch.
+const FIRST_TIMESTAMP: i64 = 1_555_200_000_000_000;
+
+/// Type of a generated column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ColumnType {
+    Bool,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    String,
+    Date,
+    Timestamp,
+}
+
+impl FromStr for ColumnType {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "bool" | "boolean" => ColumnType::Bool,
+            "int32" | "int" => ColumnType::Int32,
+            "int64" | "long" => ColumnType::Int64,
+            "float" => ColumnType::Float,
+            "double" => ColumnType::Double,
+            "string" | "utf8" => ColumnType::String,
+            "date" => ColumnType::Date,
+            "timestamp" => ColumnType::Timestamp,
+            _ => {
+                return Err(eyre!(
+                    "unknown column type {:?}, expected one of bool, int32, int64, float, \
+                     double, string, date or timestamp",
+                    s
+                ))
+            }
+        })
+    }
+}
+
+impl ColumnType {
+    fn data_type(&self) -> DataType {
+        match self {
+            ColumnType::Bool => DataType::Boolean,
+            ColumnType::Int32 => DataType::Int32,
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Float => DataType::Float32,
+            ColumnType::Double => DataType::Float64,
+            ColumnType::String => DataType::Utf8,
+            ColumnType::Date => DataType::Date32,
+            ColumnType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        }
+    }
+
+    /// Whether the writer can encode the column with a non-dictionary `encoding`.
+    fn supports(&self, encoding: Encoding) -> bool {
+        match encoding {
+            Encoding::PLAIN => true,
+            Encoding::DELTA_BINARY_PACKED => matches!(
+                self,
+                ColumnType::Int32 | ColumnType::Int64 | ColumnType::Date | ColumnType::Timestamp
+            ),
+            Encoding::DELTA_LENGTH_BYTE_ARRAY | Encoding::DELTA_BYTE_ARRAY => {
+                *self == ColumnType::String
+            }
+            Encoding::BYTE_STREAM_SPLIT => matches!(self, ColumnType::Float | ColumnType::Double),
+            _ => false,
+        }
+    }
+}
+
+/// A generated column, parsed from `NAME:TYPE[:NULL_RATIO]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub column_type: ColumnType,
+    /// Overrides the null ratio of the whole file for this column.
+    pub null_ratio: Option<f64>,
+}
+
+impl FromStr for ColumnSpec {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(name), Some(column_type)) = (parts.next(), parts.next()) else {
+            return Err(eyre!("expected NAME:TYPE[:NULL_RATIO], got {:?}", s));
+        };
+        let null_ratio = parts
+            .next()
+            .map(|ratio| {
+                ratio
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|ratio| (0.0..=1.0).contains(ratio))
+                    .ok_or_else(|| eyre!("null ratio {:?} is not between 0 and 1", ratio))
+            })
+            .transpose()?;
+        if parts.next().is_some() || name.is_empty() {
+            return Err(eyre!("expected NAME:TYPE[:NULL_RATIO], got {:?}", s));
+        }
+
+        Ok(ColumnSpec {
+            name: name.to_string(),
+            column_type: column_type.parse()?,
+            null_ratio,
+        })
+    }
+}
+
+/// Compression codec of generated files, at the default level of codecs with levels.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, ValueEnum)]
+pub enum Codec {
+    Uncompressed,
+    #[default]
+    Snappy,
+    Gzip,
+    Brotli,
+    Lz4,
+    Lz4Raw,
+    Zstd,
+}
+
+impl Codec {
+    fn compression(&self) -> Compression {
+        match self {
+            Codec::Uncompressed => Compression::UNCOMPRESSED,
+            Codec::Snappy => Compression::SNAPPY,
+            Codec::Gzip => Compression::GZIP(GzipLevel::default()),
+            Codec::Brotli => Compression::BROTLI(BrotliLevel::default()),
+            Codec::Lz4 => Compression::LZ4,
+            Codec::Lz4Raw => Compression::LZ4_RAW,
+            Codec::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+/// Parse an encoding name as in the Parquet specification, in either case.
+pub fn parse_encoding(s: &str) -> color_eyre::Result<Encoding> {
+    Encoding::from_str(&s.replace('-', "_")).map_err(|err| eyre!("{}", err))
+}
+
+/// What to write: the shape of the file and the anomalies to plant in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateOptions {
+    pub columns: Vec<ColumnSpec>,
+    pub rows: usize,
+    pub row_group_size: usize,
+    pub codec: Codec,
+    /// Encoding of the columns that support it, with dictionaries disabled for them. `None`
+    /// keeps the writer's default of dictionary encoding with a PLAIN fallback.
+    pub encoding: Option<Encoding>,
+    pub null_ratio: f64,
+    /// Distinct values of the integer, string and date columns.
+    pub cardinality: u64,
+    /// Write no statistics at all, neither in the column chunks nor in a page index.
+    pub no_stats: bool,
+    /// Write chunk statistics but no column and offset index.
+    pub no_page_index: bool,
+    /// Cap the rows of every data page, e.g. to 1 for tiny pages.
+    pub page_rows: Option<usize>,
+    pub seed: u64,
+}
+
+/// Outcome of a generation.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GenerateSummary {
+    pub rows: usize,
+    pub row_groups: usize,
+    pub columns: usize,
+    pub bytes: u64,
+}
+
+/// SplitMix64, a small deterministic generator, so that a seed always yields the same file.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Generate `rows` values of a column, each null with probability `null_ratio`.
+fn generate_array(
+    rng: &mut Rng,
+    column_type: ColumnType,
+    rows: usize,
+    null_ratio: f64,
+    cardinality: u64,
+) -> ArrayRef {
+    // A random draw per row, or `None` for nulls. Draws are taken for null rows as well, so
+    // that the null ratio does not shift the values of the other rows.
+    let draws: Vec<Option<u64>> = (0..rows)
+        .map(|_| {
+            let draw = rng.next();
+            (rng.unit() >= null_ratio).then_some(draw)
+        })
+        .collect();
+    match column_type {
+        ColumnType::Bool => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| draw & 1 == 1))
+                .collect::<BooleanArray>(),
+        ),
+        ColumnType::Int32 => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| (draw % cardinality.max(1)) as i32))
+                .collect::<Int32Array>(),
+        ),
+        ColumnType::Int64 => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| (draw % cardinality.max(1)) as i64))
+                .collect::<Int64Array>(),
+        ),
+        ColumnType::Float => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| (draw >> 40) as f32 / 16_384.0))
+                .collect::<Float32Array>(),
+        ),
+        ColumnType::Double => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| (draw >> 11) as f64 / (1u64 << 43) as f64))
+                .collect::<Float64Array>(),
+        ),
+        ColumnType::String => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| format!("value-{:06}", draw % cardinality.max(1))))
+                .collect::<StringArray>(),
+        ),
+        ColumnType::Date => Arc::new(
+            draws
+                .iter()
+                .map(|draw| draw.map(|draw| 18_000 + (draw % cardinality.max(1)) as i32))
+                .collect::<Date32Array>(),
+        ),
+        ColumnType::Timestamp => Arc::new(
+            draws
+                .iter()
+                .map(|draw| {
+                    draw.map(|draw| {
+                        FIRST_TIMESTAMP
+                            + (draw % cardinality.max(1)) as i64 * MICROS_PER_DAY
+                            + ((draw >> 32) % MICROS_PER_DAY as u64) as i64
+                    })
+                })
+                .collect::<TimestampMicrosecondArray>(),
+        ),
+    }
+}
+
+fn writer_properties(options: &GenerateOptions) -> WriterProperties {
+    let mut builder = WriterProperties::builder()
+        .set_compression(options.codec.compression())
+        .set_max_row_group_size(options.row_group_size.max(1))
+        .set_statistics_enabled(if options.no_stats {
+            EnabledStatistics::None
+        } else if options.no_page_index {
+            EnabledStatistics::Chunk
+        } else {
+            EnabledStatistics::Page
+        });
+    if let Some(page_rows) = options.page_rows {
+        // The writer checks the page limits once per write batch.
+        builder = builder
+            .set_data_page_row_count_limit(page_rows.max(1))
+            .set_write_batch_size(page_rows.max(1));
+    }
+    if let Some(encoding) = options.encoding {
+        match encoding {
+            Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY => {
+                builder = builder.set_dictionary_enabled(true);
+            }
+            encoding => {
+                for column in options
+                    .columns
+                    .iter()
+                    .filter(|column| column.column_type.supports(encoding))
+                {
+                    let path = ColumnPath::new(vec![column.name.clone()]);
+                    builder = builder
+                        .set_column_dictionary_enabled(path.clone(), false)
+                        .set_column_encoding(path, encoding);
+                }
+            }
+        }
+    }
+    builder.build()
+}
+
+/// Write a file of random rows with the given schema and layout. Values only depend on the
+/// seed, and anomalies such as missing statistics or tiny pages are planted through the
+/// writer's properties.
+pub fn generate<P: AsRef<Path>>(
+    output: P,
+    options: &GenerateOptions,
+) -> color_eyre::Result<GenerateSummary> {
+    if options.columns.is_empty() {
+        return Err(eyre!("the schema has no columns"));
+    }
+    if let Some(encoding) = options.encoding {
+        let dictionary = matches!(
+            encoding,
+            Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+        );
+        if !dictionary
+            && !options
+                .columns
+                .iter()
+                .any(|column| column.column_type.supports(encoding))
+        {
+            return Err(eyre!("no column of the schema supports {}", encoding));
+        }
+    }
+
+    let schema = Arc::new(Schema::new(
+        options
+            .columns
+            .iter()
+            .map(|column| Field::new(&column.name, column.column_type.data_type(), true))
+            .collect::<Vec<_>>(),
+    ));
+    let file = File::create(output.as_ref())
+        .wrap_err_with(|| format!("failed to create {}", output.as_ref().display()))?;
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(writer_properties(options)))?;
+
+    let mut rng = Rng(options.seed);
+    let mut written = 0;
+    while written < options.rows {
+        let rows = BATCH_SIZE.min(options.rows - written);
+        let arrays = options
+            .columns
+            .iter()
+            .map(|column| {
+                generate_array(
+                    &mut rng,
+                    column.column_type,
+                    rows,
+                    column.null_ratio.unwrap_or(options.null_ratio),
+                    options.cardinality,
+                )
+            })
+            .collect();
+        writer.write(&RecordBatch::try_new(schema.clone(), arrays)?)?;
+        written += rows;
+    }
+    let metadata = writer.close()?;
+
+    Ok(GenerateSummary {
+        rows: written,
+        row_groups: metadata.row_groups.len(),
+        columns: options.columns.len(),
+        bytes: std::fs::metadata(output.as_ref())?.len(),
+    })
+}
@@ -0,0 +1,288 @@
+use parquet2::metadata::FileMetaData;
+
+use crate::records::num_rows;
+
+/// State of the `g` goto prompt.
+///
+/// The prompt accepts a row group index, a column path, or both separated by whitespace
+/// (e.g. `12 events.payload.id`). Column paths are autocompleted against the file schema.
+/// `row N` instead goes to the record at global row index `N`.
+#[derive(Debug, Default, Clone)]
+pub struct GotoPrompt {
+    pub input: String,
+    /// Input as typed before Tab started cycling through completions.
+    completion_base: Option<String>,
+    /// Index into the completion list that Tab will apply next.
+    completion_index: usize,
+}
+
+/// Location resolved from the goto prompt input.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GotoTarget {
+    pub row_group: Option<usize>,
+    pub column: Option<usize>,
+    /// Global row index of a record to show.
+    pub row: Option<u64>,
+}
+
+impl GotoPrompt {
+    /// Split the input that completions are computed from, i.e. the text typed before Tab cycling.
+    fn parts(&self) -> (Option<&str>, &str) {
+        split_input(self.completion_base.as_deref().unwrap_or(&self.input))
+    }
+
+    /// Column paths matching the column part of the input. Prefix matches are listed before
+    /// substring matches.
+    pub fn completions(&self, metadata: &FileMetaData) -> Vec<String> {
+        let (_, column) = self.parts();
+        if column.is_empty() {
+            return Vec::new();
+        }
+
+        let paths = column_paths(metadata);
+        let needle = column.to_lowercase();
+        let mut prefixed: Vec<String> = Vec::new();
+        let mut contained: Vec<String> = Vec::new();
+        for path in paths {
+            let lower = path.to_lowercase();
+            if lower.starts_with(&needle) {
+                prefixed.push(path);
+            } else if lower.contains(&needle) {
+                contained.push(path);
+            }
+        }
+        prefixed.extend(contained);
+
+        prefixed
+    }
+
+    /// Replace the column part of the input with the next completion candidate.
+    pub fn complete(&mut self, metadata: &FileMetaData) {
+        let completions = self.completions(metadata);
+        if completions.is_empty() {
+            return;
+        }
+
+        let choice = completions[self.completion_index % completions.len()].clone();
+        let input = match self.parts() {
+            (Some(row_group), _) => format!("{} {}", row_group, choice),
+            (None, _) => choice,
+        };
+        self.completion_base
+            .get_or_insert_with(|| self.input.clone());
+        self.input = input;
+        self.completion_index += 1;
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.reset_completion();
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.reset_completion();
+    }
+
+    fn reset_completion(&mut self) {
+        self.completion_base = None;
+        self.completion_index = 0;
+    }
+
+    /// Resolve the prompt input against the file. Returns `None` if the row group is out of
+    /// range or no column matches.
+    pub fn resolve(&self, metadata: &FileMetaData) -> Option<GotoTarget> {
+        if let Some(row) = parse_row(&self.input) {
+            if row >= num_rows(metadata) {
+                return None;
+            }
+            return Some(GotoTarget {
+                row: Some(row),
+                ..Default::default()
+            });
+        }
+
+        let (row_group, column) = split_input(&self.input);
+
+        let row_group = match row_group {
+            Some(digits) => {
+                let index = digits.parse::<usize>().ok()?;
+                if index >= metadata.row_groups.len() {
+                    return None;
+                }
+                Some(index)
+            }
+            None => None,
+        };
+
+        let column = if column.is_empty() {
+            None
+        } else {
+            let paths = column_paths(metadata);
+            let exact = paths.iter().position(|path| path == column);
+            let column = exact.or_else(|| {
+                let first = GotoPrompt::from(self.input.as_str())
+                    .completions(metadata)
+                    .into_iter()
+                    .next()?;
+                paths.iter().position(|path| *path == first)
+            })?;
+            Some(column)
+        };
+
+        if row_group.is_none() && column.is_none() {
+            return None;
+        }
+
+        Some(GotoTarget {
+            row_group,
+            column,
+            row: None,
+        })
+    }
+}
+
+impl From<&str> for GotoPrompt {
+    fn from(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse a `row N` input into the global row index.
+fn parse_row(input: &str) -> Option<u64> {
+    let rest = input.trim().strip_prefix("row")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    rest.trim_start().parse().ok()
+}
+
+/// Split prompt input into an optional leading row group number and the remaining column text.
+fn split_input(input: &str) -> (Option<&str>, &str) {
+    let input = input.trim_start();
+    match input.split_once(char::is_whitespace) {
+        Some((first, rest)) if first.chars().all(|c| c.is_ascii_digit()) => {
+            (Some(first), rest.trim_start())
+        }
+        None if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) => (Some(input), ""),
+        _ => (None, input),
+    }
+}
+
+/// Dotted paths of every leaf column in the file schema, in column chunk order.
+pub fn column_paths(metadata: &FileMetaData) -> Vec<String> {
+    metadata
+        .schema()
+        .columns()
+        .iter()
+        .map(|col| col.path_in_schema.join("."))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Arc};
+
+    use parquet::{
+        data_type::Int64Type,
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+
+    use super::*;
+
+    /// Metadata of a file with leaves `id`, `user.age` and `user.zip`, and two row groups of
+    /// three rows.
+    fn metadata() -> FileMetaData {
+        let schema = parse_message_type(
+            "message m {
+                required int64 id;
+                required group user {
+                    required int64 age;
+                    required int64 zip;
+                }
+            }",
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut bytes,
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+        for _ in 0..2 {
+            let mut row_group = writer.next_row_group().unwrap();
+            while let Some(mut column) = row_group.next_column().unwrap() {
+                column
+                    .typed::<Int64Type>()
+                    .write_batch(&[1, 2, 3], None, None)
+                    .unwrap();
+                column.close().unwrap();
+            }
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+        parquet2::read::read_metadata(&mut Cursor::new(bytes)).unwrap()
+    }
+
+    fn resolve(input: &str) -> Option<GotoTarget> {
+        GotoPrompt::from(input).resolve(&metadata())
+    }
+
+    #[test]
+    fn resolves_row_groups_in_range() {
+        assert_eq!(
+            resolve("1"),
+            Some(GotoTarget {
+                row_group: Some(1),
+                ..Default::default()
+            })
+        );
+        assert_eq!(resolve("2"), None);
+    }
+
+    #[test]
+    fn resolves_columns_by_path_then_by_completion() {
+        assert_eq!(
+            resolve("user.age").and_then(|target| target.column),
+            Some(1)
+        );
+        assert_eq!(resolve("USER.Z").and_then(|target| target.column), Some(2));
+        assert_eq!(resolve("zip").and_then(|target| target.column), Some(2));
+        assert_eq!(resolve("missing"), None);
+    }
+
+    #[test]
+    fn resolves_row_group_and_column() {
+        assert_eq!(
+            resolve("1 age"),
+            Some(GotoTarget {
+                row_group: Some(1),
+                column: Some(1),
+                row: None,
+            })
+        );
+        assert_eq!(resolve("7 age"), None);
+    }
+
+    #[test]
+    fn resolves_rows_across_row_groups() {
+        assert_eq!(resolve("row 5").and_then(|target| target.row), Some(5));
+        assert_eq!(resolve("row 6"), None);
+    }
+
+    #[test]
+    fn completes_prefixes_before_substrings() {
+        let metadata = metadata();
+        let mut prompt = GotoPrompt::from("1 u");
+        assert_eq!(prompt.completions(&metadata), ["user.age", "user.zip"]);
+        prompt.complete(&metadata);
+        prompt.complete(&metadata);
+        assert_eq!(prompt.input, "1 user.zip");
+        assert_eq!(GotoPrompt::from("a").completions(&metadata), ["user.age"]);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    io::{self, Write},
+    sync::Mutex,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use ratatui::layout::Rect;
+
+/// Terminal graphics protocol charts are drawn with, as raster images instead of block
+/// characters.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum GraphicsMode {
+    /// Kitty or Sixel when the terminal is known to support it, block characters otherwise.
+    #[default]
+    Auto,
+    Kitty,
+    Sixel,
+    /// Block and braille characters only.
+    Off,
+}
+
+/// Protocol images are written with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+/// Size of a terminal cell in pixels, known when the terminal reports its pixel size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CellSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+/// An image of palette colors, drawn by charts and written to the terminal over the cells of
+/// an area. The first palette color is transparent, showing the terminal background.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pub palette: Vec<[u8; 3]>,
+    /// Palette index of every pixel, row by row.
+    pub pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// A transparent image drawn with the colors of `palette`.
+    pub fn new(width: usize, height: usize, palette: &[[u8; 3]]) -> Self {
+        Self {
+            width,
+            height,
+            palette: std::iter::once([0; 3])
+                .chain(palette.iter().copied())
+                .collect(),
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// Fill a rectangle with a color, 1 for the first color of the palette the image was
+    /// created with, clipped to the image.
+    pub fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: u8) {
+        for row in y.min(self.height)..(y + height).min(self.height) {
+            let start = row * self.width;
+            self.pixels[start + x.min(self.width)..start + (x + width).min(self.width)].fill(color);
+        }
+    }
+
+    fn to_rgba(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&index| {
+                let [r, g, b] = self.palette[usize::from(index)];
+                [r, g, b, if index == 0 { 0 } else { 255 }]
+            })
+            .collect()
+    }
+}
+
+struct State {
+    protocol: Option<(Protocol, CellSize)>,
+    /// Images queued by the frame being drawn, and the ones on screen.
+    queued: Vec<(Rect, Raster)>,
+    shown: Vec<(Rect, Raster)>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    protocol: None,
+    queued: Vec::new(),
+    shown: Vec::new(),
+});
+
+/// Protocol the environment suggests the terminal supports, from the variables set by
+/// terminals with Kitty or Sixel graphics.
+fn detect() -> Option<Protocol> {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || ["WezTerm", "ghostty"].contains(&program.as_str())
+    {
+        Some(Protocol::Kitty)
+    } else if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("mlterm")
+        || ["iTerm.app", "mintty"].contains(&program.as_str())
+    {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Enable raster charts for the rest of the process, with the protocol `mode` selects.
+/// Charts keep block characters when the terminal does not report its size in pixels, which
+/// images are scaled to fit cells by.
+pub fn enable(mode: GraphicsMode) {
+    let protocol = match mode {
+        GraphicsMode::Auto => detect(),
+        GraphicsMode::Kitty => Some(Protocol::Kitty),
+        GraphicsMode::Sixel => Some(Protocol::Sixel),
+        GraphicsMode::Off => None,
+    };
+    let cell_size = crossterm::terminal::window_size()
+        .ok()
+        .filter(|size| size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0)
+        .map(|size| CellSize {
+            width: size.width / size.columns,
+            height: size.height / size.rows,
+        });
+    STATE.lock().unwrap().protocol = protocol.zip(cell_size);
+}
+
+/// Pixel size of a cell when charts are drawn as images, `None` when they use characters.
+pub fn cell_size() -> Option<CellSize> {
+    STATE
+        .lock()
+        .unwrap()
+        .protocol
+        .map(|(_, cell_size)| cell_size)
+}
+
+/// Queue an image to be drawn over `area` once the frame is drawn.
+pub fn queue(area: Rect, raster: Raster) {
+    STATE.lock().unwrap().queued.push((area, raster));
+}
+
+/// Write the images queued by the last frame, unless they are already on screen. Returns
+/// whether the screen must be cleared and drawn again, as sixel images stay painted over
+/// cells until they are redrawn.
+pub fn flush(out: &mut impl Write) -> io::Result<bool> {
+    let mut state = STATE.lock().unwrap();
+    let Some((protocol, _)) = state.protocol else {
+        return Ok(false);
+    };
+    let queued = std::mem::take(&mut state.queued);
+    if queued == state.shown {
+        return Ok(false);
+    }
+
+    let mut redraw = false;
+    match protocol {
+        // Delete every image placed before.
+        Protocol::Kitty => write!(out, "\x1b_Ga=d,q=2\x1b\\")?,
+        Protocol::Sixel => redraw = !state.shown.is_empty(),
+    }
+    if !redraw {
+        for (area, raster) in &queued {
+            write!(out, "\x1b7\x1b[{};{}H", area.y + 1, area.x + 1)?;
+            match protocol {
+                Protocol::Kitty => write_kitty(out, area, raster)?,
+                Protocol::Sixel => write_sixel(out, raster)?,
+            }
+            write!(out, "\x1b8")?;
+        }
+        state.shown = queued;
+    } else {
+        state.shown.clear();
+    }
+    out.flush()?;
+    Ok(redraw)
+}
+
+/// Kitty graphics protocol: RGBA pixels in base64, sent in chunks of 4096 bytes.
+fn write_kitty(out: &mut impl Write, area: &Rect, raster: &Raster) -> io::Result<()> {
+    let data = STANDARD.encode(raster.to_rgba());
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},c={},r={},C=1,q=2,m={};",
+                raster.width, raster.height, area.width, area.height, more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+    Ok(())
+}
+
+/// Sixel: the palette in percents, then bands of six pixel rows, one run-length encoded pass
+/// per color. Transparent pixels are left unset, which keeps the background.
+fn write_sixel(out: &mut impl Write, raster: &Raster) -> io::Result<()> {
+    write!(out, "\x1bP0;1q\"1;1;{};{}", raster.width, raster.height)?;
+    for (i, [r, g, b]) in raster.palette.iter().enumerate() {
+        let percent = |c: &u8| u16::from(*c) * 100 / 255;
+        write!(out, "#{};2;{};{};{}", i, percent(r), percent(g), percent(b))?;
+    }
+    for band in (0..raster.height).step_by(6) {
+        for color in 1..raster.palette.len() as u8 {
+            let sixels: Vec<u8> = (0..raster.width)
+                .map(|x| {
+                    (0..6)
+                        .filter(|dy| {
+                            let y = band + dy;
+                            y < raster.height && raster.pixels[y * raster.width + x] == color
+                        })
+                        .fold(0, |bits, dy| bits | 1 << dy)
+                })
+                .collect();
+            if sixels.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+            write!(out, "#{}", color)?;
+            let mut x = 0;
+            while x < sixels.len() {
+                let run = sixels[x..]
+                    .iter()
+                    .take_while(|&&bits| bits == sixels[x])
+                    .count();
+                let char = (sixels[x] + 63) as char;
+                if run > 3 {
+                    write!(out, "!{}{}", run, char)?;
+                } else {
+                    write!(out, "{}", char.to_string().repeat(run))?;
+                }
+                x += run;
+            }
+            write!(out, "$")?;
+        }
+        write!(out, "-")?;
+    }
+    write!(out, "\x1b\\")
+}
@@ -0,0 +1,232 @@
+use std::{
+    fs::{self, File},
+    io::{self, LineWriter, Write},
+    path::Path,
+};
+
+use color_eyre::eyre::{eyre, WrapErr};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/// Keys without a printable character, by the name they are written with.
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("Space", KeyCode::Char(' ')),
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Backspace", KeyCode::Backspace),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Delete", KeyCode::Delete),
+];
+
+/// Modifiers, by the prefix they are written with.
+const MODIFIERS: [(&str, KeyModifiers); 3] = [
+    ("C-", KeyModifiers::CONTROL),
+    ("A-", KeyModifiers::ALT),
+    ("S-", KeyModifiers::SHIFT),
+];
+
+/// One line of a key file: `C-Right` or `x` for key presses, `resize 160x40` for resizes, or
+/// `None` for events that are not recorded, such as key releases and mouse events.
+pub fn format_event(event: &Event) -> Option<String> {
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => format_key(key),
+        Event::Resize(width, height) => Some(format!("resize {}x{}", width, height)),
+        _ => None,
+    }
+}
+
+fn format_key(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        code => NAMED_KEYS
+            .iter()
+            .find(|(_, named)| *named == code)?
+            .0
+            .to_string(),
+    };
+    // Shift is implied by the case of characters.
+    let mut modifiers = key.modifiers;
+    if matches!(key.code, KeyCode::Char(_)) {
+        modifiers.remove(KeyModifiers::SHIFT);
+    }
+    let prefix: String = MODIFIERS
+        .iter()
+        .filter(|(_, modifier)| modifiers.contains(*modifier))
+        .map(|(prefix, _)| *prefix)
+        .collect();
+    Some(prefix + &name)
+}
+
+/// Parse a line written by [format_event].
+pub fn parse_event(line: &str) -> color_eyre::Result<Event> {
+    if let Some(size) = line.strip_prefix("resize ") {
+        let (width, height) = size
+            .split_once('x')
+            .ok_or_else(|| eyre!("expected a size like `160x40`, got `{}`", size))?;
+        return Ok(Event::Resize(width.trim().parse()?, height.trim().parse()?));
+    }
+
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = line;
+    // A single character is a key even when it looks like a prefix, e.g. `-`.
+    while name.chars().count() > 1 {
+        let Some((prefix, modifier)) = MODIFIERS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix) && name.len() > prefix.len())
+        else {
+            break;
+        };
+        modifiers |= *modifier;
+        name = &name[prefix.len()..];
+    }
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match NAMED_KEYS.iter().find(|(named, _)| *named == name) {
+            Some((_, code)) => *code,
+            None => KeyCode::F(
+                name.strip_prefix('F')
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(|| eyre!("unknown key `{}`", name))?,
+            ),
+        },
+    };
+    if let KeyCode::Char(c) = code {
+        if c.is_uppercase() {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+    }
+    Ok(Event::Key(KeyEvent::new(code, modifiers)))
+}
+
+/// Read the events of a key file written with `--record`, skipping blank lines and `#`
+/// comments.
+pub fn read(path: &Path) -> color_eyre::Result<Vec<Event>> {
+    let text = fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading key file {} failed", path.display()))?;
+    text.lines()
+        .enumerate()
+        .map(|(number, line)| (number, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            parse_event(line)
+                .wrap_err_with(|| format!("{}:{} is not a key", path.display(), number + 1))
+        })
+        .collect()
+}
+
+/// Writes the events handled by the TUI to a key file, one per line, so that a session can be
+/// replayed with `--replay`. Lines are flushed as they are written, to keep the keys leading
+/// to a crash.
+pub struct KeyRecorder {
+    out: LineWriter<File>,
+}
+
+impl KeyRecorder {
+    /// Create the key file, starting with the terminal size.
+    pub fn create(path: &Path, (width, height): (u16, u16)) -> io::Result<Self> {
+        let mut recorder = Self {
+            out: LineWriter::new(File::create(path)?),
+        };
+        writeln!(recorder.out, "# parquet-console key recording")?;
+        recorder.record(&Event::Resize(width, height))?;
+        Ok(recorder)
+    }
+
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        match format_event(event) {
+            Some(line) => writeln!(self.out, "{}", line),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn parses_keys_with_modifiers() {
+        assert_eq!(
+            parse_event("x").unwrap(),
+            key(KeyCode::Char('x'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_event("X").unwrap(),
+            key(KeyCode::Char('X'), KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            parse_event("C-A-Right").unwrap(),
+            key(KeyCode::Right, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            parse_event("-").unwrap(),
+            key(KeyCode::Char('-'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_event("C--").unwrap(),
+            key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse_event("Space").unwrap(),
+            key(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_event("F5").unwrap(),
+            key(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_event("resize 160x40").unwrap(),
+            Event::Resize(160, 40)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for line in ["Nope", "C-", "Fx", "resize 160", "resize axb"] {
+            assert!(parse_event(line).is_err(), "{:?} parsed", line);
+        }
+    }
+
+    #[test]
+    fn formats_keys_that_parse_back() {
+        for line in [
+            "x",
+            "X",
+            "C-x",
+            "S-Tab",
+            "C-A-Right",
+            "Space",
+            "Delete",
+            "PageDown",
+            "F12",
+            "resize 80x24",
+        ] {
+            let event = parse_event(line).unwrap();
+            assert_eq!(format_event(&event).as_deref(), Some(line));
+        }
+    }
+
+    #[test]
+    fn skips_events_that_are_not_key_presses() {
+        let mut release = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(format_event(&Event::Key(release)), None);
+        assert_eq!(format_event(&Event::FocusGained), None);
+    }
+}
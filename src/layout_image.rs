@@ -0,0 +1,134 @@
+use std::fmt::Write as _;
+
+use parquet2::metadata::FileMetaData;
+
+use crate::{goto::column_paths, snapshot::xml_escape};
+
+/// Width of the image, and of the margins around the drawing, in pixels.
+const WIDTH: f64 = 960.0;
+const MARGIN: f64 = 16.0;
+/// Width of the row group numbers left of the bars, and of the sizes right of them.
+const LABEL_WIDTH: f64 = 56.0;
+const SIZE_WIDTH: f64 = 200.0;
+/// Smallest and largest height of a row group bar, scaled like the boxes of the TUI canvas.
+const MIN_BAR_HEIGHT: f64 = 16.0;
+const MAX_BAR_HEIGHT: f64 = 48.0;
+const BAR_GAP: f64 = 8.0;
+const LINE_HEIGHT: f64 = 20.0;
+
+const BACKGROUND: &str = "#1c1c1c";
+const FOREGROUND: &str = "#d0d0d0";
+/// Colors of the column chunks, cycled through by column index.
+const PALETTE: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+];
+
+/// Standalone SVG image of the row group layout: one bar per row group, as long as its
+/// compressed size relative to the largest row group, split into its column chunks. Hovering
+/// a chunk shows its column and size, and a legend maps colors to columns.
+pub fn row_group_svg(file_name: &str, metadata: &FileMetaData) -> String {
+    let paths = column_paths(metadata);
+    let sizes: Vec<f64> = metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.compressed_size() as f64)
+        .collect();
+    let max_size = sizes.iter().cloned().fold(1.0, f64::max);
+    let bar_width = WIDTH - 2.0 * MARGIN - LABEL_WIDTH - SIZE_WIDTH;
+
+    let mut body = String::new();
+    let mut y = MARGIN;
+    writeln!(
+        body,
+        r#"<text x="{}" y="{}" font-weight="bold">{} — {} row groups, {:.2} MB</text>"#,
+        MARGIN,
+        y + 14.0,
+        xml_escape(file_name),
+        metadata.row_groups.len(),
+        sizes.iter().sum::<f64>() / 1_000_000.0
+    )
+    .unwrap();
+    y += LINE_HEIGHT + BAR_GAP;
+
+    for (index, row_group) in metadata.row_groups.iter().enumerate() {
+        let height =
+            (MIN_BAR_HEIGHT + (MAX_BAR_HEIGHT - MIN_BAR_HEIGHT) * sizes[index] / max_size).round();
+        let middle = y + height / 2.0 + 5.0;
+        writeln!(
+            body,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            MARGIN, middle, index
+        )
+        .unwrap();
+
+        let mut x = MARGIN + LABEL_WIDTH;
+        for (column, chunk) in row_group.columns().iter().enumerate() {
+            let size = chunk.compressed_size() as f64;
+            let width = bar_width * size / max_size;
+            writeln!(
+                body,
+                r#"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="{}"><title>{}: {} bytes</title></rect>"#,
+                x,
+                y,
+                width,
+                height,
+                PALETTE[column % PALETTE.len()],
+                xml_escape(&paths[column]),
+                chunk.compressed_size()
+            )
+            .unwrap();
+            x += width;
+        }
+        writeln!(
+            body,
+            r#"<text x="{}" y="{}">{} rows  {:.2} MB</text>"#,
+            MARGIN + LABEL_WIDTH + bar_width + 8.0,
+            middle,
+            row_group.num_rows(),
+            sizes[index] / 1_000_000.0
+        )
+        .unwrap();
+        y += height + BAR_GAP;
+    }
+
+    y += BAR_GAP;
+    for (column, path) in paths.iter().enumerate() {
+        writeln!(
+            body,
+            r#"<rect x="{}" y="{}" width="12" height="12" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+            MARGIN,
+            y + 3.0,
+            PALETTE[column % PALETTE.len()],
+            MARGIN + 20.0,
+            y + 14.0,
+            xml_escape(path)
+        )
+        .unwrap();
+        y += LINE_HEIGHT;
+    }
+
+    let height = y + MARGIN;
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = WIDTH,
+        h = height
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        BACKGROUND
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<g font-family="Menlo, Consolas, 'DejaVu Sans Mono', monospace" font-size="14px" fill="{}">"#,
+        FOREGROUND
+    )
+    .unwrap();
+    out.push_str(&body);
+    out.push_str("</g>\n</svg>\n");
+    out
+}
@@ -0,0 +1,564 @@
+use std::{cell::Cell, io, ops::Range};
+
+use color_eyre::eyre::eyre;
+use parquet2::{
+    compression::Compression,
+    metadata::{ColumnChunkMetaData, FileMetaData, RowGroupMetaData},
+};
+use parquet_format_safe::{
+    thrift::protocol::{TCompactInputProtocol, TInputProtocol, TType},
+    RowGroup, SortingColumn,
+};
+
+use crate::{cache::MAX_FOOTER_SIZE, parquet::RowGroupFooter};
+
+/// Row groups times leaf columns from which row groups are decoded as they are shown, rather
+/// than all of them when the file is opened.
+pub const LAZY_FOOTER_CHUNKS: usize = 100_000;
+
+/// First word of a serialized [FooterSummary], followed by the version of its format.
+const SUMMARY_TAG: &str = "summary v1";
+
+/// Field id of `FileMetaData.row_groups`.
+const ROW_GROUPS_FIELD: i16 = 4;
+
+/// Deepest nesting of structs and lists skipped while scanning.
+const MAX_SKIP_DEPTH: u8 = 64;
+
+/// Compact protocol header of an empty list of structs.
+const EMPTY_STRUCT_LIST: u8 = 0x0C;
+
+/// Reader over a serialized footer that shares its position, so that the byte range of each
+/// row group can be recorded while the protocol reads through it.
+struct TrackedReader<'a> {
+    bytes: &'a [u8],
+    position: &'a Cell<usize>,
+}
+
+impl io::Read for TrackedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = &self.bytes[self.position.get().min(self.bytes.len())..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.position.set(self.position.get() + n);
+        Ok(n)
+    }
+}
+
+/// What is known of a row group before it is decoded: where it is in the footer, and the
+/// sizes the row group browser shows.
+#[derive(Debug, Clone)]
+struct RowGroupSummary {
+    range: Range<usize>,
+    columns: usize,
+    num_rows: usize,
+    total_byte_size: usize,
+    total_compressed_size: Option<usize>,
+    footer: RowGroupFooter,
+}
+
+/// The row groups of a footer, scanned without decoding their column chunks, and the byte
+/// range of their list after its field header. Kept in the footer cache, so that footers read
+/// from it are neither scanned nor decoded in full again.
+#[derive(Debug, Clone)]
+pub struct FooterSummary {
+    row_groups: Vec<RowGroupSummary>,
+    list: Range<usize>,
+}
+
+/// A footer whose row groups are decoded on demand.
+///
+/// Decoding every column chunk of a footer with hundreds of thousands of them takes seconds
+/// and hundreds of megabytes. Instead the footer is scanned once for the byte range and the
+/// sizes of each row group, and decoded without its row groups: the metadata starts with a
+/// placeholder without column chunks for each row group, replaced by [LazyFooter::decode]
+/// when the row group is first shown.
+#[derive(Debug)]
+pub struct LazyFooter {
+    footer: Vec<u8>,
+    row_groups: Vec<RowGroupSummary>,
+    decoded: Vec<bool>,
+}
+
+/// Decode a serialized footer, lazily if it has at least [LAZY_FOOTER_CHUNKS] column chunks.
+/// Row groups of lazily decoded metadata are placeholders until [LazyFooter::decode] is
+/// called for them.
+pub fn decode(
+    footer: Vec<u8>,
+    summary: FooterSummary,
+) -> color_eyre::Result<(FileMetaData, Option<LazyFooter>)> {
+    if summary.column_chunks() < LAZY_FOOTER_CHUNKS {
+        return Ok((crate::cache::decode_footer(&footer)?, None));
+    }
+    tracing::debug!(
+        "decoding the {} row groups of a footer with {} column chunks as they are shown",
+        summary.row_groups.len(),
+        summary.column_chunks()
+    );
+    let (metadata, lazy_footer) = decode_lazily(footer, summary)?;
+    Ok((metadata, Some(lazy_footer)))
+}
+
+/// Decode a serialized footer without its row groups, whatever its size.
+pub fn decode_lazily(
+    footer: Vec<u8>,
+    summary: FooterSummary,
+) -> color_eyre::Result<(FileMetaData, LazyFooter)> {
+    let FooterSummary { row_groups, list } = summary;
+    let mut stripped = Vec::with_capacity(footer.len() - list.len() + 1);
+    stripped.extend_from_slice(&footer[..list.start]);
+    stripped.push(EMPTY_STRUCT_LIST);
+    stripped.extend_from_slice(&footer[list.end..]);
+    let mut metadata = crate::cache::decode_footer(&stripped)?;
+    metadata.row_groups = row_groups
+        .iter()
+        .map(|row_group| {
+            RowGroupMetaData::new(Vec::new(), row_group.num_rows, row_group.total_byte_size)
+        })
+        .collect();
+    let decoded = vec![false; row_groups.len()];
+    Ok((
+        metadata,
+        LazyFooter {
+            footer,
+            row_groups,
+            decoded,
+        },
+    ))
+}
+
+impl FooterSummary {
+    /// Scan a footer for the summary of each row group, without decoding their column chunks.
+    pub fn scan(footer: &[u8]) -> color_eyre::Result<Self> {
+        let position = Cell::new(0);
+        let mut protocol = TCompactInputProtocol::new(
+            TrackedReader {
+                bytes: footer,
+                position: &position,
+            },
+            MAX_FOOTER_SIZE,
+        );
+
+        let mut row_groups = Vec::new();
+        let mut list = None;
+        protocol.read_struct_begin()?;
+        loop {
+            let field = protocol.read_field_begin()?;
+            match (field.field_type, field.id) {
+                (TType::Stop, _) => break,
+                (TType::List, Some(ROW_GROUPS_FIELD)) => {
+                    let start = position.get();
+                    let header = protocol.read_list_begin()?;
+                    for _ in 0..header.size {
+                        let row_group_start = position.get();
+                        let mut summary = scan_row_group(&mut protocol)?;
+                        summary.range = row_group_start..position.get();
+                        row_groups.push(summary);
+                    }
+                    protocol.read_list_end()?;
+                    list = Some(start..position.get());
+                }
+                (field_type, _) => skip(&mut protocol, field_type, MAX_SKIP_DEPTH)?,
+            }
+            protocol.read_field_end()?;
+        }
+        protocol.read_struct_end()?;
+
+        let list = list.ok_or_else(|| eyre!("footer has no row groups"))?;
+        Ok(FooterSummary { row_groups, list })
+    }
+
+    /// Row groups times leaf columns.
+    pub fn column_chunks(&self) -> usize {
+        self.row_groups
+            .iter()
+            .map(|row_group| row_group.columns)
+            .sum()
+    }
+
+    /// Serialize the summary as text: a header line with the range of the row group list and
+    /// the number of row groups, then a line per row group.
+    pub fn encode(&self) -> String {
+        let mut text = format!(
+            "{} {} {} {}\n",
+            SUMMARY_TAG,
+            self.list.start,
+            self.list.end,
+            self.row_groups.len()
+        );
+        for row_group in &self.row_groups {
+            let sorting_columns = row_group
+                .footer
+                .sorting_columns
+                .iter()
+                .map(|column| {
+                    format!(
+                        "{}:{}:{}",
+                        column.column_idx,
+                        u8::from(column.descending),
+                        u8::from(column.nulls_first)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            text.push_str(&format!(
+                "{} {} {} {} {} {} {} {} {}\n",
+                row_group.range.start,
+                row_group.range.end,
+                row_group.columns,
+                row_group.num_rows,
+                row_group.total_byte_size,
+                optional(row_group.total_compressed_size),
+                optional(row_group.footer.file_offset),
+                optional(row_group.footer.ordinal),
+                if sorting_columns.is_empty() {
+                    "-".to_string()
+                } else {
+                    sorting_columns
+                }
+            ));
+        }
+        text
+    }
+
+    /// Parse a summary serialized by [FooterSummary::encode] at the start of `bytes`, followed
+    /// by the footer it summarizes. Returns the summary and the number of bytes it took, or
+    /// `None` if they do not hold a valid summary of the rest.
+    pub fn parse(bytes: &[u8]) -> Option<(Self, usize)> {
+        let mut lines = Lines { bytes, position: 0 };
+        let header = lines.next()?.strip_prefix(SUMMARY_TAG)?;
+        let mut header = header.split_ascii_whitespace().map(str::parse::<usize>);
+        let list = header.next()?.ok()?..header.next()?.ok()?;
+        let count = header.next()?.ok()?;
+
+        let mut row_groups = Vec::with_capacity(count.min(bytes.len()));
+        for _ in 0..count {
+            let mut fields = lines.next()?.split(' ');
+            let range: Range<usize> = fields.next()?.parse().ok()?..fields.next()?.parse().ok()?;
+            let columns = fields.next()?.parse().ok()?;
+            let num_rows = fields.next()?.parse().ok()?;
+            let total_byte_size = fields.next()?.parse().ok()?;
+            let total_compressed_size = parse_optional(fields.next()?)?;
+            let file_offset = parse_optional(fields.next()?)?;
+            let ordinal = parse_optional(fields.next()?)?;
+            let sorting_columns = match fields.next()? {
+                "-" => Vec::new(),
+                columns => columns
+                    .split(',')
+                    .map(|column| {
+                        let mut parts = column.split(':');
+                        let column_idx = parts.next()?.parse().ok()?;
+                        let descending = parts.next()? == "1";
+                        let nulls_first = parts.next()? == "1";
+                        Some(parquet::format::SortingColumn::new(
+                            column_idx,
+                            descending,
+                            nulls_first,
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            };
+            if fields.next().is_some() {
+                return None;
+            }
+            row_groups.push(RowGroupSummary {
+                range,
+                columns,
+                num_rows,
+                total_byte_size,
+                total_compressed_size,
+                footer: RowGroupFooter {
+                    file_offset,
+                    ordinal,
+                    sorting_columns,
+                },
+            });
+        }
+
+        let footer_len = bytes.len() - lines.position;
+        let in_list = |range: &Range<usize>| {
+            list.start <= range.start && range.start <= range.end && range.end <= list.end
+        };
+        if list.start > list.end
+            || list.end > footer_len
+            || !row_groups.iter().all(|row_group| in_list(&row_group.range))
+        {
+            return None;
+        }
+        Some((FooterSummary { row_groups, list }, lines.position))
+    }
+}
+
+/// Lines of text at the start of a byte buffer, which may go on with binary data.
+struct Lines<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = &self.bytes[self.position..];
+        let end = rest.iter().position(|&byte| byte == b'\n')?;
+        self.position += end + 1;
+        std::str::from_utf8(&rest[..end]).ok()
+    }
+}
+
+fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+fn parse_optional<T: std::str::FromStr>(field: &str) -> Option<Option<T>> {
+    match field {
+        "-" => Some(None),
+        field => field.parse().ok().map(Some),
+    }
+}
+
+/// Read the fields of a `RowGroup` other than its column chunks, which are only counted.
+fn scan_row_group<P: TInputProtocol>(protocol: &mut P) -> color_eyre::Result<RowGroupSummary> {
+    let mut summary = RowGroupSummary {
+        range: 0..0,
+        columns: 0,
+        num_rows: 0,
+        total_byte_size: 0,
+        total_compressed_size: None,
+        footer: RowGroupFooter::default(),
+    };
+    protocol.read_struct_begin()?;
+    loop {
+        let field = protocol.read_field_begin()?;
+        match (field.field_type, field.id) {
+            (TType::Stop, _) => break,
+            (TType::List, Some(1)) => {
+                let header = protocol.read_list_begin()?;
+                for _ in 0..header.size {
+                    skip(protocol, header.element_type, MAX_SKIP_DEPTH)?;
+                }
+                protocol.read_list_end()?;
+                summary.columns = header.size as usize;
+            }
+            (TType::I64, Some(2)) => summary.total_byte_size = protocol.read_i64()?.try_into()?,
+            (TType::I64, Some(3)) => summary.num_rows = protocol.read_i64()?.try_into()?,
+            (TType::List, Some(4)) => {
+                summary.footer.sorting_columns = protocol
+                    .read_list::<SortingColumn>()?
+                    .into_iter()
+                    .map(|column| {
+                        parquet::format::SortingColumn::new(
+                            column.column_idx,
+                            column.descending,
+                            column.nulls_first,
+                        )
+                    })
+                    .collect()
+            }
+            (TType::I64, Some(5)) => summary.footer.file_offset = Some(protocol.read_i64()?),
+            (TType::I64, Some(6)) => {
+                summary.total_compressed_size = Some(protocol.read_i64()?.try_into()?)
+            }
+            (TType::I16, Some(7)) => summary.footer.ordinal = Some(protocol.read_i16()?),
+            (field_type, _) => skip(protocol, field_type, MAX_SKIP_DEPTH)?,
+        }
+        protocol.read_field_end()?;
+    }
+    protocol.read_struct_end()?;
+    Ok(summary)
+}
+
+/// Skip a value of `field_type`. Unlike [TInputProtocol::skip], binary values are skipped as
+/// bytes: statistics hold binary values that are not UTF-8, which it rejects.
+fn skip<P: TInputProtocol>(
+    protocol: &mut P,
+    field_type: TType,
+    depth: u8,
+) -> color_eyre::Result<()> {
+    if depth == 0 {
+        return Err(eyre!("footer is nested too deeply"));
+    }
+    match field_type {
+        TType::String => {
+            protocol.read_bytes()?;
+        }
+        TType::Struct => {
+            protocol.read_struct_begin()?;
+            loop {
+                let field = protocol.read_field_begin()?;
+                if field.field_type == TType::Stop {
+                    break;
+                }
+                skip(protocol, field.field_type, depth - 1)?;
+                protocol.read_field_end()?;
+            }
+            protocol.read_struct_end()?;
+        }
+        TType::List => {
+            let header = protocol.read_list_begin()?;
+            for _ in 0..header.size {
+                skip(protocol, header.element_type, depth - 1)?;
+            }
+            protocol.read_list_end()?;
+        }
+        field_type => protocol.skip(field_type)?,
+    }
+    Ok(())
+}
+
+impl LazyFooter {
+    /// Footer fields of each row group the decoded metadata drops, read while scanning.
+    pub fn row_group_footers(&self) -> Vec<RowGroupFooter> {
+        self.row_groups
+            .iter()
+            .map(|row_group| row_group.footer.clone())
+            .collect()
+    }
+
+    pub fn is_decoded(&self, row_group: usize) -> bool {
+        self.decoded.get(row_group).copied().unwrap_or(true)
+    }
+
+    /// Compressed size of a row group as declared by its footer entry, for row groups not
+    /// decoded yet. Writers may omit it, in which case the uncompressed size stands in.
+    pub fn compressed_size(&self, row_group: usize) -> usize {
+        let summary = &self.row_groups[row_group];
+        summary
+            .total_compressed_size
+            .unwrap_or(summary.total_byte_size)
+    }
+
+    /// Decode the column chunks of a row group into `metadata`, unless they already are.
+    pub fn decode(
+        &mut self,
+        metadata: &mut FileMetaData,
+        row_group: usize,
+    ) -> color_eyre::Result<()> {
+        if self.is_decoded(row_group) {
+            return Ok(());
+        }
+        let range = self.row_groups[row_group].range.clone();
+        let mut protocol = TCompactInputProtocol::new(&self.footer[range], MAX_FOOTER_SIZE);
+        let thrift = RowGroup::read_from_in_protocol(&mut protocol)?;
+
+        let descriptors = metadata.schema().columns();
+        if thrift.columns.len() != descriptors.len() {
+            return Err(eyre!(
+                "row group {} has {} columns, but the schema has {}",
+                row_group,
+                thrift.columns.len(),
+                descriptors.len()
+            ));
+        }
+        let columns = thrift
+            .columns
+            .into_iter()
+            .zip(descriptors)
+            .map(|(chunk, descriptor)| {
+                let meta = chunk.meta_data.as_ref().ok_or_else(|| {
+                    eyre!("column chunk of row group {} has no metadata", row_group)
+                })?;
+                Compression::try_from(meta.codec)?;
+                Ok(ColumnChunkMetaData::new(chunk, descriptor.clone()))
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        metadata.row_groups[row_group] = RowGroupMetaData::new(
+            columns,
+            thrift.num_rows.try_into()?,
+            thrift.total_byte_size.try_into()?,
+        );
+        self.decoded[row_group] = true;
+        Ok(())
+    }
+
+    /// Decode every row group not decoded yet, e.g. before analyses over the whole file.
+    pub fn decode_all(&mut self, metadata: &mut FileMetaData) -> color_eyre::Result<()> {
+        (0..self.decoded.len()).try_for_each(|row_group| self.decode(metadata, row_group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Arc};
+
+    use parquet::{
+        data_type::Int64Type,
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        format::SortingColumn,
+        schema::parser::parse_message_type,
+    };
+
+    use super::*;
+
+    /// Serialized footer of a file of three row groups of two columns, sorted by the first.
+    fn footer() -> Vec<u8> {
+        let schema = parse_message_type("message m { required int64 a; required int64 b; }");
+        let properties = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![SortingColumn::new(0, true, false)]))
+            .build();
+        let mut bytes = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut bytes, Arc::new(schema.unwrap()), Arc::new(properties))
+                .unwrap();
+        for rows in 1..=3 {
+            let mut row_group = writer.next_row_group().unwrap();
+            while let Some(mut column) = row_group.next_column().unwrap() {
+                let values: Vec<i64> = (0..rows).collect();
+                column
+                    .typed::<Int64Type>()
+                    .write_batch(&values, None, None)
+                    .unwrap();
+                column.close().unwrap();
+            }
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+        crate::cache::read_footer_bytes(&mut Cursor::new(bytes)).unwrap()
+    }
+
+    #[test]
+    fn parses_encoded_summaries_back() {
+        let footer = footer();
+        let summary = FooterSummary::scan(&footer).unwrap();
+        assert_eq!(summary.column_chunks(), 6);
+
+        let mut entry = summary.encode().into_bytes();
+        let len = entry.len();
+        entry.extend_from_slice(&footer);
+        let (parsed, parsed_len) = FooterSummary::parse(&entry).unwrap();
+        assert_eq!(parsed_len, len);
+        assert_eq!(parsed.encode(), summary.encode());
+        assert_eq!(parsed.row_groups[2].num_rows, 3);
+        assert!(parsed.row_groups[2].footer.sorting_columns[0].descending);
+    }
+
+    #[test]
+    fn rejects_summaries_of_other_footers() {
+        let footer = footer();
+        let mut entry = FooterSummary::scan(&footer).unwrap().encode().into_bytes();
+        entry.extend_from_slice(&footer[..footer.len() / 2]);
+        assert!(FooterSummary::parse(&entry).is_none());
+        assert!(FooterSummary::parse(b"summary v0 0 0 0\n").is_none());
+        assert!(FooterSummary::parse(&footer).is_none());
+    }
+
+    #[test]
+    fn decodes_lazily_to_the_full_metadata() {
+        let footer = footer();
+        let full = crate::cache::decode_footer(&footer).unwrap();
+        let summary = FooterSummary::scan(&footer).unwrap();
+        let (mut metadata, mut lazy_footer) = decode_lazily(footer, summary).unwrap();
+        assert!(metadata.row_groups[1].columns().is_empty());
+        assert_eq!(
+            lazy_footer.compressed_size(1),
+            full.row_groups[1].compressed_size()
+        );
+
+        lazy_footer.decode_all(&mut metadata).unwrap();
+        assert!((0..3).all(|row_group| lazy_footer.is_decoded(row_group)));
+        for (lazy, full) in metadata.row_groups.iter().zip(&full.row_groups) {
+            assert_eq!(lazy.num_rows(), full.num_rows());
+            assert_eq!(lazy.compressed_size(), full.compressed_size());
+        }
+    }
+}
@@ -1,15 +1,92 @@
 use std::{
-    fs::File,
+    collections::{BTreeMap, BTreeSet, HashMap},
     io,
     path::{Path, PathBuf},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use analysis::{
+    booleans::BooleanCounts, checksums::ChunkChecksums, column_rollup::ColumnRollup,
+    decompression::DecompressionProfile, dictionary::DictionaryFallback,
+    distinct::DistinctEstimate, levels::LevelDistribution, memory::MemoryEstimate,
+    page_counts::PageCounts, page_index::ColumnPageIndex, page_overhead::PageOverhead,
+    row_group_sizes::RowGroupSize, scan::ColumnScan, size_breakdown::SizeBreakdown,
+    stats_presence::PresenceMatrix, value_sizes::ValueSizes,
+};
+use breadcrumbs::Crumb;
+use clipboard::SystemClipboard;
+use color_eyre::eyre::eyre;
+use column_view::{ColumnView, TypeFilter};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use dataset::FooterLoader;
+use ddl::{Ddl, Dialect};
+use goto::{GotoPrompt, GotoTarget};
+use keys::KeyRecorder;
+use lazy_footer::LazyFooter;
+use pane_layout::PaneLayout;
+use parquet::RowGroupFooter;
 use parquet2::metadata::FileMetaData;
-use ratatui::{backend::Backend, widgets::ListState, Terminal};
+use preview::{ColumnChooser, Predicate, PreviewState};
+use queries::QueryStore;
+use ratatui::{
+    backend::{Backend, TestBackend},
+    buffer::Buffer,
+    widgets::{ListState, TableState},
+    Terminal,
+};
+use records::RecordField;
+use sampling::{SampleStrategy, DEFAULT_SAMPLE_SIZE, MAX_SAMPLE_SIZE};
+use session::Bookmark;
+use snapshot::SnapshotFormat;
+use source::{FileChange, FileIdentity};
+use start_view::StartView;
+use toasts::{ToastLevel, Toasts};
+use views::compare::ComparedChunk;
 
+pub mod analysis;
+pub mod archive;
+pub mod breadcrumbs;
+pub mod cache;
+pub mod clipboard;
+pub mod codecs;
+pub mod color;
+pub mod column_view;
+pub mod commands;
+pub mod dataset;
+pub mod ddl;
+pub mod decimal;
+pub mod export;
+pub mod files;
+pub mod generate;
+pub mod goto;
+pub mod graphics;
+pub mod keys;
+pub mod layout_image;
+pub mod lazy_footer;
+pub mod logging;
+pub mod numbers;
+pub mod output;
+pub mod page_cache;
+pub mod pages;
+pub mod pane_layout;
 pub mod parquet;
+pub mod preview;
+pub mod queries;
+pub mod records;
+pub mod recovery;
+pub mod redact;
+pub mod renderers;
+pub mod s3;
+pub mod sampling;
+pub mod schema_export;
+pub mod serve;
+pub mod session;
+pub mod snapshot;
+pub mod source;
+pub mod start_view;
+pub mod stdin;
+pub mod timezone;
+pub mod toasts;
 pub mod tui;
 pub mod views;
 
@@ -18,31 +95,373 @@ pub enum ActivePane {
     #[default]
     RowGroupBrowser,
     ColumnBrowser,
+    /// Statistics of the selected column chunk, entered from the column browser with `Right`.
+    ColumnDetail,
 }
 
 impl ActivePane {
     pub fn toggle(&mut self) {
         *self = match self {
             ActivePane::RowGroupBrowser => ActivePane::ColumnBrowser,
-            ActivePane::ColumnBrowser => ActivePane::RowGroupBrowser,
+            ActivePane::ColumnBrowser | ActivePane::ColumnDetail => ActivePane::RowGroupBrowser,
         };
     }
 }
 
+/// Statistic selectable in the column detail pane, or the sampled values above them.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StatField {
+    Sample,
+    #[default]
+    Min,
+    Max,
+    Nulls,
+    DistinctValues,
+}
+
+impl StatField {
+    pub const ALL: [StatField; 5] = [
+        StatField::Sample,
+        StatField::Min,
+        StatField::Max,
+        StatField::Nulls,
+        StatField::DistinctValues,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatField::Sample => "sample",
+            StatField::Min => "min",
+            StatField::Max => "max",
+            StatField::Nulls => "nulls",
+            StatField::DistinctValues => "distinct values",
+        }
+    }
+
+    /// The field `delta` positions away, wrapping around.
+    pub fn step(self, delta: isize) -> Self {
+        let index = Self::ALL.iter().position(|field| *field == self).unwrap() as isize;
+        Self::ALL[(index + delta).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// Full-screen view drawn over the browsers, closed with Esc.
+pub enum Overlay {
+    PageOverhead {
+        row_group: usize,
+        chunks: Vec<PageOverhead>,
+        state: TableState,
+    },
+    /// Bar charts of the rows and compressed bytes of every row group.
+    SizeCharts {
+        sizes: Vec<RowGroupSize>,
+        state: TableState,
+    },
+    /// Stacked bar of the parts the file size splits into.
+    SizeBreakdown {
+        breakdown: SizeBreakdown,
+        state: TableState,
+    },
+    /// Range requests made for the file when it is read from S3, redrawn as more are made.
+    RemoteReads {
+        /// Requests made for any object when last drawn.
+        seen: u64,
+        state: TableState,
+    },
+    /// Page-level min/max ranges of a column chunk, from its column index.
+    PageRanges {
+        row_group: usize,
+        index: ColumnPageIndex,
+        state: TableState,
+    },
+    /// Definition and repetition levels decoded from a column chunk.
+    Levels {
+        row_group: usize,
+        distribution: LevelDistribution,
+        state: TableState,
+    },
+    /// Distribution of the value lengths of a BYTE_ARRAY column chunk.
+    ValueSizes {
+        row_group: usize,
+        sizes: ValueSizes,
+        state: TableState,
+    },
+    /// Decompression time and throughput of every page of a column chunk.
+    Decompression {
+        row_group: usize,
+        profile: DecompressionProfile,
+        state: TableState,
+    },
+    /// Which statistics every column chunk has, scrolled horizontally over row groups.
+    StatsPresence {
+        matrix: PresenceMatrix,
+        first_row_group: usize,
+        state: TableState,
+    },
+    /// Statistics computed by decoding the selected columns of a row group.
+    ScanStats {
+        row_group: usize,
+        columns: Vec<ColumnScan>,
+        state: TableState,
+    },
+    /// Footer statistics of every column rolled up across the row groups of the file.
+    ColumnRollup {
+        rollups: Vec<ColumnRollup>,
+        state: TableState,
+    },
+    /// Rows of a row group, optionally filtered and projected.
+    Preview(Box<PreviewState>),
+    /// Column values of the record at a global row index.
+    Record {
+        row: u64,
+        fields: Vec<RecordField>,
+        state: TableState,
+    },
+    /// Side-by-side comparison of column chunks, possibly from different files.
+    Compare {
+        chunks: Vec<ComparedChunk>,
+        state: TableState,
+    },
+    /// Files opened together, with their footer summaries as they are read.
+    Files {
+        files: Vec<PathBuf>,
+        state: TableState,
+    },
+    /// Labeled values of the main view to copy to the clipboard.
+    Yank {
+        items: Vec<(String, String)>,
+        state: TableState,
+    },
+    /// Locations of the file bookmarked with `m`, to jump back to.
+    Bookmarks {
+        bookmarks: Vec<Bookmark>,
+        state: TableState,
+    },
+    /// Types of the file's columns to filter the column browser by, `None` for every type,
+    /// with their column counts.
+    TypeFilter {
+        types: Vec<(Option<TypeFilter>, usize)>,
+        state: TableState,
+    },
+    /// `CREATE TABLE` statement of the schema in a SQL dialect, with its lossy conversions,
+    /// scrolled by the offset of its state.
+    Ddl {
+        dialect: Dialect,
+        ddl: Ddl,
+        state: TableState,
+    },
+    /// A single value too long for the pane it comes from, wrapped over the whole width or
+    /// dumped as hex, scrolled by the offset of its state.
+    Value {
+        title: String,
+        value: String,
+        hex: bool,
+        state: TableState,
+    },
+}
+
+impl Overlay {
+    /// Selection state of the overlay's scrollable content.
+    pub fn table_state(&mut self) -> &mut TableState {
+        match self {
+            Overlay::PageOverhead { state, .. }
+            | Overlay::SizeCharts { state, .. }
+            | Overlay::SizeBreakdown { state, .. }
+            | Overlay::RemoteReads { state, .. }
+            | Overlay::PageRanges { state, .. }
+            | Overlay::Levels { state, .. }
+            | Overlay::ValueSizes { state, .. }
+            | Overlay::Decompression { state, .. }
+            | Overlay::StatsPresence { state, .. }
+            | Overlay::ScanStats { state, .. }
+            | Overlay::ColumnRollup { state, .. }
+            | Overlay::Record { state, .. }
+            | Overlay::Compare { state, .. }
+            | Overlay::Files { state, .. }
+            | Overlay::Yank { state, .. }
+            | Overlay::Bookmarks { state, .. }
+            | Overlay::TypeFilter { state, .. }
+            | Overlay::Ddl { state, .. }
+            | Overlay::Value { state, .. } => state,
+            Overlay::Preview(preview) => &mut preview.state,
+        }
+    }
+
+    /// Number of selectable rows in the overlay.
+    pub fn len(&self) -> usize {
+        match self {
+            Overlay::PageOverhead { chunks, .. } => chunks.len(),
+            Overlay::SizeCharts { sizes, .. } => sizes.len(),
+            Overlay::SizeBreakdown { breakdown, .. } => breakdown.parts().len(),
+            Overlay::RemoteReads { .. } => views::remote_reads::ROW_COUNT,
+            Overlay::PageRanges { index, .. } => index.pages.len(),
+            Overlay::Levels { distribution, .. } => {
+                distribution.definition.len() + distribution.repetition.len()
+            }
+            Overlay::ValueSizes { sizes, .. } => sizes.buckets.len(),
+            // The total row is selectable too.
+            Overlay::Decompression { profile, .. } => profile.pages.len() + 1,
+            Overlay::StatsPresence { matrix, .. } => matrix.columns.len(),
+            Overlay::ScanStats { columns, .. } => columns.len(),
+            Overlay::ColumnRollup { rollups, .. } => rollups.len(),
+            Overlay::Preview(preview) => preview.preview.rows.len(),
+            Overlay::Record { fields, .. } => fields.len(),
+            Overlay::Compare { .. } => views::compare::ATTRIBUTES.len(),
+            Overlay::Files { files, .. } => files.len(),
+            Overlay::Yank { items, .. } => items.len(),
+            Overlay::Bookmarks { bookmarks, .. } => bookmarks.len(),
+            Overlay::TypeFilter { types, .. } => types.len(),
+            Overlay::Ddl { .. } | Overlay::Value { .. } => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the overlay has a text prompt open, which receives every key including `q`.
+    pub fn has_prompt(&self) -> bool {
+        matches!(self, Overlay::Preview(preview) if preview.has_prompt())
+    }
+}
+
 /// App is the main application, encapsulating all of the state and event-handling logic necessary to
 /// drive the TUI.
 pub struct App {
     pub file_name: String,
     pub path: PathBuf,
     pub parquet_metadata: FileMetaData,
+
+    /// Files opened together, e.g. from a glob, switched between with `[` and `]`. Empty
+    /// when a single file is inspected.
+    pub files: Vec<PathBuf>,
+    pub file_index: usize,
+    /// Footers of the files, read in the background.
+    pub footers: Option<FooterLoader>,
+
     pub exiting: bool,
     pub active_pane: ActivePane,
+    /// Statistic selected in the column detail pane.
+    pub stat_field: StatField,
+    /// Sizes of the panes, resized with Ctrl and the arrow keys.
+    pub pane_layout: PaneLayout,
+    /// Whether the focused pane, or the view shown over the browsers, fills the terminal.
+    pub zoomed: bool,
+    /// How the sampled values of the column detail pane are picked, and how many.
+    pub sample_strategy: SampleStrategy,
+    pub sample_size: usize,
 
     // Create a row group view state
     pub row_group_view_state: ListState,
     pub column_chunk_view_state: ListState,
+
+    /// Grouping of nested columns in the column browser, and which groups are collapsed.
+    pub column_view: ColumnView,
+
+    /// Columns marked in the column browser, which bulk actions apply to.
+    pub selected_columns: BTreeSet<usize>,
+
+    /// Whether the row group browser draws the size-scaled canvas instead of a list, and the
+    /// first row group visible in the canvas.
+    pub row_group_canvas: bool,
+    pub row_group_canvas_offset: usize,
+
+    /// Outcome of the checksum verifications run with `k`, by row group and column.
+    pub checksums: HashMap<(usize, usize), ChunkChecksums>,
+
+    /// Distinct value estimates run with `u`, by row group and column.
+    pub distinct_estimates: HashMap<(usize, usize), DistinctEstimate>,
+
+    /// Dictionary fallbacks detected by (row group, column), filled in as row groups are
+    /// shown in the column browser.
+    pub dictionary_fallbacks: HashMap<(usize, usize), Option<DictionaryFallback>>,
+
+    /// Data and dictionary page counts by (row group, column), filled in as row groups are
+    /// shown in the column browser. `None` when the pages could not be counted.
+    pub page_counts: HashMap<(usize, usize), Option<PageCounts>>,
+
+    /// True, false and null counts of BOOLEAN column chunks by (row group, column), filled in
+    /// as chunks are shown in the detail pane. `None` when the chunk could not be scanned.
+    pub boolean_counts: HashMap<(usize, usize), Option<BooleanCounts>>,
+
+    /// Footer fields of each row group the decoded metadata drops, such as the declared sort
+    /// order, read when the row group browser is first shown.
+    pub row_group_footers: Option<Vec<RowGroupFooter>>,
+
+    /// Footer of a file with too many column chunks to decode at once, whose row groups are
+    /// decoded into `parquet_metadata` as they are shown.
+    pub lazy_footer: Option<LazyFooter>,
+
+    /// In-memory size estimates of the column chunks of each row group, filled in as row
+    /// groups are shown in the row group detail panel.
+    pub memory_estimates: HashMap<usize, Vec<MemoryEstimate>>,
+
+    /// Chunk pinned with `p`, compared against the chunk under the cursor.
+    pub pinned_chunk: Option<ComparedChunk>,
+
+    /// Second file whose chunks are compared against the inspected file's, by file name.
+    pub compare_file: Option<(String, FileMetaData)>,
+    /// Whether the compare file's browsers are shown next to the inspected file's, following
+    /// their selection, instead of the detail panel.
+    pub split_compare: bool,
+
+    /// Full-screen view currently shown over the browsers, if any.
+    pub overlay: Option<Overlay>,
+
+    /// Breadcrumb segment picked to jump back to, while `Backspace` is picking one.
+    pub breadcrumb_cursor: Option<usize>,
+
+    /// Goto prompt, present while the user is typing a destination.
+    pub goto_prompt: Option<GotoPrompt>,
+
+    /// Format used by the snapshot hotkey, and whether a snapshot should be taken after the
+    /// next frame is drawn.
+    pub snapshot_format: SnapshotFormat,
+    pub snapshot_requested: bool,
+
+    /// Masks values and statistics of columns that may hold personal data, with `--redact`.
+    pub redactor: redact::Redactor,
+
+    /// Filter history and saved filters of the data preview, kept across launches.
+    pub queries: QueryStore,
+
+    /// Locations bookmarked with `m`, by session key of their file, kept across launches.
+    pub bookmarks: BTreeMap<PathBuf, Vec<Bookmark>>,
+
+    /// Size and modification time of the file when it was opened, and how it changed on disk
+    /// since, as checked on every tick.
+    pub file_identity: Option<FileIdentity>,
+    pub file_change: Option<FileChange>,
+
+    /// Row groups of a file read from S3 whose column chunks were prefetched.
+    pub prefetched: BTreeSet<usize>,
+
+    /// Notifications shown in a corner until they expire, e.g. the outcomes of actions.
+    pub toasts: Toasts,
+
+    /// Whether the log pane is shown under the browsers.
+    pub show_log: bool,
+
+    /// Whether the state changed since the last frame was drawn. Set by input handlers and
+    /// by [App::poll_background] on ticks; the render loop only draws when it is set.
+    pub dirty: bool,
+    // Background progress as of the last frame: footers loaded and events logged.
+    seen_footers: usize,
+    seen_log_count: u64,
+
+    clipboard: SystemClipboard,
+
+    // Digits typed into the row group browser, and when the last one was typed.
+    row_group_digits: String,
+    last_digit_at: Option<Instant>,
 }
 
+/// Lines scrolled by PageUp and PageDown in the value popup.
+const VALUE_PAGE_LINES: usize = 20;
+
+/// How long to wait between digits before a new row group number is started.
+const DIGIT_JUMP_TIMEOUT: Duration = Duration::from_millis(1000);
+
 impl App {
     pub fn from<P: AsRef<Path>>(file: P) -> color_eyre::Result<Self> {
         // TODO(aduffy): OsStr is so gross
@@ -55,59 +474,386 @@ impl App {
             .to_string();
 
         let path = file.as_ref().to_owned();
-        let mut file = File::open(file.as_ref()).unwrap();
-        let parquet_metadata = parquet2::read::read_metadata(&mut file).unwrap();
+        // Taken before the footer is read, so that a change while reading it is noticed.
+        let file_identity = FileIdentity::of(&path).ok();
+        let (parquet_metadata, lazy_footer) = crate::parquet::read_file_footer(&path)?;
+
+        let mut app = Self::with_metadata(path, file_name, parquet_metadata);
+        app.file_identity = file_identity;
+        if let Some(lazy_footer) = lazy_footer {
+            app.row_group_footers = Some(lazy_footer.row_group_footers());
+            app.lazy_footer = Some(lazy_footer);
+            app.decode_selected_row_group();
+        }
+        Ok(app)
+    }
 
-        Ok(Self {
+    /// Build the app for a file whose footer was already read.
+    pub fn with_metadata(path: PathBuf, file_name: String, parquet_metadata: FileMetaData) -> Self {
+        Self {
+            file_identity: FileIdentity::of(&path).ok(),
+            file_change: None,
+            prefetched: BTreeSet::new(),
             path,
             file_name,
             parquet_metadata,
+            files: Vec::new(),
+            file_index: 0,
+            footers: None,
             exiting: false,
             active_pane: ActivePane::default(),
+            stat_field: StatField::default(),
+            pane_layout: PaneLayout::default(),
+            zoomed: false,
+            sample_strategy: SampleStrategy::default(),
+            sample_size: DEFAULT_SAMPLE_SIZE,
             row_group_view_state: ListState::default().with_selected(Some(0)),
             column_chunk_view_state: ListState::default().with_selected(Some(0)),
-        })
+            column_view: ColumnView::default(),
+            selected_columns: BTreeSet::new(),
+            checksums: HashMap::new(),
+            distinct_estimates: HashMap::new(),
+            dictionary_fallbacks: HashMap::new(),
+            page_counts: HashMap::new(),
+            boolean_counts: HashMap::new(),
+            row_group_footers: None,
+            lazy_footer: None,
+            memory_estimates: HashMap::new(),
+            pinned_chunk: None,
+            compare_file: None,
+            split_compare: false,
+            row_group_canvas: false,
+            row_group_canvas_offset: 0,
+            overlay: None,
+            breadcrumb_cursor: None,
+            goto_prompt: None,
+            snapshot_format: SnapshotFormat::default(),
+            snapshot_requested: false,
+            redactor: redact::Redactor::default(),
+            queries: QueryStore::default(),
+            bookmarks: BTreeMap::new(),
+            toasts: Toasts::default(),
+            show_log: false,
+            dirty: true,
+            seen_footers: 0,
+            seen_log_count: 0,
+            clipboard: SystemClipboard::default(),
+            row_group_digits: String::new(),
+            last_digit_at: None,
+        }
+    }
+}
+
+/// Timing of the render loop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct UiTiming {
+    /// Interval between tick events, which pick up background progress such as footers read
+    /// by the loader or new log lines.
+    pub tick_rate: Duration,
+    /// Longest wait for an input event before the loop checks for a due tick.
+    pub poll_timeout: Duration,
+}
+
+impl Default for UiTiming {
+    fn default() -> Self {
+        Self {
+            tick_rate: Duration::from_millis(250),
+            poll_timeout: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Event driving the render loop.
+enum UiEvent {
+    /// Terminal input: a key press or a resize.
+    Input(Event),
+    /// A tick interval elapsed.
+    Tick,
+}
+
+/// Wait for the next input event, or for the next tick once it is due.
+fn next_event(timing: UiTiming, last_tick: &mut Instant) -> io::Result<Option<UiEvent>> {
+    let until_tick = timing.tick_rate.saturating_sub(last_tick.elapsed());
+    if event::poll(timing.poll_timeout.min(until_tick))? {
+        return Ok(Some(UiEvent::Input(event::read()?)));
     }
+    if last_tick.elapsed() >= timing.tick_rate {
+        *last_tick = Instant::now();
+        return Ok(Some(UiEvent::Tick));
+    }
+    Ok(None)
 }
 
-/// Launch the TUI for Parquet file inspection.
-pub fn start_ui<B: Backend>(term: &mut Terminal<B>, app: &mut App) -> color_eyre::Result<()> {
+/// Launch the TUI for Parquet file inspection, writing the handled events to `recorder` when
+/// given.
+///
+/// Frames are only drawn when input or a tick changed the state, see [App::dirty].
+pub fn start_ui<B: Backend>(
+    term: &mut Terminal<B>,
+    app: &mut App,
+    timing: UiTiming,
+    mut recorder: Option<&mut KeyRecorder>,
+) -> color_eyre::Result<()> {
+    let mut last_tick = Instant::now();
     loop {
         if app.exiting {
             return Ok(());
         }
 
-        term.draw(|f| views::render_ui(f, app))?;
+        if app.dirty {
+            app.dirty = false;
+            let frame = term.draw(|f| views::render_ui(f, app))?;
+            if graphics::flush(&mut std::io::stdout())? {
+                term.clear()?;
+                app.dirty = true;
+                continue;
+            }
+
+            if app.snapshot_requested {
+                app.save_snapshot(frame.buffer);
+                continue;
+            }
+        }
+
+        match next_event(timing, &mut last_tick)? {
+            Some(UiEvent::Input(evt)) => {
+                if let Some(recorder) = recorder.as_deref_mut() {
+                    recorder.record(&evt)?;
+                }
+                app.try_handle_event(evt)?
+            }
+            Some(UiEvent::Tick) => app.poll_background(),
+            None => {}
+        }
+    }
+}
+
+/// Render the main view once at the given size, without a terminal, e.g. for
+/// `--render plain`.
+pub fn render_once(app: &mut App, width: u16, height: u16) -> color_eyre::Result<Buffer> {
+    replay(app, Vec::new(), width, height)
+}
+
+/// The main view rendered once at the given size as plain text, one line per terminal row,
+/// e.g. for tests and other users of the crate to assert on what the TUI shows.
+pub fn render_snapshot(app: &mut App, width: u16, height: u16) -> String {
+    let buffer = render_once(app, width, height).expect("drawing to a TestBackend cannot fail");
+    snapshot::render(&buffer, SnapshotFormat::Text)
+}
 
-        if event::poll(Duration::from_millis(250))? {
-            let evt = event::read()?;
-            app.try_handle_event(evt)?;
+/// Handle recorded `events` as the TUI would, drawing to a terminal of the given size that
+/// is only kept in memory, and return the screen after the last event, e.g. for `--replay`.
+pub fn replay(
+    app: &mut App,
+    events: Vec<Event>,
+    width: u16,
+    height: u16,
+) -> color_eyre::Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    // Draw before every event, as selections scroll their lists while drawn.
+    let mut screen = draw_replayed(&mut terminal, app)?;
+    for event in events {
+        if app.exiting {
+            break;
         }
+        if let Event::Resize(width, height) = event {
+            terminal.backend_mut().resize(width, height);
+        }
+        app.try_handle_event(event)?;
+        app.poll_background();
+        screen = draw_replayed(&mut terminal, app)?;
+    }
+    Ok(screen)
+}
+
+fn draw_replayed(terminal: &mut Terminal<TestBackend>, app: &mut App) -> io::Result<Buffer> {
+    let frame = terminal.draw(|f| views::render_ui(f, app))?;
+    if !app.snapshot_requested {
+        return Ok(frame.buffer.clone());
     }
+    app.save_snapshot(frame.buffer);
+    Ok(terminal.draw(|f| views::render_ui(f, app))?.buffer.clone())
 }
 
 impl App {
+    /// Write the snapshot requested with `S` of the screen just drawn.
+    fn save_snapshot(&mut self, buffer: &Buffer) {
+        self.snapshot_requested = false;
+        match snapshot::write(buffer, self.snapshot_format, ".", &self.file_name) {
+            Ok(path) => self.notify(format!("Snapshot saved to {}", path.display())),
+            Err(err) => self.fail(format!("Snapshot failed: {}", err)),
+        }
+        self.dirty = true;
+    }
+
     pub fn num_row_groups(&self) -> usize {
         self.parquet_metadata.row_groups.len()
     }
 
+    /// Decode the selected row group of a lazily decoded footer, unless it already is, and
+    /// prefetch the first pages of its column chunks when the file is read from S3.
+    pub fn decode_selected_row_group(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        if let Some(lazy_footer) = &mut self.lazy_footer {
+            if let Err(err) = lazy_footer.decode(&mut self.parquet_metadata, row_group) {
+                self.fail(format!("Decoding row group {} failed: {}", row_group, err));
+                return;
+            }
+        }
+        if source::remote_scheme(&self.path).is_some() && self.prefetched.insert(row_group) {
+            let chunks = self.parquet_metadata.row_groups[row_group]
+                .columns()
+                .iter()
+                .map(|column| column.byte_range())
+                .collect();
+            source::prefetch(&self.path, chunks);
+        }
+    }
+
+    /// Decode every row group of a lazily decoded footer, before analyses over the whole file.
+    /// Returns whether they all could be.
+    pub fn decode_all_row_groups(&mut self) -> bool {
+        let Some(lazy_footer) = &mut self.lazy_footer else {
+            return true;
+        };
+        match lazy_footer.decode_all(&mut self.parquet_metadata) {
+            Ok(()) => true,
+            Err(err) => {
+                self.fail(format!("Decoding the footer failed: {}", err));
+                false
+            }
+        }
+    }
+
+    /// Compressed size of a row group, as declared by the footer for row groups of a lazily
+    /// decoded footer not decoded yet.
+    pub fn compressed_size(&self, row_group: usize) -> usize {
+        match &self.lazy_footer {
+            Some(lazy_footer) if !lazy_footer.is_decoded(row_group) => {
+                lazy_footer.compressed_size(row_group)
+            }
+            _ => self.parquet_metadata.row_groups[row_group].compressed_size(),
+        }
+    }
+
     pub fn num_column_chunks(&self) -> usize {
         self.parquet_metadata.row_groups[self.row_group_view_state.selected().unwrap()]
             .columns()
             .len()
     }
 
+    /// Mark the app dirty when background work made progress that is visible on screen:
+    /// footers read by the loader, or new log lines while the log pane is shown.
+    pub fn poll_background(&mut self) {
+        if let Some(footers) = &self.footers {
+            let loaded = footers.loaded();
+            if loaded != self.seen_footers {
+                self.seen_footers = loaded;
+                self.dirty = true;
+            }
+        }
+
+        let log_count = crate::logging::count();
+        if log_count != self.seen_log_count {
+            self.seen_log_count = log_count;
+            self.dirty |= self.show_log;
+        }
+
+        if self.toasts.expire(Instant::now()) {
+            self.dirty = true;
+        }
+
+        if let Some(Overlay::RemoteReads { seen, .. }) = &mut self.overlay {
+            let count = s3::request_count();
+            if count != *seen {
+                *seen = count;
+                self.dirty = true;
+            }
+        }
+
+        if let Some(opened) = &self.file_identity {
+            let change = FileChange::detect(opened, FileIdentity::of(&self.path));
+            if change != self.file_change {
+                if let Some(change) = change {
+                    tracing::warn!("{} was {} on disk", self.path.display(), change.as_str());
+                }
+                self.file_change = change;
+                self.dirty = true;
+            }
+        }
+    }
+
     pub fn try_handle_event(&mut self, event: Event) -> io::Result<()> {
+        self.decode_selected_row_group();
+        if let Event::Resize(..) = event {
+            self.dirty = true;
+        }
+
         if let Event::Key(key_event) = event {
             // Only process Press events, to support Windows.
             if key_event.kind != KeyEventKind::Press {
                 return Ok(());
             }
+            self.dirty = true;
+
+            if self.goto_prompt.is_some() {
+                self.handle_goto_key(key_event.code);
+                return Ok(());
+            }
+
+            if self.breadcrumb_cursor.is_some() {
+                self.handle_breadcrumb_key(key_event.code);
+                return Ok(());
+            }
+
+            if key_event.code == KeyCode::Char('~')
+                && !self.overlay.as_ref().is_some_and(Overlay::has_prompt)
+            {
+                self.show_log = !self.show_log;
+                return Ok(());
+            }
+
+            if self.overlay.as_ref().is_some_and(Overlay::has_prompt) {
+                self.handle_overlay_key(key_event.code);
+                return Ok(());
+            }
 
             if [KeyCode::Char('q'), KeyCode::Char('Q')].contains(&key_event.code) {
                 self.exiting = true;
             }
 
+            if key_event.code == KeyCode::Char('z') {
+                self.zoomed = !self.zoomed;
+                return Ok(());
+            }
+
+            if key_event.code == KeyCode::Backspace {
+                // Start on the segment above the current one.
+                self.breadcrumb_cursor = Some(breadcrumbs::breadcrumbs(self).len() - 2);
+                return Ok(());
+            }
+
+            if self.overlay.is_some() {
+                self.handle_overlay_key(key_event.code);
+                return Ok(());
+            }
+
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                match key_event.code {
+                    KeyCode::Left => self.pane_layout.resize(self.active_pane, -1),
+                    KeyCode::Right => self.pane_layout.resize(self.active_pane, 1),
+                    KeyCode::Up if self.show_log => self.pane_layout.resize_log(1),
+                    KeyCode::Down if self.show_log => self.pane_layout.resize_log(-1),
+                    _ => {}
+                }
+                if matches!(
+                    key_event.code,
+                    KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                ) {
+                    return Ok(());
+                }
+            }
+
             if key_event.code == KeyCode::Down {
                 match self.active_pane {
                     ActivePane::RowGroupBrowser => {
@@ -121,14 +867,8 @@ impl App {
                         // Reset the column selecter
                         *self.column_chunk_view_state.selected_mut() = Some(0);
                     }
-                    ActivePane::ColumnBrowser => {
-                        let last_selected = self.column_chunk_view_state.selected().unwrap();
-                        if last_selected == self.num_column_chunks() - 1 {
-                            *self.column_chunk_view_state.selected_mut() = Some(0);
-                        } else {
-                            *self.column_chunk_view_state.selected_mut() = Some(last_selected + 1);
-                        }
-                    }
+                    ActivePane::ColumnBrowser => self.step_column(1),
+                    ActivePane::ColumnDetail => self.stat_field = self.stat_field.step(1),
                 }
             }
 
@@ -146,15 +886,8 @@ impl App {
                         // Reset the column selecter
                         *self.column_chunk_view_state.selected_mut() = Some(0);
                     }
-                    ActivePane::ColumnBrowser => {
-                        let last_selected = self.column_chunk_view_state.selected().unwrap();
-                        if last_selected == 0 {
-                            *self.column_chunk_view_state.selected_mut() =
-                                Some(self.num_column_chunks() - 1);
-                        } else {
-                            *self.column_chunk_view_state.selected_mut() = Some(last_selected - 1);
-                        }
-                    }
+                    ActivePane::ColumnBrowser => self.step_column(-1),
+                    ActivePane::ColumnDetail => self.stat_field = self.stat_field.step(-1),
                 }
             }
 
@@ -162,8 +895,1673 @@ impl App {
             if key_event.code == KeyCode::Tab {
                 self.active_pane.toggle();
             }
+
+            // Step into the statistics of the selected column chunk, and back out.
+            if key_event.code == KeyCode::Right && self.active_pane == ActivePane::ColumnBrowser {
+                self.active_pane = ActivePane::ColumnDetail;
+            } else if key_event.code == KeyCode::Left
+                && self.active_pane == ActivePane::ColumnDetail
+            {
+                self.active_pane = ActivePane::ColumnBrowser;
+            }
+
+            if self.active_pane == ActivePane::ColumnDetail {
+                match key_event.code {
+                    KeyCode::Enter => self.open_stat_value(),
+                    KeyCode::Char('/') => self.filter_by_stat(),
+                    KeyCode::Char('<') => self.sample_strategy = self.sample_strategy.step(-1),
+                    KeyCode::Char('>') => self.sample_strategy = self.sample_strategy.step(1),
+                    KeyCode::Char('+') => {
+                        self.sample_size = (self.sample_size * 2).min(MAX_SAMPLE_SIZE)
+                    }
+                    KeyCode::Char('-') => self.sample_size = (self.sample_size / 2).max(1),
+                    _ => {}
+                }
+            }
+
+            if key_event.code == KeyCode::Char('v') {
+                self.row_group_canvas = !self.row_group_canvas;
+            }
+
+            if key_event.code == KeyCode::Char('S') {
+                self.snapshot_requested = true;
+            }
+
+            if key_event.code == KeyCode::Char('b') {
+                self.export_chunk_bytes();
+            }
+
+            if key_event.code == KeyCode::Char('x') {
+                self.export_row_group();
+            }
+
+            if key_event.code == KeyCode::Char('e') {
+                self.export_canvas_image();
+            }
+
+            if key_event.code == KeyCode::Char('Z') {
+                let mode = timezone::current().next();
+                timezone::set(mode);
+                self.notify(format!("Timestamps: {}", mode));
+            }
+
+            if key_event.code == KeyCode::Char('n') {
+                let format = numbers::current().next();
+                numbers::set(format);
+                self.notify(format!("Numbers: {}", format));
+            }
+
+            if key_event.code == KeyCode::Char(' ') && self.active_pane == ActivePane::ColumnBrowser
+            {
+                self.toggle_column_selection();
+            }
+
+            if key_event.code == KeyCode::Esc {
+                self.selected_columns.clear();
+            }
+
+            if self.active_pane == ActivePane::ColumnBrowser {
+                match key_event.code {
+                    KeyCode::Enter => self.toggle_column_group(),
+                    KeyCode::Char('-') => {
+                        self.column_view
+                            .set_all_collapsed(&self.parquet_metadata, true);
+                        self.step_column(0);
+                    }
+                    KeyCode::Char('+') => self
+                        .column_view
+                        .set_all_collapsed(&self.parquet_metadata, false),
+                    _ => {}
+                }
+            }
+
+            if key_event.code == KeyCode::Char('s') {
+                self.open_scan_stats();
+            }
+
+            if key_event.code == KeyCode::Char('k') {
+                self.verify_checksums();
+            }
+
+            if key_event.code == KeyCode::Char('u') {
+                self.estimate_distinct();
+            }
+
+            if key_event.code == KeyCode::Char('r') {
+                self.reload();
+            }
+
+            if key_event.code == KeyCode::Char('m') {
+                self.add_bookmark();
+            }
+
+            if key_event.code == KeyCode::Char('\'') {
+                self.open_bookmarks();
+            }
+
+            if key_event.code == KeyCode::Char('p') {
+                self.toggle_pinned_chunk();
+            }
+
+            if key_event.code == KeyCode::Char('c') {
+                self.open_compare();
+            }
+
+            if key_event.code == KeyCode::Char('y') {
+                if self.active_pane == ActivePane::ColumnDetail {
+                    self.yank_stat();
+                } else {
+                    self.open_yank();
+                }
+            }
+
+            if key_event.code == KeyCode::Char('d') {
+                self.open_preview(None, None);
+            }
+
+            if key_event.code == KeyCode::Char('o') {
+                self.open_page_overhead();
+            }
+
+            if key_event.code == KeyCode::Char('i') {
+                self.open_page_ranges();
+            }
+
+            if key_event.code == KeyCode::Char('l') {
+                self.open_levels();
+            }
+
+            if key_event.code == KeyCode::Char('L') {
+                self.open_value_sizes();
+            }
+
+            if key_event.code == KeyCode::Char('T') {
+                self.open_decompression_profile();
+            }
+
+            if key_event.code == KeyCode::Char('M') {
+                self.open_stats_presence();
+            }
+
+            if key_event.code == KeyCode::Char('G') && self.decode_all_row_groups() {
+                self.overlay = Some(Overlay::ColumnRollup {
+                    rollups: analysis::column_rollup::column_rollups(&self.parquet_metadata),
+                    state: TableState::default()
+                        .with_selected(self.column_chunk_view_state.selected()),
+                });
+            }
+
+            if key_event.code == KeyCode::Char('h') && self.decode_all_row_groups() {
+                self.overlay = Some(Overlay::SizeCharts {
+                    sizes: analysis::row_group_sizes::row_group_sizes(&self.parquet_metadata),
+                    state: TableState::default()
+                        .with_selected(self.row_group_view_state.selected()),
+                });
+            }
+
+            if key_event.code == KeyCode::Char('t') {
+                self.open_type_filter();
+            }
+
+            if key_event.code == KeyCode::Char('D') {
+                self.open_ddl(Dialect::default());
+            }
+
+            if key_event.code == KeyCode::Char('B') {
+                self.open_size_breakdown();
+            }
+
+            if key_event.code == KeyCode::Char('R') {
+                self.open_remote_reads();
+            }
+
+            if key_event.code == KeyCode::Char('f') {
+                self.open_file_list();
+            }
+
+            if key_event.code == KeyCode::Char(']') {
+                self.switch_file(1);
+            }
+
+            if key_event.code == KeyCode::Char('[') {
+                self.switch_file(-1);
+            }
+
+            if key_event.code == KeyCode::Char('g') {
+                self.goto_prompt = Some(GotoPrompt::default());
+            }
+
+            if let KeyCode::Char(digit @ '0'..='9') = key_event.code {
+                if self.active_pane == ActivePane::RowGroupBrowser {
+                    self.jump_to_digit(digit);
+                }
+            }
         }
 
         Ok(())
     }
+
+    fn handle_overlay_key(&mut self, code: KeyCode) {
+        if self.handle_preview_key(code) {
+            return;
+        }
+
+        let Some(overlay) = self.overlay.as_mut() else {
+            return;
+        };
+
+        // Step between adjacent records in the record detail view.
+        if let Overlay::Record { row, .. } = overlay {
+            let row = *row;
+            match code {
+                KeyCode::Char('n') if row + 1 < records::num_rows(&self.parquet_metadata) => {
+                    return self.goto(GotoTarget {
+                        row: Some(row + 1),
+                        ..Default::default()
+                    });
+                }
+                KeyCode::Char('p') if row > 0 => {
+                    return self.goto(GotoTarget {
+                        row: Some(row - 1),
+                        ..Default::default()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if let Overlay::Value { hex, state, .. } = overlay {
+            let offset = state.offset();
+            match code {
+                KeyCode::Char('x') => *hex = !*hex,
+                KeyCode::Down => *state.offset_mut() = offset + 1,
+                KeyCode::Up => *state.offset_mut() = offset.saturating_sub(1),
+                KeyCode::PageDown => *state.offset_mut() = offset + VALUE_PAGE_LINES,
+                KeyCode::PageUp => *state.offset_mut() = offset.saturating_sub(VALUE_PAGE_LINES),
+                KeyCode::Home => *state.offset_mut() = 0,
+                // Clamped to the last page when drawn.
+                KeyCode::End => *state.offset_mut() = usize::MAX,
+                _ => {}
+            }
+        }
+
+        if let Overlay::Ddl { dialect, state, .. } = overlay {
+            let (dialect, offset) = (*dialect, state.offset());
+            match code {
+                KeyCode::Right | KeyCode::Tab => return self.open_ddl(dialect.step(1)),
+                KeyCode::Left | KeyCode::BackTab => return self.open_ddl(dialect.step(-1)),
+                KeyCode::Down => *state.offset_mut() = offset + 1,
+                KeyCode::Up => *state.offset_mut() = offset.saturating_sub(1),
+                KeyCode::PageDown => *state.offset_mut() = offset + VALUE_PAGE_LINES,
+                KeyCode::PageUp => *state.offset_mut() = offset.saturating_sub(VALUE_PAGE_LINES),
+                KeyCode::Home => *state.offset_mut() = 0,
+                KeyCode::End => *state.offset_mut() = usize::MAX,
+                _ => {}
+            }
+        }
+
+        if let Overlay::StatsPresence {
+            first_row_group, ..
+        } = overlay
+        {
+            // Clamped to the last row groups when drawn.
+            match code {
+                KeyCode::Right => *first_row_group += 1,
+                KeyCode::Left => *first_row_group = first_row_group.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        if let Overlay::TypeFilter { types, state } = overlay {
+            if code == KeyCode::Enter {
+                if let Some(&(type_filter, _)) = state.selected().and_then(|i| types.get(i)) {
+                    self.overlay = None;
+                    return self.filter_columns_by_type(type_filter);
+                }
+            }
+        }
+
+        if let Overlay::Bookmarks { bookmarks, state } = overlay {
+            match code {
+                KeyCode::Enter => {
+                    if let Some(bookmark) = state.selected().and_then(|i| bookmarks.get(i)) {
+                        let bookmark = bookmark.clone();
+                        self.overlay = None;
+                        return self.jump_to_bookmark(&bookmark);
+                    }
+                }
+                KeyCode::Delete => {
+                    if let Some(selected) = state.selected().filter(|&i| i < bookmarks.len()) {
+                        bookmarks.remove(selected);
+                        state.select(Some(selected.min(bookmarks.len().saturating_sub(1))));
+                        let remaining = bookmarks.clone();
+                        if remaining.is_empty() {
+                            self.overlay = None;
+                        }
+                        *self.file_bookmarks() = remaining;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Overlay::Files { state, .. } = overlay {
+            if code == KeyCode::Enter {
+                if let Some(index) = state.selected() {
+                    self.overlay = None;
+                    return self.open_file(index);
+                }
+            }
+        }
+
+        // Values copied from overlays: the selected record field, the yank popup's choice, or
+        // the value popup's value.
+        if code == KeyCode::Char('y') || code == KeyCode::Enter {
+            let value = match overlay {
+                Overlay::Record { fields, state, .. } if code == KeyCode::Char('y') => state
+                    .selected()
+                    .and_then(|i| fields.get(i))
+                    .map(|field| field.value.clone()),
+                Overlay::Yank { items, state } if code == KeyCode::Enter => state
+                    .selected()
+                    .and_then(|i| items.get(i))
+                    .map(|(_, value)| value.clone()),
+                Overlay::Value { value, .. } if code == KeyCode::Char('y') => Some(value.clone()),
+                Overlay::Ddl { ddl, .. } if code == KeyCode::Char('y') => {
+                    Some(ddl.statement.clone())
+                }
+                _ => None,
+            };
+            if let Some(value) = value {
+                if matches!(overlay, Overlay::Yank { .. }) {
+                    self.overlay = None;
+                }
+                return self.copy_to_clipboard(&value);
+            }
+        }
+
+        // Pages and row groups of the charts are laid out horizontally.
+        let code = match (&overlay, code) {
+            (Overlay::PageRanges { .. } | Overlay::SizeCharts { .. }, KeyCode::Right) => {
+                KeyCode::Down
+            }
+            (Overlay::PageRanges { .. } | Overlay::SizeCharts { .. }, KeyCode::Left) => KeyCode::Up,
+            _ => code,
+        };
+
+        let len = overlay.len();
+        let state = overlay.table_state();
+        match code {
+            KeyCode::Esc => self.overlay = None,
+            KeyCode::Down if len > 0 => {
+                let next = state.selected().map_or(0, |i| (i + 1) % len);
+                state.select(Some(next));
+            }
+            KeyCode::Up if len > 0 => {
+                let prev = state.selected().map_or(0, |i| (i + len - 1) % len);
+                state.select(Some(prev));
+            }
+            _ => {}
+        }
+    }
+
+    /// Report the first of the given column chunks whose codec this build cannot
+    /// decompress, returning whether all of them can be decompressed.
+    fn check_codecs(&mut self, row_group: usize, columns: &[usize]) -> bool {
+        let chunks = self.parquet_metadata.row_groups[row_group].columns();
+        for &column in columns {
+            let compression = chunks[column].compression();
+            let support = codecs::codec_support(compression);
+            if !support.is_supported() {
+                self.fail(format!(
+                    "{} uses {}, which is {}",
+                    chunks[column].descriptor().path_in_schema.join("."),
+                    codecs::codec_name(compression),
+                    support
+                ));
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Break the size of the file down into pages, headers, indexes and footer and show it in
+    /// an overlay.
+    fn open_size_breakdown(&mut self) {
+        if !self.decode_all_row_groups() {
+            return;
+        }
+        match analysis::size_breakdown::size_breakdown(&self.path, &self.parquet_metadata) {
+            Ok(breakdown) => {
+                self.overlay = Some(Overlay::SizeBreakdown {
+                    breakdown,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Breaking down the file size failed: {}", err)),
+        }
+    }
+
+    /// Show the range requests made for the file when it is read from S3.
+    fn open_remote_reads(&mut self) {
+        if source::remote_scheme(&self.path) != Some("s3") {
+            self.warn("Read metrics are only kept for files read from s3:// URIs");
+            return;
+        }
+        self.overlay = Some(Overlay::RemoteReads {
+            seen: s3::request_count(),
+            state: TableState::default(),
+        });
+    }
+
+    /// Analyze the page overhead of the selected row group and show it in an overlay.
+    fn open_page_overhead(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let columns: Vec<usize> = (0..self.num_column_chunks()).collect();
+        if !self.check_codecs(row_group, &columns) {
+            return;
+        }
+        match analysis::page_overhead::analyze_row_group(
+            &self.path,
+            &self.parquet_metadata,
+            row_group,
+        ) {
+            Ok(chunks) => {
+                self.overlay = Some(Overlay::PageOverhead {
+                    row_group,
+                    chunks,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Page overhead analysis failed: {}", err)),
+        }
+    }
+
+    /// Read the column index of the selected column chunk and chart its page ranges in an
+    /// overlay.
+    fn open_page_ranges(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let column = self.column_chunk_view_state.selected().unwrap();
+        match analysis::page_index::read_column_index(&self.path, row_group, column) {
+            Ok(Some(index)) => {
+                self.overlay = Some(Overlay::PageRanges {
+                    row_group,
+                    index,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Ok(None) => self.notify("The column chunk has no column index".to_string()),
+            Err(err) => self.fail(format!("Reading the column index failed: {}", err)),
+        }
+    }
+
+    /// Decompress the selected column chunk page by page, timing each page.
+    fn open_decompression_profile(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let chunk = self.current_chunk().chunk;
+        match analysis::decompression::profile_decompression(&self.path, &chunk) {
+            Ok(profile) => {
+                self.overlay = Some(Overlay::Decompression {
+                    row_group,
+                    profile,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Decompression profiling failed: {}", err)),
+        }
+    }
+
+    /// Show the `CREATE TABLE` statement of the schema in a dialect, for a table named after
+    /// the file.
+    fn open_ddl(&mut self, dialect: Dialect) {
+        let table = Path::new(&self.file_name).file_stem().map_or_else(
+            || self.file_name.clone(),
+            |stem| stem.to_string_lossy().into(),
+        );
+        self.overlay = Some(Overlay::Ddl {
+            dialect,
+            ddl: ddl::create_table(self.parquet_metadata.schema(), &table, dialect),
+            state: TableState::default(),
+        });
+    }
+
+    /// Decode the definition and repetition levels of the selected column chunk and show their
+    /// distribution in an overlay.
+    fn open_levels(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let chunk = self.current_chunk().chunk;
+        match analysis::levels::level_distribution(&self.path, &chunk) {
+            Ok(distribution) => {
+                self.overlay = Some(Overlay::Levels {
+                    row_group,
+                    distribution,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Decoding the levels failed: {}", err)),
+        }
+    }
+
+    /// Measure the value lengths of the selected column chunk and show their distribution in an
+    /// overlay.
+    fn open_value_sizes(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let column = self.column_chunk_view_state.selected().unwrap();
+        if !self.check_codecs(row_group, &[column]) {
+            return;
+        }
+        match analysis::value_sizes::value_sizes(
+            &self.path,
+            &self.parquet_metadata,
+            row_group,
+            column,
+        ) {
+            Ok(sizes) => {
+                self.overlay = Some(Overlay::ValueSizes {
+                    row_group,
+                    sizes,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Measuring the value sizes failed: {}", err)),
+        }
+    }
+
+    /// Detect dictionary fallbacks in the column chunks of a row group, unless they were
+    /// already detected. Chunks whose pages cannot be read are reported as not falling back.
+    pub fn detect_dictionary_fallbacks(&mut self, row_group: usize) {
+        for (column, chunk) in self.parquet_metadata.row_groups[row_group]
+            .columns()
+            .iter()
+            .enumerate()
+        {
+            self.dictionary_fallbacks
+                .entry((row_group, column))
+                .or_insert_with(|| {
+                    analysis::dictionary::detect_fallback(&self.path, chunk).unwrap_or_else(|err| {
+                        tracing::debug!("dictionary fallback detection failed: {}", err);
+                        None
+                    })
+                });
+        }
+    }
+
+    /// Count the pages of the column chunks of a row group, unless they were already counted.
+    pub fn count_pages(&mut self, row_group: usize) {
+        for (column, chunk) in self.parquet_metadata.row_groups[row_group]
+            .columns()
+            .iter()
+            .enumerate()
+        {
+            self.page_counts
+                .entry((row_group, column))
+                .or_insert_with(|| {
+                    analysis::page_counts::count_pages(&self.path, chunk)
+                        .map_err(|err| tracing::debug!("page count failed: {}", err))
+                        .ok()
+                });
+        }
+    }
+
+    /// Count the values of a BOOLEAN column chunk, unless they were already counted. Chunks
+    /// of other types, or whose codec the build cannot decompress, are not counted.
+    pub fn count_booleans(&mut self, row_group: usize, column: usize) -> Option<BooleanCounts> {
+        let chunk = &self.parquet_metadata.row_groups[row_group].columns()[column];
+        if chunk.descriptor().descriptor.primitive_type.physical_type
+            != parquet2::schema::types::PhysicalType::Boolean
+            || !codecs::codec_support(chunk.compression()).is_supported()
+        {
+            return None;
+        }
+        *self
+            .boolean_counts
+            .entry((row_group, column))
+            .or_insert_with(|| {
+                analysis::booleans::count_booleans(
+                    &self.path,
+                    &self.parquet_metadata,
+                    row_group,
+                    column,
+                )
+                .map_err(|err| tracing::debug!("boolean count failed: {}", err))
+                .ok()
+            })
+    }
+
+    /// Estimate the in-memory size of the column chunks of a row group, unless it was already
+    /// estimated.
+    pub fn estimate_memory(&mut self, row_group: usize) -> &[MemoryEstimate] {
+        self.memory_estimates.entry(row_group).or_insert_with(|| {
+            analysis::memory::estimate_row_group(
+                &self.path,
+                &self.parquet_metadata,
+                row_group,
+                analysis::memory::DEFAULT_SAMPLE,
+            )
+        })
+    }
+
+    /// Read the footer fields of the row groups the decoded metadata drops, unless they were
+    /// already read. A footer that cannot be read again is reported as having none.
+    pub fn load_row_group_footers(&mut self) -> &[RowGroupFooter] {
+        self.row_group_footers.get_or_insert_with(|| {
+            crate::parquet::read_row_group_footers(&self.path).unwrap_or_else(|err| {
+                tracing::debug!("reading row group footers failed: {}", err);
+                Vec::new()
+            })
+        })
+    }
+
+    /// Move the column selection by `delta` rows of the column browser, skipping the headers of
+    /// expanded groups and the columns of collapsed ones.
+    fn step_column(&mut self, delta: isize) {
+        let column = self.column_chunk_view_state.selected().unwrap();
+        let column = self.column_view.step(&self.parquet_metadata, column, delta);
+        *self.column_chunk_view_state.selected_mut() = Some(column);
+    }
+
+    /// Collapse or expand the group of nested columns the selected column belongs to.
+    fn toggle_column_group(&mut self) {
+        let column = self.column_chunk_view_state.selected().unwrap();
+        if let Some(column) = self.column_view.toggle(&self.parquet_metadata, column) {
+            *self.column_chunk_view_state.selected_mut() = Some(column);
+        }
+    }
+
+    /// Open the popup choosing the type of the columns shown in the column browser.
+    fn open_type_filter(&mut self) {
+        let mut types = vec![(None, self.parquet_metadata.schema().columns().len())];
+        types.extend(
+            TypeFilter::available(&self.parquet_metadata)
+                .into_iter()
+                .map(|(type_filter, count)| (Some(type_filter), count)),
+        );
+        let selected = types
+            .iter()
+            .position(|(type_filter, _)| *type_filter == self.column_view.type_filter);
+        self.overlay = Some(Overlay::TypeFilter {
+            types,
+            state: TableState::default().with_selected(selected.or(Some(0))),
+        });
+    }
+
+    /// Show only the columns of a type in the column browser, or every column, and move the
+    /// selection to a shown column.
+    fn filter_columns_by_type(&mut self, type_filter: Option<TypeFilter>) {
+        self.column_view.type_filter = type_filter;
+        self.active_pane = ActivePane::ColumnBrowser;
+        self.step_column(0);
+    }
+
+    /// Mark or unmark the column under the cursor.
+    fn toggle_column_selection(&mut self) {
+        let column = self.column_chunk_view_state.selected().unwrap();
+        if !self.selected_columns.remove(&column) {
+            self.selected_columns.insert(column);
+        }
+    }
+
+    /// Columns that bulk actions apply to: the marked columns, or the column under the cursor
+    /// when none are marked.
+    pub fn action_columns(&self) -> Vec<usize> {
+        if self.selected_columns.is_empty() {
+            vec![self.column_chunk_view_state.selected().unwrap()]
+        } else {
+            self.selected_columns.iter().copied().collect()
+        }
+    }
+
+    /// Decode the action columns of the selected row group and show their statistics in an
+    /// overlay.
+    fn open_scan_stats(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let columns = self.action_columns();
+        if !self.check_codecs(row_group, &columns) {
+            return;
+        }
+        match analysis::scan::scan_columns(&self.path, &self.parquet_metadata, row_group, &columns)
+        {
+            Ok(columns) => {
+                self.overlay = Some(Overlay::ScanStats {
+                    row_group,
+                    columns,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Scan failed: {}", err)),
+        }
+    }
+
+    /// Verify the page checksums of the action columns of the selected row group.
+    fn verify_checksums(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let columns = self.action_columns();
+        match analysis::checksums::verify_row_group(
+            &self.path,
+            &self.parquet_metadata,
+            row_group,
+            &columns,
+        ) {
+            Ok(results) => {
+                let (mut with_crc, mut failed) = (0, 0);
+                for (column, checksums) in columns.into_iter().zip(results) {
+                    with_crc += checksums.with_crc;
+                    failed += checksums.failed;
+                    self.checksums.insert((row_group, column), checksums);
+                }
+                if with_crc == 0 {
+                    self.notify("No page carries a checksum");
+                } else {
+                    let message = format!(
+                        "Verified {} page checksums: {} passed, {} failed",
+                        with_crc,
+                        with_crc - failed,
+                        failed
+                    );
+                    if failed == 0 {
+                        self.notify(message);
+                    } else {
+                        self.warn(message);
+                    }
+                }
+            }
+            Err(err) => self.fail(format!("Checksum verification failed: {}", err)),
+        }
+    }
+
+    /// Estimate the distinct values of the action columns of the selected row group with a
+    /// HyperLogLog sketch, for the column detail pane.
+    fn estimate_distinct(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let columns = self.action_columns();
+        if !self.check_codecs(row_group, &columns) {
+            return;
+        }
+        for &column in &columns {
+            match analysis::distinct::estimate_distinct(&self.path, row_group, column) {
+                Ok(estimate) => {
+                    self.distinct_estimates
+                        .insert((row_group, column), estimate);
+                }
+                Err(err) => {
+                    self.fail(format!("Distinct value estimation failed: {}", err));
+                    return;
+                }
+            }
+        }
+        self.notify(match columns.as_slice() {
+            [column] => {
+                let estimate = &self.distinct_estimates[&(row_group, *column)];
+                format!(
+                    "About {} distinct values in {} non-null values",
+                    numbers::count(estimate.estimate),
+                    numbers::count(estimate.values)
+                )
+            }
+            columns => format!("Estimated the distinct values of {} columns", columns.len()),
+        });
+    }
+
+    /// Handle a key in the data preview, returning whether it was consumed. The filter prompt
+    /// and the column chooser receive every key while they are open.
+    fn handle_preview_key(&mut self, code: KeyCode) -> bool {
+        let Some(Overlay::Preview(preview)) = self.overlay.as_mut() else {
+            return false;
+        };
+
+        if let Some(input) = preview.filter_input.as_mut() {
+            match code {
+                KeyCode::Esc => preview.filter_input = None,
+                KeyCode::Enter => {
+                    let filter = if input.trim().is_empty() {
+                        None
+                    } else {
+                        match input.parse::<Predicate>() {
+                            Ok(filter) => Some(filter),
+                            // Keep the prompt open, it shows the expected syntax.
+                            Err(_) => return true,
+                        }
+                    };
+                    self.queries.record(input);
+                    let projection = preview.projection.take();
+                    self.open_preview(filter, projection);
+                }
+                KeyCode::Up => preview
+                    .history_cursor
+                    .step(&self.queries.history, input, -1),
+                KeyCode::Down => preview.history_cursor.step(&self.queries.history, input, 1),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        if let Some(name) = preview.save_input.as_mut() {
+            match code {
+                KeyCode::Esc => preview.save_input = None,
+                KeyCode::Enter => {
+                    let query = preview
+                        .filter
+                        .as_ref()
+                        .map(|filter| filter.to_string())
+                        .unwrap_or_default();
+                    // Keep the prompt open on names the query file cannot hold.
+                    if self.queries.save_as(name, &query) {
+                        self.toasts.push(
+                            ToastLevel::Info,
+                            format!("Saved the filter as {}", name.trim()),
+                        );
+                        preview.save_input = None;
+                    }
+                }
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Char(c) => name.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        if let Some(picker) = preview.saved_picker.as_mut() {
+            let selected = picker.selected().unwrap_or_default();
+            match code {
+                KeyCode::Esc => preview.saved_picker = None,
+                KeyCode::Up => picker.select(Some(selected.saturating_sub(1))),
+                KeyCode::Down => picker.select(Some(
+                    (selected + 1).min(self.queries.saved.len().saturating_sub(1)),
+                )),
+                KeyCode::Delete => {
+                    if let Some(name) = self.queries.saved.keys().nth(selected).cloned() {
+                        self.queries.saved.remove(&name);
+                        if self.queries.saved.is_empty() {
+                            preview.saved_picker = None;
+                        } else {
+                            picker.select(Some(selected.min(self.queries.saved.len() - 1)));
+                        }
+                    }
+                }
+                KeyCode::Enter => {
+                    let Some((name, query)) = self.queries.saved.iter().nth(selected) else {
+                        return true;
+                    };
+                    match query.parse::<Predicate>() {
+                        Ok(filter) => {
+                            let query = query.clone();
+                            self.queries.record(&query);
+                            let projection = preview.projection.take();
+                            self.open_preview(Some(filter), projection);
+                        }
+                        Err(err) => {
+                            let message = format!("Saved filter {} is invalid: {}", name, err);
+                            self.fail(message);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        if let Some(chooser) = preview.chooser.as_mut() {
+            match code {
+                KeyCode::Esc => preview.chooser = None,
+                KeyCode::Enter => {
+                    let projection = chooser.projection();
+                    let filter = preview.filter.take();
+                    self.open_preview(filter, projection);
+                }
+                KeyCode::Up => chooser.move_selection(-1),
+                KeyCode::Down => chooser.move_selection(1),
+                KeyCode::Char(' ') => chooser.toggle(),
+                KeyCode::Backspace => chooser.pop(),
+                KeyCode::Char(c) => chooser.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('/') => {
+                preview.history_cursor = Default::default();
+                preview.filter_input = Some(
+                    preview
+                        .filter
+                        .as_ref()
+                        .map(|filter| filter.to_string())
+                        .unwrap_or_default(),
+                )
+            }
+            KeyCode::Char('c') => {
+                let all_columns: Vec<String> = self
+                    .parquet_metadata
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().to_string())
+                    .collect();
+                preview.chooser = Some(ColumnChooser::new(
+                    &all_columns,
+                    preview.projection.as_deref(),
+                ));
+            }
+            KeyCode::Left => preview.scroll_columns(-1),
+            KeyCode::Right => preview.scroll_columns(1),
+            KeyCode::Char('p') => preview.pin_next_column(),
+            KeyCode::Char('y') => {
+                if let Some(cell) = preview.current_cell().map(str::to_string) {
+                    self.copy_to_clipboard(&cell);
+                }
+            }
+            KeyCode::Char('P') => preview.unpin_columns(),
+            KeyCode::Char('w') => {
+                if preview.filter.is_some() {
+                    preview.save_input = Some(String::new());
+                } else {
+                    self.fail("No filter to save, set one with /".to_string());
+                }
+            }
+            KeyCode::Char('o') => {
+                if self.queries.saved.is_empty() {
+                    self.fail("No saved filters, save the current one with w".to_string());
+                } else {
+                    preview.saved_picker = Some(ListState::default().with_selected(Some(0)));
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Bookmarks of the inspected file.
+    fn file_bookmarks(&mut self) -> &mut Vec<Bookmark> {
+        self.bookmarks
+            .entry(session::session_key(&self.path))
+            .or_default()
+    }
+
+    /// Bookmark the selected row group and column.
+    fn add_bookmark(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap_or_default();
+        let column = self.column_chunk_view_state.selected().unwrap_or_default();
+        let Some(column) = goto::column_paths(&self.parquet_metadata)
+            .get(column)
+            .cloned()
+        else {
+            return;
+        };
+        let message = format!("row group {}, column {}", row_group, column);
+        let bookmark = Bookmark { row_group, column };
+        let bookmarks = self.file_bookmarks();
+        if bookmarks.contains(&bookmark) {
+            self.notify(format!("Already bookmarked {}", message));
+        } else {
+            bookmarks.push(bookmark);
+            self.notify(format!("Bookmarked {}", message));
+        }
+    }
+
+    /// Open the popup listing the bookmarks of the inspected file.
+    fn open_bookmarks(&mut self) {
+        let bookmarks = self.file_bookmarks().clone();
+        if bookmarks.is_empty() {
+            return self.notify("No bookmarks yet, press m to bookmark the selection");
+        }
+        self.overlay = Some(Overlay::Bookmarks {
+            bookmarks,
+            state: TableState::default().with_selected(Some(0)),
+        });
+    }
+
+    /// Select the row group and column of a bookmark, unless the file no longer has them.
+    fn jump_to_bookmark(&mut self, bookmark: &Bookmark) {
+        let column = goto::column_paths(&self.parquet_metadata)
+            .iter()
+            .position(|path| *path == bookmark.column);
+        match column {
+            Some(column) if bookmark.row_group < self.num_row_groups() => self.goto(GotoTarget {
+                row_group: Some(bookmark.row_group),
+                column: Some(column),
+                row: None,
+            }),
+            _ => self.warn(format!(
+                "The file has no column {} in row group {} anymore",
+                bookmark.column, bookmark.row_group
+            )),
+        }
+    }
+
+    /// Open the popup listing the values of the main view that can be copied: the file path,
+    /// the selected column path and the selected column chunk's statistics.
+    fn open_yank(&mut self) {
+        let chunk = self.current_chunk().chunk;
+        let stats = crate::parquet::ColumnChunkMetaDataExt::stats(&chunk);
+        let column = chunk.descriptor().path_in_schema.join(".");
+
+        let mut items = vec![
+            ("file path".to_string(), self.path.display().to_string()),
+            ("column path".to_string(), column.clone()),
+        ];
+        for (label, value) in [
+            (
+                "min",
+                stats.min.map(|min| self.redactor.redact(&column, min)),
+            ),
+            (
+                "max",
+                stats.max.map(|max| self.redactor.redact(&column, max)),
+            ),
+            ("nulls", stats.null_count.map(|n| n.to_string())),
+            (
+                "distinct values",
+                stats.distinct_values.map(|n| n.to_string()),
+            ),
+        ] {
+            if let Some(value) = value {
+                items.push((label.to_string(), value));
+            }
+        }
+
+        // Start on the column path when the column browser is focused.
+        let selected = match self.active_pane {
+            ActivePane::RowGroupBrowser => 0,
+            ActivePane::ColumnBrowser | ActivePane::ColumnDetail => 1,
+        };
+        self.overlay = Some(Overlay::Yank {
+            items,
+            state: TableState::default().with_selected(Some(selected)),
+        });
+    }
+
+    /// The dotted path of the column under the cursor, and the value of the statistic selected
+    /// in the column detail pane, redacted, or `None` when the chunk statistics lack it.
+    fn selected_stat(&self) -> (String, Option<String>) {
+        let chunk = self.current_chunk().chunk;
+        let stats = crate::parquet::ColumnChunkMetaDataExt::stats(&chunk);
+        let column = chunk.descriptor().path_in_schema.join(".");
+        let value = match self.stat_field {
+            StatField::Sample => Some(self.redactor.redact(
+                &column,
+                views::column_detail::sample(
+                    &self.path,
+                    &self.parquet_metadata,
+                    self.row_group_view_state.selected().unwrap(),
+                    self.column_chunk_view_state.selected().unwrap(),
+                    self.sample_strategy,
+                    self.sample_size,
+                ),
+            )),
+            StatField::Min => stats.min.map(|min| self.redactor.redact(&column, min)),
+            StatField::Max => stats.max.map(|max| self.redactor.redact(&column, max)),
+            StatField::Nulls => stats.null_count.map(|n| n.to_string()),
+            StatField::DistinctValues => stats.distinct_values.map(|n| n.to_string()),
+        };
+        (column, value)
+    }
+
+    fn yank_stat(&mut self) {
+        match self.selected_stat() {
+            (_, Some(value)) => self.copy_to_clipboard(&value),
+            (column, None) => self.fail(format!(
+                "{} has no {} statistic",
+                column,
+                self.stat_field.label()
+            )),
+        }
+    }
+
+    /// Show the selected statistic in a popup wide enough for long strings.
+    fn open_stat_value(&mut self) {
+        match self.selected_stat() {
+            (column, Some(value)) => {
+                self.overlay = Some(Overlay::Value {
+                    title: format!("{} of {}", self.stat_field.label(), column),
+                    value,
+                    hex: false,
+                    state: TableState::default(),
+                })
+            }
+            (column, None) => self.fail(format!(
+                "{} has no {} statistic",
+                column,
+                self.stat_field.label()
+            )),
+        }
+    }
+
+    /// Open the data preview with its filter prompt pre-filled from the selected statistic:
+    /// rows equal to the min or max, null rows, or rows of the column for the distinct count.
+    /// Redacted values are left for the user to type.
+    fn filter_by_stat(&mut self) {
+        let (column, value) = self.selected_stat();
+        let value = value.filter(|_| !self.redactor.matches(&column));
+        let input = match (self.stat_field, value) {
+            (StatField::Min | StatField::Max, Some(value)) => format!("{} = {}", column, value),
+            (StatField::Nulls, _) => format!("{} is null", column),
+            _ => format!("{} = ", column),
+        };
+        self.open_preview(None, None);
+        if let Some(Overlay::Preview(preview)) = self.overlay.as_mut() {
+            preview.filter_input = Some(input);
+        }
+    }
+
+    /// Show a failure to the user and log it.
+    pub fn fail(&mut self, mut message: String) {
+        // Reads of a file that changed on disk fail in confusing ways, e.g. with corrupt pages.
+        if let Some(change) = self.file_change {
+            message = format!("{} (the file was {} on disk)", message, change.as_str());
+        }
+        tracing::warn!("{}", message);
+        self.toasts.push(ToastLevel::Error, message);
+    }
+
+    /// Tell the user the outcome of an action.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.toasts.push(ToastLevel::Info, message);
+    }
+
+    /// Tell the user that an action did less than asked, without failing.
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.toasts.push(ToastLevel::Warn, message);
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str) {
+        match self.clipboard.copy(text) {
+            Ok(()) => self.notify(format!("Copied {:?}", text)),
+            Err(err) => self.fail(format!("Copy failed: {}", err)),
+        }
+    }
+
+    /// Show the rows of the selected row group matching `filter` in the data preview, limited
+    /// to the top-level fields in `projection`.
+    fn open_preview(&mut self, filter: Option<Predicate>, projection: Option<Vec<String>>) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        // The pushdown estimate of a filter goes over every row group.
+        if filter.is_some() && !self.decode_all_row_groups() {
+            return;
+        }
+        if let Some(Err(err)) = filter
+            .as_ref()
+            .map(|filter| filter.decimal_literal(&self.parquet_metadata))
+        {
+            self.fail(format!("Filter failed: {:#}", err));
+            return;
+        }
+        match preview::load_preview(
+            &self.path,
+            &self.parquet_metadata,
+            row_group,
+            filter.as_ref(),
+            projection.as_deref(),
+            preview::PREVIEW_ROWS,
+        ) {
+            Ok(mut preview) => {
+                self.redactor.redact_preview(&mut preview);
+                let pushdown = filter.as_ref().and_then(|filter| {
+                    analysis::pushdown::estimate_scan(
+                        &self.path,
+                        &self.parquet_metadata,
+                        filter,
+                        projection.as_deref().unwrap_or_default(),
+                    )
+                    .map_err(|err| tracing::debug!("pushdown estimate failed: {}", err))
+                    .ok()
+                });
+                self.overlay = Some(Overlay::Preview(Box::new(PreviewState {
+                    row_group,
+                    preview,
+                    filter,
+                    pushdown,
+                    projection,
+                    state: TableState::default().with_selected(Some(0)),
+                    ..Default::default()
+                })))
+            }
+            Err(err) => self.fail(format!("Reading rows failed: {}", err)),
+        }
+    }
+
+    /// Read the record at a global row index and show it in an overlay.
+    fn open_record(&mut self, row: u64) {
+        match records::read_record(&self.path, &self.parquet_metadata, row) {
+            Ok(mut fields) => {
+                self.redactor.redact_record(&mut fields);
+                self.overlay = Some(Overlay::Record {
+                    row,
+                    fields,
+                    state: TableState::default().with_selected(Some(0)),
+                })
+            }
+            Err(err) => self.fail(format!("Reading row {} failed: {}", row, err)),
+        }
+    }
+
+    /// The chunk under the cursor.
+    pub fn current_chunk(&self) -> ComparedChunk {
+        ComparedChunk::new(
+            &self.file_name,
+            &self.parquet_metadata,
+            self.row_group_view_state.selected().unwrap(),
+            self.column_chunk_view_state.selected().unwrap(),
+        )
+    }
+
+    /// Pin the chunk under the cursor for comparison, or unpin it if it is already pinned.
+    fn toggle_pinned_chunk(&mut self) {
+        let current = self.current_chunk();
+        let already_pinned = self.pinned_chunk.as_ref().is_some_and(|pinned| {
+            pinned.file_name == current.file_name
+                && pinned.row_group == current.row_group
+                && pinned.chunk.descriptor().path_in_schema
+                    == current.chunk.descriptor().path_in_schema
+        });
+
+        if already_pinned {
+            self.pinned_chunk = None;
+            self.notify("Unpinned column chunk".to_string());
+        } else {
+            self.notify(format!(
+                "Pinned {} of row group {}, press c to compare",
+                current.chunk.descriptor().path_in_schema.join("."),
+                current.row_group
+            ));
+            self.pinned_chunk = Some(current);
+        }
+    }
+
+    /// Compare column chunks side by side, picking the chunks from, in order of precedence:
+    /// the pinned chunk and the chunk under the cursor, the marked columns of the selected row
+    /// group, the same chunk in the compare file, or the selected column across row groups.
+    fn open_compare(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let columns = self.action_columns();
+        let current = self.current_chunk();
+        let across_row_groups =
+            self.pinned_chunk.is_none() && columns.len() <= 1 && self.compare_file.is_none();
+        if across_row_groups && !self.decode_all_row_groups() {
+            return;
+        }
+
+        let chunks = if let Some(pinned) = &self.pinned_chunk {
+            vec![pinned.clone(), current]
+        } else if columns.len() > 1 {
+            columns
+                .into_iter()
+                .map(|column| {
+                    ComparedChunk::new(&self.file_name, &self.parquet_metadata, row_group, column)
+                })
+                .collect()
+        } else if let Some((file_name, metadata)) = &self.compare_file {
+            let path = goto::column_paths(&self.parquet_metadata)[columns[0]].clone();
+            let column = goto::column_paths(metadata)
+                .iter()
+                .position(|other| *other == path);
+            match column {
+                Some(column) if row_group < metadata.row_groups.len() => vec![
+                    current,
+                    ComparedChunk::new(file_name, metadata, row_group, column),
+                ],
+                _ => {
+                    self.warn(format!(
+                        "{} has no column {} in row group {}",
+                        file_name, path, row_group
+                    ));
+                    return;
+                }
+            }
+        } else {
+            (0..self.num_row_groups())
+                .map(|row_group| {
+                    ComparedChunk::new(
+                        &self.file_name,
+                        &self.parquet_metadata,
+                        row_group,
+                        columns[0],
+                    )
+                })
+                .collect()
+        };
+
+        self.overlay = Some(Overlay::Compare {
+            chunks,
+            state: TableState::default().with_selected(Some(0)),
+        });
+    }
+
+    /// Open the file `step` places after the current one in the file list, wrapping around.
+    /// The view state is reset, except for the pinned chunk, which stays comparable.
+    fn switch_file(&mut self, step: isize) {
+        if self.files.len() < 2 {
+            return;
+        }
+
+        let index = (self.file_index as isize + step).rem_euclid(self.files.len() as isize);
+        self.open_file(index as usize);
+    }
+
+    /// List the open files, when there are several.
+    fn open_file_list(&mut self) {
+        if self.files.len() > 1 {
+            self.overlay = Some(Overlay::Files {
+                files: self.files.clone(),
+                state: TableState::default().with_selected(Some(self.file_index)),
+            });
+        }
+    }
+
+    /// Show which column chunks of every row group have statistics in an overlay.
+    fn open_stats_presence(&mut self) {
+        if !self.decode_all_row_groups() {
+            return;
+        }
+        self.overlay = Some(Overlay::StatsPresence {
+            matrix: analysis::stats_presence::stats_presence(&self.parquet_metadata),
+            first_row_group: self.row_group_view_state.selected().unwrap(),
+            state: TableState::default().with_selected(self.column_chunk_view_state.selected()),
+        });
+    }
+
+    /// Show the view the TUI starts on. The dataset view falls back to the row group browser
+    /// when a single file is inspected.
+    pub fn open_start_view(&mut self, view: StartView) {
+        match view {
+            StartView::RowGroups => self.active_pane = ActivePane::RowGroupBrowser,
+            StartView::Layout => {
+                self.active_pane = ActivePane::RowGroupBrowser;
+                self.row_group_canvas = true;
+            }
+            StartView::Schema => self.active_pane = ActivePane::ColumnBrowser,
+            StartView::Stats => self.open_stats_presence(),
+            StartView::Dataset => self.open_file_list(),
+            StartView::Preview => self.open_preview(None, None),
+            StartView::Pages => self.open_page_overhead(),
+        }
+    }
+
+    /// Open the file at `index` in the file list, reusing its footer if the background loader
+    /// already read it.
+    pub fn open_file(&mut self, index: usize) {
+        let path = self.files[index].clone();
+        let opened = match self.footers.as_ref().and_then(|footers| footers.get(index)) {
+            Some(Ok(metadata)) => Ok(App::with_metadata(
+                path.clone(),
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string()),
+                metadata,
+            )),
+            Some(Err(err)) => Err(eyre!(err)),
+            None => App::from(&path),
+        };
+        match opened {
+            Ok(app) => {
+                self.file_index = index;
+                self.replace_with(app);
+            }
+            Err(err) => self.fail(format!("Failed to open {}: {}", path.display(), err)),
+        }
+    }
+
+    /// Replace the app with one opened for another file or re-read from disk, keeping the
+    /// file list, the layout and the settings that are not about a single file.
+    fn replace_with(&mut self, app: App) {
+        *self = App {
+            files: std::mem::take(&mut self.files),
+            file_index: self.file_index,
+            footers: self.footers.take(),
+            pinned_chunk: self.pinned_chunk.take(),
+            compare_file: self.compare_file.take(),
+            split_compare: self.split_compare,
+            pane_layout: self.pane_layout,
+            zoomed: self.zoomed,
+            sample_strategy: self.sample_strategy,
+            sample_size: self.sample_size,
+            snapshot_format: self.snapshot_format,
+            redactor: std::mem::take(&mut self.redactor),
+            queries: std::mem::take(&mut self.queries),
+            bookmarks: std::mem::take(&mut self.bookmarks),
+            show_log: self.show_log,
+            ..app
+        };
+    }
+
+    /// Re-read the footer of the inspected file from disk, e.g. after it was rewritten. The
+    /// selected row group and column stay selected where the new footer still has them: the
+    /// row group by index, the column by path.
+    fn reload(&mut self) {
+        let row_group = self.row_group_view_state.selected();
+        let column = self.column_chunk_view_state.selected().and_then(|column| {
+            goto::column_paths(&self.parquet_metadata)
+                .get(column)
+                .cloned()
+        });
+        let active_pane = self.active_pane;
+
+        page_cache::clear();
+        let app = match App::from(&self.path) {
+            Ok(app) => app,
+            Err(err) => {
+                self.fail(format!("Failed to reload {}: {}", self.path.display(), err));
+                return;
+            }
+        };
+        if let Some(footers) = &self.footers {
+            footers.replace(self.file_index, Ok(app.parquet_metadata.clone()));
+        }
+        self.replace_with(app);
+
+        if let Some(row_group) = row_group.filter(|&row_group| row_group < self.num_row_groups()) {
+            *self.row_group_view_state.selected_mut() = Some(row_group);
+            self.decode_selected_row_group();
+        }
+        if let Some(column) = column.and_then(|column| {
+            goto::column_paths(&self.parquet_metadata)
+                .iter()
+                .position(|path| *path == column)
+        }) {
+            *self.column_chunk_view_state.selected_mut() = Some(column);
+        }
+        self.active_pane = active_pane;
+        self.notify(format!("Reloaded {}", self.file_name));
+    }
+
+    /// File name without its extension, used to name files derived from the inspected file.
+    pub fn file_stem(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.file_name.clone())
+    }
+
+    /// Export the selected row group into a standalone Parquet file in the working directory,
+    /// keeping only the marked columns if there are any.
+    fn export_row_group(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let stem = self.file_stem();
+        let output = PathBuf::from(format!("{}-rg{}.parquet", stem, row_group));
+        let paths = goto::column_paths(&self.parquet_metadata);
+        let columns: Vec<String> = self
+            .selected_columns
+            .iter()
+            .map(|&column| paths[column].clone())
+            .collect();
+
+        match export::export_parquet(&self.path, Some(row_group), &columns, &output) {
+            Ok(summary) => self.notify(format!(
+                "Exported {} rows of row group {} to {}",
+                summary.rows,
+                row_group,
+                output.display()
+            )),
+            Err(err) => self.fail(format!("Export failed: {}", err)),
+        }
+    }
+
+    /// Draw the row group canvas as an SVG image in the working directory.
+    fn export_canvas_image(&mut self) {
+        if !self.decode_all_row_groups() {
+            return;
+        }
+        let output = PathBuf::from(format!("{}-row-groups.svg", self.file_stem()));
+        let svg = layout_image::row_group_svg(&self.file_name, &self.parquet_metadata);
+        match std::fs::write(&output, svg) {
+            Ok(()) => self.notify(format!("Drew the row groups to {}", output.display())),
+            Err(err) => self.fail(format!("Export failed: {}", err)),
+        }
+    }
+
+    /// Copy the raw bytes of the selected column chunk into a binary file in the working
+    /// directory.
+    fn export_chunk_bytes(&mut self) {
+        let row_group = self.row_group_view_state.selected().unwrap();
+        let column = self.column_chunk_view_state.selected().unwrap();
+        let stem = self.file_stem();
+        let path = goto::column_paths(&self.parquet_metadata)[column].clone();
+        let output = PathBuf::from(format!("{}-rg{}-{}.bin", stem, row_group, path));
+
+        match export::export_chunk_bytes(
+            &self.path,
+            &self.parquet_metadata,
+            row_group,
+            column,
+            &output,
+        ) {
+            Ok((offset, length)) => self.notify(format!(
+                "Copied bytes {}..{} to {}",
+                offset,
+                offset + length,
+                output.display()
+            )),
+            Err(err) => self.fail(format!("Export failed: {}", err)),
+        }
+    }
+
+    fn handle_breadcrumb_key(&mut self, code: KeyCode) {
+        let Some(cursor) = self.breadcrumb_cursor else {
+            return;
+        };
+        let crumbs = breadcrumbs::breadcrumbs(self);
+
+        match code {
+            KeyCode::Esc | KeyCode::Backspace => self.breadcrumb_cursor = None,
+            KeyCode::Left => self.breadcrumb_cursor = Some(cursor.saturating_sub(1)),
+            KeyCode::Right => self.breadcrumb_cursor = Some((cursor + 1).min(crumbs.len() - 1)),
+            KeyCode::Enter => {
+                self.breadcrumb_cursor = None;
+                self.jump_to_crumb(&crumbs[cursor]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Navigate back up to a breadcrumb segment, closing the views and leaving the panes below
+    /// it. The file segment opens the file list when several files are open.
+    fn jump_to_crumb(&mut self, crumb: &Crumb) {
+        match crumb {
+            Crumb::View(_) => return,
+            Crumb::File(_) if self.files.len() > 1 => {
+                self.overlay = Some(Overlay::Files {
+                    files: self.files.clone(),
+                    state: TableState::default().with_selected(Some(self.file_index)),
+                });
+                return;
+            }
+            Crumb::File(_) | Crumb::RowGroup(_) => self.active_pane = ActivePane::RowGroupBrowser,
+            Crumb::Column(_) => self.active_pane = ActivePane::ColumnBrowser,
+            Crumb::Stat(_) => self.active_pane = ActivePane::ColumnDetail,
+        }
+        self.overlay = None;
+    }
+
+    fn handle_goto_key(&mut self, code: KeyCode) {
+        let Some(prompt) = self.goto_prompt.as_mut() else {
+            return;
+        };
+
+        match code {
+            KeyCode::Esc => self.goto_prompt = None,
+            KeyCode::Enter => {
+                if let Some(target) = prompt.resolve(&self.parquet_metadata) {
+                    self.goto(target);
+                }
+                self.goto_prompt = None;
+            }
+            KeyCode::Tab => prompt.complete(&self.parquet_metadata),
+            KeyCode::Backspace => prompt.pop(),
+            KeyCode::Char(c) => prompt.push(c),
+            _ => {}
+        }
+    }
+
+    /// Move the row group and column selections to the given target. A row target selects its
+    /// row group and opens the record in the record detail view.
+    pub fn goto(&mut self, target: GotoTarget) {
+        if let Some(row) = target.row {
+            if let Some((row_group, _)) = records::locate_row(&self.parquet_metadata, row) {
+                *self.row_group_view_state.selected_mut() = Some(row_group);
+                *self.column_chunk_view_state.selected_mut() = Some(0);
+                self.active_pane = ActivePane::RowGroupBrowser;
+            }
+            self.open_record(row);
+        }
+
+        if let Some(row_group) = target.row_group {
+            *self.row_group_view_state.selected_mut() = Some(row_group);
+            *self.column_chunk_view_state.selected_mut() = Some(0);
+            self.active_pane = ActivePane::RowGroupBrowser;
+        }
+
+        if let Some(column) = target.column {
+            // Show the target column if the type filter hides it.
+            if !self.column_view.shows(&self.parquet_metadata, column) {
+                self.column_view.type_filter = None;
+            }
+            *self.column_chunk_view_state.selected_mut() = Some(column);
+            self.active_pane = ActivePane::ColumnBrowser;
+        }
+    }
+
+    /// Jump to a row group by typing its number. Digits typed in quick succession accumulate.
+    fn jump_to_digit(&mut self, digit: char) {
+        let now = Instant::now();
+        let continues = self
+            .last_digit_at
+            .is_some_and(|last| now.duration_since(last) < DIGIT_JUMP_TIMEOUT);
+        if !continues {
+            self.row_group_digits.clear();
+        }
+        self.row_group_digits.push(digit);
+        self.last_digit_at = Some(now);
+
+        let Ok(mut row_group) = self.row_group_digits.parse::<usize>() else {
+            return;
+        };
+        if row_group >= self.num_row_groups() {
+            // Start over with just this digit if the accumulated number is out of range.
+            self.row_group_digits = digit.to_string();
+            row_group = digit.to_digit(10).unwrap() as usize;
+            if row_group >= self.num_row_groups() {
+                return;
+            }
+        }
+
+        self.goto(GotoTarget {
+            row_group: Some(row_group),
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::generate::{generate, Codec, GenerateOptions};
+
+    #[test]
+    fn snapshots_the_main_view() {
+        let dir =
+            std::env::temp_dir().join(format!("parquet_console_snapshot_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("events.parquet");
+        let options = GenerateOptions {
+            columns: vec!["id:int64".parse().unwrap(), "name:string".parse().unwrap()],
+            rows: 300,
+            row_group_size: 100,
+            codec: Codec::Uncompressed,
+            encoding: None,
+            null_ratio: 0.0,
+            cardinality: 10,
+            no_stats: false,
+            no_page_index: false,
+            page_rows: None,
+            seed: 1,
+        };
+        generate(&path, &options).unwrap();
+
+        let mut app = App::from(&path).unwrap();
+        let snapshot = render_snapshot(&mut app, 80, 12);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let lines: Vec<&str> = snapshot.lines().collect();
+        assert_eq!(lines.len(), 12);
+        // Sizes depend on the writer, so only the lines without them are compared.
+        assert_eq!(
+            lines[..3],
+            [
+                "events.parquet ▸ row group 0",
+                "┌Row Groups──────────────┐┌Column Chunks────────────┐┌Row Group 0──────────────┐",
+                "│> Row Group 0           ││>   id  INT64  1 page +di││num_rows = 100           │",
+            ]
+        );
+        assert!(lines[3].starts_with("│  Row Group 1           ││    name  BYTEARRAY  1 pa││"));
+        assert!(lines[4].starts_with("│  Row Group 2           │"));
+        assert_eq!(lines[11].trim_end(), "events.parquet │ rg 0 / col id");
+    }
 }
@@ -0,0 +1,136 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    layer::{Context, SubscriberExt},
+    util::SubscriberInitExt,
+    Layer,
+};
+
+/// Number of log lines kept for the log pane.
+const MAX_LOG_LINES: usize = 1000;
+
+/// A log event, as shown in the log pane.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Time since logging was initialized.
+    pub elapsed: Duration,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+struct LogBuffer {
+    started: Instant,
+    lines: Mutex<VecDeque<LogLine>>,
+    /// Number of events logged so far, including lines since dropped from the buffer.
+    count: AtomicU64,
+}
+
+static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+
+fn buffer() -> &'static LogBuffer {
+    BUFFER.get_or_init(|| LogBuffer {
+        started: Instant::now(),
+        lines: Mutex::new(VecDeque::new()),
+        count: AtomicU64::new(0),
+    })
+}
+
+/// Install the global tracing subscriber.
+///
+/// Events are always kept in memory for the TUI log pane, at INFO level or at DEBUG level with
+/// `verbose`. With `stderr`, they are also printed to stderr, which the TUI must not do since
+/// it owns the terminal; only warnings and errors are printed unless `verbose` is set.
+pub fn init(verbose: bool, stderr: bool) {
+    let level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::INFO
+    };
+    let stderr_level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::WARN
+    };
+
+    buffer();
+    let stderr_layer = stderr.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(io::stderr)
+            .with_ansi(crate::color::stderr_colored())
+            .without_time()
+            .with_filter(stderr_level)
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(BufferLayer.with_filter(level))
+        .with(stderr_layer)
+        .try_init();
+}
+
+/// Snapshot of the buffered log lines, oldest first.
+pub fn lines() -> Vec<LogLine> {
+    buffer().lines.lock().unwrap().iter().cloned().collect()
+}
+
+/// Number of events logged so far. It changes whenever new lines are buffered, so the TUI
+/// can tell when the log pane needs a redraw.
+pub fn count() -> u64 {
+    buffer().count.load(Ordering::Relaxed)
+}
+
+/// Layer appending every event to the in-memory log buffer.
+struct BufferLayer;
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let buffer = buffer();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let mut lines = buffer.lines.lock().unwrap();
+        if lines.len() == MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(LogLine {
+            elapsed: buffer.started.elapsed(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        });
+        buffer.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Formats an event's message followed by its other fields as `name=value`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.message.is_empty() {
+            self.message.push(' ');
+        }
+        if field.name() == "message" {
+            write!(self.message, "{:?}", value).unwrap();
+        } else {
+            write!(self.message, "{}={:?}", field.name(), value).unwrap();
+        }
+    }
+}
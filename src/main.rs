@@ -1,43 +1,599 @@
 // Add support for ratatui
 
-use std::path::PathBuf;
+use std::{io, path::PathBuf, time::Duration};
 
-use clap::{Args, Parser};
-use color_eyre::eyre::Context;
-use parquet_console::{start_ui, tui, App};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use color_eyre::eyre::{eyre, Context};
+use parquet_console::{
+    color,
+    commands::{
+        self, audit::AuditArgs, bloom::BloomArgs, canvas::CanvasArgs, chunk_bytes::ChunkBytesArgs,
+        count::CountArgs, dataset_stats::DatasetStatsArgs, diff_column::DiffColumnArgs,
+        encodings::EncodingsArgs, export::ExportArgs, generate::GenerateArgs, layout::LayoutArgs,
+        layout_check::LayoutCheckArgs, memory::MemoryArgs, meta::MetaArgs, offset::OffsetArgs,
+        pushdown::PushdownArgs, query::QueryArgs, recover::RecoverArgs, schema::SchemaArgs,
+        schema_check::SchemaCheckArgs, serve::ServeArgs, sortedness::SortednessArgs,
+        strings::StringsArgs, CompletionsArgs,
+    },
+    dataset::{default_threads, find_files, FooterLoader},
+    files::{self, FileOrder},
+    goto::{column_paths, GotoTarget},
+    graphics::{self, GraphicsMode},
+    keys::{self, KeyRecorder},
+    numbers::{self, NumberFormat},
+    output::{self, OutputFormat},
+    parquet::read_file_metadata,
+    queries::QueryStore,
+    redact::{self, Redactor},
+    render_once, replay,
+    sampling::{parse_sample_size, SampleStrategy, DEFAULT_SAMPLE_SIZE},
+    session::{session_key, SessionState},
+    snapshot::{self, SnapshotFormat},
+    start_ui,
+    start_view::StartView,
+    stdin::{self, BufferedStdin},
+    timezone::{self, TimeZoneMode},
+    tui::{self, RenderMode},
+    App, UiTiming,
+};
 
 #[derive(Parser, Debug)]
+#[command(version, about = "Inspect Parquet files from the terminal")]
+struct Cli {
+    /// Output format of the non-TUI subcommands.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Always read footers from the file instead of the footer cache.
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Memory-map files instead of reading them, avoiding heap copies of pages for large local
+    /// files.
+    #[arg(long, global = true)]
+    mmap: bool,
+
+    /// Memory budget of value scans such as sampling and scanned stats, e.g. `512M` or `2G`.
+    /// Pages are decoded in batches sized to stay within it.
+    #[arg(long, global = true, value_name = "SIZE", value_parser = parquet_console::parquet::parse_byte_size)]
+    max_memory: Option<usize>,
+
+    /// Render without colors. Also the default when `NO_COLOR` is set, `TERM=dumb` or the
+    /// output is not a terminal.
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Time zone TIMESTAMP values adjusted to UTC are shown in, or `original` for the stored
+    /// integers. Timestamps not adjusted to UTC are shown as stored. Cycle with `Z` in the TUI.
+    #[arg(long, global = true, value_enum, value_name = "ZONE", default_value_t = TimeZoneMode::Utc)]
+    tz: TimeZoneMode,
+
+    /// Log debug events such as IO timings, to stderr or to the TUI log pane (`~`).
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    #[command(flatten)]
+    s3: parquet_console::s3::S3Options,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
 enum Commands {
+    /// Browse a file in the interactive TUI.
     Inspect(InspectArgs),
+    /// Browse two files side by side in the TUI, with synchronized navigation and
+    /// differences highlighted.
+    Compare(CompareArgs),
+    /// Print the footer metadata of a file.
+    Meta(MetaArgs),
+    /// List the columns of a file, or convert its schema with `--to`, e.g. into a JSON Schema,
+    /// Avro or Protobuf schema.
+    Schema(SchemaArgs),
+    /// Count the rows of a file, optionally matching a filter.
+    Count(CountArgs),
+    /// Infer the sort order of every column and suggest sort keys.
+    Sortedness(SortednessArgs),
+    /// Profile value lengths and UTF-8 validity of byte array columns.
+    Strings(StringsArgs),
+    /// Estimate how alternative encodings would change the size of every column.
+    Encodings(EncodingsArgs),
+    /// Extract a row group and/or columns into a standalone Parquet file.
+    Export(ExportArgs),
+    /// Copy the raw byte range of a column chunk into a binary file.
+    ChunkBytes(ChunkBytesArgs),
+    /// Estimate the decoded, in-memory size of every row group and column.
+    Memory(MemoryArgs),
+    /// List bloom filters with their estimated false positive rate, and columns lacking them.
+    Bloom(BloomArgs),
+    /// Find the row group, column chunk, page and rows covering a byte offset.
+    Offset(OffsetArgs),
+    /// Estimate the bytes a filtered scan reads after row group and page pruning.
+    Pushdown(PushdownArgs),
+    /// Print the first rows of a file matching a filter, read by the console or, with
+    /// `--engine duckdb`, by DuckDB.
+    Query(QueryArgs),
+    /// Print every row group, column chunk, page, index and the footer by byte offset.
+    Layout(LayoutArgs),
+    /// Draw the row groups and their column chunks, scaled by size, as an SVG image.
+    Canvas(CanvasArgs),
+    /// Check that row groups are in order, at their declared offsets and without overlaps or
+    /// gaps.
+    LayoutCheck(LayoutCheckArgs),
+    /// Scan a truncated or corrupt file for pages and salvage the complete row groups.
+    Recover(RecoverArgs),
+    /// Compare the row group statistics of a column in two files.
+    DiffColumn(DiffColumnArgs),
+    /// Aggregate the footers of every Parquet file under a directory.
+    DatasetStats(DatasetStatsArgs),
+    /// Compare schemas across a directory and print the timeline of schema versions.
+    SchemaCheck(SchemaCheckArgs),
+    /// Flag columns likely to contain personal data, from names, types and sampled values.
+    Audit(AuditArgs),
+    /// Serve the metadata of a file as JSON over HTTP, for dashboards and notebooks.
+    Serve(ServeArgs),
+    /// Write a synthetic Parquet file with a given schema, layout and anomalies.
+    Generate(GenerateArgs),
+    /// Generate shell completions.
+    Completions(CompletionsArgs),
 }
 
 #[derive(Args, Debug)]
 struct InspectArgs {
+    /// Files or glob patterns to inspect, switched between with `[` and `]`. Defaults to the
+    /// last inspected file.
     #[arg(value_name = "FILE")]
-    pub file: PathBuf,
+    pub files: Vec<PathBuf>,
+
+    /// Order of the inspected files.
+    #[arg(long, value_enum, default_value_t = FileOrder::Name)]
+    pub order: FileOrder,
+
+    /// Second file to compare column chunks against with `c`.
+    #[arg(long, value_name = "FILE")]
+    pub compare_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub tui: TuiArgs,
+}
+
+/// Options of the TUI shared by `inspect` and `compare`.
+#[derive(Args, Debug)]
+struct TuiArgs {
+    /// Row group to select on startup.
+    #[arg(long)]
+    pub row_group: Option<usize>,
+
+    /// Dotted path of the column to select and focus on startup, e.g. `events.payload.id`.
+    #[arg(long)]
+    pub column: Option<String>,
+
+    /// Format of the screen snapshots taken with `S`.
+    #[arg(long, value_enum, default_value_t = SnapshotFormat::Text)]
+    pub snapshot_format: SnapshotFormat,
+
+    /// Mask sampled values and min/max statistics of columns whose name suggests personal data,
+    /// e.g. emails, SSNs or names, so that screenshots can be shared.
+    #[arg(long)]
+    pub redact: bool,
+
+    /// Comma-separated glob patterns of the column names to redact, matched against the dotted
+    /// path or any of its fields, ignoring case. Implies `--redact` and replaces the default
+    /// patterns.
+    #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+    pub redact_columns: Vec<String>,
+
+    /// How the values sampled in the column detail panel are picked. Cycle with `<` and `>`.
+    #[arg(long, value_enum, default_value_t = SampleStrategy::Head)]
+    pub sample_strategy: SampleStrategy,
+
+    /// Number of values sampled in the column detail panel. Double and halve with `+` and
+    /// `-`.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_SAMPLE_SIZE, value_parser = parse_sample_size)]
+    pub sample_size: usize,
+
+    /// Show the interactive TUI, or render the main view once to stdout in the
+    /// `--snapshot-format` and exit.
+    #[arg(long, value_enum, default_value_t = RenderMode::Tui)]
+    pub render: RenderMode,
+
+    /// Write the keys pressed in the TUI to FILE, one per line, to replay them with
+    /// `--replay`, e.g. for bug reports.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["replay", "render"])]
+    pub record: Option<PathBuf>,
+
+    /// Handle the keys of a file written with `--record` without a terminal, and print the
+    /// screen after the last one in the `--snapshot-format`, e.g. for scripted demos and tests.
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Draw charts such as the row group canvas and size charts as images with the kitty or
+    /// sixel graphics protocol. `auto` uses them when the terminal is known to support one.
+    #[arg(long, value_enum, default_value_t = GraphicsMode::Auto)]
+    pub graphics: GraphicsMode,
+
+    /// How counts and sizes are written, e.g. `1234567`, `1,234,567` or `1.23M` and
+    /// `1.18 MiB`. Cycle with `n`. Defaults to the format last used.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub numbers: Option<NumberFormat>,
+
+    /// View to open on: the row group browser or its `layout` canvas, the schema in the column
+    /// browser, the statistics presence matrix, the list of inspected files, the data preview or
+    /// the page overhead of the selected row group. Defaults to `start_view` in the state file,
+    /// if set.
+    #[arg(long, value_enum, value_name = "VIEW")]
+    pub start_view: Option<StartView>,
+
+    /// Launch straight into one of the `--start-view` views full screen, e.g. the row group
+    /// `layout` canvas or the `data` preview. `z` then shows the browsers.
+    #[arg(long, value_enum, value_name = "VIEW", conflicts_with = "start_view")]
+    pub view: Option<StartView>,
+
+    /// Interval in milliseconds between ticks, which refresh background progress such as
+    /// footers read from a directory or new log lines.
+    #[arg(long, value_name = "MS", default_value_t = 250)]
+    pub tick_rate: u64,
+
+    /// Longest wait in milliseconds for a key press before checking for a due tick.
+    #[arg(long, value_name = "MS", default_value_t = 100)]
+    pub poll_timeout: u64,
+}
+
+#[derive(Args, Debug)]
+struct CompareArgs {
+    /// File to inspect.
+    #[arg(value_name = "FILE_A")]
+    pub file_a: PathBuf,
+
+    /// File shown next to it.
+    #[arg(value_name = "FILE_B")]
+    pub file_b: PathBuf,
+
+    #[command(flatten)]
+    pub tui: TuiArgs,
 }
 
 fn main() -> color_eyre::Result<()> {
-    let command = Commands::parse();
+    let mut cli = Cli::parse();
+    if cli.no_color || color::disabled_by_env() {
+        color::disable();
+    }
+    parquet_console::logging::init(
+        cli.verbose,
+        !matches!(cli.command, Commands::Inspect(_) | Commands::Compare(_)),
+    );
+    if cli.no_cache {
+        parquet_console::cache::disable();
+    }
+    if cli.mmap {
+        parquet_console::source::enable_mmap();
+    }
+    timezone::set(cli.tz);
+    parquet_console::s3::configure(cli.s3.clone());
+    if let Some(max_memory) = cli.max_memory {
+        parquet_console::parquet::set_max_memory(max_memory);
+    }
+
+    // Keeps standard input buffered, for `-` file arguments, until the command is done.
+    let mut buffered_stdin: Option<BufferedStdin> = None;
+    match &mut cli.command {
+        Commands::Inspect(args) => {
+            if args.files.iter().any(|file| stdin::is_stdin(file)) {
+                return Err(eyre!("inspect cannot read from standard input"));
+            }
+        }
+        Commands::Compare(args) => {
+            if stdin::is_stdin(&args.file_a) || stdin::is_stdin(&args.file_b) {
+                return Err(eyre!("compare cannot read from standard input"));
+            }
+        }
+        Commands::Meta(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Schema(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Count(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Sortedness(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Strings(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Encodings(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Export(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::ChunkBytes(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Memory(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Bloom(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Offset(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Pushdown(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Query(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Layout(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Canvas(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::LayoutCheck(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Recover(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::DiffColumn(args) => {
+            stdin::resolve(&mut args.file_a, &mut buffered_stdin)?;
+            stdin::resolve(&mut args.file_b, &mut buffered_stdin)?;
+        }
+        Commands::Audit(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::Serve(args) => stdin::resolve(&mut args.file, &mut buffered_stdin)?,
+        Commands::DatasetStats(_)
+        | Commands::SchemaCheck(_)
+        | Commands::Generate(_)
+        | Commands::Completions(_) => {}
+    }
 
-    // Show version of the app, based off of git
-    match command {
-        Commands::Inspect(args) => run_tui(args).wrap_err("run tui failed")?,
+    match cli.command {
+        Commands::Inspect(args) => run_tui(args, false).wrap_err("run tui failed")?,
+        Commands::Compare(args) => run_tui(
+            InspectArgs {
+                files: vec![args.file_a],
+                order: FileOrder::default(),
+                compare_file: Some(args.file_b),
+                tui: args.tui,
+            },
+            true,
+        )
+        .wrap_err("run tui failed")?,
+        Commands::Meta(args) => print!(
+            "{}",
+            output::render(&commands::meta::run(&args)?, cli.format)
+        ),
+        Commands::Schema(args) => match args.to {
+            Some(to) => print!("{}", commands::schema::convert(&args.file, to)?),
+            None => print!(
+                "{}",
+                output::render(&commands::schema::run(&args)?, cli.format)
+            ),
+        },
+        Commands::Count(args) => print!(
+            "{}",
+            output::render(&commands::count::run(&args)?, cli.format)
+        ),
+        Commands::Sortedness(args) => print!(
+            "{}",
+            output::render(&commands::sortedness::run(&args)?, cli.format)
+        ),
+        Commands::Strings(args) => print!(
+            "{}",
+            output::render(&commands::strings::run(&args)?, cli.format)
+        ),
+        Commands::Encodings(args) => print!(
+            "{}",
+            output::render(&commands::encodings::run(&args)?, cli.format)
+        ),
+        Commands::Export(args) => print!(
+            "{}",
+            output::render(&commands::export::run(&args)?, cli.format)
+        ),
+        Commands::ChunkBytes(args) => print!(
+            "{}",
+            output::render(&commands::chunk_bytes::run(&args)?, cli.format)
+        ),
+        Commands::Memory(args) => print!(
+            "{}",
+            output::render(&commands::memory::run(&args)?, cli.format)
+        ),
+        Commands::Bloom(args) => print!(
+            "{}",
+            output::render(&commands::bloom::run(&args)?, cli.format)
+        ),
+        Commands::Offset(args) => print!(
+            "{}",
+            output::render(&commands::offset::run(&args)?, cli.format)
+        ),
+        Commands::Pushdown(args) => print!(
+            "{}",
+            output::render(&commands::pushdown::run(&args)?, cli.format)
+        ),
+        Commands::Query(args) => print!(
+            "{}",
+            output::render(&commands::query::run(&args)?, cli.format)
+        ),
+        Commands::Layout(args) => print!(
+            "{}",
+            output::render(&commands::layout::run(&args)?, cli.format)
+        ),
+        Commands::Canvas(args) => print!(
+            "{}",
+            output::render(&commands::canvas::run(&args)?, cli.format)
+        ),
+        Commands::LayoutCheck(args) => print!(
+            "{}",
+            output::render(&commands::layout_check::run(&args)?, cli.format)
+        ),
+        Commands::Recover(args) => print!(
+            "{}",
+            output::render(&commands::recover::run(&args)?, cli.format)
+        ),
+        Commands::DiffColumn(args) => print!(
+            "{}",
+            output::render(&commands::diff_column::run(&args)?, cli.format)
+        ),
+        Commands::DatasetStats(args) => print!(
+            "{}",
+            output::render(&commands::dataset_stats::run(&args)?, cli.format)
+        ),
+        Commands::SchemaCheck(args) => print!(
+            "{}",
+            output::render(&commands::schema_check::run(&args)?, cli.format)
+        ),
+        Commands::Audit(args) => print!(
+            "{}",
+            output::render(&commands::audit::run(&args)?, cli.format)
+        ),
+        Commands::Serve(args) => commands::serve::run(&args)?,
+        Commands::Generate(args) => print!(
+            "{}",
+            output::render(&commands::generate::run(&args)?, cli.format)
+        ),
+        Commands::Completions(args) => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(args.shell, &mut command, name, &mut io::stdout());
+        }
     }
 
     Ok(())
 }
 
-/// Run TUI application for inspecting Parquet files
-fn run_tui(args: InspectArgs) -> color_eyre::Result<()> {
+/// Resolve the `--row-group` and `--column` flags of `inspect` against the file.
+fn startup_target(
+    app: &App,
+    row_group: Option<usize>,
+    column: Option<&str>,
+) -> color_eyre::Result<GotoTarget> {
+    if let Some(row_group) = row_group {
+        if row_group >= app.num_row_groups() {
+            return Err(eyre!(
+                "row group {} out of range, the file has {}",
+                row_group,
+                app.num_row_groups()
+            ));
+        }
+    }
+
+    let column = column
+        .map(|column| {
+            column_paths(&app.parquet_metadata)
+                .iter()
+                .position(|path| path == column)
+                .ok_or_else(|| eyre!("no column named {:?}", column))
+        })
+        .transpose()?;
+
+    Ok(GotoTarget {
+        row_group,
+        column,
+        ..Default::default()
+    })
+}
+
+/// Run TUI application for inspecting Parquet files, with the compare file's browsers next to
+/// the inspected file's when `split_compare` is set.
+fn run_tui(args: InspectArgs, split_compare: bool) -> color_eyre::Result<()> {
+    let mut session = SessionState::load();
+    let files = if args.files.is_empty() {
+        vec![session
+            .last_file
+            .clone()
+            .ok_or_else(|| eyre!("no FILE given and no previously inspected file to reopen"))?]
+    } else if let [dir] = args.files.as_slice() {
+        if dir.is_dir() {
+            let files = files::expand(&find_files(dir)?, args.order)?;
+            if files.is_empty() {
+                return Err(eyre!("no Parquet file under {}", dir.display()));
+            }
+            files
+        } else {
+            files::expand(&args.files, args.order)?
+        }
+    } else {
+        files::expand(&args.files, args.order)?
+    };
+    let file = files[0].clone();
+
+    let interactive = args.tui.render == RenderMode::Tui && args.tui.replay.is_none();
+    if interactive && color::is_dumb_terminal() {
+        eprintln!("This terminal cannot show the TUI, printing the footer metadata instead.");
+        print!(
+            "{}",
+            output::render(
+                &commands::meta::run(&MetaArgs { file })?,
+                OutputFormat::Table
+            )
+        );
+        return Ok(());
+    }
+
+    if interactive && color::is_enabled() {
+        graphics::enable(args.tui.graphics);
+    }
+
+    let mut app = App::from(&file)?;
+    if files.len() > 1 {
+        app.footers = Some(FooterLoader::spawn(files.clone(), default_threads()));
+        app.files = files;
+    }
+    app.snapshot_format = args.tui.snapshot_format;
+    // Recordings start from the default view, so that they replay the same under other
+    // session states.
+    let scripted = args.tui.record.is_some() || args.tui.replay.is_some();
+    if !scripted {
+        app.pane_layout = session.panes;
+        numbers::set(session.numbers);
+        app.queries = QueryStore::load();
+        app.bookmarks = session.bookmarks();
+    }
+    if let Some(format) = args.tui.numbers {
+        numbers::set(format);
+    }
+    app.sample_strategy = args.tui.sample_strategy;
+    app.sample_size = args.tui.sample_size;
+    if !args.tui.redact_columns.is_empty() {
+        app.redactor = Redactor::new(&args.tui.redact_columns)?;
+    } else if args.tui.redact {
+        app.redactor = Redactor::new(redact::DEFAULT_PATTERNS)?;
+    }
+    if let Some(compare_file) = &args.compare_file {
+        let file_name = compare_file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| compare_file.display().to_string());
+        app.compare_file = Some((file_name, read_file_metadata(compare_file)?));
+        app.split_compare = split_compare;
+        // Every row group is compared with the compare file's as the browser lists them.
+        app.decode_all_row_groups();
+    }
+    if let Some(file_session) = session.files.get(&session_key(&file)).filter(|_| !scripted) {
+        file_session.apply(&mut app);
+    }
+    app.goto(startup_target(
+        &app,
+        args.tui.row_group,
+        args.tui.column.as_deref(),
+    )?);
+    if let Some(view) = args.tui.view {
+        app.open_start_view(view);
+        app.zoomed = true;
+    } else if let Some(view) = args
+        .tui
+        .start_view
+        .or(session.start_view.filter(|_| !scripted))
+    {
+        app.open_start_view(view);
+    }
+
+    if !interactive {
+        let (width, height) = tui::plain_size();
+        let buffer = match &args.tui.replay {
+            Some(path) => replay(&mut app, keys::read(path)?, width, height)?,
+            None => render_once(&mut app, width, height)?,
+        };
+        print!("{}", snapshot::render(&buffer, args.tui.snapshot_format));
+        return Ok(());
+    }
+
+    let mut recorder = match &args.tui.record {
+        Some(path) => Some(
+            KeyRecorder::create(path, tui::plain_size())
+                .wrap_err_with(|| format!("creating key file {} failed", path.display()))?,
+        ),
+        None => None,
+    };
+
     tui::install_hooks()?;
     let mut terminal = tui::init().wrap_err("tui::init failed")?;
 
-    let mut app = App::from(args.file)?;
-    start_ui(&mut terminal, &mut app)?;
+    let timing = UiTiming {
+        tick_rate: Duration::from_millis(args.tui.tick_rate.max(1)),
+        poll_timeout: Duration::from_millis(args.tui.poll_timeout),
+    };
+    let result = start_ui(&mut terminal, &mut app, timing, recorder.as_mut());
 
     // Teardown
     tui::restore()?;
+    result?;
+
+    if !scripted {
+        session.record(&app);
+        session.save().wrap_err("saving session state failed")?;
+        app.queries.save().wrap_err("saving queries failed")?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,215 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use clap::ValueEnum;
+
+/// How the TUI writes counts and sizes, such as row counts, null counts and byte sizes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum NumberFormat {
+    /// Digits only, e.g. `1234567`.
+    #[default]
+    Plain,
+    /// Digits in groups of three, separated as the locale does, e.g. `1,234,567`, or
+    /// `1_234_567` without a locale.
+    Grouped,
+    /// Rounded to three significant digits with a suffix, e.g. `1.23M`, and sizes in binary
+    /// units, e.g. `1.18 MiB`.
+    Human,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 3] = [
+        NumberFormat::Plain,
+        NumberFormat::Grouped,
+        NumberFormat::Human,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NumberFormat::Plain => "plain",
+            NumberFormat::Grouped => "grouped",
+            NumberFormat::Human => "human",
+        }
+    }
+
+    /// The next format, wrapping around, as cycled with `n`.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|format| *format == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for NumberFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as ValueEnum>::from_str(s, true)
+    }
+}
+
+static FORMAT: AtomicU8 = AtomicU8::new(NumberFormat::Plain as u8);
+
+/// Write numbers in `format` for the rest of the process, e.g. for `--numbers` or `n`.
+pub fn set(format: NumberFormat) {
+    FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> NumberFormat {
+    NumberFormat::ALL[FORMAT.load(Ordering::Relaxed) as usize]
+}
+
+/// Separator of digit groups in the locale of `LC_ALL`, `LC_NUMERIC` or `LANG`, by language.
+fn group_separator() -> char {
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let language = locale.split(['_', '.', '@']).next().unwrap_or_default();
+    match language {
+        "" | "C" | "POSIX" => '_',
+        "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" => '.',
+        "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" => ' ',
+        _ => ',',
+    }
+}
+
+fn grouped(n: i128) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let separator = group_separator();
+    let mut out = String::with_capacity(digits.len() * 4 / 3 + 1);
+    if n < 0 {
+        out.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(separator);
+        }
+        out.push(digit);
+    }
+    out
+}
+
+/// `value` with three significant digits and the largest of `units` that keeps it at 1 or
+/// more, each unit `base` times the previous one.
+fn scaled(value: i128, base: f64, units: &[&str]) -> String {
+    let mut scaled = value as f64;
+    let mut unit = 0;
+    while scaled.abs() >= base && unit + 1 < units.len() {
+        scaled /= base;
+        unit += 1;
+    }
+    if unit == 0 {
+        return format!("{}{}", value, units[0]);
+    }
+    // Rounding may carry into the next unit, e.g. 999.9K.
+    if scaled.abs().round() >= base && unit + 1 < units.len() {
+        scaled /= base;
+        unit += 1;
+    }
+    let decimals = match scaled.abs() {
+        x if x < 10.0 => 2,
+        x if x < 100.0 => 1,
+        _ => 0,
+    };
+    format!("{:.*}{}", decimals, scaled, units[unit])
+}
+
+/// A count of rows, values, nulls or other things, in the current format.
+pub fn count(n: impl Into<i128>) -> String {
+    let n = n.into();
+    match current() {
+        NumberFormat::Plain => n.to_string(),
+        NumberFormat::Grouped => grouped(n),
+        NumberFormat::Human => scaled(n, 1000.0, &["", "K", "M", "B", "T"]),
+    }
+}
+
+/// A size in bytes, in the current format: without a unit unless human sizes are shown, as
+/// in tables whose header gives it.
+pub fn bytes(n: impl Into<i128>) -> String {
+    match current() {
+        NumberFormat::Human => size(n),
+        _ => count(n),
+    }
+}
+
+/// A size in bytes followed by `bytes`, or in binary units when human sizes are shown.
+pub fn bytes_labeled(n: impl Into<i128>) -> String {
+    match current() {
+        NumberFormat::Human => size(n),
+        _ => format!("{} bytes", count(n)),
+    }
+}
+
+/// A size in bytes rounded to its unit: megabytes with two decimals, or binary units when human
+/// sizes are shown.
+pub fn size(n: impl Into<i128>) -> String {
+    let n = n.into();
+    match current() {
+        NumberFormat::Human => scaled(n, 1024.0, &[" B", " KiB", " MiB", " GiB", " TiB"]),
+        _ => format!("{:.2} MB", n as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_digits_by_three() {
+        let separator = group_separator();
+        let expected = |groups: &[&str]| groups.join(&separator.to_string());
+        assert_eq!(grouped(0), "0");
+        assert_eq!(grouped(999), "999");
+        assert_eq!(grouped(1_000), expected(&["1", "000"]));
+        assert_eq!(grouped(1_234_567), expected(&["1", "234", "567"]));
+        assert_eq!(grouped(-123_456), format!("-{}", expected(&["123", "456"])));
+        assert_eq!(
+            grouped(i64::MIN.into()),
+            format!(
+                "-{}",
+                expected(&["9", "223", "372", "036", "854", "775", "808"])
+            )
+        );
+    }
+
+    #[test]
+    fn scales_to_three_significant_digits() {
+        let si = |n: i128| scaled(n, 1000.0, &["", "K", "M", "B", "T"]);
+        assert_eq!(si(999), "999");
+        assert_eq!(si(1_000), "1.00K");
+        assert_eq!(si(1_234_567), "1.23M");
+        assert_eq!(si(12_345_678), "12.3M");
+        assert_eq!(si(-45_600), "-45.6K");
+        // Rounding carries into the next unit.
+        assert_eq!(si(999_950), "1.00M");
+        // The largest unit keeps growing.
+        assert_eq!(si(1_234_000_000_000_000), "1234T");
+    }
+
+    #[test]
+    fn scales_sizes_to_binary_units() {
+        let binary = |n: i128| scaled(n, 1024.0, &[" B", " KiB", " MiB", " GiB", " TiB"]);
+        assert_eq!(binary(512), "512 B");
+        assert_eq!(binary(1536), "1.50 KiB");
+        assert_eq!(binary(1_234_567), "1.18 MiB");
+        assert_eq!(binary(5 << 30), "5.00 GiB");
+    }
+
+    #[test]
+    fn cycles_formats() {
+        assert_eq!(NumberFormat::Plain.next(), NumberFormat::Grouped);
+        assert_eq!(NumberFormat::Human.next(), NumberFormat::Plain);
+        assert_eq!("Human".parse(), Ok(NumberFormat::Human));
+    }
+}
@@ -0,0 +1,379 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+
+/// Output format for the non-TUI subcommands.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned, human-readable text.
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+/// A single scalar in a report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+/// A named section of a report: either a single record of fields, or a table of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Section {
+    Record {
+        name: String,
+        fields: Vec<(String, Value)>,
+    },
+    Table {
+        name: String,
+        columns: Vec<String>,
+        rows: Vec<Vec<Value>>,
+    },
+}
+
+/// Output of a non-TUI subcommand.
+///
+/// Subcommands build a report out of named [Section]s, and [render] turns it into the format
+/// selected with the global `--format` flag.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    pub sections: Vec<Section>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a record section, built from `(field, value)` pairs.
+    pub fn record<K, V, I>(mut self, name: &str, fields: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.sections.push(Section::Record {
+            name: name.to_string(),
+            fields: fields
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Add a table section. Every row must have one value per column.
+    pub fn table(mut self, name: &str, columns: &[&str], rows: Vec<Vec<Value>>) -> Self {
+        self.sections.push(Section::Table {
+            name: name.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+        });
+        self
+    }
+}
+
+impl Section {
+    fn name(&self) -> &str {
+        match self {
+            Section::Record { name, .. } | Section::Table { name, .. } => name,
+        }
+    }
+}
+
+impl Value {
+    /// Plain-text rendering, used by the table and CSV formats.
+    pub fn to_text(&self) -> String {
+        match self {
+            Value::Null => String::new(),
+            Value::Bool(b) => b.to_string(),
+            Value::Int(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Str(s) => s.clone(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Float(f) if !f.is_finite() => "null".to_string(),
+            Value::Str(s) => json_string(s),
+            other => other.to_text(),
+        }
+    }
+
+    fn to_yaml(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Float(f) if f.is_nan() => ".nan".to_string(),
+            Value::Float(f) if f.is_infinite() => {
+                if *f > 0.0 { ".inf" } else { "-.inf" }.to_string()
+            }
+            // JSON string escapes are also valid in YAML double-quoted scalars.
+            Value::Str(s) => json_string(s),
+            other => other.to_text(),
+        }
+    }
+}
+
+macro_rules! value_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Value::Int(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+value_from_int!(i16, i32, i64, u32, u64, usize);
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+/// Render a report in the requested format.
+pub fn render(report: &Report, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Table => render_table(report),
+        OutputFormat::Json => render_json(report),
+        OutputFormat::Yaml => render_yaml(report),
+        OutputFormat::Csv => render_csv(report),
+    }
+}
+
+fn render_table(report: &Report) -> String {
+    let mut out = String::new();
+
+    for (i, section) in report.sections.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "{}", section.name()).unwrap();
+
+        match section {
+            Section::Record { fields, .. } => {
+                let width = fields.iter().map(|(k, _)| k.chars().count()).max();
+                for (key, value) in fields {
+                    writeln!(
+                        out,
+                        "  {:width$}  {}",
+                        key,
+                        value.to_text(),
+                        width = width.unwrap_or_default()
+                    )
+                    .unwrap();
+                }
+            }
+            Section::Table { columns, rows, .. } => {
+                let cells: Vec<Vec<String>> = rows
+                    .iter()
+                    .map(|row| row.iter().map(Value::to_text).collect())
+                    .collect();
+                let widths: Vec<usize> = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        cells
+                            .iter()
+                            .filter_map(|row| row.get(i))
+                            .map(|cell| cell.chars().count())
+                            .chain(std::iter::once(column.chars().count()))
+                            .max()
+                            .unwrap_or_default()
+                    })
+                    .collect();
+
+                let header: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+                write_aligned_row(&mut out, &header, &widths);
+                for row in &cells {
+                    write_aligned_row(&mut out, row, &widths);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn write_aligned_row(out: &mut String, cells: &[String], widths: &[usize]) {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    writeln!(out, "  {}", line.trim_end()).unwrap();
+}
+
+fn render_json(report: &Report) -> String {
+    let sections: Vec<String> = report
+        .sections
+        .iter()
+        .map(|section| {
+            let body = match section {
+                Section::Record { fields, .. } => {
+                    json_object(fields.iter().map(|(k, v)| (k.as_str(), v)), "    ")
+                }
+                Section::Table { columns, rows, .. } => {
+                    if rows.is_empty() {
+                        "[]".to_string()
+                    } else {
+                        let objects: Vec<String> = rows
+                            .iter()
+                            .map(|row| {
+                                format!(
+                                    "    {}",
+                                    json_object(
+                                        columns.iter().map(String::as_str).zip(row),
+                                        "      "
+                                    )
+                                )
+                            })
+                            .collect();
+                        format!("[\n{}\n  ]", objects.join(",\n"))
+                    }
+                }
+            };
+            format!("  {}: {}", json_string(section.name()), body)
+        })
+        .collect();
+
+    format!("{{\n{}\n}}\n", sections.join(",\n"))
+}
+
+fn json_object<'a>(fields: impl Iterator<Item = (&'a str, &'a Value)>, indent: &str) -> String {
+    let fields: Vec<String> = fields
+        .map(|(k, v)| format!("{}{}: {}", indent, json_string(k), v.to_json()))
+        .collect();
+    if fields.is_empty() {
+        return "{}".to_string();
+    }
+
+    let closing = &indent[..indent.len() - 2];
+    format!("{{\n{}\n{}}}", fields.join(",\n"), closing)
+}
+
+/// Quote and escape a string as a JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn render_yaml(report: &Report) -> String {
+    let mut out = String::new();
+
+    for section in &report.sections {
+        match section {
+            Section::Record { name, fields } => {
+                writeln!(out, "{}:", name).unwrap();
+                for (key, value) in fields {
+                    writeln!(out, "  {}: {}", key, value.to_yaml()).unwrap();
+                }
+            }
+            Section::Table {
+                name,
+                columns,
+                rows,
+            } => {
+                if rows.is_empty() {
+                    writeln!(out, "{}: []", name).unwrap();
+                    continue;
+                }
+                writeln!(out, "{}:", name).unwrap();
+                for row in rows {
+                    for (i, (column, value)) in columns.iter().zip(row).enumerate() {
+                        let marker = if i == 0 { "- " } else { "  " };
+                        writeln!(out, "  {}{}: {}", marker, column, value.to_yaml()).unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn render_csv(report: &Report) -> String {
+    let mut out = String::new();
+
+    for (i, section) in report.sections.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        match section {
+            Section::Record { fields, .. } => {
+                writeln!(out, "field,value").unwrap();
+                for (key, value) in fields {
+                    writeln!(out, "{},{}", csv_field(key), csv_field(&value.to_text())).unwrap();
+                }
+            }
+            Section::Table { columns, rows, .. } => {
+                let header: Vec<String> = columns.iter().map(|c| csv_field(c)).collect();
+                writeln!(out, "{}", header.join(",")).unwrap();
+                for row in rows {
+                    let row: Vec<String> = row.iter().map(|v| csv_field(&v.to_text())).collect();
+                    writeln!(out, "{}", row.join(",")).unwrap();
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
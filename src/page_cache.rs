@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use bytes::Bytes;
+
+use crate::source::FileIdentity;
+
+/// Bytes the page cache holds at most, raw ranges and decompressed pages together.
+pub const PAGE_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Bytes read from the file on a cache miss, at least. Reading ahead keeps the small reads of
+/// page header scans from hitting the file one by one.
+pub const READ_WINDOW: usize = 64 * 1024;
+
+/// What a cached range holds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RangeKind {
+    /// Bytes of the file, as stored.
+    Raw,
+    /// A page payload after decompression, keyed by the offset of the page header.
+    Decompressed,
+}
+
+type Key = (PathBuf, RangeKind, u64);
+
+struct Entry {
+    bytes: Bytes,
+    last_used: u64,
+}
+
+/// Least recently used byte ranges of files, by path, kind and start offset.
+///
+/// Ranges are only valid for the file they were read from: each path's size and modification
+/// time when its ranges were cached are kept, and its ranges are dropped once the file is seen
+/// with another, e.g. after it was rewritten in place.
+struct PageCache {
+    entries: BTreeMap<Key, Entry>,
+    identities: BTreeMap<PathBuf, FileIdentity>,
+    /// Keys by the tick they were last used at, oldest first.
+    recency: BTreeMap<u64, Key>,
+    size: usize,
+    tick: u64,
+}
+
+static CACHE: Mutex<PageCache> = Mutex::new(PageCache {
+    entries: BTreeMap::new(),
+    identities: BTreeMap::new(),
+    recency: BTreeMap::new(),
+    size: 0,
+    tick: 0,
+});
+
+impl PageCache {
+    /// Drop the ranges of `path` if they were read from a file other than `identity`.
+    fn validate(&mut self, path: &Path, identity: FileIdentity) {
+        match self.identities.get(path) {
+            Some(cached) if *cached == identity => return,
+            Some(_) => {
+                let stale: Vec<Key> = self
+                    .entries
+                    .range((path.to_path_buf(), RangeKind::Raw, 0)..)
+                    .map(|(key, _)| key)
+                    .take_while(|(cached_path, _, _)| cached_path == path)
+                    .cloned()
+                    .collect();
+                for key in stale {
+                    if let Some(entry) = self.entries.remove(&key) {
+                        self.recency.remove(&entry.last_used);
+                        self.size -= entry.bytes.len();
+                    }
+                }
+            }
+            None => {}
+        }
+        self.identities.insert(path.to_path_buf(), identity);
+    }
+
+    fn touch(&mut self, key: &Key) -> Option<Bytes> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.last_used);
+        entry.last_used = self.tick;
+        self.recency.insert(self.tick, key.clone());
+        Some(entry.bytes.clone())
+    }
+
+    fn insert(&mut self, key: Key, bytes: Bytes) {
+        if let Some(entry) = self.entries.remove(&key) {
+            self.recency.remove(&entry.last_used);
+            self.size -= entry.bytes.len();
+        }
+        while self.size + bytes.len() > PAGE_CACHE_SIZE {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.size -= entry.bytes.len();
+            }
+        }
+        self.tick += 1;
+        self.size += bytes.len();
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                bytes,
+                last_used: self.tick,
+            },
+        );
+    }
+}
+
+/// Cached bytes of a file from `offset` to the end of the raw range containing it.
+pub fn find(path: &Path, identity: FileIdentity, offset: u64) -> Option<Bytes> {
+    let mut cache = CACHE.lock().unwrap();
+    cache.validate(path, identity);
+    let (key, entry) = cache
+        .entries
+        .range(..=(path.to_path_buf(), RangeKind::Raw, offset))
+        .next_back()?;
+    let (cached_path, kind, start) = key;
+    let end = start + entry.bytes.len() as u64;
+    if cached_path != path || *kind != RangeKind::Raw || offset >= end {
+        return None;
+    }
+    let skip = (offset - start) as usize;
+    let key = key.clone();
+    cache.touch(&key).map(|bytes| bytes.slice(skip..))
+}
+
+/// Cached decompressed payload of the page whose header is at `offset`.
+pub fn find_decompressed(path: &Path, identity: FileIdentity, offset: u64) -> Option<Bytes> {
+    let key = (path.to_path_buf(), RangeKind::Decompressed, offset);
+    let mut cache = CACHE.lock().unwrap();
+    cache.validate(path, identity);
+    cache.touch(&key)
+}
+
+/// Cache `bytes` read from `offset`. Ranges larger than a quarter of the cache are not kept,
+/// so that a single large chunk does not evict every page viewed before it.
+pub fn insert(path: &Path, identity: FileIdentity, kind: RangeKind, offset: u64, bytes: Bytes) {
+    if bytes.is_empty() || bytes.len() > PAGE_CACHE_SIZE / 4 {
+        return;
+    }
+    let mut cache = CACHE.lock().unwrap();
+    cache.validate(path, identity);
+    cache.insert((path.to_path_buf(), kind, offset), bytes);
+}
+
+/// Drop every cached range, e.g. after files changed on disk.
+pub fn clear() {
+    let mut cache = CACHE.lock().unwrap();
+    cache.entries.clear();
+    cache.identities.clear();
+    cache.recency.clear();
+    cache.size = 0;
+}
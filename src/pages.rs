@@ -0,0 +1,289 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use bytes::Bytes;
+
+use color_eyre::eyre::{eyre, Context};
+use parquet2::{compression::Compression, metadata::ColumnChunkMetaData};
+use parquet_format_safe::{
+    thrift::protocol::TCompactInputProtocol, Encoding, PageHeader, PageType,
+};
+
+use crate::{
+    page_cache::{self, RangeKind},
+    source::FileIdentity,
+};
+
+/// Upper bound on the size of a serialized page header, to protect against corrupt files.
+const MAX_PAGE_HEADER_SIZE: usize = 16 * 1024 * 1024;
+
+/// A page of a column chunk, as located by scanning the chunk's page headers.
+#[derive(Debug, Clone)]
+pub struct PageInfo {
+    /// File offset of the page header.
+    pub offset: u64,
+    /// Size of the serialized page header.
+    pub header_size: u64,
+    pub header: PageHeader,
+}
+
+impl PageInfo {
+    /// File offset of the page payload, directly after the header.
+    pub fn data_offset(&self) -> u64 {
+        self.offset + self.header_size
+    }
+
+    /// Size of the page payload as stored in the file.
+    pub fn compressed_size(&self) -> u64 {
+        self.header.compressed_page_size.max(0) as u64
+    }
+
+    pub fn uncompressed_size(&self) -> u64 {
+        self.header.uncompressed_page_size.max(0) as u64
+    }
+
+    pub fn page_type(&self) -> PageType {
+        self.header.type_
+    }
+
+    pub fn is_dictionary(&self) -> bool {
+        self.header.type_ == PageType::DICTIONARY_PAGE
+    }
+
+    pub fn is_data(&self) -> bool {
+        matches!(
+            self.header.type_,
+            PageType::DATA_PAGE | PageType::DATA_PAGE_V2
+        )
+    }
+
+    /// Number of values (including nulls) in a data or dictionary page.
+    pub fn num_values(&self) -> i64 {
+        if let Some(header) = &self.header.data_page_header {
+            i64::from(header.num_values)
+        } else if let Some(header) = &self.header.data_page_header_v2 {
+            i64::from(header.num_values)
+        } else if let Some(header) = &self.header.dictionary_page_header {
+            i64::from(header.num_values)
+        } else {
+            0
+        }
+    }
+
+    /// Encoding of the values of a data or dictionary page.
+    pub fn encoding(&self) -> Option<Encoding> {
+        if let Some(header) = &self.header.data_page_header {
+            Some(header.encoding)
+        } else if let Some(header) = &self.header.data_page_header_v2 {
+            Some(header.encoding)
+        } else {
+            self.header
+                .dictionary_page_header
+                .as_ref()
+                .map(|header| header.encoding)
+        }
+    }
+}
+
+/// Human-readable name of a page type.
+pub fn page_type_name(page_type: PageType) -> &'static str {
+    match page_type {
+        PageType::DATA_PAGE => "DATA_PAGE",
+        PageType::INDEX_PAGE => "INDEX_PAGE",
+        PageType::DICTIONARY_PAGE => "DICTIONARY_PAGE",
+        PageType::DATA_PAGE_V2 => "DATA_PAGE_V2",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Human-readable name of an encoding.
+pub fn encoding_name(encoding: Encoding) -> String {
+    match parquet2::encoding::Encoding::try_from(encoding) {
+        Ok(encoding) => format!("{:?}", encoding),
+        Err(_) => format!("UNKNOWN({})", encoding.0),
+    }
+}
+
+/// Reader adapter that counts the bytes read through it.
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+}
+
+/// Read a single page header at the reader's current position, returning it along with its
+/// serialized size.
+pub fn read_page_header<R: Read>(reader: R) -> color_eyre::Result<(PageHeader, u64)> {
+    let mut counting = CountingReader {
+        inner: reader,
+        count: 0,
+    };
+    let mut protocol = TCompactInputProtocol::new(&mut counting, MAX_PAGE_HEADER_SIZE);
+    let header = PageHeader::read_from_in_protocol(&mut protocol)?;
+
+    Ok((header, counting.count))
+}
+
+/// Scan the page headers of a column chunk, without reading the page payloads.
+pub fn read_page_headers<R: Read + Seek>(
+    reader: &mut R,
+    chunk: &ColumnChunkMetaData,
+) -> color_eyre::Result<Vec<PageInfo>> {
+    let (start, length) = chunk.byte_range();
+    let end = start + length;
+    let total_values = chunk.num_values();
+
+    let mut pages = Vec::new();
+    let mut offset = start;
+    let mut seen_values = 0;
+
+    while offset < end && seen_values < total_values {
+        reader.seek(SeekFrom::Start(offset))?;
+        let (header, header_size) = read_page_header(&mut *reader)
+            .wrap_err_with(|| format!("failed to read page header at offset {}", offset))?;
+
+        let page = PageInfo {
+            offset,
+            header_size,
+            header,
+        };
+        if page.is_data() {
+            seen_values += page.num_values();
+        }
+        offset = page.data_offset() + page.compressed_size();
+        pages.push(page);
+    }
+
+    Ok(pages)
+}
+
+/// Read the raw, possibly compressed, payload of a page.
+pub fn read_page_payload<R: Read + Seek>(
+    reader: &mut R,
+    page: &PageInfo,
+) -> color_eyre::Result<Vec<u8>> {
+    let mut payload = vec![0; page.compressed_size() as usize];
+    reader.seek(SeekFrom::Start(page.data_offset()))?;
+    reader.read_exact(&mut payload)?;
+
+    Ok(payload)
+}
+
+/// Decompress a page payload. For v2 data pages the uncompressed level bytes at the start of
+/// the payload are kept as-is.
+pub fn decompress_page(
+    page: &PageInfo,
+    payload: &[u8],
+    compression: Compression,
+) -> color_eyre::Result<Vec<u8>> {
+    let (prefix_len, is_compressed) = match &page.header.data_page_header_v2 {
+        Some(header) => (
+            (header.definition_levels_byte_length + header.repetition_levels_byte_length).max(0)
+                as usize,
+            header.is_compressed.unwrap_or(true),
+        ),
+        None => (0, true),
+    };
+
+    if compression == Compression::Uncompressed || !is_compressed {
+        return Ok(payload.to_vec());
+    }
+    if prefix_len > payload.len() || prefix_len > page.uncompressed_size() as usize {
+        return Err(eyre!("page level lengths exceed the page size"));
+    }
+
+    let mut output = vec![0; page.uncompressed_size() as usize];
+    output[..prefix_len].copy_from_slice(&payload[..prefix_len]);
+    parquet2::compression::decompress(
+        compression,
+        &payload[prefix_len..],
+        &mut output[prefix_len..],
+    )?;
+
+    Ok(output)
+}
+
+/// Read and decompress the payload of a page of `path`, through the [page_cache] so that
+/// pages viewed again are not decompressed again.
+pub fn read_decompressed_page<R: Read + Seek>(
+    path: &Path,
+    reader: &mut R,
+    page: &PageInfo,
+    compression: Compression,
+) -> color_eyre::Result<Bytes> {
+    // Pages of a file that cannot be stat'ed are decompressed without caching them.
+    let identity = FileIdentity::of(path).ok();
+    if let Some(decompressed) =
+        identity.and_then(|identity| page_cache::find_decompressed(path, identity, page.offset))
+    {
+        return Ok(decompressed);
+    }
+    let payload = read_page_payload(reader, page)?;
+    let decompressed = Bytes::from(decompress_page(page, &payload, compression)?);
+    let Some(identity) = identity else {
+        return Ok(decompressed);
+    };
+    page_cache::insert(
+        path,
+        identity,
+        RangeKind::Decompressed,
+        page.offset,
+        decompressed.clone(),
+    );
+    Ok(decompressed)
+}
+
+/// Byte lengths of the repetition and definition levels at the start of a decompressed data
+/// page, given the maximum levels of the column.
+pub fn level_lengths(
+    page: &PageInfo,
+    decompressed: &[u8],
+    max_rep_level: i16,
+    max_def_level: i16,
+) -> (u64, u64) {
+    if let Some(header) = &page.header.data_page_header_v2 {
+        return (
+            header.repetition_levels_byte_length.max(0) as u64,
+            header.definition_levels_byte_length.max(0) as u64,
+        );
+    }
+
+    let Some(header) = &page.header.data_page_header else {
+        return (0, 0);
+    };
+    let num_values = header.num_values.max(0) as u64;
+
+    let mut position = 0;
+    let mut level_length = |max_level: i16, encoding: Encoding| -> u64 {
+        if max_level == 0 {
+            return 0;
+        }
+
+        let length = if encoding == Encoding::BIT_PACKED {
+            let bit_width = 16 - (max_level as u16).leading_zeros() as u64;
+            (num_values * bit_width).div_ceil(8)
+        } else {
+            // RLE levels in v1 pages are prefixed by their length as a 4-byte little endian int.
+            decompressed
+                .get(position..position + 4)
+                .map(|bytes| 4 + u32::from_le_bytes(bytes.try_into().unwrap()) as u64)
+                .unwrap_or_default()
+        };
+        position += length as usize;
+        length
+    };
+
+    let rep = level_length(max_rep_level, header.repetition_level_encoding);
+    let def = level_length(max_def_level, header.definition_level_encoding);
+
+    (rep, def)
+}
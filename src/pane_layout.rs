@@ -0,0 +1,111 @@
+use std::{fmt, str::FromStr};
+
+use color_eyre::eyre::eyre;
+
+use crate::ActivePane;
+
+/// Percent of the screen width a pane keeps at least when resized.
+const MIN_PANE_WIDTH: u16 = 10;
+/// Percent of the screen width a pane border moves by per key press.
+const WIDTH_STEP: i16 = 5;
+/// Bounds and step of the log pane height, in terminal rows.
+const MIN_LOG_HEIGHT: u16 = 3;
+const MAX_LOG_HEIGHT: u16 = 40;
+const HEIGHT_STEP: i16 = 2;
+
+/// Sizes of the main view's panes, resized with Ctrl and the arrow keys and kept in the
+/// session state across launches.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PaneLayout {
+    /// Width of the row group browser, in percent of the screen.
+    pub row_groups: u16,
+    /// Width of the column browser, in percent of the screen. The detail panel takes the
+    /// rest.
+    pub columns: u16,
+    /// Height of the log pane toggled with `~`, including its borders, in terminal rows.
+    pub log_height: u16,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            row_groups: 33,
+            columns: 33,
+            log_height: 12,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// Width of the detail panel, in percent of the screen.
+    pub fn detail(&self) -> u16 {
+        100 - self.row_groups - self.columns
+    }
+
+    /// Move the right border of the focused pane by `steps` steps, to the right for positive
+    /// steps. The column detail pane moves its left border, shared with the column browser.
+    pub fn resize(&mut self, pane: ActivePane, steps: i16) {
+        let delta = steps * WIDTH_STEP;
+        match pane {
+            ActivePane::RowGroupBrowser => {
+                let row_groups = self.row_groups as i16 + delta;
+                let columns = self.columns as i16 - delta;
+                if row_groups >= MIN_PANE_WIDTH as i16 && columns >= MIN_PANE_WIDTH as i16 {
+                    self.row_groups = row_groups as u16;
+                    self.columns = columns as u16;
+                }
+            }
+            ActivePane::ColumnBrowser | ActivePane::ColumnDetail => {
+                let columns = self.columns as i16 + delta;
+                let detail = self.detail() as i16 - delta;
+                if columns >= MIN_PANE_WIDTH as i16 && detail >= MIN_PANE_WIDTH as i16 {
+                    self.columns = columns as u16;
+                }
+            }
+        }
+    }
+
+    /// Grow the log pane by `steps` steps, or shrink it for negative steps.
+    pub fn resize_log(&mut self, steps: i16) {
+        self.log_height = (self.log_height as i16 + steps * HEIGHT_STEP)
+            .clamp(MIN_LOG_HEIGHT as i16, MAX_LOG_HEIGHT as i16) as u16;
+    }
+}
+
+/// Written as `ROW_GROUPS,COLUMNS,LOG_HEIGHT`, e.g. `33,33,12`.
+impl fmt::Display for PaneLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{}",
+            self.row_groups, self.columns, self.log_height
+        )
+    }
+}
+
+impl FromStr for PaneLayout {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sizes: Vec<u16> = s
+            .split(',')
+            .map(|size| size.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| eyre!("invalid pane layout {:?}", s))?;
+        let [row_groups, columns, log_height] = sizes[..] else {
+            return Err(eyre!("invalid pane layout {:?}, expected 3 sizes", s));
+        };
+        if row_groups < MIN_PANE_WIDTH
+            || columns < MIN_PANE_WIDTH
+            || row_groups + columns > 100 - MIN_PANE_WIDTH
+            || !(MIN_LOG_HEIGHT..=MAX_LOG_HEIGHT).contains(&log_height)
+        {
+            return Err(eyre!("pane layout {:?} out of bounds", s));
+        }
+        Ok(Self {
+            row_groups,
+            columns,
+            log_height,
+        })
+    }
+}
@@ -1,13 +1,64 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use color_eyre::eyre::{eyre, Context};
 use parquet::{
+    column::reader::ColumnReader,
     data_type::{ByteArray, FixedLenByteArray},
-    file::reader::{ChunkReader, FileReader, SerializedFileReader},
+    file::{
+        footer::parse_metadata, properties::ReaderProperties, reader::RowGroupReader,
+        serialized_reader::SerializedRowGroupReader,
+    },
+    format::SortingColumn,
 };
 use parquet2::{
+    metadata::FileMetaData,
     schema::types::PhysicalType,
     statistics::{BinaryStatistics, BooleanStatistics, FixedLenStatistics, PrimitiveStatistics},
     types::NativeType,
 };
 
+use crate::{lazy_footer::LazyFooter, source::FileSource};
+
+/// Open a file and decode its footer metadata, going through the footer cache unless it was
+/// disabled with [crate::cache::disable].
+pub fn read_file_metadata<P: AsRef<Path>>(path: P) -> color_eyre::Result<FileMetaData> {
+    let path = path.as_ref();
+    let started = Instant::now();
+    let metadata = crate::cache::read_metadata_cached(path)
+        .wrap_err_with(|| format!("failed to read Parquet metadata of {}", path.display()))?;
+    tracing::debug!(
+        "read metadata of {} in {:?}",
+        path.display(),
+        started.elapsed()
+    );
+    Ok(metadata)
+}
+
+/// Read the footer metadata of a file for the TUI, decoding the row groups of large footers
+/// as they are shown. See [LazyFooter].
+pub fn read_file_footer<P: AsRef<Path>>(
+    path: P,
+) -> color_eyre::Result<(FileMetaData, Option<LazyFooter>)> {
+    let path = path.as_ref();
+    let started = Instant::now();
+    let footer = crate::cache::read_footer_cached(path)
+        .wrap_err_with(|| format!("failed to read Parquet metadata of {}", path.display()))?;
+    tracing::debug!(
+        "read metadata of {} in {:?}",
+        path.display(),
+        started.elapsed()
+    );
+    Ok(footer)
+}
+
 pub trait PhysicalTypeExt {
     fn human_readable(&self) -> &'static str;
 }
@@ -115,16 +166,63 @@ impl From<&FixedLenStatistics> for HumanFriendlyStats {
     }
 }
 
+/// Typed, comparable value of a column, as found in statistics or decoded from pages.
+///
+/// Values of the same column always share a variant, so they can be ordered with
+/// [PartialOrd]. INT96 values are not represented.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum StatValue {
+    Boolean(bool),
+    Int(i64),
+    Float(f64),
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Display for StatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatValue::Boolean(b) => write!(f, "{}", b),
+            StatValue::Int(i) => write!(f, "{}", i),
+            StatValue::Float(x) => write!(f, "{:?}", x),
+            StatValue::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(s) => write!(f, "{}", s),
+                Err(_) => write!(f, "{:?}", bytes),
+            },
+        }
+    }
+}
+
 /// Extension trait that turns a parquet2 ColumnChunkMetadata into a list of viewable elements
 /// This is meant to make it very easy to extract out relevant information from a column chunk.
 pub trait ColumnChunkMetaDataExt {
     fn stats(self) -> HumanFriendlyStats;
+
+    /// Typed min and max statistics of the chunk, if both are present.
+    fn min_max(self) -> Option<(StatValue, StatValue)>;
+}
+
+fn primitive_min_max<T: NativeType>(
+    stats: &dyn std::any::Any,
+    to_value: impl Fn(T) -> StatValue,
+) -> Option<(StatValue, StatValue)> {
+    let stats = stats.downcast_ref::<PrimitiveStatistics<T>>()?;
+    Some((to_value(stats.min_value?), to_value(stats.max_value?)))
 }
 
 impl ColumnChunkMetaDataExt for &parquet2::metadata::ColumnChunkMetaData {
     fn stats(self) -> HumanFriendlyStats {
         let stats: HumanFriendlyStats = match self.physical_type() {
-            parquet2::schema::types::PhysicalType::Boolean => HumanFriendlyStats::default(),
+            parquet2::schema::types::PhysicalType::Boolean => self
+                .statistics()
+                .map(|stats| {
+                    stats
+                        .unwrap()
+                        .as_any()
+                        .downcast_ref::<BooleanStatistics>()
+                        .cloned()
+                })
+                .and_then(|stats| stats.map(|s| HumanFriendlyStats::from(&s)))
+                .unwrap_or_default(),
             parquet2::schema::types::PhysicalType::Int32 => self
                 .statistics()
                 .map(|stats| {
@@ -194,45 +292,376 @@ impl ColumnChunkMetaDataExt for &parquet2::metadata::ColumnChunkMetaData {
                 .unwrap_or_default(),
         };
 
+        // Logical types with a registered renderer override the physical representation.
+        let mut stats = stats;
+        let primitive_type = &self.descriptor().descriptor.primitive_type;
+        if let Some(renderer) = crate::renderers::registry().renderer_for(primitive_type) {
+            if let Some((min, max)) = self.min_max() {
+                stats.min = renderer.render(primitive_type, &min).or(stats.min);
+                stats.max = renderer.render(primitive_type, &max).or(stats.max);
+            }
+        }
+
         stats
     }
+
+    fn min_max(self) -> Option<(StatValue, StatValue)> {
+        let stats = self.statistics()?.ok()?;
+        let stats = stats.as_any();
+
+        match self.physical_type() {
+            parquet2::schema::types::PhysicalType::Boolean => {
+                let stats = stats.downcast_ref::<BooleanStatistics>()?;
+                Some((
+                    StatValue::Boolean(stats.min_value?),
+                    StatValue::Boolean(stats.max_value?),
+                ))
+            }
+            parquet2::schema::types::PhysicalType::Int32 => {
+                primitive_min_max(stats, |v: i32| StatValue::Int(i64::from(v)))
+            }
+            parquet2::schema::types::PhysicalType::Int64 => {
+                primitive_min_max(stats, StatValue::Int)
+            }
+            parquet2::schema::types::PhysicalType::Int96 => None,
+            parquet2::schema::types::PhysicalType::Float => {
+                primitive_min_max(stats, |v: f32| StatValue::Float(f64::from(v)))
+            }
+            parquet2::schema::types::PhysicalType::Double => {
+                primitive_min_max(stats, StatValue::Float)
+            }
+            parquet2::schema::types::PhysicalType::ByteArray => {
+                let stats = stats.downcast_ref::<BinaryStatistics>()?;
+                Some((
+                    StatValue::Bytes(stats.min_value.clone()?),
+                    StatValue::Bytes(stats.max_value.clone()?),
+                ))
+            }
+            parquet2::schema::types::PhysicalType::FixedLenByteArray(_) => {
+                let stats = stats.downcast_ref::<FixedLenStatistics>()?;
+                Some((
+                    StatValue::Bytes(stats.min_value.clone()?),
+                    StatValue::Bytes(stats.max_value.clone()?),
+                ))
+            }
+        }
+    }
+}
+
+/// Path of the file holding the data of a column chunk. Chunks may point to another file
+/// through their `file_path`, which is resolved relative to the directory of the main file.
+pub fn chunk_data_path(path: &Path, file_path: Option<&str>) -> PathBuf {
+    match file_path {
+        Some(file_path) => path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(file_path),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Row group fields of the footer that the decoded metadata drops.
+#[derive(Debug, Clone, Default)]
+pub struct RowGroupFooter {
+    pub file_offset: Option<i64>,
+    pub ordinal: Option<i16>,
+    /// Sort order declared by the writer; empty when none was declared.
+    pub sorting_columns: Vec<SortingColumn>,
+}
+
+/// Read the footer fields of each row group that [read_file_metadata] does not keep.
+pub fn read_row_group_footers(path: &Path) -> color_eyre::Result<Vec<RowGroupFooter>> {
+    let metadata = parse_metadata(&FileSource::open(path)?)?;
+    Ok(metadata
+        .row_groups()
+        .iter()
+        .map(|row_group| RowGroupFooter {
+            file_offset: row_group.file_offset(),
+            ordinal: row_group.ordinal(),
+            sorting_columns: row_group.sorting_columns().cloned().unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Open a reader over the values of a column chunk, reading its pages from the external file
+/// the chunk references, if any.
+pub fn column_reader(
+    path: &Path,
+    row_group: usize,
+    column_chunk: usize,
+) -> color_eyre::Result<ColumnReader> {
+    Ok(open_column(path, row_group, column_chunk)?.0)
+}
+
+/// Open a reader over the values of a column chunk, along with the chunk's metadata.
+fn open_column(
+    path: &Path,
+    row_group: usize,
+    column_chunk: usize,
+) -> color_eyre::Result<(ColumnReader, parquet::file::metadata::ColumnChunkMetaData)> {
+    let metadata = parse_metadata(&FileSource::open(path)?)?;
+    if row_group >= metadata.num_row_groups() {
+        return Err(eyre!("row group {} out of range", row_group));
+    }
+    let row_group_metadata = metadata.row_group(row_group);
+    if column_chunk >= row_group_metadata.num_columns() {
+        return Err(eyre!("column {} out of range", column_chunk));
+    }
+    let chunk_metadata = row_group_metadata.column(column_chunk).clone();
+
+    let data_path = chunk_data_path(path, chunk_metadata.file_path());
+    let data = FileSource::open(&data_path)
+        .wrap_err_with(|| format!("failed to open {}", data_path.display()))?;
+    let row_group_reader = SerializedRowGroupReader::new(
+        Arc::new(data),
+        row_group_metadata,
+        None,
+        Arc::new(ReaderProperties::builder().build()),
+    )?;
+
+    Ok((
+        row_group_reader.get_column_reader(column_chunk)?,
+        chunk_metadata,
+    ))
+}
+
+/// Default memory budget of streaming scans, see [set_max_memory].
+pub const DEFAULT_MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+static MAX_MEMORY: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_MEMORY);
+
+/// Set the memory budget of streaming scans for the rest of the process, e.g. for
+/// `--max-memory`. Scans size their batches, and cap any per-value state such as distinct
+/// value sets, to stay within it.
+pub fn set_max_memory(bytes: usize) {
+    MAX_MEMORY.store(bytes.max(1), Ordering::Relaxed);
+}
+
+pub fn max_memory() -> usize {
+    MAX_MEMORY.load(Ordering::Relaxed)
+}
+
+/// Parse a byte size such as `512M`, `2G`, `64KiB` or `1048576`. Units are powers of 1024.
+pub fn parse_byte_size(s: &str) -> Result<usize, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: usize = number
+        .parse()
+        .map_err(|_| format!("invalid size {:?}, expected e.g. 512M or 2G", s))?;
+    let multiplier: usize = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("unknown size unit {:?}, expected K, M or G", unit)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size {:?} is too large", s))
+}
+
+/// Largest number of records decoded in one batch, however small the values are.
+const MAX_BATCH_RECORDS: usize = 64 * 1024;
+
+/// Streaming decoder over the non-null values of a column chunk.
+///
+/// Pages are read and decoded as batches are requested. Each batch is sized so that, with
+/// the decoded native values it is converted from, it stays within a quarter of
+/// [max_memory], so a column chunk is never materialized in memory as a whole.
+/// INT96 columns yield no values.
+pub struct ValueStream {
+    reader: ColumnReader,
+    batch_records: usize,
+    remaining: usize,
+    def_levels: Vec<i16>,
+    rep_levels: Vec<i16>,
+}
+
+impl ValueStream {
+    /// Open a stream over up to `limit` values of a column chunk.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        row_group: usize,
+        column_chunk: usize,
+        limit: usize,
+    ) -> color_eyre::Result<Self> {
+        let (reader, chunk) = open_column(path.as_ref(), row_group, column_chunk)?;
+
+        // Values are decoded once natively and once as StatValues, next to their levels.
+        let value_size = (chunk.uncompressed_size().max(0) as usize)
+            .checked_div(chunk.num_values().max(0) as usize)
+            .unwrap_or_default();
+        let record_size =
+            2 * (std::mem::size_of::<StatValue>() + value_size) + 2 * std::mem::size_of::<i16>();
+        let batch_records = (max_memory() / 4 / record_size).clamp(1, MAX_BATCH_RECORDS);
+
+        Ok(Self {
+            reader,
+            batch_records,
+            remaining: limit,
+            def_levels: Vec::new(),
+            rep_levels: Vec::new(),
+        })
+    }
+
+    /// Decode the next batch of values. Batches may be empty when they only hold nulls.
+    /// Returns `None` at the end of the chunk or once `limit` values were returned.
+    pub fn next_batch(&mut self) -> color_eyre::Result<Option<Vec<StatValue>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let batch_records = self.batch_records;
+        let def_levels = &mut self.def_levels;
+        let rep_levels = &mut self.rep_levels;
+        def_levels.clear();
+        rep_levels.clear();
+
+        macro_rules! read_batch {
+            ($reader:expr, $native:ty, $to_value:expr) => {{
+                let mut batch: Vec<$native> = Vec::new();
+                let (records, _, _) = $reader.read_records(
+                    batch_records,
+                    Some(def_levels),
+                    Some(rep_levels),
+                    &mut batch,
+                )?;
+                if records == 0 {
+                    return Ok(None);
+                }
+                batch.into_iter().map($to_value).collect::<Vec<StatValue>>()
+            }};
+        }
+
+        let mut values = match self.reader {
+            ColumnReader::BoolColumnReader(ref mut reader) => {
+                read_batch!(reader, bool, StatValue::Boolean)
+            }
+            ColumnReader::Int32ColumnReader(ref mut reader) => {
+                read_batch!(reader, i32, |v| StatValue::Int(i64::from(v)))
+            }
+            ColumnReader::Int64ColumnReader(ref mut reader) => {
+                read_batch!(reader, i64, StatValue::Int)
+            }
+            ColumnReader::Int96ColumnReader(_) => return Ok(None),
+            ColumnReader::FloatColumnReader(ref mut reader) => {
+                read_batch!(reader, f32, |v| StatValue::Float(f64::from(v)))
+            }
+            ColumnReader::DoubleColumnReader(ref mut reader) => {
+                read_batch!(reader, f64, StatValue::Float)
+            }
+            ColumnReader::ByteArrayColumnReader(ref mut reader) => {
+                read_batch!(reader, ByteArray, |v: ByteArray| StatValue::Bytes(
+                    v.data().to_vec()
+                ))
+            }
+            ColumnReader::FixedLenByteArrayColumnReader(ref mut reader) => {
+                read_batch!(reader, FixedLenByteArray, |v: FixedLenByteArray| {
+                    StatValue::Bytes(v.data().to_vec())
+                })
+            }
+        };
+
+        values.truncate(self.remaining);
+        self.remaining -= values.len();
+        Ok(Some(values))
+    }
+
+    /// Definition levels of the last batch, one per value including nulls. Empty for required
+    /// columns, whose batches have no nulls.
+    pub fn def_levels(&self) -> &[i16] {
+        &self.def_levels
+    }
+}
+
+impl Iterator for ValueStream {
+    type Item = color_eyre::Result<Vec<StatValue>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_batch().transpose()
+    }
+}
+
+/// Read up to `limit` non-null values of a column chunk, in file order.
+///
+/// The values are collected in memory, so this is meant for samples. Scans over whole chunks
+/// should go through [ValueStream].
+pub fn read_values<P: AsRef<Path>>(
+    path: P,
+    row_group: usize,
+    column_chunk: usize,
+    limit: usize,
+) -> color_eyre::Result<Vec<StatValue>> {
+    let mut values = Vec::new();
+    for batch in ValueStream::open(path, row_group, column_chunk, limit)? {
+        values.extend(batch?);
+    }
+    Ok(values)
+}
+
+/// Display strings of values, rendered by the renderer registered for the column's logical
+/// type if any.
+pub fn render_values(
+    primitive_type: &parquet2::schema::types::PrimitiveType,
+    values: &[StatValue],
+) -> Vec<String> {
+    let renderer = crate::renderers::registry().renderer_for(primitive_type);
+    values
+        .iter()
+        .map(|value| {
+            renderer
+                .and_then(|renderer| renderer.render(primitive_type, value))
+                .unwrap_or_else(|| value.to_string())
+        })
+        .collect()
+}
+
+/// Read a sample of `size` values from a column chunk whose logical type has a registered
+/// renderer, rendered by it. Returns `None` for other columns.
+pub fn sample_rendered<P: AsRef<Path>>(
+    path: P,
+    primitive_type: &parquet2::schema::types::PrimitiveType,
+    row_group: usize,
+    column_chunk: usize,
+    size: usize,
+) -> Option<color_eyre::Result<String>> {
+    crate::renderers::registry().renderer_for(primitive_type)?;
+    Some(
+        read_values(path, row_group, column_chunk, size)
+            .map(|values| format!("sample: {:?}", render_values(primitive_type, &values))),
+    )
 }
 
 /// Read a sample of values from the column chunk. Or just read the individual values from it.
 ///
 /// Returns a Stringified sample of column value that we can display.
-pub fn sample_column<R: ChunkReader + 'static>(
-    chunk_reader: R,
+pub fn sample_column<P: AsRef<Path>>(
+    path: P,
     row_group: usize,
     column_chunk: usize,
-) -> String {
+    size: usize,
+) -> color_eyre::Result<String> {
     // How can you read a batch of records from a single ColumnChunk?
     // Find a way to deploy using the native type here.
-    let file_reader = SerializedFileReader::new(chunk_reader).unwrap();
-    let mut column_reader = file_reader
-        .get_row_group(row_group)
-        .unwrap()
-        .get_column_reader(column_chunk)
-        .unwrap();
+    let mut column_reader = column_reader(path.as_ref(), row_group, column_chunk)?;
 
     let mut def_levels: Vec<i16> = Vec::new();
     let mut rep_levels: Vec<i16> = Vec::new();
 
-    match column_reader {
-        parquet::column::reader::ColumnReader::BoolColumnReader(ref mut bool_reader) => {
+    let sample = match column_reader {
+        ColumnReader::BoolColumnReader(ref mut bool_reader) => {
             let mut values_vec: Vec<bool> = Vec::new();
-            let (complete, non_null, _) = bool_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = bool_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|b| b.to_string())
                 .collect::<Vec<_>>();
 
@@ -241,20 +670,18 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::Int32ColumnReader(ref mut int32_reader) => {
+        ColumnReader::Int32ColumnReader(ref mut int32_reader) => {
             let mut values_vec: Vec<i32> = Vec::new();
-            let (complete, non_null, _) = int32_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = int32_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -263,20 +690,18 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::Int64ColumnReader(ref mut int64_reader) => {
+        ColumnReader::Int64ColumnReader(ref mut int64_reader) => {
             let mut values_vec: Vec<i64> = Vec::new();
-            let (complete, non_null, _) = int64_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = int64_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -285,23 +710,19 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::Int96ColumnReader(_) => {
-            "INT96 sampling not supported".to_string()
-        }
-        parquet::column::reader::ColumnReader::FloatColumnReader(ref mut float32_reader) => {
+        ColumnReader::Int96ColumnReader(_) => "INT96 sampling not supported".to_string(),
+        ColumnReader::FloatColumnReader(ref mut float32_reader) => {
             let mut values_vec: Vec<f32> = Vec::new();
-            let (complete, non_null, _) = float32_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = float32_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -310,20 +731,18 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::DoubleColumnReader(ref mut float64_reader) => {
+        ColumnReader::DoubleColumnReader(ref mut float64_reader) => {
             let mut values_vec: Vec<f64> = Vec::new();
-            let (complete, non_null, _) = float64_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = float64_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -332,20 +751,18 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::ByteArrayColumnReader(ref mut bytearray_reader) => {
+        ColumnReader::ByteArrayColumnReader(ref mut bytearray_reader) => {
             let mut values_vec: Vec<ByteArray> = Vec::new();
-            let (complete, non_null, _) = bytearray_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = bytearray_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -354,22 +771,18 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-        parquet::column::reader::ColumnReader::FixedLenByteArrayColumnReader(
-            ref mut fixedlen_reader,
-        ) => {
+        ColumnReader::FixedLenByteArrayColumnReader(ref mut fixedlen_reader) => {
             let mut values_vec: Vec<FixedLenByteArray> = Vec::new();
-            let (complete, non_null, _) = fixedlen_reader
-                .read_records(
-                    10,
-                    Some(&mut def_levels),
-                    Some(&mut rep_levels),
-                    &mut values_vec,
-                )
-                .unwrap();
+            let (complete, non_null, _) = fixedlen_reader.read_records(
+                size,
+                Some(&mut def_levels),
+                Some(&mut rep_levels),
+                &mut values_vec,
+            )?;
 
             let sample = values_vec
                 .iter()
-                .take(10)
+                .take(size)
                 .map(|i| i.to_string())
                 .collect::<Vec<_>>();
 
@@ -378,5 +791,7 @@ pub fn sample_column<R: ChunkReader + 'static>(
                 complete, non_null, &sample
             )
         }
-    }
+    };
+
+    Ok(sample)
 }
@@ -0,0 +1,741 @@
+use std::{cmp::Ordering, fmt, path::Path, str::FromStr, time::Instant};
+
+use color_eyre::eyre::{eyre, Report, WrapErr};
+use parquet::{
+    basic::{ConvertedType, LogicalType, TimeUnit as ParquetTimeUnit},
+    file::reader::{FileReader, SerializedFileReader},
+    record::{Field, Row},
+    schema::types::{Type, TypePtr},
+};
+use parquet2::{metadata::FileMetaData, schema::types::TimeUnit};
+use ratatui::widgets::{ListState, TableState};
+
+use crate::{
+    analysis::pushdown::ScanEstimate,
+    decimal::{self, decimal_type, Decimal},
+    queries::HistoryCursor,
+    records::{self, RenderedColumn},
+    source::FileSource,
+    timezone,
+};
+
+/// Maximum number of rows kept for display in the data preview. Matching rows past this limit
+/// are still counted.
+pub const PREVIEW_ROWS: usize = 500;
+
+/// Rows of a row group, as shown in the data preview table.
+#[derive(Debug, Default, Clone)]
+pub struct Preview {
+    /// Names of the displayed top-level fields.
+    pub columns: Vec<String>,
+    /// Display values of the first matching rows, [PREVIEW_ROWS] in the TUI.
+    pub rows: Vec<Vec<String>>,
+    /// Number of rows matching the filter in the whole row group.
+    pub matched: usize,
+    /// Number of rows scanned, i.e. the row count of the row group.
+    pub scanned: usize,
+}
+
+/// Comparison operator of a [Predicate].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    IsNull,
+    IsNotNull,
+}
+
+/// A simple row filter: `col = value`, `col > value`, `col is null`, ...
+///
+/// Values are compared numerically when both sides parse as numbers, and as strings otherwise.
+/// DECIMAL values compare exactly as decimals. Nested fields are addressed with dotted paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub column: String,
+    pub op: Op,
+    pub value: String,
+}
+
+impl FromStr for Predicate {
+    type Err = Report;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let lower = input.to_ascii_lowercase();
+        for (suffix, op) in [(" is not null", Op::IsNotNull), (" is null", Op::IsNull)] {
+            if lower.ends_with(suffix) {
+                let column = input[..input.len() - suffix.len()].trim();
+                if column.is_empty() {
+                    return Err(eyre!("missing column before {:?}", suffix.trim()));
+                }
+                return Ok(Predicate {
+                    column: column.to_string(),
+                    op,
+                    value: String::new(),
+                });
+            }
+        }
+
+        // The operator is the first one in the input, so that values may contain operator
+        // characters. Two-character operators must be tried before their one-character prefixes.
+        let operator = input.char_indices().find_map(|(start, _)| {
+            [
+                ("!=", Op::Ne),
+                ("<=", Op::Le),
+                (">=", Op::Ge),
+                ("=", Op::Eq),
+                ("<", Op::Lt),
+                (">", Op::Gt),
+            ]
+            .into_iter()
+            .find(|(symbol, _)| input[start..].starts_with(symbol))
+            .map(|(symbol, op)| (start, symbol, op))
+        });
+        if let Some((start, symbol, op)) = operator {
+            let column = input[..start].trim();
+            let value = input[start + symbol.len()..].trim();
+            if column.is_empty() {
+                return Err(eyre!("missing column before `{}` in {:?}", symbol, input));
+            }
+            if value.is_empty() {
+                return Err(eyre!("missing value after `{}` in {:?}", symbol, input));
+            }
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .or_else(|| {
+                    value
+                        .strip_prefix('\'')
+                        .and_then(|value| value.strip_suffix('\''))
+                })
+                .unwrap_or(value);
+            return Ok(Predicate {
+                column: column.to_string(),
+                op,
+                value: value.to_string(),
+            });
+        }
+
+        Err(eyre!(
+            "expected `col = value`, `col > value` or `col is null`, got {:?}",
+            input
+        ))
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self.op {
+            Op::Eq => "=",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::IsNull => return write!(f, "{} is null", self.column),
+            Op::IsNotNull => return write!(f, "{} is not null", self.column),
+        };
+        // Quote the values that would not parse back as they are.
+        if self.value.is_empty() || self.value.trim() != self.value {
+            write!(f, "{} {} \"{}\"", self.column, symbol, self.value)
+        } else {
+            write!(f, "{} {} {}", self.column, symbol, self.value)
+        }
+    }
+}
+
+impl Predicate {
+    /// The literal parsed as a decimal and the scale of the filtered column when it is a
+    /// DECIMAL, e.g. to compare it with statistics. Fails when the literal of a comparison is
+    /// not a decimal number that fits the column's precision.
+    pub fn decimal_literal(
+        &self,
+        metadata: &FileMetaData,
+    ) -> color_eyre::Result<Option<(Decimal, usize)>> {
+        if matches!(self.op, Op::IsNull | Op::IsNotNull) {
+            return Ok(None);
+        }
+        let Some(column) = metadata
+            .schema()
+            .columns()
+            .iter()
+            .find(|column| column.path_in_schema.join(".") == self.column)
+        else {
+            return Ok(None);
+        };
+        let Some((precision, scale)) = decimal_type(&column.descriptor.primitive_type) else {
+            return Ok(None);
+        };
+        let literal = decimal::parse_literal(&self.value, precision, scale)
+            .wrap_err_with(|| format!("{} is DECIMAL({}, {})", self.column, precision, scale))?;
+        Ok(Some((literal, scale)))
+    }
+
+    /// Evaluate the predicate against a row. Comparisons with a null or missing field are
+    /// false.
+    pub fn matches(&self, row: &Row) -> bool {
+        let field = find_field(row, &self.column);
+        let is_null = matches!(field, None | Some(Field::Null));
+        match self.op {
+            Op::IsNull => return is_null,
+            Op::IsNotNull => return !is_null,
+            _ if is_null => return false,
+            _ => {}
+        }
+
+        let field = field.unwrap();
+        let value = display_field(field);
+        let ordering = match (field, value.parse::<f64>(), self.value.parse::<f64>()) {
+            (Field::Decimal(_), _, _) => value
+                .parse::<Decimal>()
+                .ok()
+                .zip(self.value.parse::<Decimal>().ok())
+                .map(|(left, right)| left.cmp(&right)),
+            (_, Ok(left), Ok(right)) => left.partial_cmp(&right),
+            _ => Some(value.as_str().cmp(self.value.as_str())),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self.op {
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Ne => ordering != Ordering::Equal,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+            Op::IsNull | Op::IsNotNull => unreachable!(),
+        }
+    }
+}
+
+/// Look up a field by dotted path, descending into structs.
+fn find_field<'a>(row: &'a Row, path: &str) -> Option<&'a Field> {
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let (_, field) = row.get_column_iter().find(|(column, _)| *column == name)?;
+    match (rest, field) {
+        (None, field) => Some(field),
+        (Some(rest), Field::Group(group)) => find_field(group, rest),
+        _ => None,
+    }
+}
+
+/// Display value of a field. Strings are shown without the quotes of [Field]'s Display.
+pub fn display_field(field: &Field) -> String {
+    match field {
+        Field::Str(s) => s.clone(),
+        field => field.to_string(),
+    }
+}
+
+/// Unit of a top-level TIMESTAMP field and whether it is adjusted to UTC, which the values
+/// of [Field] do not carry.
+fn timestamp_unit(field: &Type) -> Option<(TimeUnit, bool)> {
+    let info = field.get_basic_info();
+    match (info.logical_type(), info.converted_type()) {
+        (
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c,
+                unit,
+            }),
+            _,
+        ) => Some((
+            match unit {
+                ParquetTimeUnit::MILLIS(_) => TimeUnit::Milliseconds,
+                ParquetTimeUnit::MICROS(_) => TimeUnit::Microseconds,
+                ParquetTimeUnit::NANOS(_) => TimeUnit::Nanoseconds,
+            },
+            is_adjusted_to_u_t_c,
+        )),
+        (_, ConvertedType::TIMESTAMP_MILLIS) => Some((TimeUnit::Milliseconds, true)),
+        (_, ConvertedType::TIMESTAMP_MICROS) => Some((TimeUnit::Microseconds, true)),
+        _ => None,
+    }
+}
+
+/// Display value of a top-level field, with timestamps in the time zone chosen with `--tz`.
+fn display_column_field(field: &Field, timestamp: Option<(TimeUnit, bool)>) -> String {
+    let stored = match field {
+        Field::TimestampMillis(value) | Field::TimestampMicros(value) | Field::Long(value) => {
+            *value
+        }
+        field => return display_field(field),
+    };
+    match timestamp {
+        Some((unit, adjusted_to_utc)) => timezone::render_timestamp(stored, unit, adjusted_to_utc)
+            .unwrap_or_else(|| stored.to_string()),
+        None => display_field(field),
+    }
+}
+
+/// Where the values of a displayed column of the preview come from.
+enum ColumnSource {
+    /// The rows of the record API, with the unit of TIMESTAMP fields.
+    Record(Option<(TimeUnit, bool)>),
+    /// A column the record API cannot convert, read on its own.
+    Rendered(Box<RenderedColumn>),
+    /// A nested field holding values the record API cannot convert.
+    Unconvertible,
+}
+
+/// Stream the rows of a row group, keeping the first `max_rows` that match the filter and
+/// counting all of them.
+///
+/// Only the top-level fields in `projection`, plus the field the filter reads, are decoded.
+/// `None` shows every field. Fields the record API cannot convert are left out of the rows it
+/// reads, and shown through [RenderedColumn] instead.
+pub fn load_preview<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    filter: Option<&Predicate>,
+    projection: Option<&[String]>,
+    max_rows: usize,
+) -> color_eyre::Result<Preview> {
+    let started = Instant::now();
+    let path = path.as_ref();
+    let reader = SerializedFileReader::new(FileSource::open(path)?)?;
+    let schema = reader.metadata().file_metadata().schema();
+    let displayed: Vec<&TypePtr> = schema
+        .get_fields()
+        .iter()
+        .filter(|field| {
+            projection.is_none_or(|projection| projection.iter().any(|name| name == field.name()))
+        })
+        .collect();
+
+    let filter_field = filter.map(|filter| filter.column.split('.').next().unwrap_or_default());
+    if let Some(field) = schema
+        .get_fields()
+        .iter()
+        .find(|field| filter_field == Some(field.name()))
+    {
+        records::check_convertible(field)?;
+    }
+
+    let mut sources = Vec::with_capacity(displayed.len());
+    for field in &displayed {
+        sources.push(match records::unconvertible_leaf(field) {
+            None => ColumnSource::Record(timestamp_unit(field)),
+            Some(_) => match RenderedColumn::open(path, metadata, row_group, field.name())? {
+                Some(column) => ColumnSource::Rendered(Box::new(column)),
+                None => ColumnSource::Unconvertible,
+            },
+        });
+    }
+
+    let read_fields: Vec<TypePtr> = schema
+        .get_fields()
+        .iter()
+        .filter(|field| {
+            records::unconvertible_leaf(field).is_none()
+                && (displayed.iter().any(|shown| shown.name() == field.name())
+                    || filter_field == Some(field.name()))
+        })
+        .cloned()
+        .collect();
+    let row_group_reader = reader.get_row_group(row_group)?;
+    // Without fields for the record API, e.g. when every displayed field is read on its own,
+    // rows are only counted.
+    let rows: Box<dyn Iterator<Item = parquet::errors::Result<Option<Row>>>> =
+        if read_fields.is_empty() {
+            let num_rows = row_group_reader.metadata().num_rows() as usize;
+            Box::new((0..num_rows).map(|_| Ok(None)))
+        } else {
+            let read_schema = Type::group_type_builder(schema.name())
+                .with_fields(read_fields)
+                .build()?;
+            Box::new(
+                row_group_reader
+                    .get_row_iter(Some(read_schema))?
+                    .map(|row| row.map(Some)),
+            )
+        };
+
+    let mut preview = Preview {
+        columns: displayed
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect(),
+        ..Default::default()
+    };
+    for row in rows {
+        let row = row?;
+        preview.scanned += 1;
+        // Columns read on their own advance with every row, until the preview is full.
+        let full = preview.rows.len() >= max_rows;
+        let mut rendered = Vec::new();
+        if !full {
+            for source in &mut sources {
+                if let ColumnSource::Rendered(column) = source {
+                    rendered.push(column.next_row()?);
+                }
+            }
+        }
+        let matches = match (filter, &row) {
+            (None, _) => true,
+            (Some(filter), Some(row)) => filter.matches(row),
+            // The filtered field does not exist.
+            (Some(filter), None) => filter.op == Op::IsNull,
+        };
+        if !matches {
+            continue;
+        }
+
+        preview.matched += 1;
+        if !full {
+            let mut rendered = rendered.into_iter();
+            let values = preview
+                .columns
+                .iter()
+                .zip(&sources)
+                .map(|(name, source)| match source {
+                    ColumnSource::Record(timestamp) => row
+                        .iter()
+                        .flat_map(|row| row.get_column_iter())
+                        .find(|(column, _)| *column == name)
+                        .map(|(_, field)| display_column_field(field, *timestamp))
+                        .unwrap_or_default(),
+                    ColumnSource::Rendered(_) => rendered
+                        .next()
+                        .flatten()
+                        .unwrap_or_else(|| Field::Null.to_string()),
+                    ColumnSource::Unconvertible => records::UNCONVERTIBLE.to_string(),
+                })
+                .collect();
+            preview.rows.push(values);
+        }
+    }
+    tracing::debug!(
+        "scanned {} rows of row group {} in {:?}, {} matched",
+        preview.scanned,
+        row_group,
+        started.elapsed(),
+        preview.matched
+    );
+
+    Ok(preview)
+}
+
+/// Count the rows of a file matching a filter, returning the matched and scanned row counts.
+///
+/// Rows are streamed one row group at a time, decoding only the top-level field the filter
+/// reads.
+pub fn count_matches<P: AsRef<Path>>(
+    path: P,
+    filter: &Predicate,
+) -> color_eyre::Result<(usize, usize)> {
+    let started = Instant::now();
+    let reader = SerializedFileReader::new(FileSource::open(path)?)?;
+    let schema = reader.metadata().file_metadata().schema();
+    let filter_field = filter.column.split('.').next().unwrap_or_default();
+    let fields: Vec<_> = schema
+        .get_fields()
+        .iter()
+        .filter(|field| field.name() == filter_field)
+        .cloned()
+        .collect();
+    match fields.first() {
+        Some(field) => records::check_convertible(field)?,
+        None => return Err(eyre!("no column named {:?}", filter_field)),
+    }
+    let read_schema = Type::group_type_builder(schema.name())
+        .with_fields(fields)
+        .build()?;
+
+    let (mut matched, mut scanned) = (0, 0);
+    for row_group in 0..reader.num_row_groups() {
+        let row_group = reader.get_row_group(row_group)?;
+        for row in row_group.get_row_iter(Some(read_schema.clone()))? {
+            scanned += 1;
+            if filter.matches(&row?) {
+                matched += 1;
+            }
+        }
+    }
+    tracing::debug!(
+        "counted {} of {} rows matching {} in {:?}",
+        matched,
+        scanned,
+        filter,
+        started.elapsed()
+    );
+
+    Ok((matched, scanned))
+}
+
+/// Whether all characters of `needle` appear in order in `haystack`, ignoring case.
+pub fn fuzzy_match(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars().flat_map(char::to_lowercase);
+    needle
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|c| haystack.any(|h| h == c))
+}
+
+/// Checkbox list choosing the columns of the data preview, narrowed by a fuzzy search.
+#[derive(Debug, Default, Clone)]
+pub struct ColumnChooser {
+    pub search: String,
+    /// Every top-level field and whether it is shown.
+    pub columns: Vec<(String, bool)>,
+    /// Selection among the [ColumnChooser::visible] columns.
+    pub state: ListState,
+}
+
+impl ColumnChooser {
+    pub fn new(all_columns: &[String], projection: Option<&[String]>) -> Self {
+        Self {
+            search: String::new(),
+            columns: all_columns
+                .iter()
+                .map(|name| {
+                    let shown = projection.is_none_or(|projection| projection.contains(name));
+                    (name.clone(), shown)
+                })
+                .collect(),
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    /// Indexes of the columns matching the search.
+    pub fn visible(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| fuzzy_match(&self.search, name))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.search.push(c);
+        self.state.select(Some(0));
+    }
+
+    pub fn pop(&mut self) {
+        self.search.pop();
+        self.state.select(Some(0));
+    }
+
+    /// Move the selection by `delta` rows, wrapping around.
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len() as isize;
+        if len == 0 {
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        self.state
+            .select(Some((current + delta).rem_euclid(len) as usize));
+    }
+
+    /// Check or uncheck the selected column.
+    pub fn toggle(&mut self) {
+        let visible = self.visible();
+        if let Some(&index) = self.state.selected().and_then(|i| visible.get(i)) {
+            self.columns[index].1 = !self.columns[index].1;
+        }
+    }
+
+    /// Checked columns, or `None` when all of them are checked.
+    pub fn projection(&self) -> Option<Vec<String>> {
+        if self.columns.iter().all(|(_, shown)| *shown) {
+            return None;
+        }
+        Some(
+            self.columns
+                .iter()
+                .filter(|(_, shown)| *shown)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        )
+    }
+}
+
+/// State of the data preview overlay.
+#[derive(Debug, Default, Clone)]
+pub struct PreviewState {
+    pub row_group: usize,
+    pub preview: Preview,
+    pub filter: Option<Predicate>,
+    /// Bytes the filter would scan in the whole file after statistics pruning.
+    pub pushdown: Option<ScanEstimate>,
+    /// Displayed top-level fields, `None` for all of them.
+    pub projection: Option<Vec<String>>,
+    /// Filter being typed, while the filter prompt is open.
+    pub filter_input: Option<String>,
+    /// Position of the filter prompt in the filter history, browsed with Up and Down.
+    pub history_cursor: HistoryCursor,
+    /// Name being typed to save the current filter under, while the save prompt is open.
+    pub save_input: Option<String>,
+    /// List of the saved filters, while it is open.
+    pub saved_picker: Option<ListState>,
+    /// Column chooser, while it is open.
+    pub chooser: Option<ColumnChooser>,
+    /// Indexes of the columns pinned to the left, in pin order.
+    pub pinned: Vec<usize>,
+    /// Number of unpinned columns scrolled out to the left.
+    pub column_offset: usize,
+    pub state: TableState,
+}
+
+impl PreviewState {
+    /// Whether a prompt is open, which receives every key.
+    pub fn has_prompt(&self) -> bool {
+        self.filter_input.is_some()
+            || self.chooser.is_some()
+            || self.save_input.is_some()
+            || self.saved_picker.is_some()
+    }
+
+    /// Columns that scroll horizontally, i.e. the unpinned ones.
+    pub fn scrolling_columns(&self) -> Vec<usize> {
+        (0..self.preview.columns.len())
+            .filter(|i| !self.pinned.contains(i))
+            .collect()
+    }
+
+    /// Displayed columns, in display order: the pinned columns, then the unpinned columns past
+    /// the scroll offset.
+    pub fn display_columns(&self) -> Vec<usize> {
+        let mut columns = self.pinned.clone();
+        columns.extend(
+            self.scrolling_columns()
+                .into_iter()
+                .skip(self.column_offset),
+        );
+        columns
+    }
+
+    /// Scroll the unpinned columns by `delta`, keeping at least one of them visible.
+    pub fn scroll_columns(&mut self, delta: isize) {
+        let last = self.scrolling_columns().len().saturating_sub(1);
+        self.column_offset = self.column_offset.saturating_add_signed(delta).min(last);
+    }
+
+    /// The leftmost unpinned column that is displayed, which `p` pins and `y` copies from.
+    pub fn current_column(&self) -> Option<usize> {
+        self.scrolling_columns().get(self.column_offset).copied()
+    }
+
+    /// Value of the current column in the selected row.
+    pub fn current_cell(&self) -> Option<&str> {
+        let row = self.preview.rows.get(self.state.selected()?)?;
+        Some(row[self.current_column()?].as_str())
+    }
+
+    /// Pin the leftmost unpinned column that is displayed.
+    pub fn pin_next_column(&mut self) {
+        if let Some(column) = self.current_column() {
+            self.pinned.push(column);
+            self.scroll_columns(0);
+        }
+    }
+
+    pub fn unpin_columns(&mut self) {
+        self.pinned.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicate(column: &str, op: Op, value: &str) -> Predicate {
+        Predicate {
+            column: column.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_comparisons() {
+        assert_eq!(
+            "id > 3".parse::<Predicate>().unwrap(),
+            predicate("id", Op::Gt, "3")
+        );
+        assert_eq!(
+            "id>=3".parse::<Predicate>().unwrap(),
+            predicate("id", Op::Ge, "3")
+        );
+        assert_eq!(
+            "a.b <= -1".parse::<Predicate>().unwrap(),
+            predicate("a.b", Op::Le, "-1")
+        );
+        assert_eq!(
+            "id != 3".parse::<Predicate>().unwrap(),
+            predicate("id", Op::Ne, "3")
+        );
+        assert_eq!(
+            "id < 3".parse::<Predicate>().unwrap(),
+            predicate("id", Op::Lt, "3")
+        );
+    }
+
+    #[test]
+    fn strips_quotes_from_values() {
+        assert_eq!(
+            "name = 'a b'".parse::<Predicate>().unwrap(),
+            predicate("name", Op::Eq, "a b")
+        );
+        assert_eq!(
+            "name = \"\"".parse::<Predicate>().unwrap(),
+            predicate("name", Op::Eq, "")
+        );
+    }
+
+    #[test]
+    fn splits_on_the_first_operator() {
+        assert_eq!(
+            "x > a=b".parse::<Predicate>().unwrap(),
+            predicate("x", Op::Gt, "a=b")
+        );
+        assert_eq!(
+            "x = a>=b".parse::<Predicate>().unwrap(),
+            predicate("x", Op::Eq, "a>=b")
+        );
+    }
+
+    #[test]
+    fn parses_null_checks() {
+        assert_eq!(
+            "id IS NULL".parse::<Predicate>().unwrap(),
+            predicate("id", Op::IsNull, "")
+        );
+        assert_eq!(
+            "id is not null".parse::<Predicate>().unwrap(),
+            predicate("id", Op::IsNotNull, "")
+        );
+    }
+
+    #[test]
+    fn rejects_missing_columns_and_values() {
+        for input in ["", "id", "id >", "> 3", "= ", "is null", " is not null"] {
+            assert!(input.parse::<Predicate>().is_err(), "{:?} parsed", input);
+        }
+    }
+
+    #[test]
+    fn displays_predicates_that_parse_back() {
+        for predicate in [
+            predicate("id", Op::Ge, "3"),
+            predicate("name", Op::Eq, ""),
+            predicate("name", Op::Ne, " padded "),
+            predicate("id", Op::IsNotNull, ""),
+        ] {
+            assert_eq!(
+                predicate.to_string().parse::<Predicate>().unwrap(),
+                predicate
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, fmt, fs, io};
+
+use crate::session::state_dir;
+
+/// Name of the query file inside the state directory.
+const QUERIES_FILE_NAME: &str = "queries";
+
+/// Filters kept in the history, the oldest being dropped first.
+const MAX_HISTORY: usize = 200;
+
+/// Filters applied in the data preview, and filters saved under a name, kept across launches.
+///
+/// The query file is written next to the session state, with the history oldest first and the
+/// saved filters in a `[saved]` section:
+///
+/// ```text
+/// history=score > 10
+/// history=name is null
+///
+/// [saved]
+/// recent=created > 2024-01-01
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct QueryStore {
+    pub history: Vec<String>,
+    pub saved: BTreeMap<String, String>,
+}
+
+impl QueryStore {
+    /// Load the query file. A missing or unreadable file yields no queries.
+    pub fn load() -> Self {
+        let Some(path) = state_dir().map(|dir| dir.join(QUERIES_FILE_NAME)) else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(err) => {
+                if err.kind() != io::ErrorKind::NotFound {
+                    tracing::warn!("ignoring queries {}: {}", path.display(), err);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the query file, creating the state directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(dir) = state_dir() else {
+            return Ok(());
+        };
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(QUERIES_FILE_NAME), self.to_string())
+    }
+
+    /// Parse the query file contents, skipping any lines that are not understood.
+    pub fn parse(contents: &str) -> Self {
+        let mut queries = Self::default();
+        let mut in_saved = false;
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                in_saved = line == "[saved]";
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match (in_saved, key.trim()) {
+                (false, "history") => queries.record(value.trim()),
+                (true, name) if !name.is_empty() => {
+                    queries
+                        .saved
+                        .insert(name.to_string(), value.trim().to_string());
+                }
+                _ => {}
+            }
+        }
+        queries
+    }
+
+    /// Add an applied filter to the history, moving it to the end if it is already there.
+    pub fn record(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.history.retain(|previous| previous != query);
+        self.history.push(query.to_string());
+        if self.history.len() > MAX_HISTORY {
+            self.history.drain(..self.history.len() - MAX_HISTORY);
+        }
+    }
+
+    /// Save a filter under `name`, replacing the filter saved under it before. Names cannot
+    /// hold `=`, which ends them in the query file.
+    pub fn save_as(&mut self, name: &str, query: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || name.contains('=') || name.starts_with(['[', '#']) {
+            return false;
+        }
+        self.saved
+            .insert(name.to_string(), query.trim().to_string());
+        true
+    }
+}
+
+impl fmt::Display for QueryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for query in &self.history {
+            writeln!(f, "history={}", query)?;
+        }
+        if !self.saved.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "[saved]")?;
+            for (name, query) in &self.saved {
+                writeln!(f, "{}={}", name, query)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Position of a prompt in the history while it is browsed with Up and Down, with the input
+/// typed before browsing, restored when stepping past the newest entry.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HistoryCursor {
+    position: Option<usize>,
+    draft: String,
+}
+
+impl HistoryCursor {
+    /// Step `delta` entries through `history`, negative steps going back in time, and replace
+    /// `input` with the entry reached.
+    pub fn step(&mut self, history: &[String], input: &mut String, delta: isize) {
+        if history.is_empty() {
+            return;
+        }
+        let position = match self.position {
+            None if delta >= 0 => return,
+            None => {
+                self.draft = input.clone();
+                history.len() as isize + delta
+            }
+            Some(position) => position as isize + delta,
+        };
+        if position >= history.len() as isize {
+            self.position = None;
+            *input = std::mem::take(&mut self.draft);
+            return;
+        }
+        let position = position.max(0) as usize;
+        self.position = Some(position);
+        *input = history[position].clone();
+    }
+}
@@ -0,0 +1,260 @@
+use std::{collections::VecDeque, path::Path};
+
+use color_eyre::eyre::eyre;
+use parquet::{
+    basic::{ConvertedType, Type as PhysicalType},
+    file::reader::{FileReader, SerializedFileReader},
+    record::Field,
+    schema::types::Type,
+};
+use parquet2::{metadata::FileMetaData, schema::types::PrimitiveType};
+
+use crate::{
+    parquet::{render_values, ValueStream},
+    source::FileSource,
+};
+
+/// Shown in place of nested fields holding values the record API cannot convert.
+pub const UNCONVERTIBLE: &str = "(not decodable)";
+
+/// A line of the key/value tree of a record, as shown in the record detail view.
+///
+/// Structs, lists and maps produce a line summarizing them, followed by their children one
+/// level deeper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordField {
+    pub depth: usize,
+    pub name: String,
+    pub value: String,
+}
+
+/// Total number of rows in the file.
+pub fn num_rows(metadata: &FileMetaData) -> u64 {
+    metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.num_rows() as u64)
+        .sum()
+}
+
+/// Map a global row index to its row group and its index within the row group, using the
+/// cumulative row counts of the row groups.
+pub fn locate_row(metadata: &FileMetaData, row: u64) -> Option<(usize, u64)> {
+    let mut first_row = 0;
+    for (index, row_group) in metadata.row_groups.iter().enumerate() {
+        let rows = row_group.num_rows() as u64;
+        if row < first_row + rows {
+            return Some((index, row - first_row));
+        }
+        first_row += rows;
+    }
+    None
+}
+
+/// The first leaf under `field` whose values the record API of the parquet crate cannot
+/// convert, e.g. INTERVAL or TIME_MILLIS: it panics on them rather than failing.
+pub fn unconvertible_leaf(field: &Type) -> Option<&Type> {
+    match field {
+        Type::GroupType { fields, .. } => fields.iter().find_map(|field| unconvertible_leaf(field)),
+        Type::PrimitiveType {
+            basic_info,
+            physical_type,
+            ..
+        } => {
+            use ConvertedType::*;
+            let convertible = match physical_type {
+                PhysicalType::INT32 => matches!(
+                    basic_info.converted_type(),
+                    NONE | INT_8 | INT_16 | INT_32 | UINT_8 | UINT_16 | UINT_32 | DATE | DECIMAL
+                ),
+                PhysicalType::INT64 => matches!(
+                    basic_info.converted_type(),
+                    NONE | INT_64 | UINT_64 | TIMESTAMP_MILLIS | TIMESTAMP_MICROS | DECIMAL
+                ),
+                PhysicalType::BYTE_ARRAY => matches!(
+                    basic_info.converted_type(),
+                    NONE | UTF8 | ENUM | JSON | BSON | DECIMAL
+                ),
+                PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+                    matches!(basic_info.converted_type(), NONE | DECIMAL)
+                }
+                _ => true,
+            };
+            (!convertible).then_some(field)
+        }
+    }
+}
+
+/// Fail for a field the record API cannot convert, naming the type it cannot convert.
+pub fn check_convertible(field: &Type) -> color_eyre::Result<()> {
+    match unconvertible_leaf(field) {
+        Some(leaf) => Err(eyre!(
+            "{} holds {} values, which cannot be read row by row",
+            field.name(),
+            leaf.get_basic_info().converted_type()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Display values of a top-level primitive column, one per row, read through [ValueStream]
+/// and rendered by the renderer registered for its type, for columns the record API cannot
+/// convert. Nulls are `None`.
+pub struct RenderedColumn {
+    stream: ValueStream,
+    primitive_type: PrimitiveType,
+    max_def_level: i16,
+    rows: VecDeque<Option<String>>,
+}
+
+impl RenderedColumn {
+    /// Open the column of the top-level field `name`, or `None` if the field is nested.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        metadata: &FileMetaData,
+        row_group: usize,
+        name: &str,
+    ) -> color_eyre::Result<Option<Self>> {
+        let Some((column, descriptor)) = metadata
+            .schema()
+            .columns()
+            .iter()
+            .enumerate()
+            .find(|(_, column)| column.path_in_schema == [name])
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            stream: ValueStream::open(path, row_group, column, usize::MAX)?,
+            primitive_type: descriptor.descriptor.primitive_type.clone(),
+            max_def_level: descriptor.descriptor.max_def_level,
+            rows: VecDeque::new(),
+        }))
+    }
+
+    /// Display value of the next row.
+    pub fn next_row(&mut self) -> color_eyre::Result<Option<String>> {
+        while self.rows.is_empty() {
+            let values = self
+                .stream
+                .next_batch()?
+                .ok_or_else(|| eyre!("column ended before its row group"))?;
+            let mut values = render_values(&self.primitive_type, &values).into_iter();
+            if self.max_def_level == 0 {
+                self.rows.extend(values.map(Some));
+            } else {
+                for &level in self.stream.def_levels() {
+                    self.rows.push_back(
+                        (level == self.max_def_level)
+                            .then(|| values.next())
+                            .flatten(),
+                    );
+                }
+            }
+        }
+        Ok(self.rows.pop_front().flatten())
+    }
+}
+
+/// Read the record at a global row index.
+///
+/// Top-level fields the record API cannot convert are read through [RenderedColumn] when
+/// they are primitive, and shown as [UNCONVERTIBLE] when they are nested.
+pub fn read_record<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row: u64,
+) -> color_eyre::Result<Vec<RecordField>> {
+    let (row_group, offset) = locate_row(metadata, row).ok_or_else(|| {
+        eyre!(
+            "row {} out of range, the file has {} rows",
+            row,
+            num_rows(metadata)
+        )
+    })?;
+
+    let reader = SerializedFileReader::new(FileSource::open(path.as_ref())?)?;
+    let schema = reader.metadata().file_metadata().schema();
+    let convertible: Vec<_> = schema
+        .get_fields()
+        .iter()
+        .filter(|field| unconvertible_leaf(field).is_none())
+        .cloned()
+        .collect();
+    let record = if convertible.is_empty() {
+        None
+    } else {
+        let read_schema = Type::group_type_builder(schema.name())
+            .with_fields(convertible)
+            .build()?;
+        let record = reader
+            .get_row_group(row_group)?
+            .get_row_iter(Some(read_schema))?
+            .nth(offset as usize)
+            .ok_or_else(|| eyre!("row group {} ended before row {}", row_group, row))??;
+        Some(record)
+    };
+
+    let mut fields = Vec::new();
+    for field in schema.get_fields() {
+        let name = field.name().to_string();
+        if unconvertible_leaf(field).is_none() {
+            let value = record.as_ref().and_then(|record| {
+                record
+                    .get_column_iter()
+                    .find(|(column, _)| **column == name)
+                    .map(|(_, value)| value)
+            });
+            if let Some(value) = value {
+                flatten(&mut fields, 0, name, value);
+            }
+            continue;
+        }
+        let value = match RenderedColumn::open(path.as_ref(), metadata, row_group, &name)? {
+            Some(mut column) => {
+                for _ in 0..offset {
+                    column.next_row()?;
+                }
+                column.next_row()?.unwrap_or_else(|| "null".to_string())
+            }
+            None => UNCONVERTIBLE.to_string(),
+        };
+        fields.push(RecordField {
+            depth: 0,
+            name,
+            value,
+        });
+    }
+
+    Ok(fields)
+}
+
+/// Append the tree lines of a field and its children.
+fn flatten(fields: &mut Vec<RecordField>, depth: usize, name: String, field: &Field) {
+    let value = match field {
+        Field::Group(row) => format!("{{{} fields}}", row.len()),
+        Field::ListInternal(list) => format!("[{} items]", list.len()),
+        Field::MapInternal(map) => format!("{{{} entries}}", map.len()),
+        field => field.to_string(),
+    };
+    fields.push(RecordField { depth, name, value });
+
+    match field {
+        Field::Group(row) => {
+            for (name, child) in row.get_column_iter() {
+                flatten(fields, depth + 1, name.clone(), child);
+            }
+        }
+        Field::ListInternal(list) => {
+            for (index, child) in list.elements().iter().enumerate() {
+                flatten(fields, depth + 1, format!("[{}]", index), child);
+            }
+        }
+        Field::MapInternal(map) => {
+            for (key, child) in map.entries() {
+                flatten(fields, depth + 1, key.to_string(), child);
+            }
+        }
+        _ => {}
+    }
+}
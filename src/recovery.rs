@@ -0,0 +1,605 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Cursor, Write},
+    path::Path,
+};
+
+use color_eyre::eyre::{eyre, Context};
+use parquet::file::reader::{ChunkReader, Length};
+use parquet_format_safe::{
+    thrift::protocol::TCompactOutputProtocol, ColumnChunk, ColumnMetaData, CompressionCodec,
+    Encoding, PageType, RowGroup, Type,
+};
+
+use crate::{
+    pages::{read_page_header, PageInfo},
+    source::FileSource,
+};
+
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// Upper bound on the uncompressed size of a page, to reject bytes that merely happen to
+/// decode as a page header.
+const MAX_PLAUSIBLE_PAGE_SIZE: i32 = 1 << 30;
+
+/// A run of contiguous pages that likely forms one column chunk.
+#[derive(Debug, Clone)]
+pub struct RecoveredChunk {
+    pub pages: Vec<PageInfo>,
+    /// File offset of the first page header.
+    pub start: u64,
+    /// End of the last page, clamped to the end of the file.
+    pub end: u64,
+    /// Values (including nulls) in the data pages.
+    pub num_values: i64,
+    /// Rows in the data pages, only known when all of them are v2 pages.
+    pub num_rows: Option<i64>,
+    /// Whether the last page extends past the end of the file.
+    pub truncated: bool,
+}
+
+impl RecoveredChunk {
+    fn new(page: &PageInfo) -> Self {
+        RecoveredChunk {
+            pages: Vec::new(),
+            start: page.offset,
+            end: page.offset,
+            num_values: 0,
+            num_rows: Some(0),
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, page: PageInfo, file_size: u64) {
+        let end = page.data_offset() + page.compressed_size();
+        self.truncated = end > file_size;
+        self.end = end.min(file_size);
+        if page.is_data() {
+            self.num_values += page.num_values();
+            self.num_rows = match (&page.header.data_page_header_v2, self.num_rows) {
+                (Some(header), Some(rows)) => Some(rows + i64::from(header.num_rows)),
+                _ => None,
+            };
+        }
+        self.pages.push(page);
+    }
+
+    pub fn has_dictionary(&self) -> bool {
+        self.pages.iter().any(PageInfo::is_dictionary)
+    }
+
+    fn data_page_kind(&self) -> Option<PageType> {
+        self.pages
+            .iter()
+            .find(|page| page.is_data())
+            .map(PageInfo::page_type)
+    }
+
+    /// Whether `page` cannot belong to this chunk: it does not directly follow it, it is a
+    /// dictionary page (which always starts a chunk), it switches between v1 and v2 data
+    /// pages, or it is dictionary-encoded while the chunk has no dictionary.
+    fn ends_before(&self, page: &PageInfo) -> bool {
+        if self.truncated || page.offset != self.end || page.is_dictionary() {
+            return true;
+        }
+        if !page.is_data() {
+            return false;
+        }
+        if self
+            .data_page_kind()
+            .is_some_and(|kind| kind != page.page_type())
+        {
+            return true;
+        }
+        let dictionary_encoded = matches!(
+            page.encoding(),
+            Some(Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY)
+        );
+        dictionary_encoded && !self.has_dictionary()
+    }
+}
+
+/// Structure recovered from a file by scanning for page headers.
+#[derive(Debug, Clone)]
+pub struct Recovery {
+    pub file_size: u64,
+    pub has_leading_magic: bool,
+    pub chunks: Vec<RecoveredChunk>,
+    /// Bytes between the first and the last page that no page header claimed.
+    pub skipped_bytes: u64,
+}
+
+impl Recovery {
+    pub fn num_pages(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.pages.len()).sum()
+    }
+}
+
+/// Whether a decoded page header is plausible enough to be trusted. Headers found while
+/// resynchronizing after garbage are held to stricter checks than those directly following a
+/// valid page.
+fn is_plausible(page: &PageInfo, resync: bool) -> bool {
+    let header = &page.header;
+    if header.compressed_page_size < 0
+        || header.uncompressed_page_size < 0
+        || header.uncompressed_page_size > MAX_PLAUSIBLE_PAGE_SIZE
+    {
+        return false;
+    }
+    if let Some(encoding) = page.encoding() {
+        if parquet2::encoding::Encoding::try_from(encoding).is_err() {
+            return false;
+        }
+    }
+
+    let headers = [
+        header.data_page_header.is_some(),
+        header.index_page_header.is_some(),
+        header.dictionary_page_header.is_some(),
+        header.data_page_header_v2.is_some(),
+    ];
+    let expected = match header.type_ {
+        PageType::DATA_PAGE => 0,
+        PageType::INDEX_PAGE => 1,
+        PageType::DICTIONARY_PAGE => 2,
+        PageType::DATA_PAGE_V2 => 3,
+        _ => return false,
+    };
+    if headers
+        .iter()
+        .enumerate()
+        .any(|(i, present)| *present != (i == expected))
+    {
+        return false;
+    }
+
+    if let Some(v2) = &header.data_page_header_v2 {
+        let levels = i64::from(v2.definition_levels_byte_length)
+            + i64::from(v2.repetition_levels_byte_length);
+        if v2.definition_levels_byte_length < 0
+            || v2.repetition_levels_byte_length < 0
+            || levels > i64::from(header.compressed_page_size)
+            || v2.num_nulls < 0
+            || v2.num_nulls > v2.num_values
+            || v2.num_rows < 0
+            || v2.num_rows > v2.num_values
+        {
+            return false;
+        }
+    }
+
+    let num_values = page.num_values();
+    num_values >= 0 && !(resync && (num_values == 0 || header.uncompressed_page_size == 0))
+}
+
+/// Scan a file whose footer is unreadable for page headers, and group the pages found into
+/// likely column chunks.
+///
+/// The scan starts after the leading magic bytes and follows page sizes from header to header.
+/// When bytes do not decode as a plausible page header, it moves forward one byte at a time
+/// until it finds one again. Chunk boundaries are a heuristic: without the footer, two
+/// contiguous chunks of plain-encoded pages are indistinguishable from one.
+pub fn scan_pages(path: &Path) -> color_eyre::Result<Recovery> {
+    let source =
+        FileSource::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let file_size = source.len();
+    let bytes = source.get_bytes(0, file_size as usize)?;
+
+    let has_leading_magic = bytes.starts_with(PARQUET_MAGIC);
+    let mut offset = if has_leading_magic { 4 } else { 0 };
+    let mut resync = false;
+    let mut skipped_bytes = 0;
+    let mut skipped_pending = 0;
+    let mut chunks: Vec<RecoveredChunk> = Vec::new();
+
+    while offset < file_size {
+        let page = read_page_header(Cursor::new(&bytes[offset as usize..]))
+            .ok()
+            .map(|(header, header_size)| PageInfo {
+                offset,
+                header_size,
+                header,
+            })
+            .filter(|page| is_plausible(page, resync));
+
+        let Some(page) = page else {
+            offset += 1;
+            skipped_pending += 1;
+            resync = true;
+            continue;
+        };
+
+        if !chunks.is_empty() {
+            skipped_bytes += skipped_pending;
+        }
+        skipped_pending = 0;
+        resync = false;
+        offset = page.data_offset() + page.compressed_size();
+
+        match chunks.last_mut() {
+            Some(chunk) if !chunk.ends_before(&page) => chunk.push(page, file_size),
+            _ => {
+                let mut chunk = RecoveredChunk::new(&page);
+                chunk.push(page, file_size);
+                chunks.push(chunk);
+            }
+        }
+    }
+
+    Ok(Recovery {
+        file_size,
+        has_leading_magic,
+        chunks,
+        skipped_bytes,
+    })
+}
+
+/// Leaf column of the reference file recovered chunks are matched against.
+#[derive(Debug, Clone)]
+pub struct ReferenceColumn {
+    pub path: String,
+    pub path_in_schema: Vec<String>,
+    pub physical_type: Type,
+    pub codec: CompressionCodec,
+    /// Whether the column is not repeated, so that its value count is its row count.
+    pub flat: bool,
+}
+
+/// Schema and column codecs of a healthy file written like the damaged one, e.g. another file
+/// of the same dataset.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub columns: Vec<ReferenceColumn>,
+    metadata: parquet_format_safe::FileMetaData,
+}
+
+impl Reference {
+    pub fn read(path: &Path) -> color_eyre::Result<Self> {
+        let mut reader = FileSource::open(path)
+            .and_then(|source| source.reader())
+            .wrap_err_with(|| format!("failed to open {}", path.display()))?;
+        let footer = crate::cache::read_footer_bytes(&mut reader)
+            .wrap_err_with(|| format!("failed to read the footer of {}", path.display()))?;
+        let mut protocol =
+            parquet_format_safe::thrift::protocol::TCompactInputProtocol::new(&*footer, usize::MAX);
+        let metadata = parquet_format_safe::FileMetaData::read_from_in_protocol(&mut protocol)?;
+        let decoded = parquet2::metadata::FileMetaData::try_from_thrift(metadata.clone())?;
+
+        let codecs: Vec<CompressionCodec> = metadata
+            .row_groups
+            .first()
+            .ok_or_else(|| eyre!("{} has no row groups to take codecs from", path.display()))?
+            .columns
+            .iter()
+            .map(|column| {
+                column
+                    .meta_data
+                    .as_ref()
+                    .map(|meta| meta.codec)
+                    .unwrap_or(CompressionCodec::UNCOMPRESSED)
+            })
+            .collect();
+
+        let columns = decoded
+            .schema()
+            .columns()
+            .iter()
+            .zip(codecs)
+            .map(|(column, codec)| {
+                let descriptor = &column.descriptor;
+                ReferenceColumn {
+                    path: column.path_in_schema.join("."),
+                    path_in_schema: column.path_in_schema.clone(),
+                    physical_type: physical_type_to_thrift(descriptor.primitive_type.physical_type),
+                    codec,
+                    flat: descriptor.max_rep_level == 0,
+                }
+            })
+            .collect();
+
+        Ok(Reference { columns, metadata })
+    }
+}
+
+fn physical_type_to_thrift(physical_type: parquet2::schema::types::PhysicalType) -> Type {
+    use parquet2::schema::types::PhysicalType;
+    match physical_type {
+        PhysicalType::Boolean => Type::BOOLEAN,
+        PhysicalType::Int32 => Type::INT32,
+        PhysicalType::Int64 => Type::INT64,
+        PhysicalType::Int96 => Type::INT96,
+        PhysicalType::Float => Type::FLOAT,
+        PhysicalType::Double => Type::DOUBLE,
+        PhysicalType::ByteArray => Type::BYTE_ARRAY,
+        PhysicalType::FixedLenByteArray(_) => Type::FIXED_LEN_BYTE_ARRAY,
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RowGroupStatus {
+    Complete,
+    /// The file ends before all the row group's chunks were written.
+    Truncated,
+    /// The chunks do not agree on the number of rows, so the chunk boundaries are likely wrong.
+    Inconsistent,
+}
+
+impl RowGroupStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RowGroupStatus::Complete => "complete",
+            RowGroupStatus::Truncated => "truncated",
+            RowGroupStatus::Inconsistent => "inconsistent",
+        }
+    }
+}
+
+/// Recovered chunks assigned to the columns of a row group, in schema order.
+#[derive(Debug, Clone)]
+pub struct RecoveredRowGroup {
+    /// Indices into [Recovery::chunks].
+    pub chunks: Vec<usize>,
+    pub num_rows: Option<i64>,
+    pub status: RowGroupStatus,
+}
+
+/// Assign recovered chunks to the columns of the reference schema, one row group after the
+/// other, and check that each row group's chunks agree on its row count.
+pub fn group_row_groups(recovery: &Recovery, reference: &Reference) -> Vec<RecoveredRowGroup> {
+    let num_columns = reference.columns.len().max(1);
+
+    (0..recovery.chunks.len())
+        .collect::<Vec<_>>()
+        .chunks(num_columns)
+        .map(|chunks| {
+            let members: Vec<&RecoveredChunk> =
+                chunks.iter().map(|&i| &recovery.chunks[i]).collect();
+            let mut rows = members
+                .iter()
+                .zip(&reference.columns)
+                .filter_map(|(chunk, column)| {
+                    if column.flat {
+                        Some(chunk.num_values)
+                    } else {
+                        chunk.num_rows
+                    }
+                });
+            let num_rows = rows.next();
+            let consistent = num_rows.is_some() && rows.all(|rows| Some(rows) == num_rows);
+
+            let status = if chunks.len() < num_columns || members.iter().any(|c| c.truncated) {
+                RowGroupStatus::Truncated
+            } else if !consistent {
+                RowGroupStatus::Inconsistent
+            } else {
+                RowGroupStatus::Complete
+            };
+
+            RecoveredRowGroup {
+                chunks: chunks.to_vec(),
+                num_rows,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Encodings used by the pages of a chunk, including those of the levels.
+fn chunk_encodings(chunk: &RecoveredChunk) -> Vec<Encoding> {
+    let mut encodings = Vec::new();
+    for page in &chunk.pages {
+        encodings.extend(page.encoding());
+        if let Some(header) = &page.header.data_page_header {
+            encodings.push(header.definition_level_encoding);
+            encodings.push(header.repetition_level_encoding);
+        }
+        if page.header.data_page_header_v2.is_some() {
+            encodings.push(Encoding::RLE);
+        }
+    }
+    encodings.sort_by_key(|encoding| encoding.0);
+    encodings.dedup();
+    encodings
+}
+
+/// Write the complete row groups into a new Parquet file: the chunks are copied byte for byte
+/// and described by a new footer using the reference schema. Returns the number of rows
+/// written.
+pub fn export_recovered(
+    path: &Path,
+    recovery: &Recovery,
+    reference: &Reference,
+    row_groups: &[RecoveredRowGroup],
+    output: &Path,
+) -> color_eyre::Result<i64> {
+    let source =
+        FileSource::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let mut writer = BufWriter::new(
+        File::create(output).wrap_err_with(|| format!("failed to create {}", output.display()))?,
+    );
+    writer.write_all(PARQUET_MAGIC)?;
+    let mut position = PARQUET_MAGIC.len() as i64;
+
+    let mut thrift_row_groups = Vec::new();
+    for row_group in row_groups
+        .iter()
+        .filter(|row_group| row_group.status == RowGroupStatus::Complete)
+    {
+        let row_group_start = position;
+        let mut columns = Vec::new();
+        let mut total_byte_size = 0;
+
+        for (&index, column) in row_group.chunks.iter().zip(&reference.columns) {
+            let chunk = &recovery.chunks[index];
+            let length = chunk.end - chunk.start;
+            writer.write_all(&source.get_bytes(chunk.start, length as usize)?)?;
+
+            let relocate = |offset: u64| position + (offset - chunk.start) as i64;
+            let uncompressed: i64 = chunk
+                .pages
+                .iter()
+                .map(|page| (page.header_size + page.uncompressed_size()) as i64)
+                .sum();
+            let data_page_offset = chunk
+                .pages
+                .iter()
+                .find(|page| page.is_data())
+                .map_or(position, |page| relocate(page.offset));
+            let dictionary_page_offset = chunk
+                .pages
+                .iter()
+                .find(|page| page.is_dictionary())
+                .map(|page| relocate(page.offset));
+
+            total_byte_size += uncompressed;
+            position += length as i64;
+            columns.push(ColumnChunk {
+                file_path: None,
+                file_offset: position,
+                meta_data: Some(ColumnMetaData {
+                    type_: column.physical_type,
+                    encodings: chunk_encodings(chunk),
+                    path_in_schema: column.path_in_schema.clone(),
+                    codec: column.codec,
+                    num_values: chunk.num_values,
+                    total_uncompressed_size: uncompressed,
+                    total_compressed_size: length as i64,
+                    key_value_metadata: None,
+                    data_page_offset,
+                    index_page_offset: None,
+                    dictionary_page_offset,
+                    statistics: None,
+                    encoding_stats: None,
+                    bloom_filter_offset: None,
+                }),
+                offset_index_offset: None,
+                offset_index_length: None,
+                column_index_offset: None,
+                column_index_length: None,
+                crypto_metadata: None,
+                encrypted_column_metadata: None,
+            });
+        }
+
+        thrift_row_groups.push(RowGroup {
+            columns,
+            total_byte_size,
+            num_rows: row_group.num_rows.unwrap_or_default(),
+            sorting_columns: None,
+            file_offset: Some(row_group_start),
+            total_compressed_size: Some(position - row_group_start),
+            ordinal: Some(thrift_row_groups.len() as i16),
+        });
+    }
+
+    let num_rows = thrift_row_groups.iter().map(|rg| rg.num_rows).sum();
+    let metadata = parquet_format_safe::FileMetaData {
+        num_rows,
+        row_groups: thrift_row_groups,
+        created_by: Some(format!(
+            "parquet-console {} recover",
+            env!("CARGO_PKG_VERSION")
+        )),
+        ..reference.metadata.clone()
+    };
+
+    let mut footer = Vec::new();
+    metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut footer))?;
+    writer.write_all(&footer)?;
+    writer.write_all(&(footer.len() as u32).to_le_bytes())?;
+    writer.write_all(PARQUET_MAGIC)?;
+    writer.flush()?;
+
+    Ok(num_rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        generate::{generate, Codec, GenerateOptions},
+        parquet::read_file_metadata,
+    };
+
+    /// Generate a file of 3 row groups of 100 rows and 2 columns, and copies of it cut off after
+    /// its last column chunk and halfway through it. Returns the paths of the three files.
+    fn truncated_files(dir: &Path) -> [std::path::PathBuf; 3] {
+        let healthy = dir.join("healthy.parquet");
+        let options = GenerateOptions {
+            columns: vec!["id:int64".parse().unwrap(), "name:string".parse().unwrap()],
+            rows: 300,
+            row_group_size: 100,
+            codec: Codec::Uncompressed,
+            encoding: None,
+            null_ratio: 0.0,
+            cardinality: 50,
+            no_stats: false,
+            no_page_index: true,
+            page_rows: None,
+            seed: 7,
+        };
+        generate(&healthy, &options).unwrap();
+
+        let metadata = read_file_metadata(&healthy).unwrap();
+        let (start, length) = metadata.row_groups[2].columns()[1].byte_range();
+        let bytes = fs::read(&healthy).unwrap();
+        let no_footer = dir.join("no_footer.parquet");
+        fs::write(&no_footer, &bytes[..(start + length) as usize]).unwrap();
+        let cut = dir.join("cut.parquet");
+        fs::write(&cut, &bytes[..(start + length / 2) as usize]).unwrap();
+        [healthy, no_footer, cut]
+    }
+
+    #[test]
+    fn recovers_row_groups_of_truncated_files() {
+        let dir =
+            std::env::temp_dir().join(format!("parquet_console_recovery_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let [healthy, no_footer, cut] = truncated_files(&dir);
+        let reference = Reference::read(&healthy).unwrap();
+        assert_eq!(
+            reference
+                .columns
+                .iter()
+                .map(|column| column.path.as_str())
+                .collect::<Vec<_>>(),
+            ["id", "name"]
+        );
+
+        let recovery = scan_pages(&no_footer).unwrap();
+        assert!(recovery.has_leading_magic);
+        // The writer follows every chunk with a copy of its metadata, which ends the chunk
+        // and is skipped.
+        assert_eq!(recovery.chunks.len(), 6);
+        let gaps: u64 = recovery
+            .chunks
+            .windows(2)
+            .map(|pair| pair[1].start - pair[0].end)
+            .sum();
+        assert_eq!(recovery.skipped_bytes, gaps);
+        assert!(recovery.chunks.iter().all(|chunk| chunk.has_dictionary()));
+        let row_groups = group_row_groups(&recovery, &reference);
+        assert_eq!(row_groups.len(), 3);
+        for row_group in &row_groups {
+            assert_eq!(row_group.status, RowGroupStatus::Complete);
+            assert_eq!(row_group.num_rows, Some(100));
+        }
+
+        let recovery = scan_pages(&cut).unwrap();
+        assert_eq!(recovery.chunks.len(), 6);
+        assert!(recovery.chunks[5].truncated);
+        let row_groups = group_row_groups(&recovery, &reference);
+        assert_eq!(row_groups[2].status, RowGroupStatus::Truncated);
+
+        // Only the complete row groups are exported, and the file reads back.
+        let output = dir.join("recovered.parquet");
+        let rows = export_recovered(&cut, &recovery, &reference, &row_groups, &output).unwrap();
+        assert_eq!(rows, 200);
+        let recovered = read_file_metadata(&output).unwrap();
+        assert_eq!(recovered.num_rows, 200);
+        assert_eq!(recovered.row_groups.len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+use color_eyre::eyre::eyre;
+use glob::{MatchOptions, Pattern};
+
+use crate::{preview::Preview, records::RecordField};
+
+/// Column name patterns redacted by `--redact` when no patterns are given: names that commonly
+/// hold personal data.
+pub const DEFAULT_PATTERNS: &[&str] = &[
+    "*email*",
+    "*e_mail*",
+    "*ssn*",
+    "*social_security*",
+    "*name*",
+    "*phone*",
+    "*address*",
+    "*birth*",
+    "*passport*",
+];
+
+/// Shown instead of redacted values. Fixed, so that it does not leak their length.
+pub const MASK: &str = "••••••";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Masks sampled values and statistics of the columns whose name matches a glob pattern, so
+/// that screenshots of the console can be shared. Redacts nothing when it has no patterns.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    patterns: Vec<Pattern>,
+}
+
+impl Redactor {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> color_eyre::Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern.as_ref())
+                    .map_err(|err| eyre!("invalid pattern {:?}: {}", pattern.as_ref(), err))
+            })
+            .collect::<color_eyre::Result<_>>()?;
+        Ok(Redactor { patterns })
+    }
+
+    /// Whether any column may be redacted.
+    pub fn is_enabled(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Whether a column is redacted: a pattern matches its dotted path or one of its fields,
+    /// ignoring case.
+    pub fn matches(&self, column: &str) -> bool {
+        self.patterns.iter().any(|pattern| {
+            pattern.matches_with(column, MATCH_OPTIONS)
+                || column
+                    .split('.')
+                    .any(|field| pattern.matches_with(field, MATCH_OPTIONS))
+        })
+    }
+
+    /// `value`, or the mask if the column is redacted.
+    pub fn redact(&self, column: &str, value: String) -> String {
+        if self.matches(column) {
+            MASK.to_string()
+        } else {
+            value
+        }
+    }
+
+    /// Mask the values of the redacted top-level fields of a data preview.
+    pub fn redact_preview(&self, preview: &mut Preview) {
+        let redacted: Vec<usize> = (0..preview.columns.len())
+            .filter(|&column| self.matches(&preview.columns[column]))
+            .collect();
+        for row in &mut preview.rows {
+            for &column in &redacted {
+                row[column] = MASK.to_string();
+            }
+        }
+    }
+
+    /// Mask the values of the redacted fields of a record, and of every field nested in them.
+    pub fn redact_record(&self, fields: &mut [RecordField]) {
+        let mut redacted_depth = None;
+        for field in fields {
+            if redacted_depth.is_some_and(|depth| field.depth <= depth) {
+                redacted_depth = None;
+            }
+            if redacted_depth.is_none() && self.matches(&field.name) {
+                redacted_depth = Some(field.depth);
+            }
+            if redacted_depth.is_some() && !field.value.is_empty() {
+                field.value = MASK.to_string();
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{fmt::Write as _, sync::OnceLock};
+
+use parquet2::schema::types::{
+    PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType, TimeUnit,
+};
+
+use crate::{
+    decimal::{decimal_type, Decimal},
+    parquet::StatValue,
+    timezone,
+};
+
+/// Renders the values of columns with a given logical type in a human-readable form, for use
+/// in samples and statistics.
+pub trait ValueRenderer: Send + Sync {
+    /// Whether the renderer handles values of the given column type.
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool;
+
+    /// Render a value, or `None` if it is not in the expected form.
+    fn render(&self, primitive_type: &PrimitiveType, value: &StatValue) -> Option<String>;
+}
+
+/// Ordered list of value renderers. The first renderer that applies to a column wins.
+pub struct RendererRegistry {
+    renderers: Vec<Box<dyn ValueRenderer>>,
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(UuidRenderer);
+        registry.register(DecimalRenderer);
+        registry.register(IntervalRenderer);
+        registry.register(TimestampRenderer);
+        registry.register(JsonRenderer);
+        registry.register(BsonRenderer);
+        registry
+    }
+}
+
+impl RendererRegistry {
+    pub fn empty() -> Self {
+        Self {
+            renderers: Vec::new(),
+        }
+    }
+
+    pub fn register<R: ValueRenderer + 'static>(&mut self, renderer: R) {
+        self.renderers.push(Box::new(renderer));
+    }
+
+    /// The renderer for a column type, if any.
+    pub fn renderer_for(&self, primitive_type: &PrimitiveType) -> Option<&dyn ValueRenderer> {
+        self.renderers
+            .iter()
+            .find(|renderer| renderer.applies(primitive_type))
+            .map(|renderer| renderer.as_ref())
+    }
+
+    /// Render a value of a column, or `None` if no renderer handles the column type.
+    pub fn render(&self, primitive_type: &PrimitiveType, value: &StatValue) -> Option<String> {
+        self.renderer_for(primitive_type)?
+            .render(primitive_type, value)
+    }
+}
+
+/// The registry of built-in renderers.
+pub fn registry() -> &'static RendererRegistry {
+    static REGISTRY: OnceLock<RendererRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(RendererRegistry::default)
+}
+
+/// FIXED_LEN_BYTE_ARRAY(16) with the UUID logical type, as `8-4-4-4-12` hex groups.
+struct UuidRenderer;
+
+impl ValueRenderer for UuidRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        primitive_type.logical_type == Some(PrimitiveLogicalType::Uuid)
+            && primitive_type.physical_type == PhysicalType::FixedLenByteArray(16)
+    }
+
+    fn render(&self, _: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let StatValue::Bytes(bytes) = value else {
+            return None;
+        };
+        if bytes.len() != 16 {
+            return None;
+        }
+
+        let mut uuid = String::with_capacity(36);
+        for (i, byte) in bytes.iter().enumerate() {
+            if [4, 6, 8, 10].contains(&i) {
+                uuid.push('-');
+            }
+            write!(uuid, "{:02x}", byte).unwrap();
+        }
+        Some(uuid)
+    }
+}
+
+/// DECIMAL stored as INT32, INT64 or big-endian two's complement bytes, scaled.
+struct DecimalRenderer;
+
+impl ValueRenderer for DecimalRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        decimal_type(primitive_type).is_some()
+    }
+
+    fn render(&self, primitive_type: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let (_, scale) = decimal_type(primitive_type)?;
+        let decimal = Decimal::from_stat(value, scale)?;
+        Some(format_decimal(decimal.unscaled, scale))
+    }
+}
+
+/// Format an unscaled integer with `scale` digits after the decimal point.
+fn format_decimal(unscaled: i128, scale: usize) -> String {
+    let digits = unscaled.unsigned_abs().to_string();
+    let sign = if unscaled < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// TIMESTAMP stored as INT64, as a date and time in the time zone chosen with `--tz`.
+struct TimestampRenderer;
+
+impl TimestampRenderer {
+    /// Unit of the timestamps and whether they are adjusted to UTC.
+    fn unit(primitive_type: &PrimitiveType) -> Option<(TimeUnit, bool)> {
+        if primitive_type.physical_type != PhysicalType::Int64 {
+            return None;
+        }
+        match (primitive_type.logical_type, primitive_type.converted_type) {
+            (
+                Some(PrimitiveLogicalType::Timestamp {
+                    unit,
+                    is_adjusted_to_utc,
+                }),
+                _,
+            ) => Some((unit, is_adjusted_to_utc)),
+            (_, Some(PrimitiveConvertedType::TimestampMillis)) => {
+                Some((TimeUnit::Milliseconds, true))
+            }
+            (_, Some(PrimitiveConvertedType::TimestampMicros)) => {
+                Some((TimeUnit::Microseconds, true))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ValueRenderer for TimestampRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        Self::unit(primitive_type).is_some()
+    }
+
+    fn render(&self, primitive_type: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let StatValue::Int(value) = value else {
+            return None;
+        };
+        let (unit, adjusted_to_utc) = Self::unit(primitive_type)?;
+        timezone::render_timestamp(*value, unit, adjusted_to_utc)
+    }
+}
+
+/// INTERVAL, a FIXED_LEN_BYTE_ARRAY(12) of little-endian months, days and milliseconds.
+struct IntervalRenderer;
+
+impl ValueRenderer for IntervalRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        primitive_type.converted_type == Some(PrimitiveConvertedType::Interval)
+    }
+
+    fn render(&self, _: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let StatValue::Bytes(bytes) = value else {
+            return None;
+        };
+        if bytes.len() != 12 {
+            return None;
+        }
+
+        let part = |i: usize| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+        let (months, days, millis) = (part(0), part(1), part(2));
+
+        let mut parts = Vec::new();
+        if months > 0 {
+            parts.push(format!("{} mon", months));
+        }
+        if days > 0 {
+            parts.push(format!("{} days", days));
+        }
+        if millis > 0 || parts.is_empty() {
+            parts.push(format!("{}.{:03}s", millis / 1000, millis % 1000));
+        }
+        Some(parts.join(" "))
+    }
+}
+
+/// JSON strings, with insignificant whitespace removed.
+struct JsonRenderer;
+
+impl ValueRenderer for JsonRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        primitive_type.logical_type == Some(PrimitiveLogicalType::Json)
+            || primitive_type.converted_type == Some(PrimitiveConvertedType::Json)
+    }
+
+    fn render(&self, _: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let StatValue::Bytes(bytes) = value else {
+            return None;
+        };
+        Some(compact_json(std::str::from_utf8(bytes).ok()?))
+    }
+}
+
+/// Remove the whitespace outside of string literals, and put a space after `:` and `,`.
+fn compact_json(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in json.chars() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            c if c.is_whitespace() => {}
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            ':' | ',' => {
+                out.push(c);
+                out.push(' ');
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// BSON documents, rendered as JSON-like text.
+struct BsonRenderer;
+
+impl ValueRenderer for BsonRenderer {
+    fn applies(&self, primitive_type: &PrimitiveType) -> bool {
+        primitive_type.logical_type == Some(PrimitiveLogicalType::Bson)
+            || primitive_type.converted_type == Some(PrimitiveConvertedType::Bson)
+    }
+
+    fn render(&self, _: &PrimitiveType, value: &StatValue) -> Option<String> {
+        let StatValue::Bytes(bytes) = value else {
+            return None;
+        };
+        let mut out = String::new();
+        bson_document(bytes, false, &mut out)?;
+        Some(out)
+    }
+}
+
+/// Render a BSON document or array. Returns `None` on malformed input or unsupported types.
+fn bson_document(bytes: &[u8], is_array: bool, out: &mut String) -> Option<()> {
+    let len = i32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+    let body = bytes.get(4..len.checked_sub(1)?)?;
+
+    out.push(if is_array { '[' } else { '{' });
+    let mut pos = 0;
+    let mut first = true;
+    while pos < body.len() {
+        let element_type = body[pos];
+        let name_end = pos + 1 + body[pos + 1..].iter().position(|&b| b == 0)?;
+        let name = std::str::from_utf8(&body[pos + 1..name_end]).ok()?;
+        pos = name_end + 1;
+
+        if !first {
+            out.push_str(", ");
+        }
+        first = false;
+        if !is_array {
+            write!(out, "{:?}: ", name).unwrap();
+        }
+
+        let rest = &body[pos..];
+        let read_i32 = |rest: &[u8]| Some(i32::from_le_bytes(rest.get(..4)?.try_into().ok()?));
+        let read_i64 = |rest: &[u8]| Some(i64::from_le_bytes(rest.get(..8)?.try_into().ok()?));
+        pos += match element_type {
+            0x01 => {
+                let v = f64::from_le_bytes(rest.get(..8)?.try_into().ok()?);
+                write!(out, "{}", v).unwrap();
+                8
+            }
+            0x02 => {
+                let len = read_i32(rest)? as usize;
+                let s = std::str::from_utf8(rest.get(4..4 + len.checked_sub(1)?)?).ok()?;
+                write!(out, "{:?}", s).unwrap();
+                4 + len
+            }
+            0x03 | 0x04 => {
+                let len = read_i32(rest)? as usize;
+                bson_document(rest.get(..len)?, element_type == 0x04, out)?;
+                len
+            }
+            0x05 => {
+                let len = read_i32(rest)? as usize;
+                write!(out, "<binary {} bytes>", len).unwrap();
+                5 + len
+            }
+            0x07 => {
+                for byte in rest.get(..12)? {
+                    write!(out, "{:02x}", byte).unwrap();
+                }
+                12
+            }
+            0x08 => {
+                out.push_str(if *rest.first()? != 0 { "true" } else { "false" });
+                1
+            }
+            0x09 => {
+                write!(out, "Date({})", read_i64(rest)?).unwrap();
+                8
+            }
+            0x0A => {
+                out.push_str("null");
+                0
+            }
+            0x10 => {
+                write!(out, "{}", read_i32(rest)?).unwrap();
+                4
+            }
+            0x11 | 0x12 => {
+                write!(out, "{}", read_i64(rest)?).unwrap();
+                8
+            }
+            _ => return None,
+        };
+    }
+    out.push(if is_array { ']' } else { '}' });
+
+    Some(())
+}
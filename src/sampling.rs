@@ -0,0 +1,153 @@
+use std::{collections::VecDeque, path::Path};
+
+use clap::ValueEnum;
+use color_eyre::eyre::eyre;
+use parquet2::metadata::FileMetaData;
+
+use crate::{
+    generate::Rng,
+    parquet::{read_values, StatValue, ValueStream},
+};
+
+/// Values in a sample unless configured otherwise.
+pub const DEFAULT_SAMPLE_SIZE: usize = 10;
+
+/// Largest configurable sample, as samples are collected in memory and shown on one line.
+pub const MAX_SAMPLE_SIZE: usize = 1000;
+
+/// How the values of a sample are picked from a column.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum SampleStrategy {
+    /// The first values of the column chunk.
+    #[default]
+    Head,
+    /// Values picked uniformly at random across all pages of the column chunk.
+    Random,
+    /// Random values from every row group of the column, an even share from each.
+    Stratified,
+    /// The last values of the column chunk.
+    Tail,
+}
+
+impl SampleStrategy {
+    pub const ALL: [SampleStrategy; 4] = [
+        SampleStrategy::Head,
+        SampleStrategy::Random,
+        SampleStrategy::Stratified,
+        SampleStrategy::Tail,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SampleStrategy::Head => "head",
+            SampleStrategy::Random => "random",
+            SampleStrategy::Stratified => "stratified",
+            SampleStrategy::Tail => "tail",
+        }
+    }
+
+    /// The strategy `delta` places away in [SampleStrategy::ALL], wrapping around.
+    pub fn step(self, delta: isize) -> Self {
+        let index = Self::ALL.iter().position(|&s| s == self).unwrap() as isize;
+        Self::ALL[(index + delta).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+impl std::str::FromStr for SampleStrategy {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == s)
+            .ok_or_else(|| {
+                eyre!(
+                    "unknown sample strategy {:?}, expected head, random, stratified or tail",
+                    s
+                )
+            })
+    }
+}
+
+/// Parse a sample size between 1 and [MAX_SAMPLE_SIZE].
+pub fn parse_sample_size(s: &str) -> Result<usize, String> {
+    match s.trim().parse() {
+        Ok(size @ 1..=MAX_SAMPLE_SIZE) => Ok(size),
+        _ => Err(format!(
+            "invalid sample size {:?}, expected 1 to {}",
+            s, MAX_SAMPLE_SIZE
+        )),
+    }
+}
+
+/// Up to `size` non-null values of a column picked by `strategy`, in file order. Stratified
+/// samples span every row group of the column and ignore `row_group`.
+///
+/// Random picks are seeded by the position of the chunk, so that sampling a chunk again
+/// shows the same values.
+pub fn sample_values<P: AsRef<Path>>(
+    path: P,
+    metadata: &FileMetaData,
+    row_group: usize,
+    column: usize,
+    strategy: SampleStrategy,
+    size: usize,
+) -> color_eyre::Result<Vec<StatValue>> {
+    let path = path.as_ref();
+    match strategy {
+        SampleStrategy::Head => read_values(path, row_group, column, size),
+        SampleStrategy::Random => reservoir(path, row_group, column, size),
+        SampleStrategy::Stratified => {
+            let num_row_groups = metadata.row_groups.len();
+            let mut values = Vec::new();
+            for group in 0..num_row_groups {
+                // Shares differ by one at most, and add up to `size`.
+                let share = size * (group + 1) / num_row_groups - size * group / num_row_groups;
+                if share > 0 {
+                    values.extend(reservoir(path, group, column, share)?);
+                }
+            }
+            Ok(values)
+        }
+        SampleStrategy::Tail => {
+            let mut tail = VecDeque::with_capacity(size);
+            for batch in ValueStream::open(path, row_group, column, usize::MAX)? {
+                for value in batch? {
+                    tail.push_back(value);
+                    if tail.len() > size {
+                        tail.pop_front();
+                    }
+                }
+            }
+            Ok(tail.into())
+        }
+    }
+}
+
+/// Reservoir sample of `size` values of a column chunk, streamed page by page.
+fn reservoir(
+    path: &Path,
+    row_group: usize,
+    column: usize,
+    size: usize,
+) -> color_eyre::Result<Vec<StatValue>> {
+    let mut rng = Rng(((row_group as u64) << 32) ^ column as u64);
+    // Picks keep their position in the chunk, to be returned in file order.
+    let mut picks: Vec<(usize, StatValue)> = Vec::with_capacity(size);
+    let mut seen = 0;
+    for batch in ValueStream::open(path, row_group, column, usize::MAX)? {
+        for value in batch? {
+            if picks.len() < size {
+                picks.push((seen, value));
+            } else {
+                let slot = (rng.next() % (seen as u64 + 1)) as usize;
+                if slot < size {
+                    picks[slot] = (seen, value);
+                }
+            }
+            seen += 1;
+        }
+    }
+    picks.sort_by_key(|(position, _)| *position);
+    Ok(picks.into_iter().map(|(_, value)| value).collect())
+}
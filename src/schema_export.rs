@@ -0,0 +1,902 @@
+use std::fmt::Write;
+
+use parquet2::{
+    metadata::SchemaDescriptor,
+    schema::{
+        types::{
+            GroupConvertedType, GroupLogicalType, IntegerType, ParquetType, PhysicalType,
+            PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType, TimeUnit,
+        },
+        Repetition,
+    },
+};
+
+use crate::output::json_string;
+
+/// Shape of a [Field], with the list and map conventions of Parquet resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    Primitive(PrimitiveType),
+    Struct(Vec<Field>),
+    List(Box<Field>),
+    Map(Box<Field>, Box<Field>),
+}
+
+/// A field of a Parquet schema as seen by readers: lists and maps instead of the repeated
+/// groups that encode them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub nullable: bool,
+    pub kind: FieldKind,
+}
+
+/// Logical type of a primitive, falling back to the converted type older writers annotate
+/// columns with.
+pub fn logical_type(primitive_type: &PrimitiveType) -> Option<PrimitiveLogicalType> {
+    if primitive_type.logical_type.is_some() {
+        return primitive_type.logical_type;
+    }
+    let integer = |integer_type| Some(PrimitiveLogicalType::Integer(integer_type));
+    let utc = |unit, timestamp| {
+        Some(match timestamp {
+            true => PrimitiveLogicalType::Timestamp {
+                unit,
+                is_adjusted_to_utc: true,
+            },
+            false => PrimitiveLogicalType::Time {
+                unit,
+                is_adjusted_to_utc: true,
+            },
+        })
+    };
+    match primitive_type.converted_type? {
+        PrimitiveConvertedType::Utf8 => Some(PrimitiveLogicalType::String),
+        PrimitiveConvertedType::Enum => Some(PrimitiveLogicalType::Enum),
+        PrimitiveConvertedType::Decimal(precision, scale) => {
+            Some(PrimitiveLogicalType::Decimal(precision, scale))
+        }
+        PrimitiveConvertedType::Date => Some(PrimitiveLogicalType::Date),
+        PrimitiveConvertedType::TimeMillis => utc(TimeUnit::Milliseconds, false),
+        PrimitiveConvertedType::TimeMicros => utc(TimeUnit::Microseconds, false),
+        PrimitiveConvertedType::TimestampMillis => utc(TimeUnit::Milliseconds, true),
+        PrimitiveConvertedType::TimestampMicros => utc(TimeUnit::Microseconds, true),
+        PrimitiveConvertedType::Uint8 => integer(IntegerType::UInt8),
+        PrimitiveConvertedType::Uint16 => integer(IntegerType::UInt16),
+        PrimitiveConvertedType::Uint32 => integer(IntegerType::UInt32),
+        PrimitiveConvertedType::Uint64 => integer(IntegerType::UInt64),
+        PrimitiveConvertedType::Int8 => integer(IntegerType::Int8),
+        PrimitiveConvertedType::Int16 => integer(IntegerType::Int16),
+        PrimitiveConvertedType::Int32 => integer(IntegerType::Int32),
+        PrimitiveConvertedType::Int64 => integer(IntegerType::Int64),
+        PrimitiveConvertedType::Json => Some(PrimitiveLogicalType::Json),
+        PrimitiveConvertedType::Bson => Some(PrimitiveLogicalType::Bson),
+        PrimitiveConvertedType::Interval => None,
+    }
+}
+
+/// The field a Parquet type describes, ignoring its repetition.
+fn field_kind(parquet_type: &ParquetType) -> FieldKind {
+    let (logical_type, converted_type, fields) = match parquet_type {
+        ParquetType::PrimitiveType(primitive_type) => {
+            return FieldKind::Primitive(primitive_type.clone())
+        }
+        ParquetType::GroupType {
+            logical_type,
+            converted_type,
+            fields,
+            ..
+        } => (logical_type, converted_type, fields),
+    };
+
+    let is_list = *logical_type == Some(GroupLogicalType::List)
+        || *converted_type == Some(GroupConvertedType::List);
+    let is_map = *logical_type == Some(GroupLogicalType::Map)
+        || matches!(
+            converted_type,
+            Some(GroupConvertedType::Map | GroupConvertedType::MapKeyValue)
+        );
+    let repeated = match fields.as_slice() {
+        [field] if field.get_field_info().repetition == Repetition::Repeated => Some(field),
+        _ => None,
+    };
+
+    match (repeated, is_list, is_map) {
+        (Some(repeated), true, _) => {
+            // The backward-compatibility rules of the spec: a repeated primitive, or a
+            // repeated group of several fields, named `array` or `<list>_tuple` is the element
+            // itself. Otherwise the repeated group wraps the element.
+            let element = match repeated {
+                ParquetType::GroupType { fields, .. }
+                    if fields.len() == 1
+                        && repeated.name() != "array"
+                        && repeated.name() != format!("{}_tuple", parquet_type.name()) =>
+                {
+                    field(&fields[0])
+                }
+                _ => Field {
+                    name: repeated.name().to_string(),
+                    nullable: false,
+                    kind: field_kind(repeated),
+                },
+            };
+            FieldKind::List(Box::new(element))
+        }
+        (Some(ParquetType::GroupType { fields, .. }), _, true) if fields.len() == 2 => {
+            FieldKind::Map(Box::new(field(&fields[0])), Box::new(field(&fields[1])))
+        }
+        _ => FieldKind::Struct(fields.iter().map(field).collect()),
+    }
+}
+
+/// The field a Parquet type describes. Repeated fields outside of a list annotation are
+/// lists of required elements.
+pub fn field(parquet_type: &ParquetType) -> Field {
+    let name = parquet_type.name().to_string();
+    let kind = field_kind(parquet_type);
+    match parquet_type.get_field_info().repetition {
+        Repetition::Required => Field {
+            name,
+            nullable: false,
+            kind,
+        },
+        Repetition::Optional => Field {
+            name,
+            nullable: true,
+            kind,
+        },
+        Repetition::Repeated => Field {
+            name: name.clone(),
+            nullable: false,
+            kind: FieldKind::List(Box::new(Field {
+                name,
+                nullable: false,
+                kind,
+            })),
+        },
+    }
+}
+
+/// Top-level fields of a schema.
+pub fn fields(schema: &SchemaDescriptor) -> Vec<Field> {
+    schema.fields().iter().map(field).collect()
+}
+
+/// A JSON document, with object members kept in order.
+enum Json {
+    Null,
+    Str(String),
+    /// A number, already formatted.
+    Number(String),
+    Bool(bool),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn str(s: &str) -> Json {
+        Json::Str(s.to_string())
+    }
+
+    fn write(&self, out: &mut String, indent: usize) {
+        let pad = "  ".repeat(indent + 1);
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Str(s) => out.push_str(&json_string(s)),
+            Json::Number(n) => out.push_str(n),
+            Json::Bool(b) => write!(out, "{}", b).unwrap(),
+            Json::Array(items) if items.iter().all(|item| matches!(item, Json::Str(_))) => {
+                // Type unions and required lists stay on one line.
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(out, indent);
+                }
+                out.push(']');
+            }
+            Json::Array(items) => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(&pad);
+                    item.write(out, indent + 1);
+                    out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                write!(out, "{}]", "  ".repeat(indent)).unwrap();
+            }
+            Json::Object(members) if members.is_empty() => out.push_str("{}"),
+            Json::Object(members) => {
+                out.push_str("{\n");
+                for (i, (key, value)) in members.iter().enumerate() {
+                    write!(out, "{}{}: ", pad, json_string(key)).unwrap();
+                    value.write(out, indent + 1);
+                    out.push_str(if i + 1 < members.len() { ",\n" } else { "\n" });
+                }
+                write!(out, "{}}}", "  ".repeat(indent)).unwrap();
+            }
+        }
+    }
+}
+
+/// Bounds of an integer logical type.
+fn integer_bounds(integer_type: IntegerType) -> (String, String) {
+    let (min, max) = match integer_type {
+        IntegerType::Int8 => (i8::MIN as i128, i8::MAX as i128),
+        IntegerType::Int16 => (i16::MIN as i128, i16::MAX as i128),
+        IntegerType::Int32 => (i32::MIN as i128, i32::MAX as i128),
+        IntegerType::Int64 => (i64::MIN as i128, i64::MAX as i128),
+        IntegerType::UInt8 => (0, u8::MAX as i128),
+        IntegerType::UInt16 => (0, u16::MAX as i128),
+        IntegerType::UInt32 => (0, u32::MAX as i128),
+        IntegerType::UInt64 => (0, u64::MAX as i128),
+    };
+    (min.to_string(), max.to_string())
+}
+
+/// JSON Schema keywords of a primitive, as its values are represented in JSON: temporal
+/// types as ISO 8601 strings and binary as base64.
+fn primitive_schema(primitive_type: &PrimitiveType) -> Vec<(String, Json)> {
+    let typed = |json_type: &str| vec![("type".to_string(), Json::str(json_type))];
+    let formatted = |format: &str| {
+        vec![
+            ("type".to_string(), Json::str("string")),
+            ("format".to_string(), Json::str(format)),
+        ]
+    };
+    match logical_type(primitive_type) {
+        Some(PrimitiveLogicalType::String | PrimitiveLogicalType::Json) => typed("string"),
+        Some(PrimitiveLogicalType::Enum) => typed("string"),
+        Some(PrimitiveLogicalType::Uuid) => formatted("uuid"),
+        Some(PrimitiveLogicalType::Date) => formatted("date"),
+        Some(PrimitiveLogicalType::Time { .. }) => formatted("time"),
+        Some(PrimitiveLogicalType::Timestamp { .. }) => formatted("date-time"),
+        Some(PrimitiveLogicalType::Decimal(precision, scale)) => {
+            let mut schema = typed("number");
+            schema.push((
+                "description".to_string(),
+                Json::Str(format!("DECIMAL({}, {})", precision, scale)),
+            ));
+            schema
+        }
+        Some(PrimitiveLogicalType::Integer(integer_type)) => {
+            let (min, max) = integer_bounds(integer_type);
+            let mut schema = typed("integer");
+            schema.push(("minimum".to_string(), Json::Number(min)));
+            schema.push(("maximum".to_string(), Json::Number(max)));
+            schema
+        }
+        Some(PrimitiveLogicalType::Bson | PrimitiveLogicalType::Unknown) | None => {
+            match primitive_type.physical_type {
+                PhysicalType::Boolean => typed("boolean"),
+                PhysicalType::Int32 | PhysicalType::Int64 => typed("integer"),
+                PhysicalType::Float | PhysicalType::Double => typed("number"),
+                // INT96 is the legacy timestamp encoding.
+                PhysicalType::Int96 => formatted("date-time"),
+                PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => {
+                    let mut schema = typed("string");
+                    schema.push(("contentEncoding".to_string(), Json::str("base64")));
+                    schema
+                }
+            }
+        }
+    }
+}
+
+/// Keywords of an object with `fields` as properties. Objects are closed, so that contracts
+/// reject fields the file does not have.
+fn object_schema(fields: &[Field]) -> Vec<(String, Json)> {
+    let mut schema = vec![
+        ("type".to_string(), Json::str("object")),
+        (
+            "properties".to_string(),
+            Json::Object(
+                fields
+                    .iter()
+                    .map(|field| (field.name.clone(), field_schema(field)))
+                    .collect(),
+            ),
+        ),
+    ];
+    let required: Vec<Json> = fields
+        .iter()
+        .filter(|field| !field.nullable)
+        .map(|field| Json::str(&field.name))
+        .collect();
+    if !required.is_empty() {
+        schema.push(("required".to_string(), Json::Array(required)));
+    }
+    schema.push(("additionalProperties".to_string(), Json::Bool(false)));
+    schema
+}
+
+fn field_schema(field: &Field) -> Json {
+    let mut schema = match &field.kind {
+        FieldKind::Primitive(primitive_type) => primitive_schema(primitive_type),
+        FieldKind::Struct(fields) => object_schema(fields),
+        FieldKind::List(element) => vec![
+            ("type".to_string(), Json::str("array")),
+            ("items".to_string(), field_schema(element)),
+        ],
+        // Maps with string keys are objects, others arrays of key/value objects.
+        FieldKind::Map(key, value)
+            if matches!(
+                &key.kind,
+                FieldKind::Primitive(key) if logical_type(key) == Some(PrimitiveLogicalType::String)
+            ) =>
+        {
+            vec![
+                ("type".to_string(), Json::str("object")),
+                ("additionalProperties".to_string(), field_schema(value)),
+            ]
+        }
+        FieldKind::Map(key, value) => vec![
+            ("type".to_string(), Json::str("array")),
+            (
+                "items".to_string(),
+                Json::Object(object_schema(&[*key.clone(), *value.clone()])),
+            ),
+        ],
+    };
+    if field.nullable {
+        if let Some((_, Json::Str(json_type))) = schema.first() {
+            let union = Json::Array(vec![Json::str(json_type), Json::str("null")]);
+            schema[0].1 = union;
+        }
+    }
+    Json::Object(schema)
+}
+
+/// Convert a schema into a draft-07 JSON Schema of its records, for contract validation.
+/// Optional fields may be null, and required ones must be present.
+pub fn json_schema(schema: &SchemaDescriptor) -> String {
+    let mut document = vec![
+        (
+            "$schema".to_string(),
+            Json::str("http://json-schema.org/draft-07/schema#"),
+        ),
+        ("title".to_string(), Json::str(schema.name())),
+    ];
+    document.extend(object_schema(&fields(schema)));
+
+    let mut out = String::new();
+    Json::Object(document).write(&mut out, 0);
+    out.push('\n');
+    out
+}
+
+/// Identifier made of the letters, digits and underscores of `name`, as Avro and Protobuf
+/// require.
+fn identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+/// Avro type of a primitive. Types Avro lacks, such as nanosecond timestamps and unsigned
+/// 64-bit integers, fall back to their physical type.
+fn avro_primitive(primitive_type: &PrimitiveType, name: &str) -> Json {
+    let logical = |avro_type: &str, logical_type: &str| {
+        Json::Object(vec![
+            ("type".to_string(), Json::str(avro_type)),
+            ("logicalType".to_string(), Json::str(logical_type)),
+        ])
+    };
+    match logical_type(primitive_type) {
+        Some(
+            PrimitiveLogicalType::String | PrimitiveLogicalType::Enum | PrimitiveLogicalType::Json,
+        ) => return Json::str("string"),
+        Some(PrimitiveLogicalType::Uuid) => return logical("string", "uuid"),
+        Some(PrimitiveLogicalType::Date) => return logical("int", "date"),
+        Some(PrimitiveLogicalType::Time { unit, .. }) => match unit {
+            TimeUnit::Milliseconds => return logical("int", "time-millis"),
+            TimeUnit::Microseconds => return logical("long", "time-micros"),
+            TimeUnit::Nanoseconds => {}
+        },
+        Some(PrimitiveLogicalType::Timestamp {
+            unit,
+            is_adjusted_to_utc,
+        }) => {
+            let prefix = if is_adjusted_to_utc { "" } else { "local-" };
+            match unit {
+                TimeUnit::Milliseconds => {
+                    return logical("long", &format!("{}timestamp-millis", prefix))
+                }
+                TimeUnit::Microseconds => {
+                    return logical("long", &format!("{}timestamp-micros", prefix))
+                }
+                TimeUnit::Nanoseconds => {}
+            }
+        }
+        Some(PrimitiveLogicalType::Decimal(precision, scale)) => {
+            return Json::Object(vec![
+                ("type".to_string(), Json::str("bytes")),
+                ("logicalType".to_string(), Json::str("decimal")),
+                ("precision".to_string(), Json::Number(precision.to_string())),
+                ("scale".to_string(), Json::Number(scale.to_string())),
+            ])
+        }
+        Some(PrimitiveLogicalType::Integer(
+            IntegerType::Int8
+            | IntegerType::Int16
+            | IntegerType::Int32
+            | IntegerType::UInt8
+            | IntegerType::UInt16,
+        )) => return Json::str("int"),
+        Some(PrimitiveLogicalType::Integer(_)) => return Json::str("long"),
+        Some(PrimitiveLogicalType::Bson | PrimitiveLogicalType::Unknown) | None => {}
+    }
+    match primitive_type.physical_type {
+        PhysicalType::Boolean => Json::str("boolean"),
+        PhysicalType::Int32 => Json::str("int"),
+        PhysicalType::Int64 => Json::str("long"),
+        PhysicalType::Float => Json::str("float"),
+        PhysicalType::Double => Json::str("double"),
+        PhysicalType::ByteArray => Json::str("bytes"),
+        PhysicalType::Int96 => avro_fixed(name, 12),
+        PhysicalType::FixedLenByteArray(size) => avro_fixed(name, size),
+    }
+}
+
+fn avro_fixed(name: &str, size: usize) -> Json {
+    Json::Object(vec![
+        ("type".to_string(), Json::str("fixed")),
+        ("name".to_string(), Json::Str(name.to_string())),
+        ("size".to_string(), Json::Number(size.to_string())),
+    ])
+}
+
+/// Avro record of `fields`. Named types are named after their path, which keeps them unique.
+fn avro_record(name: &str, fields: &[Field]) -> Json {
+    Json::Object(vec![
+        ("type".to_string(), Json::str("record")),
+        ("name".to_string(), Json::Str(name.to_string())),
+        (
+            "fields".to_string(),
+            Json::Array(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let path = format!("{}_{}", name, identifier(&field.name));
+                        let mut member = vec![
+                            ("name".to_string(), Json::Str(identifier(&field.name))),
+                            ("type".to_string(), avro_type(field, &path)),
+                        ];
+                        if field.nullable {
+                            member.push(("default".to_string(), Json::Null));
+                        }
+                        Json::Object(member)
+                    })
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+fn avro_type(field: &Field, path: &str) -> Json {
+    let avro_type = match &field.kind {
+        FieldKind::Primitive(primitive_type) => avro_primitive(primitive_type, path),
+        FieldKind::Struct(fields) => avro_record(path, fields),
+        FieldKind::List(element) => Json::Object(vec![
+            ("type".to_string(), Json::str("array")),
+            (
+                "items".to_string(),
+                avro_type(element, &format!("{}_element", path)),
+            ),
+        ]),
+        // Avro map keys are strings, other maps become arrays of key/value records.
+        FieldKind::Map(key, value)
+            if matches!(
+                &key.kind,
+                FieldKind::Primitive(key) if logical_type(key) == Some(PrimitiveLogicalType::String)
+            ) =>
+        {
+            Json::Object(vec![
+                ("type".to_string(), Json::str("map")),
+                (
+                    "values".to_string(),
+                    avro_type(value, &format!("{}_value", path)),
+                ),
+            ])
+        }
+        FieldKind::Map(key, value) => Json::Object(vec![
+            ("type".to_string(), Json::str("array")),
+            (
+                "items".to_string(),
+                avro_record(&format!("{}_entry", path), &[*key.clone(), *value.clone()]),
+            ),
+        ]),
+    };
+    match field.nullable {
+        true => Json::Array(vec![Json::str("null"), avro_type]),
+        false => avro_type,
+    }
+}
+
+/// Convert a schema into an Avro schema (`.avsc`) of its records. Optional fields are unions
+/// with null, defaulting to null.
+pub fn avro_schema(schema: &SchemaDescriptor) -> String {
+    let mut out = String::new();
+    avro_record(&identifier(schema.name()), &fields(schema)).write(&mut out, 0);
+    out.push('\n');
+    out
+}
+
+/// `snake_case` or `kebab-case` name in `CamelCase`, for Protobuf messages.
+fn camel_case(name: &str) -> String {
+    identifier(name)
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Protobuf scalar type of a primitive, and the meaning of its values when the type alone
+/// loses it, such as the epoch and unit of temporal types.
+fn protobuf_scalar(primitive_type: &PrimitiveType) -> (&'static str, Option<String>) {
+    let unit = |unit: TimeUnit| match unit {
+        TimeUnit::Milliseconds => "milliseconds",
+        TimeUnit::Microseconds => "microseconds",
+        TimeUnit::Nanoseconds => "nanoseconds",
+    };
+    match logical_type(primitive_type) {
+        Some(
+            PrimitiveLogicalType::String | PrimitiveLogicalType::Enum | PrimitiveLogicalType::Json,
+        ) => ("string", None),
+        Some(PrimitiveLogicalType::Uuid) => ("bytes", Some("UUID".to_string())),
+        Some(PrimitiveLogicalType::Date) => ("int32", Some("days since the epoch".to_string())),
+        Some(PrimitiveLogicalType::Time {
+            unit: time_unit, ..
+        }) => ("int64", Some(format!("{} since midnight", unit(time_unit)))),
+        Some(PrimitiveLogicalType::Timestamp {
+            unit: time_unit, ..
+        }) => (
+            "int64",
+            Some(format!("{} since the epoch", unit(time_unit))),
+        ),
+        Some(PrimitiveLogicalType::Decimal(precision, scale)) => {
+            ("string", Some(format!("DECIMAL({}, {})", precision, scale)))
+        }
+        Some(PrimitiveLogicalType::Integer(integer_type)) => (
+            match integer_type {
+                IntegerType::Int8 | IntegerType::Int16 | IntegerType::Int32 => "int32",
+                IntegerType::Int64 => "int64",
+                IntegerType::UInt8 | IntegerType::UInt16 | IntegerType::UInt32 => "uint32",
+                IntegerType::UInt64 => "uint64",
+            },
+            None,
+        ),
+        Some(PrimitiveLogicalType::Bson | PrimitiveLogicalType::Unknown) | None => {
+            match primitive_type.physical_type {
+                PhysicalType::Boolean => ("bool", None),
+                PhysicalType::Int32 => ("int32", None),
+                PhysicalType::Int64 => ("int64", None),
+                PhysicalType::Float => ("float", None),
+                PhysicalType::Double => ("double", None),
+                PhysicalType::Int96 => ("bytes", Some("INT96 timestamp".to_string())),
+                PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => ("bytes", None),
+            }
+        }
+    }
+}
+
+/// Writes Protobuf messages, declaring the messages nested fields need inside their parent.
+struct ProtobufWriter {
+    out: String,
+}
+
+impl ProtobufWriter {
+    /// Type of a single (non-repeated) value of `field`, declaring the message it needs.
+    fn value_type(&mut self, field: &Field, indent: usize) -> (String, Option<String>) {
+        match &field.kind {
+            FieldKind::Primitive(primitive_type) => {
+                let (scalar, comment) = protobuf_scalar(primitive_type);
+                (scalar.to_string(), comment)
+            }
+            FieldKind::Struct(fields) => {
+                let name = camel_case(&field.name);
+                self.message(&name, fields, indent);
+                (name, None)
+            }
+            // Protobuf has no nested repeated fields: lists and maps in lists are wrapped.
+            FieldKind::List(_) | FieldKind::Map(..) => {
+                let name = camel_case(&field.name);
+                let wrapped = Field {
+                    name: "values".to_string(),
+                    nullable: false,
+                    kind: field.kind.clone(),
+                };
+                self.message(&name, &[wrapped], indent);
+                (name, None)
+            }
+        }
+    }
+
+    fn message(&mut self, name: &str, fields: &[Field], indent: usize) {
+        let pad = "  ".repeat(indent);
+        let mut lines = Vec::new();
+        writeln!(self.out, "{}message {} {{", pad, name).unwrap();
+        for (number, field) in fields.iter().enumerate() {
+            let field_name = identifier(&field.name);
+            let (label, field_type, comment) = match &field.kind {
+                FieldKind::List(element) => {
+                    let element = Field {
+                        name: format!("{}_element", field.name),
+                        ..*element.clone()
+                    };
+                    let (element_type, comment) = self.value_type(&element, indent + 1);
+                    ("repeated ", element_type, comment)
+                }
+                // Map keys must be integral or strings, and values cannot be repeated.
+                FieldKind::Map(key, value)
+                    if matches!(&key.kind, FieldKind::Primitive(key)
+                        if ["string", "int32", "int64", "uint32", "uint64", "bool"]
+                            .contains(&protobuf_scalar(key).0))
+                        && !matches!(value.kind, FieldKind::List(_) | FieldKind::Map(..)) =>
+                {
+                    let FieldKind::Primitive(key) = &key.kind else {
+                        unreachable!()
+                    };
+                    let value = Field {
+                        name: format!("{}_value", field.name),
+                        ..*value.clone()
+                    };
+                    let (value_type, comment) = self.value_type(&value, indent + 1);
+                    (
+                        "",
+                        format!("map<{}, {}>", protobuf_scalar(key).0, value_type),
+                        comment,
+                    )
+                }
+                FieldKind::Map(key, value) => {
+                    let entry = Field {
+                        name: format!("{}_entry", field.name),
+                        nullable: false,
+                        kind: FieldKind::Struct(vec![*key.clone(), *value.clone()]),
+                    };
+                    let (entry_type, comment) = self.value_type(&entry, indent + 1);
+                    ("repeated ", entry_type, comment)
+                }
+                FieldKind::Primitive(_) if field.nullable => {
+                    let (scalar, comment) = self.value_type(field, indent + 1);
+                    ("optional ", scalar, comment)
+                }
+                _ => {
+                    let (value_type, comment) = self.value_type(field, indent + 1);
+                    ("", value_type, comment)
+                }
+            };
+            let comment = comment
+                .map(|comment| format!("  // {}", comment))
+                .unwrap_or_default();
+            lines.push(format!(
+                "{}  {}{} {} = {};{}",
+                pad,
+                label,
+                field_type,
+                field_name,
+                number + 1,
+                comment
+            ));
+        }
+        for line in lines {
+            writeln!(self.out, "{}", line).unwrap();
+        }
+        writeln!(self.out, "{}}}", pad).unwrap();
+    }
+}
+
+/// Convert a schema into a proto3 message (`.proto`) of its records. Optional scalars are
+/// `optional` fields; nulls in lists and the nullability of messages are lost.
+pub fn protobuf_schema(schema: &SchemaDescriptor) -> String {
+    let mut writer = ProtobufWriter {
+        out: "syntax = \"proto3\";\n\n".to_string(),
+    };
+    writer.message(&camel_case(schema.name()), &fields(schema), 0);
+    writer.out
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet2::schema::io_message::from_message;
+
+    use super::*;
+
+    fn schema(message: &str) -> SchemaDescriptor {
+        match from_message(message).unwrap() {
+            ParquetType::GroupType {
+                field_info, fields, ..
+            } => SchemaDescriptor::new(field_info.name, fields),
+            ParquetType::PrimitiveType(_) => unreachable!(),
+        }
+    }
+
+    fn users() -> SchemaDescriptor {
+        schema(
+            "message users {
+                required int64 id;
+                optional binary email (UTF8);
+                optional int32 age (INTEGER(8, false));
+                optional int64 balance (DECIMAL(12, 2));
+                optional int64 signed_up (TIMESTAMP(MICROS, true));
+                optional group tags (LIST) {
+                    repeated group list {
+                        required binary element (UTF8);
+                    }
+                }
+                optional group address {
+                    required binary city (UTF8);
+                    optional int32 zip;
+                }
+                optional group scores (MAP) {
+                    repeated group key_value {
+                        required binary key (UTF8);
+                        optional double value;
+                    }
+                }
+            }",
+        )
+    }
+
+    #[test]
+    fn converts_to_json_schema() {
+        assert_eq!(
+            json_schema(&users()),
+            r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "title": "users",
+  "type": "object",
+  "properties": {
+    "id": {
+      "type": "integer"
+    },
+    "email": {
+      "type": ["string", "null"]
+    },
+    "age": {
+      "type": ["integer", "null"],
+      "minimum": 0,
+      "maximum": 255
+    },
+    "balance": {
+      "type": ["number", "null"],
+      "description": "DECIMAL(12, 2)"
+    },
+    "signed_up": {
+      "type": ["string", "null"],
+      "format": "date-time"
+    },
+    "tags": {
+      "type": ["array", "null"],
+      "items": {
+        "type": "string"
+      }
+    },
+    "address": {
+      "type": ["object", "null"],
+      "properties": {
+        "city": {
+          "type": "string"
+        },
+        "zip": {
+          "type": ["integer", "null"]
+        }
+      },
+      "required": ["city"],
+      "additionalProperties": false
+    },
+    "scores": {
+      "type": ["object", "null"],
+      "additionalProperties": {
+        "type": ["number", "null"]
+      }
+    }
+  },
+  "required": ["id"],
+  "additionalProperties": false
+}
+"#
+        );
+    }
+
+    #[test]
+    fn converts_to_avro() {
+        let avro = avro_schema(&users());
+        assert!(avro.starts_with("{\n  \"type\": \"record\",\n  \"name\": \"users\",\n"));
+        assert!(avro.contains(
+            r#"{
+          "type": "bytes",
+          "logicalType": "decimal",
+          "precision": 12,
+          "scale": 2
+        }"#
+        ));
+        assert!(avro.contains(
+            r#"{
+          "type": "long",
+          "logicalType": "timestamp-micros"
+        }"#
+        ));
+        assert!(avro.contains(r#""name": "users_address","#));
+        assert!(avro.contains(
+            r#"{
+          "type": "map",
+          "values": ["null", "double"]
+        }"#
+        ));
+    }
+
+    #[test]
+    fn converts_to_protobuf() {
+        assert_eq!(
+            protobuf_schema(&users()),
+            "syntax = \"proto3\";
+
+message Users {
+  message Address {
+    string city = 1;
+    optional int32 zip = 2;
+  }
+  int64 id = 1;
+  optional string email = 2;
+  optional uint32 age = 3;
+  optional string balance = 4;  // DECIMAL(12, 2)
+  optional int64 signed_up = 5;  // microseconds since the epoch
+  repeated string tags = 6;
+  Address address = 7;
+  map<string, double> scores = 8;
+}
+"
+        );
+    }
+
+    #[test]
+    fn resolves_legacy_lists() {
+        let schema = schema(
+            "message legacy {
+                optional group points (LIST) {
+                    repeated group array {
+                        required int32 x;
+                    }
+                }
+                optional group pairs (LIST) {
+                    repeated group pairs_tuple {
+                        required int32 a;
+                        required int32 b;
+                    }
+                }
+                repeated int32 values;
+            }",
+        );
+        let kinds: Vec<String> = fields(&schema)
+            .iter()
+            .map(|field| match &field.kind {
+                FieldKind::List(element) => match &element.kind {
+                    FieldKind::Struct(members) => format!("list of {}-field struct", members.len()),
+                    FieldKind::Primitive(_) => "list of primitives".to_string(),
+                    _ => "list".to_string(),
+                },
+                _ => "other".to_string(),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "list of 1-field struct",
+                "list of 2-field struct",
+                "list of primitives"
+            ]
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use color_eyre::eyre::eyre;
+
+use crate::{
+    codecs::codec_name,
+    commands::meta::{self, MetaArgs},
+    goto::column_paths,
+    output::{render, OutputFormat, Report, Value},
+    parquet::{read_file_metadata, ColumnChunkMetaDataExt, PhysicalTypeExt, StatValue},
+    sampling::{sample_values, SampleStrategy},
+};
+
+/// Values returned by `/sample` when no `limit` is given.
+const DEFAULT_SAMPLE_LIMIT: usize = 20;
+
+/// How long a connection may take to send its request before it is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Response of an endpoint: an HTTP status and a JSON body.
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(report: &Report) -> Self {
+        Response {
+            status: 200,
+            body: render(report, OutputFormat::Json),
+        }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        let report = Report::new().record("error", [("message", message.to_string())]);
+        Response {
+            status,
+            body: render(&report, OutputFormat::Json),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Decode `%XX` escapes in a URL component.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let escaped = std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match escaped {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Value of a query string parameter, decoded. Unlike in paths, `+` stands for a space.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(&value.replace('+', " ")))
+}
+
+fn parse_param<T: std::str::FromStr>(
+    query: &str,
+    name: &str,
+    default: Option<T>,
+) -> color_eyre::Result<T> {
+    match query_param(query, name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| eyre!("invalid value {:?} for {}", value, name)),
+        None => default.ok_or_else(|| eyre!("missing query parameter {}", name)),
+    }
+}
+
+fn stat_value(value: StatValue) -> Value {
+    match value {
+        StatValue::Boolean(b) => b.into(),
+        StatValue::Int(i) => i.into(),
+        StatValue::Float(x) => x.into(),
+        bytes @ StatValue::Bytes(_) => bytes.to_string().into(),
+    }
+}
+
+/// `/row-groups/{i}`: the sizes of a row group and of each of its column chunks.
+fn row_group(path: &Path, index: &str) -> color_eyre::Result<Option<Report>> {
+    let metadata = read_file_metadata(path)?;
+    let Some((index, row_group)) = index
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| Some((index, metadata.row_groups.get(index)?)))
+    else {
+        return Ok(None);
+    };
+
+    let columns = row_group
+        .columns()
+        .iter()
+        .map(|chunk| {
+            vec![
+                Value::from(chunk.descriptor().path_in_schema.join(".")),
+                chunk.physical_type().human_readable().into(),
+                chunk.num_values().into(),
+                chunk.compressed_size().into(),
+                chunk.uncompressed_size().into(),
+                codec_name(chunk.compression()).into(),
+            ]
+        })
+        .collect();
+
+    Ok(Some(
+        Report::new()
+            .record(
+                "row_group",
+                [
+                    ("index", Value::from(index)),
+                    ("num_rows", row_group.num_rows().into()),
+                    ("total_byte_size", row_group.total_byte_size().into()),
+                    ("compressed_size", row_group.compressed_size().into()),
+                ],
+            )
+            .table(
+                "columns",
+                &[
+                    "column",
+                    "type",
+                    "num_values",
+                    "compressed_size",
+                    "uncompressed_size",
+                    "codec",
+                ],
+                columns,
+            ),
+    ))
+}
+
+/// `/columns/{path}/stats`: the statistics of a column in every row group.
+fn column_stats(path: &Path, column: &str) -> color_eyre::Result<Option<Report>> {
+    let metadata = read_file_metadata(path)?;
+    let Some(index) = column_paths(&metadata)
+        .iter()
+        .position(|path| path == column)
+    else {
+        return Ok(None);
+    };
+
+    let rows = metadata
+        .row_groups
+        .iter()
+        .enumerate()
+        .map(|(row_group, metadata)| {
+            let stats = (&metadata.columns()[index]).stats();
+            vec![
+                Value::from(row_group),
+                stats.min.into(),
+                stats.max.into(),
+                stats.null_count.into(),
+                stats.distinct_values.into(),
+            ]
+        })
+        .collect();
+
+    Ok(Some(Report::new().table(
+        "stats",
+        &["row_group", "min", "max", "null_count", "distinct_values"],
+        rows,
+    )))
+}
+
+/// `/sample?column=PATH&row_group=N&limit=N&strategy=S`: values of a column chunk, the
+/// first ones unless another [SampleStrategy] is given.
+fn sample(path: &Path, query: &str) -> color_eyre::Result<Option<Report>> {
+    let metadata = read_file_metadata(path)?;
+    let column: String = parse_param(query, "column", None)?;
+    let row_group: usize = parse_param(query, "row_group", Some(0))?;
+    let limit: usize = parse_param(query, "limit", Some(DEFAULT_SAMPLE_LIMIT))?;
+    let strategy: SampleStrategy = parse_param(query, "strategy", Some(SampleStrategy::Head))?;
+
+    let Some(index) = column_paths(&metadata)
+        .iter()
+        .position(|path| *path == column)
+    else {
+        return Ok(None);
+    };
+    if row_group >= metadata.row_groups.len() {
+        return Err(eyre!(
+            "row group {} out of range, the file has {} row groups",
+            row_group,
+            metadata.row_groups.len()
+        ));
+    }
+
+    let values = sample_values(path, &metadata, row_group, index, strategy, limit)?
+        .into_iter()
+        .map(|value| vec![stat_value(value)])
+        .collect();
+
+    Ok(Some(Report::new().table("values", &["value"], values)))
+}
+
+/// Route a request target to its endpoint.
+fn route(path: &Path, target: &str) -> Response {
+    let (resource, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments: Vec<String> = resource
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let result = match segments.as_slice() {
+        ["metadata"] => meta::run(&MetaArgs {
+            file: path.to_path_buf(),
+        })
+        .map(Some),
+        ["row-groups", index] => row_group(path, index),
+        ["columns", column, "stats"] => column_stats(path, column),
+        ["sample"] => sample(path, query),
+        _ => Ok(None),
+    };
+
+    match result {
+        Ok(Some(report)) => Response::json(&report),
+        Ok(None) => Response::error(404, format!("no resource at {}", resource)),
+        Err(err) if segments.as_slice() == ["sample"] => Response::error(400, err),
+        Err(err) => Response::error(500, err),
+    }
+}
+
+/// Read one request from a connection and answer it. Connections are not kept alive.
+fn handle_connection(path: &Path, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers, the endpoints take no request body.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header != "\r\n" && header != "\n" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let response = if method == "GET" {
+        route(path, target)
+    } else {
+        Response::error(405, format!("method {} is not supported", method))
+    };
+    tracing::info!("{} {} -> {}", method, target, response.status);
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    )?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+/// Serve the metadata of a file as JSON, each connection on a thread of its own, until the
+/// process is stopped. The footer is read again for every request, through the footer cache,
+/// so the endpoints follow changes to the file.
+pub fn serve(path: &Path, listener: TcpListener) -> color_eyre::Result<()> {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let path = path.to_path_buf();
+                thread::spawn(move || {
+                    if let Err(err) = handle_connection(&path, stream) {
+                        tracing::warn!("connection failed: {}", err);
+                    }
+                });
+            }
+            Err(err) => tracing::warn!("accepting a connection failed: {}", err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(percent_decode("a%20b"), "a b");
+        assert_eq!(percent_decode("%E2%9C%93"), "✓");
+        assert_eq!(percent_decode("a+b"), "a+b");
+    }
+
+    #[test]
+    fn keeps_invalid_escapes() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        assert_eq!(percent_decode("%4"), "%4");
+    }
+
+    #[test]
+    fn decodes_plus_as_space_in_query_parameters() {
+        let query = "where=name+%3D+a%2Bb&row_group=2";
+        assert_eq!(query_param(query, "where").as_deref(), Some("name = a+b"));
+        assert_eq!(parse_param::<usize>(query, "row_group", None).unwrap(), 2);
+        assert_eq!(parse_param(query, "limit", Some(10)).unwrap(), 10);
+        assert!(parse_param::<usize>(query, "limit", None).is_err());
+        assert!(parse_param::<usize>(query, "where", None).is_err());
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    column_view::TypeFilter,
+    goto,
+    numbers::{self, NumberFormat},
+    pane_layout::PaneLayout,
+    preview::Predicate,
+    start_view::StartView,
+    ActivePane, App, Overlay,
+};
+
+/// Name of the state file inside the state directory.
+const STATE_FILE_NAME: &str = "session";
+
+/// Persistent state carried across launches of the console.
+///
+/// The state file is a small INI-style text file. Top-level keys hold global state, and each
+/// `[path]` section holds the view state of one Parquet file:
+///
+/// ```text
+/// last_file=/data/events.parquet
+/// panes=33,33,12
+/// numbers=grouped
+/// start_view=schema
+///
+/// [/data/events.parquet]
+/// row_group=3
+/// column=user.id
+/// active_pane=column
+/// type_filter=STRING
+/// preview_filter=country = NL
+/// bookmark=0:user.id
+/// bookmark=7:payload
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SessionState {
+    pub last_file: Option<PathBuf>,
+    /// Pane sizes, as last resized.
+    pub panes: PaneLayout,
+    /// How counts and sizes are written, as last cycled with `n`.
+    pub numbers: NumberFormat,
+    /// View the TUI opens on, set by hand in the state file; `--start-view` overrides it.
+    pub start_view: Option<StartView>,
+    pub files: BTreeMap<PathBuf, FileSession>,
+}
+
+/// View state saved for a single file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileSession {
+    pub row_group: usize,
+    /// Selected column, by path as for bookmarks.
+    pub column: Option<String>,
+    pub active_pane: ActivePane,
+    /// Name of the type the column browser shows the columns of, e.g. `INT64` or `STRING`.
+    pub type_filter: Option<String>,
+    /// Filter of the data preview, if it was open with one. It is opened again with it.
+    pub preview_filter: Option<String>,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// A location bookmarked with `m`: a row group by index and a column by path, so that it
+/// still points at the same column if the file is rewritten with its columns reordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub row_group: usize,
+    pub column: String,
+}
+
+/// Directory holding the console's state, `$XDG_STATE_HOME/parquet_console` or
+/// `~/.local/state/parquet_console`.
+pub fn state_dir() -> Option<PathBuf> {
+    if let Some(state_home) = std::env::var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(state_home).join("parquet_console"));
+    }
+
+    let home = std::env::var_os("HOME").filter(|dir| !dir.is_empty())?;
+    Some(PathBuf::from(home).join(".local/state/parquet_console"))
+}
+
+/// Key used to identify a file in the state file. Falls back to the path as given when it
+/// cannot be canonicalized.
+pub fn session_key<P: AsRef<Path>>(path: P) -> PathBuf {
+    fs::canonicalize(path.as_ref()).unwrap_or_else(|_| path.as_ref().to_owned())
+}
+
+impl SessionState {
+    /// Load the saved state. A missing or unreadable state file yields an empty state, since
+    /// losing the session should never prevent the console from starting.
+    pub fn load() -> Self {
+        let Some(path) = state_dir().map(|dir| dir.join(STATE_FILE_NAME)) else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(err) => {
+                if err.kind() != io::ErrorKind::NotFound {
+                    tracing::warn!("ignoring session state {}: {}", path.display(), err);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the state file, creating the state directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(dir) = state_dir() else {
+            return Ok(());
+        };
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(STATE_FILE_NAME), self.to_string())
+    }
+
+    /// Parse the state file contents, skipping any lines that are not understood.
+    pub fn parse(contents: &str) -> Self {
+        let mut state = Self::default();
+        let mut section: Option<PathBuf> = None;
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(path) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let path = PathBuf::from(path);
+                state.files.entry(path.clone()).or_default();
+                section = Some(path);
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            match &section {
+                None => match key {
+                    "last_file" => state.last_file = Some(PathBuf::from(value)),
+                    "panes" => state.panes = value.parse().unwrap_or_default(),
+                    "numbers" => state.numbers = value.parse().unwrap_or_default(),
+                    "start_view" => state.start_view = value.parse().ok(),
+                    _ => {}
+                },
+                Some(path) => {
+                    let file = state.files.entry(path.clone()).or_default();
+                    file.set(key, value);
+                }
+            }
+        }
+
+        state
+    }
+
+    /// Record the current view state of the app as the most recent session, and the bookmarks
+    /// of the files it changed them for.
+    pub fn record(&mut self, app: &App) {
+        let key = session_key(&app.path);
+        let file = self.files.entry(key.clone()).or_default();
+        *file = FileSession {
+            bookmarks: std::mem::take(&mut file.bookmarks),
+            ..FileSession::capture(app)
+        };
+        for (path, bookmarks) in &app.bookmarks {
+            self.files.entry(path.clone()).or_default().bookmarks = bookmarks.clone();
+        }
+        self.last_file = Some(key);
+        self.panes = app.pane_layout;
+        self.numbers = numbers::current();
+    }
+
+    /// Bookmarks of every file that has some, by session key.
+    pub fn bookmarks(&self) -> BTreeMap<PathBuf, Vec<Bookmark>> {
+        self.files
+            .iter()
+            .filter(|(_, file)| !file.bookmarks.is_empty())
+            .map(|(path, file)| (path.clone(), file.bookmarks.clone()))
+            .collect()
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(last_file) = &self.last_file {
+            writeln!(f, "last_file={}", last_file.display())?;
+        }
+        if self.panes != PaneLayout::default() {
+            writeln!(f, "panes={}", self.panes)?;
+        }
+        if self.numbers != NumberFormat::default() {
+            writeln!(f, "numbers={}", self.numbers)?;
+        }
+        if let Some(start_view) = self.start_view {
+            writeln!(f, "start_view={}", start_view)?;
+        }
+
+        for (path, file) in &self.files {
+            writeln!(f)?;
+            writeln!(f, "[{}]", path.display())?;
+            write!(f, "{}", file)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FileSession {
+    /// Snapshot the view state of the app.
+    pub fn capture(app: &App) -> Self {
+        let column = app.column_chunk_view_state.selected().unwrap_or_default();
+        let preview_filter = match &app.overlay {
+            Some(Overlay::Preview(preview)) => preview.filter.as_ref().map(Predicate::to_string),
+            _ => None,
+        };
+        Self {
+            row_group: app.row_group_view_state.selected().unwrap_or_default(),
+            column: goto::column_paths(&app.parquet_metadata)
+                .get(column)
+                .cloned(),
+            active_pane: app.active_pane,
+            type_filter: app
+                .column_view
+                .type_filter
+                .map(|type_filter| type_filter.name().to_string()),
+            preview_filter,
+            bookmarks: Vec::new(),
+        }
+    }
+
+    /// Restore the view state into the app. Selections that no longer exist in the file (e.g.
+    /// because it was rewritten) are ignored.
+    pub fn apply(&self, app: &mut App) {
+        if self.row_group >= app.num_row_groups() {
+            return;
+        }
+        *app.row_group_view_state.selected_mut() = Some(self.row_group);
+        app.decode_selected_row_group();
+
+        app.column_view.type_filter = self.type_filter.as_ref().and_then(|name| {
+            TypeFilter::available(&app.parquet_metadata)
+                .into_iter()
+                .map(|(type_filter, _)| type_filter)
+                .find(|type_filter| type_filter.name() == name)
+        });
+        let column = self.column.as_ref().and_then(|column| {
+            goto::column_paths(&app.parquet_metadata)
+                .iter()
+                .position(|path| path == column)
+        });
+        if let Some(column) = column {
+            *app.column_chunk_view_state.selected_mut() = Some(column);
+        }
+        // Keep the selection on a column the type filter shows.
+        app.step_column(0);
+        app.active_pane = self.active_pane;
+
+        if let Some(filter) = self
+            .preview_filter
+            .as_ref()
+            .and_then(|filter| filter.parse::<Predicate>().ok())
+        {
+            app.open_preview(Some(filter), None);
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "row_group" => self.row_group = value.parse().unwrap_or_default(),
+            "column" => self.column = Some(value.to_string()),
+            "active_pane" => {
+                self.active_pane = match value {
+                    "column" => ActivePane::ColumnBrowser,
+                    "column_detail" => ActivePane::ColumnDetail,
+                    _ => ActivePane::RowGroupBrowser,
+                }
+            }
+            "type_filter" => self.type_filter = Some(value.to_string()),
+            "preview_filter" => self.preview_filter = Some(value.to_string()),
+            "bookmark" => {
+                if let Some((row_group, column)) = value.split_once(':') {
+                    if let Ok(row_group) = row_group.parse() {
+                        self.bookmarks.push(Bookmark {
+                            row_group,
+                            column: column.to_string(),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for FileSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "row_group={}", self.row_group)?;
+        if let Some(column) = &self.column {
+            writeln!(f, "column={}", column)?;
+        }
+        writeln!(
+            f,
+            "active_pane={}",
+            match self.active_pane {
+                ActivePane::RowGroupBrowser => "row_group",
+                ActivePane::ColumnBrowser => "column",
+                ActivePane::ColumnDetail => "column_detail",
+            }
+        )?;
+        if let Some(type_filter) = &self.type_filter {
+            writeln!(f, "type_filter={}", type_filter)?;
+        }
+        if let Some(preview_filter) = &self.preview_filter {
+            writeln!(f, "preview_filter={}", preview_filter)?;
+        }
+        for bookmark in &self.bookmarks {
+            writeln!(f, "bookmark={}:{}", bookmark.row_group, bookmark.column)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATE: &str = "last_file=/data/events.parquet
+panes=30,40,12
+numbers=grouped
+start_view=schema
+
+[/data/events.parquet]
+row_group=3
+column=user.id
+active_pane=column
+type_filter=STRING
+preview_filter=country = NL
+bookmark=0:user.id
+bookmark=7:payload
+
+[/data/other.parquet]
+row_group=0
+active_pane=row_group
+";
+
+    #[test]
+    fn parses_the_state_file() {
+        let state = SessionState::parse(STATE);
+        assert_eq!(state.last_file, Some(PathBuf::from("/data/events.parquet")));
+        assert_eq!(state.panes, "30,40,12".parse().unwrap());
+        assert_eq!(state.numbers, NumberFormat::Grouped);
+        assert_eq!(state.start_view, Some(StartView::Schema));
+
+        let file = &state.files[Path::new("/data/events.parquet")];
+        assert_eq!(
+            *file,
+            FileSession {
+                row_group: 3,
+                column: Some("user.id".to_string()),
+                active_pane: ActivePane::ColumnBrowser,
+                type_filter: Some("STRING".to_string()),
+                preview_filter: Some("country = NL".to_string()),
+                bookmarks: vec![
+                    Bookmark {
+                        row_group: 0,
+                        column: "user.id".to_string(),
+                    },
+                    Bookmark {
+                        row_group: 7,
+                        column: "payload".to_string(),
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            state.files[Path::new("/data/other.parquet")],
+            FileSession::default()
+        );
+    }
+
+    #[test]
+    fn writes_the_state_file_back_unchanged() {
+        let state = SessionState::parse(STATE);
+        assert_eq!(state.to_string(), STATE);
+        assert_eq!(SessionState::parse(&state.to_string()), state);
+    }
+
+    #[test]
+    fn skips_lines_it_does_not_understand() {
+        let state = SessionState::parse(
+            "# comment\nunknown=1\nnumbers=nonsense\n[/a.parquet]\nrow_group=x\nbookmark=y\n",
+        );
+        assert_eq!(state.numbers, NumberFormat::default());
+        assert_eq!(state.files[Path::new("/a.parquet")], FileSession::default());
+    }
+}
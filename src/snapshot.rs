@@ -0,0 +1,354 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::ValueEnum;
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// File format used when dumping the rendered screen.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, ValueEnum)]
+pub enum SnapshotFormat {
+    /// Plain text, without any styling.
+    #[default]
+    Text,
+    /// Text with ANSI escape sequences for colors and modifiers, viewable with `cat` or `less -R`.
+    Ansi,
+    /// Standalone SVG image rendering the text in a monospace font.
+    Svg,
+}
+
+impl SnapshotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SnapshotFormat::Text => "txt",
+            SnapshotFormat::Ansi => "ans",
+            SnapshotFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Render a buffer in the given snapshot format.
+pub fn render(buf: &Buffer, format: SnapshotFormat) -> String {
+    match format {
+        SnapshotFormat::Text => render_text(buf),
+        SnapshotFormat::Ansi => render_ansi(buf),
+        SnapshotFormat::Svg => render_svg(buf),
+    }
+}
+
+/// Write a snapshot of the buffer into `dir`, named after the inspected file and the current
+/// time. Returns the path that was written.
+pub fn write<P: AsRef<Path>>(
+    buf: &Buffer,
+    format: SnapshotFormat,
+    dir: P,
+    file_name: &str,
+) -> io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = dir.as_ref().join(format!(
+        "{}-{}.{}",
+        file_name,
+        timestamp,
+        format.extension()
+    ));
+    fs::write(&path, render(buf, format))?;
+
+    Ok(path)
+}
+
+/// Iterate over the visible cells of each row of the buffer, skipping the cells covered by
+/// the trailing half of wide characters.
+fn rows(buf: &Buffer) -> impl Iterator<Item = Vec<&Cell>> {
+    let area = buf.area;
+    (area.top()..area.bottom()).map(move |y| {
+        let mut cells = Vec::with_capacity(area.width as usize);
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell = buf.get(x, y);
+            skip = cell.symbol().width().saturating_sub(1);
+            cells.push(cell);
+        }
+        cells
+    })
+}
+
+fn render_text(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buf) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    out
+}
+
+fn render_ansi(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buf) {
+        let mut current: Option<(Color, Color, Modifier)> = None;
+        for cell in row {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Select Graphic Rendition sequence that resets the style and applies the given one.
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+
+    if let Some(code) = ansi_color(fg, false) {
+        codes.push(code);
+    }
+    if let Some(code) = ansi_color(bg, true) {
+        codes.push(code);
+    }
+
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn ansi_color(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base.to_string(),
+        Color::Red => (base + 1).to_string(),
+        Color::Green => (base + 2).to_string(),
+        Color::Yellow => (base + 3).to_string(),
+        Color::Blue => (base + 4).to_string(),
+        Color::Magenta => (base + 5).to_string(),
+        Color::Cyan => (base + 6).to_string(),
+        Color::Gray => (base + 7).to_string(),
+        Color::DarkGray => (base + 60).to_string(),
+        Color::LightRed => (base + 61).to_string(),
+        Color::LightGreen => (base + 62).to_string(),
+        Color::LightYellow => (base + 63).to_string(),
+        Color::LightBlue => (base + 64).to_string(),
+        Color::LightMagenta => (base + 65).to_string(),
+        Color::LightCyan => (base + 66).to_string(),
+        Color::White => (base + 67).to_string(),
+        Color::Indexed(i) => format!("{};5;{}", base + 8, i),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    };
+    Some(code)
+}
+
+/// Width and height of one character cell in the SVG, in pixels.
+const SVG_CELL_WIDTH: u32 = 9;
+const SVG_CELL_HEIGHT: u32 = 18;
+
+const SVG_DEFAULT_FG: &str = "#d0d0d0";
+const SVG_DEFAULT_BG: &str = "#1c1c1c";
+
+fn render_svg(buf: &Buffer) -> String {
+    let width = u32::from(buf.area.width) * SVG_CELL_WIDTH;
+    let height = u32::from(buf.area.height) * SVG_CELL_HEIGHT;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        SVG_DEFAULT_BG
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<g font-family="Menlo, Consolas, 'DejaVu Sans Mono', monospace" font-size="14px" xml:space="preserve">"#
+    )
+    .unwrap();
+
+    for (y, row) in rows(buf).enumerate() {
+        let top = y as u32 * SVG_CELL_HEIGHT;
+        let baseline = top + SVG_CELL_HEIGHT - 5;
+
+        // Text is emitted in runs of cells sharing a style. `textLength` stretches each run to
+        // the grid so it lines up regardless of the viewer's font metrics.
+        let mut run = SvgRun::default();
+        let mut x = 0;
+
+        for cell in row {
+            let cell_width = cell.symbol().width().max(1) as u32;
+            let (mut fg, mut bg) = (svg_color(cell.fg), svg_color(cell.bg));
+            if cell.modifier.contains(Modifier::REVERSED) {
+                std::mem::swap(&mut fg, &mut bg);
+            }
+
+            if let Some(bg) = bg {
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x * SVG_CELL_WIDTH,
+                    top,
+                    cell_width * SVG_CELL_WIDTH,
+                    SVG_CELL_HEIGHT,
+                    bg
+                )
+                .unwrap();
+            }
+
+            let fg = fg.unwrap_or(SVG_DEFAULT_FG.to_string());
+            let bold = cell.modifier.contains(Modifier::BOLD);
+            if run.fg != fg || run.bold != bold || cell_width > 1 {
+                run.flush(&mut out, baseline);
+                run = SvgRun {
+                    start: x,
+                    fg,
+                    bold,
+                    ..Default::default()
+                };
+            }
+            run.text.push_str(cell.symbol());
+            run.width += cell_width;
+
+            x += cell_width;
+        }
+        run.flush(&mut out, baseline);
+    }
+
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+/// Consecutive cells of one SVG row sharing the same text style.
+#[derive(Default)]
+struct SvgRun {
+    start: u32,
+    width: u32,
+    fg: String,
+    bold: bool,
+    text: String,
+}
+
+impl SvgRun {
+    fn flush(&self, out: &mut String, baseline: u32) {
+        let trimmed = self.text.trim_end();
+        if trimmed.trim_start().is_empty() {
+            return;
+        }
+
+        let width = self.width - (self.text.len() - trimmed.len()) as u32;
+        writeln!(
+            out,
+            r#"<text x="{}" y="{}" fill="{}"{} textLength="{}" lengthAdjust="spacingAndGlyphs">{}</text>"#,
+            self.start * SVG_CELL_WIDTH,
+            baseline,
+            self.fg,
+            if self.bold { r#" font-weight="bold""# } else { "" },
+            width * SVG_CELL_WIDTH,
+            xml_escape(trimmed)
+        )
+        .unwrap();
+    }
+}
+
+fn svg_color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Reset => return None,
+        Color::Black => "#000000",
+        Color::Red => "#cd3131",
+        Color::Green => "#0dbc79",
+        Color::Yellow => "#e5e510",
+        Color::Blue => "#2472c8",
+        Color::Magenta => "#bc3fbc",
+        Color::Cyan => "#11a8cd",
+        Color::Gray => "#c0c0c0",
+        Color::DarkGray => "#666666",
+        Color::LightRed => "#f14c4c",
+        Color::LightGreen => "#23d18b",
+        Color::LightYellow => "#f5f543",
+        Color::LightBlue => "#3b8eea",
+        Color::LightMagenta => "#d670d6",
+        Color::LightCyan => "#29b8db",
+        Color::White => "#ffffff",
+        Color::Indexed(i) => return Some(indexed_color(i)),
+        Color::Rgb(r, g, b) => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+    };
+    Some(hex.to_string())
+}
+
+/// Convert an xterm 256-color palette index to a hex color.
+fn indexed_color(i: u8) -> String {
+    match i {
+        0..=15 => {
+            const BASIC: [Color; 16] = [
+                Color::Black,
+                Color::Red,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::Gray,
+                Color::DarkGray,
+                Color::LightRed,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightMagenta,
+                Color::LightCyan,
+                Color::White,
+            ];
+            svg_color(BASIC[i as usize]).unwrap()
+        }
+        16..=231 => {
+            let i = i - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(i / 36),
+                level((i / 6) % 6),
+                level(i % 6)
+            )
+        }
+        _ => {
+            let gray = 8 + (i - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", gray, gray, gray)
+        }
+    }
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
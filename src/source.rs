@@ -0,0 +1,504 @@
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+    time::{Instant, SystemTime},
+};
+
+use bytes::Bytes;
+use memmap2::Mmap;
+use parquet::file::reader::{ChunkReader, Length};
+
+use crate::{
+    archive,
+    page_cache::{self, RangeKind, READ_WINDOW},
+    s3::{self, REMOTE_READ_WINDOW},
+};
+
+static MMAP_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Bytes prefetched from the start of each column chunk, enough for the dictionary page and the
+/// first data pages of most.
+pub const PREFETCH_WINDOW: u64 = 256 * 1024;
+
+/// Range requests [prefetch] makes at once.
+const PREFETCH_REQUESTS: usize = 8;
+
+/// Largest range [prefetch] requests at once, after merging the ranges of nearby chunks.
+const MAX_PREFETCH_REQUEST: u64 = 4 * 1024 * 1024;
+
+/// Memory-map files instead of reading them, for the rest of the process, e.g. for `--mmap`.
+pub fn enable_mmap() {
+    MMAP_ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_mmap_enabled() -> bool {
+    MMAP_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Scheme of a path given as a URI, such as `s3` for `s3://bucket/key.parquet`. Only s3:// URIs
+/// can be read; the others would otherwise fail with a confusing missing file error.
+pub fn remote_scheme(path: &Path) -> Option<&str> {
+    let (scheme, _) = path.to_str()?.split_once("://")?;
+    (!scheme.is_empty()
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+'))
+    .then_some(scheme)
+}
+
+/// Size of a file in bytes, or of the Parquet file inside an archive.
+pub fn file_size<P: AsRef<Path>>(path: P) -> io::Result<u64> {
+    if let Some(uri) = s3_uri(path.as_ref()) {
+        return Ok(s3::stat(uri)?.len());
+    }
+    match archive::split_member(path.as_ref()).filter(|_| !path.as_ref().exists()) {
+        Some(_) => Ok(Length::len(&FileSource::open(path)?)),
+        None => Ok(std::fs::metadata(path)?.len()),
+    }
+}
+
+/// Size and modification time of a file, or of the archive holding it, to notice when it is
+/// replaced or truncated while it is open.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FileIdentity {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileIdentity {
+    pub fn of<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(uri) = s3_uri(path) {
+            return Ok(s3::stat(uri)?.identity);
+        }
+        let metadata = match archive::split_member(path).filter(|_| !path.exists()) {
+            Some((archive, _)) => std::fs::metadata(archive)?,
+            None => std::fs::metadata(path)?,
+        };
+        Ok(Self::from(&metadata))
+    }
+
+    /// Identity of an open file, which stays the one it was opened as if its path is replaced.
+    pub fn of_file(file: &File) -> io::Result<Self> {
+        Ok(Self::from(&file.metadata()?))
+    }
+}
+
+impl From<&std::fs::Metadata> for FileIdentity {
+    fn from(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+}
+
+/// The URI of an s3:// path.
+fn s3_uri(path: &Path) -> Option<&str> {
+    remote_scheme(path)
+        .filter(|scheme| *scheme == "s3")
+        .and(path.to_str())
+}
+
+/// Read the first [PREFETCH_WINDOW] bytes of each of the column chunks of a file read from S3,
+/// given as their start and length, into the [page_cache] on a background thread, so that
+/// sampling and page analyses of them do not wait for requests. Chunks whose start is already
+/// cached are skipped, and the ranges of nearby chunks are fetched with a single request. Local
+/// files are left alone.
+///
+/// Failures are only logged: the reads that need the chunks fail with them instead.
+pub fn prefetch(path: &Path, chunks: Vec<(u64, u64)>) {
+    let Some(uri) = s3_uri(path).map(str::to_string) else {
+        return;
+    };
+    let path = path.to_path_buf();
+    thread::spawn(move || {
+        let object = match s3::stat(&uri) {
+            Ok(object) => object,
+            Err(err) => return tracing::debug!("not prefetching {}: {}", uri, err),
+        };
+        let chunks = chunks
+            .into_iter()
+            .filter(|(start, len)| {
+                *len > 0 && page_cache::find(&path, object.identity, *start).is_none()
+            })
+            .map(|(start, len)| (start, len.min(PREFETCH_WINDOW)))
+            .collect();
+        let ranges = coalesce(chunks, READ_WINDOW as u64, MAX_PREFETCH_REQUEST);
+        let started = Instant::now();
+        let next = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..PREFETCH_REQUESTS.min(ranges.len()) {
+                scope.spawn(|| {
+                    while let Some(&(start, len)) = ranges.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        match s3::read_range(&object, start, len) {
+                            Ok(bytes) => page_cache::insert(
+                                &path,
+                                object.identity,
+                                RangeKind::Raw,
+                                start,
+                                bytes,
+                            ),
+                            Err(err) => {
+                                tracing::debug!(
+                                    "prefetching {} from {} failed: {}",
+                                    uri,
+                                    start,
+                                    err
+                                )
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        tracing::debug!(
+            "prefetched {} ranges of {} in {:?}",
+            ranges.len(),
+            uri,
+            started.elapsed()
+        );
+    });
+}
+
+/// Merge byte ranges, given as their start and length, that are at most `gap` bytes apart into
+/// ranges of at most `max_len` bytes, unless a single range is longer. Sorted by start.
+fn coalesce(mut ranges: Vec<(u64, u64)>, gap: u64, max_len: u64) -> Vec<(u64, u64)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, len) in ranges {
+        if let Some((merged_start, merged_len)) = merged.last_mut() {
+            let merged_end = *merged_start + *merged_len;
+            let end = (start + len).max(merged_end);
+            if start <= merged_end + gap && end - *merged_start <= max_len {
+                *merged_len = end - *merged_start;
+                continue;
+            }
+        }
+        merged.push((start, len));
+    }
+    merged
+}
+
+/// How an open file changed on disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// Rewritten or replaced: its metadata may no longer match its pages.
+    Modified,
+    /// Shorter than when it was opened, so reads past its new end fail.
+    Truncated,
+    Removed,
+}
+
+impl FileChange {
+    /// Compare the identity of a file with the one it had when it was opened.
+    pub fn detect(opened: &FileIdentity, now: io::Result<FileIdentity>) -> Option<Self> {
+        match now {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Some(FileChange::Removed),
+            // Other errors, such as a permission change, say nothing about the contents.
+            Err(_) => None,
+            Ok(now) if now.len < opened.len => Some(FileChange::Truncated),
+            Ok(now) if now != *opened => Some(FileChange::Modified),
+            Ok(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileChange::Modified => "changed",
+            FileChange::Truncated => "truncated",
+            FileChange::Removed => "removed",
+        }
+    }
+}
+
+/// Readable and seekable byte stream, as used to scan footers and page headers.
+pub trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Reader over a file through the [page_cache]: reads are served from cached ranges, and
+/// misses read a window of the file ahead and cache it, so that sampling, scans and
+/// verifications repeated over the same chunk do not read it again.
+///
+/// Archive members are read as the byte range of the archive from `offset`, of `len` bytes.
+/// Objects of a bucket are read with range requests, of [REMOTE_READ_WINDOW] bytes at least.
+struct CachedReader {
+    backing: Backing,
+    path: PathBuf,
+    identity: FileIdentity,
+    offset: u64,
+    len: Option<u64>,
+    position: u64,
+}
+
+enum Backing {
+    File(File),
+    Remote(s3::Object),
+}
+
+impl Read for CachedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = match self.len {
+            Some(len) => len.saturating_sub(self.position),
+            None => u64::MAX,
+        };
+        if buf.is_empty() || remaining == 0 {
+            return Ok(0);
+        }
+        let cached = match page_cache::find(&self.path, self.identity, self.position) {
+            Some(cached) => cached,
+            None => {
+                let window = match &mut self.backing {
+                    Backing::File(file) => {
+                        let mut window = Vec::new();
+                        file.seek(SeekFrom::Start(self.offset + self.position))?;
+                        (&*file)
+                            .take((buf.len().max(READ_WINDOW) as u64).min(remaining))
+                            .read_to_end(&mut window)?;
+                        Bytes::from(window)
+                    }
+                    Backing::Remote(object) => s3::read_range(
+                        object,
+                        self.offset + self.position,
+                        (buf.len().max(REMOTE_READ_WINDOW) as u64).min(remaining),
+                    )?,
+                };
+                page_cache::insert(
+                    &self.path,
+                    self.identity,
+                    RangeKind::Raw,
+                    self.position,
+                    window.clone(),
+                );
+                window
+            }
+        };
+        let n = cached.len().min(buf.len());
+        buf[..n].copy_from_slice(&cached[..n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for CachedReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+            SeekFrom::End(delta) => match (self.len, &self.backing) {
+                (Some(len), _) => len,
+                (None, Backing::File(file)) => file.metadata()?.len(),
+                (None, Backing::Remote(object)) => object.len(),
+            }
+            .checked_add_signed(delta),
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative position")
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// A Parquet file opened for reading, either through regular file IO or memory-mapped.
+///
+/// Regular files are read through the [page_cache], keyed by their path and identity. Mapped files hand
+/// out pages as slices of the mapping, so sampling and scans do not copy column chunks onto
+/// the heap. The mapping assumes the file is not modified while it is open.
+///
+/// Parquet files inside archives, given as `archive.zip!member.parquet`, are read as a byte
+/// range of the archive when stored as is, or from memory once decompressed.
+///
+/// Objects of a bucket, given as `s3://bucket/key.parquet`, are read with range requests
+/// through the [page_cache], keyed by their URI and the size and date of the object. They are
+/// never memory-mapped.
+#[derive(Debug)]
+pub enum FileSource {
+    File {
+        file: File,
+        path: PathBuf,
+    },
+    Range {
+        file: File,
+        path: PathBuf,
+        offset: u64,
+        len: u64,
+    },
+    Remote {
+        path: PathBuf,
+        object: s3::Object,
+    },
+    Mapped(Bytes),
+}
+
+impl FileSource {
+    /// Open a file, memory-mapping it if [enable_mmap] was called.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        if !path.as_ref().exists() {
+            if let Some((archive, member)) = archive::split_member(path.as_ref()) {
+                return archive::open_member(&archive, &member);
+            }
+        }
+        if let Some(uri) = s3_uri(path.as_ref()) {
+            return Ok(FileSource::Remote {
+                path: path.as_ref().to_path_buf(),
+                object: s3::stat(uri)?,
+            });
+        }
+        if let Some(scheme) = remote_scheme(path.as_ref()) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "{}:// URIs are not supported, only s3:// URIs and local files: download \
+                     the file first",
+                    scheme
+                ),
+            ));
+        }
+        let file = File::open(&path)?;
+        if !is_mmap_enabled() {
+            return Ok(FileSource::File {
+                file,
+                path: path.as_ref().to_path_buf(),
+            });
+        }
+
+        // SAFETY: the console only reads files, and the mapping is documented to assume the
+        // file is not truncated while it is inspected.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(FileSource::Mapped(Bytes::from_owner(mmap)))
+    }
+
+    /// The `len` bytes of `file` from `offset`, read as a file of their own under `path`, e.g.
+    /// a member of an archive. Memory-mapped if [enable_mmap] was called.
+    pub fn range(file: File, path: PathBuf, offset: u64, len: u64) -> io::Result<Self> {
+        if offset.saturating_add(len) > file.metadata()?.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("{} is truncated", path.display()),
+            ));
+        }
+        if !is_mmap_enabled() {
+            return Ok(FileSource::Range {
+                file,
+                path,
+                offset,
+                len,
+            });
+        }
+
+        // SAFETY: as for [FileSource::open].
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(FileSource::Mapped(
+            Bytes::from_owner(mmap).slice(offset as usize..(offset + len) as usize),
+        ))
+    }
+
+    /// Sequential reader over the whole file.
+    pub fn reader(&self) -> io::Result<Box<dyn ReadSeek>> {
+        self.reader_at(0)
+    }
+
+    fn reader_at(&self, start: u64) -> io::Result<Box<dyn ReadSeek>> {
+        Ok(match self {
+            FileSource::File { file, path } => Box::new(CachedReader {
+                backing: Backing::File(file.try_clone()?),
+                path: path.clone(),
+                identity: FileIdentity::of_file(file)?,
+                offset: 0,
+                len: None,
+                position: start,
+            }),
+            FileSource::Range {
+                file,
+                path,
+                offset,
+                len,
+            } => Box::new(CachedReader {
+                backing: Backing::File(file.try_clone()?),
+                path: path.clone(),
+                identity: FileIdentity::of_file(file)?,
+                offset: *offset,
+                len: Some(*len),
+                position: start,
+            }),
+            FileSource::Remote { path, object } => Box::new(CachedReader {
+                backing: Backing::Remote(object.clone()),
+                path: path.clone(),
+                identity: object.identity,
+                offset: 0,
+                len: Some(object.len()),
+                position: start,
+            }),
+            FileSource::Mapped(bytes) => {
+                let mut cursor = Cursor::new(bytes.clone());
+                cursor.set_position(start);
+                Box::new(cursor)
+            }
+        })
+    }
+}
+
+impl Length for FileSource {
+    fn len(&self) -> u64 {
+        match self {
+            FileSource::File { file, .. } => Length::len(file),
+            FileSource::Range { len, .. } => *len,
+            FileSource::Remote { object, .. } => object.len(),
+            FileSource::Mapped(bytes) => bytes.len() as u64,
+        }
+    }
+}
+
+impl ChunkReader for FileSource {
+    type T = Box<dyn Read + Send>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        Ok(match self {
+            FileSource::File { .. } | FileSource::Range { .. } | FileSource::Remote { .. } => {
+                Box::new(self.reader_at(start)?)
+            }
+            FileSource::Mapped(bytes) => Box::new(bytes.get_read(start)?),
+        })
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        match self {
+            FileSource::File { .. } | FileSource::Range { .. } | FileSource::Remote { .. } => {
+                let mut bytes = vec![0; length];
+                self.reader_at(start)?.read_exact(&mut bytes)?;
+                Ok(bytes.into())
+            }
+            FileSource::Mapped(bytes) => bytes.get_bytes(start, length),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coalesces_nearby_ranges() {
+        assert_eq!(
+            coalesce(vec![(100, 10), (0, 50), (60, 20)], 20, 1000),
+            vec![(0, 110)]
+        );
+        // Too far apart.
+        assert_eq!(
+            coalesce(vec![(0, 10), (100, 10)], 20, 1000),
+            vec![(0, 10), (100, 10)]
+        );
+        // Too long once merged, but kept whole on their own.
+        assert_eq!(
+            coalesce(vec![(0, 60), (60, 60), (120, 200)], 0, 150),
+            vec![(0, 120), (120, 200)]
+        );
+        // Overlapping.
+        assert_eq!(coalesce(vec![(0, 100), (10, 20)], 0, 1000), vec![(0, 100)]);
+    }
+}
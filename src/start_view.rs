@@ -0,0 +1,54 @@
+use std::{fmt, str::FromStr};
+
+use clap::ValueEnum;
+
+/// View the TUI opens on, for users who start from different questions: how the file is laid
+/// out, what its schema is, which files a dataset holds, or what the data looks like. With
+/// `inspect --view` it is shown full screen.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum StartView {
+    /// The row group browser.
+    #[default]
+    RowGroups,
+    /// The row groups drawn as boxes scaled by their compressed size.
+    Layout,
+    /// The column browser, with nested columns grouped under their parents.
+    Schema,
+    /// Which column chunks have min/max, null count and distinct count statistics.
+    Stats,
+    /// The list of inspected files with their rows and sizes, when several are inspected.
+    Dataset,
+    /// The data preview of the selected row group.
+    #[value(alias = "data")]
+    Preview,
+    /// The page overhead analysis of the selected row group.
+    Pages,
+}
+
+impl StartView {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartView::RowGroups => "row-groups",
+            StartView::Layout => "layout",
+            StartView::Schema => "schema",
+            StartView::Stats => "stats",
+            StartView::Dataset => "dataset",
+            StartView::Preview => "preview",
+            StartView::Pages => "pages",
+        }
+    }
+}
+
+impl fmt::Display for StartView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for StartView {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as ValueEnum>::from_str(s, true)
+    }
+}
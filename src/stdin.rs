@@ -0,0 +1,64 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Context};
+
+/// File argument that reads the file from standard input.
+pub const STDIN_ARG: &str = "-";
+
+pub fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == STDIN_ARG
+}
+
+/// Standard input buffered into a temporary file, so it can be read with random access like
+/// any other file. The file is removed when this is dropped.
+#[derive(Debug)]
+pub struct BufferedStdin {
+    path: PathBuf,
+}
+
+impl BufferedStdin {
+    pub fn read() -> io::Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "parquet_console-stdin-{}.parquet",
+            std::process::id()
+        ));
+        let buffered = Self { path };
+        io::copy(&mut io::stdin().lock(), &mut File::create(&buffered.path)?)?;
+        Ok(buffered)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for BufferedStdin {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Replace a `-` file argument by the path of standard input buffered into a temporary file.
+///
+/// Standard input can only be read once, so at most one argument may be `-`. Footers of
+/// buffered files are never cached, since the temporary file does not outlive the process.
+pub fn resolve(file: &mut PathBuf, buffered: &mut Option<BufferedStdin>) -> color_eyre::Result<()> {
+    if !is_stdin(file) {
+        return Ok(());
+    }
+    if buffered.is_some() {
+        return Err(eyre!(
+            "standard input can only be used for one FILE argument"
+        ));
+    }
+
+    let stdin = BufferedStdin::read().wrap_err("failed to buffer standard input")?;
+    crate::cache::disable();
+    *file = stdin.path().to_path_buf();
+    *buffered = Some(stdin);
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat};
+use clap::ValueEnum;
+use parquet2::schema::types::TimeUnit;
+
+/// Time zone TIMESTAMP values are shown in, in statistics, samples and the data preview.
+///
+/// Only timestamps adjusted to UTC are instants that can be converted. Others are local date
+/// times without a zone, and are shown as stored in every mode but `original`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum TimeZoneMode {
+    /// Instants in UTC, e.g. `2024-03-01T12:00:00.000Z`.
+    #[default]
+    Utc,
+    /// Instants in the local time zone, with its offset, e.g. `2024-03-01T13:00:00.000+01:00`.
+    Local,
+    /// The stored integers, e.g. `1709294400000`.
+    Original,
+}
+
+impl TimeZoneMode {
+    pub const ALL: [TimeZoneMode; 3] = [
+        TimeZoneMode::Utc,
+        TimeZoneMode::Local,
+        TimeZoneMode::Original,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeZoneMode::Utc => "utc",
+            TimeZoneMode::Local => "local",
+            TimeZoneMode::Original => "original",
+        }
+    }
+
+    /// The next mode, wrapping around, as cycled with `Z`.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for TimeZoneMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TimeZoneMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as ValueEnum>::from_str(s, true)
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(TimeZoneMode::Utc as u8);
+
+/// Show timestamps in `mode` for the rest of the process, e.g. for `--tz` or `Z`.
+pub fn set(mode: TimeZoneMode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> TimeZoneMode {
+    TimeZoneMode::ALL[MODE.load(Ordering::Relaxed) as usize]
+}
+
+/// A timestamp of `unit` since the epoch as a date and time in the current mode, or `None` in
+/// the `original` mode or when it is out of range. `adjusted_to_utc` is the `isAdjustedToUTC`
+/// flag of the column's logical type, set for the legacy TIMESTAMP_MILLIS and
+/// TIMESTAMP_MICROS converted types.
+pub fn render_timestamp(value: i64, unit: TimeUnit, adjusted_to_utc: bool) -> Option<String> {
+    let mode = current();
+    if mode == TimeZoneMode::Original {
+        return None;
+    }
+    let (instant, digits) = match unit {
+        TimeUnit::Milliseconds => (
+            DateTime::from_timestamp_millis(value)?,
+            SecondsFormat::Millis,
+        ),
+        TimeUnit::Microseconds => (
+            DateTime::from_timestamp_micros(value)?,
+            SecondsFormat::Micros,
+        ),
+        TimeUnit::Nanoseconds => (DateTime::from_timestamp_nanos(value), SecondsFormat::Nanos),
+    };
+    Some(match (adjusted_to_utc, mode) {
+        (false, _) => naive(instant.naive_utc(), digits),
+        (true, TimeZoneMode::Local) => instant.with_timezone(&Local).to_rfc3339_opts(digits, true),
+        (true, _) => instant.to_rfc3339_opts(digits, true),
+    })
+}
+
+/// A date and time without a zone, in the RFC 3339 layout without the offset.
+fn naive(date_time: NaiveDateTime, digits: SecondsFormat) -> String {
+    let fraction = match digits {
+        SecondsFormat::Millis => "%.3f",
+        SecondsFormat::Micros => "%.6f",
+        _ => "%.9f",
+    };
+    date_time
+        .format(&format!("%Y-%m-%dT%H:%M:%S{}", fraction))
+        .to_string()
+}
@@ -0,0 +1,77 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Most notifications shown at once. Older ones are dropped first when more arrive.
+pub const MAX_TOASTS: usize = 4;
+
+/// How much a notification matters, which sets its color and how long it stays.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ToastLevel {
+    /// The outcome of an action, e.g. an export or a copy.
+    Info,
+    /// An action that did less than asked, e.g. checksums that failed.
+    Warn,
+    /// An action that failed.
+    Error,
+}
+
+impl ToastLevel {
+    /// How long a notification stays: longer for the ones users need time to read.
+    pub fn lifetime(&self) -> Duration {
+        match self {
+            ToastLevel::Info => Duration::from_secs(3),
+            ToastLevel::Warn => Duration::from_secs(6),
+            ToastLevel::Error => Duration::from_secs(10),
+        }
+    }
+
+    /// Marker in front of the text, so that levels can be told apart without colors.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ToastLevel::Info => "•",
+            ToastLevel::Warn => "!",
+            ToastLevel::Error => "✗",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub text: String,
+    shown_at: Instant,
+}
+
+/// Transient notifications, shown in a corner until they expire, oldest first.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, text: impl Into<String>) {
+        if self.queue.len() == MAX_TOASTS {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Toast {
+            level,
+            text: text.into(),
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Drop the notifications shown for longer than their level's lifetime at `now`. Returns
+    /// whether any were, so that the screen is redrawn without them.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.queue.len();
+        self.queue
+            .retain(|toast| now.duration_since(toast.shown_at) < toast.level.lifetime());
+        self.queue.len() != before
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.queue.iter()
+    }
+}
@@ -1,8 +1,11 @@
 use std::{
-    io::{self, stdout, Stdout},
+    io::{self, stdout, IsTerminal, Stdout},
     panic,
 };
 
+use clap::ValueEnum;
+
+use color_eyre::config::Theme;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -11,6 +14,35 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
+/// How `inspect` shows the main view.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, ValueEnum)]
+pub enum RenderMode {
+    /// The interactive TUI, on the alternate screen.
+    #[default]
+    Tui,
+    /// The main view rendered once to stdout, without raw mode or the alternate screen, e.g.
+    /// for pipes and screenshots in docs.
+    Plain,
+}
+
+/// Size plain renders are drawn at: the terminal's when stdout is one, else `COLUMNS` and
+/// `LINES` when set, else 120 by 40.
+pub fn plain_size() -> (u16, u16) {
+    if stdout().is_terminal() {
+        if let Ok(size) = crossterm::terminal::size() {
+            return size;
+        }
+    }
+    let var = |name, default| {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .filter(|&value| value > 0)
+            .unwrap_or(default)
+    };
+    (var("COLUMNS", 120), var("LINES", 40))
+}
+
 pub fn init() -> io::Result<Tui> {
     execute!(stdout(), EnterAlternateScreen)?;
     enable_raw_mode()?;
@@ -22,7 +54,12 @@ pub fn init() -> io::Result<Tui> {
 }
 
 pub fn install_hooks() -> color_eyre::Result<()> {
-    let hook = color_eyre::config::HookBuilder::default();
+    let theme = if crate::color::is_enabled() {
+        Theme::dark()
+    } else {
+        Theme::new()
+    };
+    let hook = color_eyre::config::HookBuilder::default().theme(theme);
     let (panic_hook, eyre_hook) = hook.into_hooks();
     let panic_hook = panic_hook.into_panic_hook();
     panic::set_hook(Box::new(move |panic_info| {
@@ -30,11 +67,10 @@ pub fn install_hooks() -> color_eyre::Result<()> {
         panic_hook(panic_info);
     }));
 
-    let eyre_hook = eyre_hook.into_eyre_hook();
-    color_eyre::eyre::set_hook(Box::new(move |error| {
-        restore().unwrap();
-        eyre_hook(error)
-    }))?;
+    // The eyre hook runs whenever an error is created, including errors handled inside the
+    // TUI or on background threads, so it must not restore the terminal. Errors escaping the
+    // TUI are reported after `restore` by the caller.
+    eyre_hook.install()?;
 
     Ok(())
 }
@@ -3,24 +3,247 @@ use ratatui::{
     Frame,
 };
 
-use crate::App;
+use ratatui::widgets::{Clear, Widget};
 
+use crate::{s3, source, ActivePane, App, Overlay};
+
+pub mod bookmarks;
+pub mod breadcrumbs;
 pub mod column_chunk_browser;
 pub mod column_detail;
+pub mod column_rollup;
+pub mod compare;
+pub mod ddl;
+pub mod decompression;
+pub mod file_list;
+pub mod file_tabs;
+pub mod goto_prompt;
+pub mod levels;
+pub mod log_pane;
+pub mod page_overhead;
+pub mod page_ranges;
+pub mod preview;
+pub mod record;
+pub mod remote_reads;
 pub mod row_group_browser;
+pub mod row_group_detail;
+pub mod scan_stats;
+pub mod size_breakdown;
+pub mod size_charts;
+pub mod split_compare;
+pub mod stale_banner;
+pub mod stats_presence;
+pub mod status_bar;
+pub mod toasts;
+pub mod type_filter;
+pub mod value;
+pub mod value_sizes;
+pub mod yank;
 
 /// Render the user interface.
 pub fn render_ui(frame: &mut Frame, app: &mut App) {
+    app.decode_selected_row_group();
+    // The stale file banner shows even over a zoomed pane.
+    let (banner_rect, screen_rect) = match app.file_change {
+        Some(_) => {
+            let [banner, screen] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(frame.size());
+            (Some(banner), screen)
+        }
+        None => (None, frame.size()),
+    };
+    // A zoomed pane hides the breadcrumbs, file tabs, status bar and log pane.
+    let (crumbs_rect, screen_rect) = if app.zoomed {
+        (None, screen_rect)
+    } else {
+        let [crumbs, screen] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(screen_rect);
+        (Some(crumbs), screen)
+    };
+    let (tabs_rect, screen_rect) = if app.files.len() > 1 && !app.zoomed {
+        let [tabs, screen] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(screen_rect);
+        (Some(tabs), screen)
+    } else {
+        (None, screen_rect)
+    };
+    let (screen_rect, status_rect) = if app.zoomed {
+        (screen_rect, None)
+    } else {
+        let [screen, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(screen_rect);
+        (screen, Some(status))
+    };
+    let (full_rect, log_rect) = if app.show_log && !app.zoomed {
+        let [main, log] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(app.pane_layout.log_height),
+        ])
+        .areas(screen_rect);
+        (main, Some(log))
+    } else {
+        (screen_rect, None)
+    };
     let [first_rect, second_rect, third_rect] = Layout::horizontal([
-        Constraint::Percentage(33),
-        Constraint::Percentage(33),
-        Constraint::Percentage(33),
+        Constraint::Percentage(app.pane_layout.row_groups),
+        Constraint::Percentage(app.pane_layout.columns),
+        Constraint::Percentage(app.pane_layout.detail()),
     ])
-    .areas(frame.size());
+    .areas(full_rect);
 
     let buf = frame.buffer_mut();
 
-    row_group_browser::render(first_rect, buf, app);
-    column_chunk_browser::render(second_rect, buf, app);
-    column_detail::render(third_rect, buf, app);
+    if let (Some(banner_rect), Some(change)) = (banner_rect, app.file_change) {
+        stale_banner::render(banner_rect, buf, change);
+    }
+
+    if let Some(crumbs_rect) = crumbs_rect {
+        breadcrumbs::render(crumbs_rect, buf, app);
+    }
+
+    if let Some(tabs_rect) = tabs_rect {
+        file_tabs::render(tabs_rect, buf, app);
+    }
+
+    if let Some(log_rect) = log_rect {
+        log_pane::render(log_rect, buf);
+    }
+
+    if let Some(status_rect) = status_rect {
+        status_bar::render(status_rect, buf, app);
+    }
+
+    if app.zoomed {
+        match app.active_pane {
+            ActivePane::RowGroupBrowser => row_group_browser::render(full_rect, buf, app),
+            ActivePane::ColumnBrowser => column_chunk_browser::render(full_rect, buf, app),
+            ActivePane::ColumnDetail => column_detail::render(full_rect, buf, app),
+        }
+    } else if app.split_compare && app.compare_file.is_some() {
+        // The compare file's browsers replace the detail panel.
+        let [first_rect, second_rect, third_rect, fourth_rect] =
+            Layout::horizontal([Constraint::Percentage(25); 4]).areas(full_rect);
+        row_group_browser::render(first_rect, buf, app);
+        column_chunk_browser::render(second_rect, buf, app);
+        split_compare::render(third_rect, fourth_rect, buf, app);
+    } else {
+        row_group_browser::render(first_rect, buf, app);
+        column_chunk_browser::render(second_rect, buf, app);
+        match app.active_pane {
+            ActivePane::RowGroupBrowser => row_group_detail::render(third_rect, buf, app),
+            ActivePane::ColumnBrowser | ActivePane::ColumnDetail => {
+                column_detail::render(third_rect, buf, app)
+            }
+        }
+    }
+
+    if let Some(overlay) = app.overlay.as_mut() {
+        // Popups are drawn over the browsers they come from.
+        if !matches!(
+            overlay,
+            Overlay::Yank { .. }
+                | Overlay::Bookmarks { .. }
+                | Overlay::TypeFilter { .. }
+                | Overlay::Value { .. }
+        ) {
+            Clear.render(full_rect, buf);
+        }
+        match overlay {
+            Overlay::PageOverhead {
+                row_group,
+                chunks,
+                state,
+            } => page_overhead::render(full_rect, buf, *row_group, chunks, state),
+            Overlay::SizeCharts { sizes, state } => {
+                size_charts::render(full_rect, buf, sizes, state)
+            }
+            Overlay::SizeBreakdown { breakdown, state } => {
+                size_breakdown::render(full_rect, buf, breakdown, state)
+            }
+            Overlay::RemoteReads { state, .. } => {
+                let uri = app.path.to_string_lossy();
+                remote_reads::render(
+                    full_rect,
+                    buf,
+                    &uri,
+                    source::file_size(&app.path).unwrap_or_default(),
+                    &s3::request_stats(&uri),
+                    state,
+                )
+            }
+            Overlay::PageRanges {
+                row_group,
+                index,
+                state,
+            } => page_ranges::render(full_rect, buf, *row_group, index, &app.redactor, state),
+            Overlay::Levels {
+                row_group,
+                distribution,
+                state,
+            } => levels::render(full_rect, buf, *row_group, distribution, state),
+            Overlay::ValueSizes {
+                row_group,
+                sizes,
+                state,
+            } => value_sizes::render(full_rect, buf, *row_group, sizes, state),
+            Overlay::StatsPresence {
+                matrix,
+                first_row_group,
+                state,
+            } => stats_presence::render(full_rect, buf, matrix, first_row_group, state),
+            Overlay::ScanStats {
+                row_group,
+                columns,
+                state,
+            } => scan_stats::render(full_rect, buf, *row_group, columns, &app.redactor, state),
+            Overlay::ColumnRollup { rollups, state } => {
+                column_rollup::render(full_rect, buf, rollups, &app.redactor, state)
+            }
+            Overlay::Preview(preview) => preview::render(full_rect, buf, preview, &app.queries),
+            Overlay::Record { row, fields, state } => {
+                record::render(full_rect, buf, *row, fields, state)
+            }
+            Overlay::Compare { chunks, state } => {
+                compare::render(full_rect, buf, chunks, &app.redactor, state)
+            }
+            Overlay::Files { files, state } => file_list::render(
+                full_rect,
+                buf,
+                files,
+                app.footers.as_ref(),
+                app.file_index,
+                state,
+            ),
+            Overlay::Yank { items, state } => yank::render(full_rect, buf, items, state),
+            Overlay::Bookmarks { bookmarks, state } => {
+                bookmarks::render(full_rect, buf, bookmarks, &app.parquet_metadata, state)
+            }
+            Overlay::TypeFilter { types, state } => {
+                type_filter::render(full_rect, buf, types, state)
+            }
+            Overlay::Decompression {
+                row_group,
+                profile,
+                state,
+            } => decompression::render(full_rect, buf, *row_group, profile, state),
+            Overlay::Ddl {
+                dialect,
+                ddl,
+                state,
+            } => ddl::render(full_rect, buf, *dialect, ddl, state),
+            Overlay::Value {
+                title,
+                value,
+                hex,
+                state,
+            } => value::render(full_rect, buf, title, value, *hex, state),
+        }
+    }
+
+    goto_prompt::render(full_rect, buf, app);
+    toasts::render(full_rect, buf, &app.toasts);
+
+    if !crate::color::is_enabled() {
+        crate::color::monochrome(buf);
+    }
 }
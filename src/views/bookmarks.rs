@@ -0,0 +1,55 @@
+use parquet2::metadata::FileMetaData;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{goto::column_paths, session::Bookmark, views::goto_prompt::centered};
+
+/// Popup listing the bookmarked locations of the file. Ones the file no longer has, e.g. since
+/// it was rewritten, are grayed out.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    bookmarks: &[Bookmark],
+    metadata: &FileMetaData,
+    state: &mut TableState,
+) {
+    let popup = centered(area, 60, bookmarks.len() as u16 + 2);
+    Clear.render(popup, buf);
+
+    let paths = column_paths(metadata);
+    let rows: Vec<Row> = bookmarks
+        .iter()
+        .map(|bookmark| {
+            let row = Row::new(vec![
+                format!("row group {}", bookmark.row_group),
+                bookmark.column.clone(),
+            ]);
+            if bookmark.row_group < metadata.row_groups.len() && paths.contains(&bookmark.column) {
+                row
+            } else {
+                row.dark_gray()
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title("Bookmarks")
+                .title_bottom(
+                    Line::from(" ENTER jump  DEL remove  ESC cancel ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, popup, buf, state);
+}
@@ -0,0 +1,32 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{breadcrumbs::breadcrumbs, App};
+
+/// Render the navigation path above the browsers. While a segment is being picked with
+/// `Backspace`, it is highlighted.
+pub fn render(area: Rect, buf: &mut Buffer, app: &App) {
+    let mut spans = Vec::new();
+    for (i, crumb) in breadcrumbs(app).iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::from(" ▸ ").dark_gray());
+        }
+        let label = Span::from(crumb.label());
+        spans.push(if app.breadcrumb_cursor == Some(i) {
+            label.style(Style::new().bold().black().on_white())
+        } else {
+            label
+        });
+    }
+    let mut line = Line::from(spans);
+    if app.breadcrumb_cursor.is_some() {
+        line.spans
+            .push(Span::from("   ←→ select  Enter jump back  Esc cancel").dark_gray());
+    }
+    Paragraph::new(line).render(area, buf);
+}
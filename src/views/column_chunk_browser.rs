@@ -3,28 +3,116 @@ use ratatui::{
     layout::Rect,
     style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, List, ListItem, StatefulWidget},
+    widgets::{Block, List, ListItem, ListState, StatefulWidget},
 };
 
-use crate::{parquet::PhysicalTypeExt, ActivePane, App};
+use crate::{
+    column_view::{ColumnRow, ColumnView},
+    parquet::PhysicalTypeExt,
+    views::{compare::column_differs, split_compare::difference_marker},
+    ActivePane, App,
+};
 
 pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
-    let chunks =
-        app.parquet_metadata.row_groups[app.row_group_view_state.selected().unwrap()].columns();
-    let items: Vec<ListItem> = chunks
+    let row_group = app.row_group_view_state.selected().unwrap();
+    app.detect_dictionary_fallbacks(row_group);
+    app.count_pages(row_group);
+    let chunks = app.parquet_metadata.row_groups[row_group].columns();
+    let rows = app.column_view.rows(&app.parquet_metadata);
+    let items: Vec<ListItem> = rows
         .iter()
-        .map(|col| {
-            ListItem::new(Line::from(vec![
-                Span::from(col.metadata().path_in_schema.join(".")).bold(),
+        .map(|row| {
+            let i = match row {
+                ColumnRow::Group {
+                    name,
+                    columns,
+                    collapsed,
+                } => {
+                    let marked = columns
+                        .iter()
+                        .filter(|i| app.selected_columns.contains(i))
+                        .count();
+                    let mut spans = vec![
+                        Span::from(if marked > 0 { "● " } else { "  " }).cyan(),
+                        Span::from(if *collapsed { "▸ " } else { "▾ " }),
+                        Span::from(name.clone()).bold(),
+                        Span::from(format!("  ({})", columns.len())).dark_gray(),
+                    ];
+                    if marked > 0 {
+                        spans.push(Span::from(format!("  {} marked", marked)).cyan());
+                    }
+                    return ListItem::new(Line::from(spans));
+                }
+                ColumnRow::Column(i) => *i,
+            };
+            let col = &chunks[i];
+            let marker = if app.selected_columns.contains(&i) {
+                Span::from("● ").cyan()
+            } else {
+                Span::from("  ")
+            };
+            // Leaves of a group are indented under its header, without its name.
+            let path = &col.metadata().path_in_schema;
+            let name = if path.len() > 1 {
+                format!("  {}", path[1..].join("."))
+            } else {
+                path.join(".")
+            };
+            let mut spans = vec![
+                marker,
+                Span::from(name).bold(),
                 Span::from("  "),
                 Span::from(col.physical_type().human_readable()).magenta(),
-            ]))
+            ];
+            if let Some(Some(counts)) = app.page_counts.get(&(row_group, i)) {
+                spans.push(Span::from("  "));
+                spans.push(Span::from(counts.badge()).dark_gray());
+            }
+            if let Some(Some(fallback)) = app.dictionary_fallbacks.get(&(row_group, i)) {
+                spans.push(Span::from("  "));
+                spans.push(Span::from(format!("dict→{}", fallback.fallback_encoding)).yellow());
+            }
+            if let Some((_, metadata)) = app.compare_file.as_ref().filter(|_| app.split_compare) {
+                if column_differs(&app.parquet_metadata, metadata, row_group, i, &app.redactor) {
+                    spans.push(difference_marker());
+                }
+            }
+            if let Some(checksums) = app.checksums.get(&(row_group, i)) {
+                let badge = Span::from(checksums.badge());
+                spans.push(Span::from("  "));
+                spans.push(match checksums.badge() {
+                    "✓" => badge.green(),
+                    "✗" => badge.red(),
+                    _ => badge.dark_gray(),
+                });
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
+    let mut notes = Vec::new();
+    if let Some(type_filter) = app.column_view.type_filter {
+        let shown = (0..chunks.len())
+            .filter(|&column| app.column_view.shows(&app.parquet_metadata, column))
+            .count();
+        notes.push(format!(
+            "{} only, {} of {}",
+            type_filter.name(),
+            shown,
+            chunks.len()
+        ));
+    }
+    if !app.selected_columns.is_empty() {
+        notes.push(format!("{} selected", app.selected_columns.len()));
+    }
+    let title = if notes.is_empty() {
+        "Column Chunks".to_string()
+    } else {
+        format!("Column Chunks ({})", notes.join(", "))
+    };
     let column_chunk_list = List::new(items)
         .highlight_symbol("> ")
         .highlight_style(Style::new().bold().black().on_white())
-        .block(Block::bordered().title("Column Chunks").border_style(
+        .block(Block::bordered().title(title).border_style(
             if app.active_pane == ActivePane::ColumnBrowser {
                 Style::default().green()
             } else {
@@ -32,10 +120,12 @@ pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
             },
         ));
 
-    StatefulWidget::render(
-        column_chunk_list,
-        area,
-        buf,
-        &mut app.column_chunk_view_state,
-    );
+    // The list selects rows rather than columns: render it with the row showing the selected
+    // column, keeping the scroll offset across frames.
+    let selected = app.column_chunk_view_state.selected().unwrap();
+    let mut state = ListState::default()
+        .with_selected(ColumnView::row_of(&rows, selected))
+        .with_offset(app.column_chunk_view_state.offset());
+    StatefulWidget::render(column_chunk_list, area, buf, &mut state);
+    *app.column_chunk_view_state.offset_mut() = state.offset();
 }
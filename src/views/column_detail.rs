@@ -1,13 +1,65 @@
-use std::fs::File;
-
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    text::Line,
+    style::{Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, Paragraph, Widget},
 };
 
-use crate::{parquet::ColumnChunkMetaDataExt, App};
+use std::path::Path;
+
+use parquet2::metadata::{ColumnOrder, FileMetaData};
+
+use crate::{
+    analysis::checksums::count_checksums,
+    codecs::{codec_name, codec_support, level_hint, CodecSupport},
+    numbers,
+    parquet::{render_values, ColumnChunkMetaDataExt},
+    sampling::{sample_values, SampleStrategy},
+    source::FileSource,
+    ActivePane, App, StatField,
+};
+
+/// Width in cells of the true/false/null proportion bar of BOOLEAN chunks.
+const BOOLEAN_BAR_WIDTH: usize = 30;
+
+/// Sampled values of a column chunk picked by `strategy`, or why they are unavailable, e.g.
+/// when the build cannot decompress the chunk's codec.
+pub fn sample(
+    path: &Path,
+    metadata: &FileMetaData,
+    row_group: usize,
+    column: usize,
+    strategy: SampleStrategy,
+    size: usize,
+) -> String {
+    let chunk = &metadata.row_groups[row_group].columns()[column];
+    let support = codec_support(chunk.compression());
+    if !support.is_supported() {
+        return format!(
+            "sample unavailable: {} is {}",
+            codec_name(chunk.compression()),
+            support
+        );
+    }
+    let primitive_type = &chunk.descriptor().descriptor.primitive_type;
+    match strategy {
+        SampleStrategy::Head => {
+            match crate::parquet::sample_rendered(path, primitive_type, row_group, column, size) {
+                Some(rendered) => rendered,
+                None => crate::parquet::sample_column(path, row_group, column, size),
+            }
+        }
+        _ => sample_values(path, metadata, row_group, column, strategy, size).map(|values| {
+            format!(
+                "{} sample: {:?}",
+                strategy.name(),
+                render_values(primitive_type, &values)
+            )
+        }),
+    }
+    .unwrap_or_else(|err| format!("sample unavailable: {}", err))
+}
 
 pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
     // Accept the column
@@ -18,9 +70,15 @@ pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
     // let phys_type = chunk.physical_type().human_readable();
     let stats = chunk.stats();
 
-    // Sample values
-    let file = File::open(&app.path).unwrap();
-    let sample = crate::parquet::sample_column(file, row_group, column);
+    let support = codec_support(chunk.compression());
+    let sample = sample(
+        &app.path,
+        &app.parquet_metadata,
+        row_group,
+        column,
+        app.sample_strategy,
+        app.sample_size,
+    );
 
     // Add a view that centers it and displays in a pretty way
     let [_, centered_rect, _] = Layout::vertical([
@@ -30,24 +88,196 @@ pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
     ])
     .areas(area);
 
-    let lines = vec![
-        Line::from(sample),
+    let path = chunk.descriptor().path_in_schema.join(".");
+    let stat_lines = vec![
+        Line::from(app.redactor.redact(&path, sample)),
         Line::from(format!(
             "min = {}",
-            stats.min.unwrap_or("undefined".to_string())
+            stats
+                .min
+                .map(|min| app.redactor.redact(&path, min))
+                .unwrap_or("undefined".to_string())
         )),
         Line::from(format!(
             "max = {}",
-            stats.max.unwrap_or("undefined".to_string())
+            stats
+                .max
+                .map(|max| app.redactor.redact(&path, max))
+                .unwrap_or("undefined".to_string())
         )),
-        Line::from(format!("nulls = {}", stats.null_count.unwrap_or(-1))),
         Line::from(format!(
-            "distinct_values = {}",
-            stats.distinct_values.unwrap_or(-1)
+            "nulls = {}",
+            numbers::count(stats.null_count.unwrap_or(-1))
         )),
+        Line::from(match app.distinct_estimates.get(&(row_group, column)) {
+            Some(estimate) => {
+                let (low, high) = estimate.bounds();
+                format!(
+                    "distinct_values = {}, estimated {} ({} to {}, ±{:.1}%)",
+                    numbers::count(stats.distinct_values.unwrap_or(-1)),
+                    numbers::count(estimate.estimate),
+                    numbers::count(low),
+                    numbers::count(high),
+                    estimate.error * 100.0
+                )
+            }
+            None => format!(
+                "distinct_values = {}",
+                numbers::count(stats.distinct_values.unwrap_or(-1))
+            ),
+        }),
     ];
+    // The sample and stat lines follow the order of StatField::ALL.
+    let focused = app.active_pane == ActivePane::ColumnDetail;
+    let mut lines: Vec<Line> = stat_lines
+        .into_iter()
+        .zip(StatField::ALL)
+        .map(|(line, field)| {
+            if focused && field == app.stat_field {
+                line.style(Style::new().bold().black().on_white())
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let data_path = crate::parquet::chunk_data_path(&app.path, chunk.file_path().as_deref());
+    let mut reader = FileSource::open(data_path).and_then(|source| source.reader());
+
+    let codec = codec_name(chunk.compression());
+    lines.push(match &support {
+        CodecSupport::Supported => {
+            let level = match &mut reader {
+                Ok(reader) => {
+                    level_hint(reader, &chunk).unwrap_or_else(|err| format!("unavailable: {}", err))
+                }
+                Err(err) => format!("unavailable: {}", err),
+            };
+            Line::from(format!("codec = {}, level {}", codec, level))
+        }
+        unsupported => Line::from(format!("codec = {}, {}", codec, unsupported)).red(),
+    });
+
+    let checksums = reader
+        .map_err(Into::into)
+        .and_then(|mut reader| count_checksums(&mut reader, &chunk));
+    lines.push(Line::from(match checksums {
+        Ok(checksums) => format!(
+            "checksums = {} of {} pages carry a CRC32",
+            checksums.with_crc, checksums.pages
+        ),
+        Err(err) => format!("checksums unavailable: {}", err),
+    }));
+    if let Some(verified) = app.checksums.get(&(row_group, column)) {
+        lines.push(Line::from(format!(
+            "verified = {} passed, {} failed",
+            verified.passed, verified.failed
+        )));
+    }
+
+    let descriptor = &chunk.descriptor().descriptor;
+    lines.push(Line::from(format!(
+        "levels = max definition {}, max repetition {}",
+        descriptor.max_def_level, descriptor.max_rep_level
+    )));
+
+    if let Some(Some(counts)) = app.page_counts.get(&(row_group, column)) {
+        lines.push(Line::from(format!(
+            "pages = {} data, {} dictionary (from the {})",
+            counts.data_pages,
+            counts.dictionary_pages,
+            counts.source.as_str()
+        )));
+    }
+
+    if let Some(counts) = app.count_booleans(row_group, column) {
+        let percent = |count: u64| count as f64 * 100.0 / counts.total().max(1) as f64;
+        lines.push(Line::from(format!(
+            "booleans = {} true ({:.1}%), {} false ({:.1}%), {} null ({:.1}%)",
+            numbers::count(counts.trues),
+            percent(counts.trues),
+            numbers::count(counts.falses),
+            percent(counts.falses),
+            numbers::count(counts.nulls),
+            percent(counts.nulls)
+        )));
+        let [trues, falses, nulls] = counts.bar(BOOLEAN_BAR_WIDTH);
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::raw("█".repeat(trues)).green(),
+            Span::raw("█".repeat(falses)).red(),
+            Span::raw("░".repeat(nulls)).dark_gray(),
+        ]));
+    }
+
+    if let Some(Some(fallback)) = app.dictionary_fallbacks.get(&(row_group, column)) {
+        lines.push(Line::from(format!(
+            "dictionary fallback = {}",
+            fallback.summary()
+        )));
+        lines.push(Line::from(
+            "  the writer likely hit its dictionary size limit",
+        ));
+    }
+
+    let sort_key = app.load_row_group_footers().get(row_group).map(|footer| {
+        let columns = &footer.sorting_columns;
+        columns
+            .iter()
+            .position(|sorting| sorting.column_idx as usize == column)
+            .map(|key| {
+                let sorting = &columns[key];
+                format!(
+                    "sort key = {} of {}, {}, nulls {}",
+                    key + 1,
+                    columns.len(),
+                    if sorting.descending {
+                        "descending"
+                    } else {
+                        "ascending"
+                    },
+                    if sorting.nulls_first { "first" } else { "last" }
+                )
+            })
+            .unwrap_or_else(|| match columns.len() {
+                0 => "sort key = none declared for the row group".to_string(),
+                n => format!("sort key = not among the row group's {} sort keys", n),
+            })
+    });
+    if let Some(sort_key) = sort_key {
+        lines.push(Line::from(sort_key));
+    }
+    lines.push(Line::from(match &app.parquet_metadata.column_orders {
+        Some(_) => match app.parquet_metadata.column_order(column) {
+            ColumnOrder::TypeDefinedOrder(order) => {
+                format!("column order = type defined ({:?})", order).to_lowercase()
+            }
+            ColumnOrder::Undefined => "column order = undefined (signed comparison)".to_string(),
+        },
+        None => "column order = not declared (signed comparison)".to_string(),
+    }));
+
+    if let Some(file_path) = chunk.file_path() {
+        let resolved = crate::parquet::chunk_data_path(&app.path, Some(file_path));
+        lines.push(Line::from(format!(
+            "external file = {} ({})",
+            file_path,
+            resolved.display()
+        )));
+    }
 
+    let block = if focused {
+        Block::bordered()
+            .title_bottom(
+                Line::from(" ENTER expand  y copy  / filter ")
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green())
+    } else {
+        Block::bordered()
+    };
     Paragraph::new(lines)
-        .block(Block::bordered().title("Column Chunk"))
+        .block(block.title("Column Chunk"))
         .render(centered_rect, buf);
 }
@@ -0,0 +1,89 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{analysis::column_rollup::ColumnRollup, numbers, redact::Redactor};
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    rollups: &[ColumnRollup],
+    redactor: &Redactor,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        "Column",
+        "Min",
+        "Max",
+        "Nulls",
+        "Values",
+        "Min/Max In",
+    ])
+    .bold()
+    .underlined();
+
+    let rows: Vec<Row> = rollups
+        .iter()
+        .map(|rollup| {
+            let bound = |value: &Option<String>| {
+                value
+                    .as_ref()
+                    .map(|v| redactor.redact(&rollup.column, v.clone()))
+                    .unwrap_or_default()
+            };
+            // Chunks without a null count may hold more nulls.
+            let nulls = match rollup.chunks_with_null_count {
+                0 => "unknown".to_string(),
+                n if n < rollup.chunks => format!("≥{}", numbers::count(rollup.nulls)),
+                _ => numbers::count(rollup.nulls),
+            };
+            let row = Row::new(vec![
+                rollup.column.clone(),
+                bound(&rollup.min),
+                bound(&rollup.max),
+                nulls,
+                numbers::count(rollup.values),
+                format!("{} of {} chunks", rollup.chunks_with_min_max, rollup.chunks),
+            ]);
+            if rollup.is_complete() {
+                row
+            } else {
+                row.yellow()
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(format!(
+                "Column Statistics — {} row groups",
+                rollups.first().map_or(0, |rollup| rollup.chunks)
+            ))
+            .title_bottom(
+                Line::from(" Rolled up from the footer statistics of every row group  ESC close ")
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
@@ -0,0 +1,200 @@
+use parquet2::metadata::{ColumnChunkMetaData, FileMetaData};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    numbers,
+    pages::encoding_name,
+    parquet::{ColumnChunkMetaDataExt, PhysicalTypeExt},
+    redact::Redactor,
+};
+
+/// A column chunk being compared, along with where it comes from.
+#[derive(Debug, Clone)]
+pub struct ComparedChunk {
+    pub file_name: String,
+    pub row_group: usize,
+    pub chunk: ColumnChunkMetaData,
+}
+
+impl ComparedChunk {
+    pub fn new(file_name: &str, metadata: &FileMetaData, row_group: usize, column: usize) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            row_group,
+            chunk: metadata.row_groups[row_group].columns()[column].clone(),
+        }
+    }
+}
+
+/// Attribute names shown in the comparison, one row each.
+pub const ATTRIBUTES: [&str; 12] = [
+    "file",
+    "row group",
+    "type",
+    "codec",
+    "encodings",
+    "compressed",
+    "uncompressed",
+    "values",
+    "min",
+    "max",
+    "nulls",
+    "distinct",
+];
+
+/// Values of [ATTRIBUTES] for a column chunk.
+pub fn attributes(compared: &ComparedChunk, redactor: &Redactor) -> Vec<String> {
+    let chunk = &compared.chunk;
+    let stats = chunk.stats();
+    let column = chunk.descriptor().path_in_schema.join(".");
+    vec![
+        compared.file_name.clone(),
+        compared.row_group.to_string(),
+        chunk.physical_type().human_readable().to_string(),
+        format!("{:?}", chunk.compression()),
+        chunk
+            .column_encoding()
+            .iter()
+            .map(|encoding| encoding_name(*encoding))
+            .collect::<Vec<_>>()
+            .join(", "),
+        numbers::bytes(chunk.compressed_size()),
+        numbers::bytes(chunk.uncompressed_size()),
+        numbers::count(chunk.num_values()),
+        redactor.redact(&column, stats.min.unwrap_or_default()),
+        redactor.redact(&column, stats.max.unwrap_or_default()),
+        stats.null_count.map(numbers::count).unwrap_or_default(),
+        stats
+            .distinct_values
+            .map(numbers::count)
+            .unwrap_or_default(),
+    ]
+}
+
+/// Whether two column chunks differ in any attribute but the file and row group they come
+/// from.
+pub fn chunks_differ(
+    a: &ColumnChunkMetaData,
+    b: &ColumnChunkMetaData,
+    redactor: &Redactor,
+) -> bool {
+    let compared = |chunk: &ColumnChunkMetaData| {
+        attributes(
+            &ComparedChunk {
+                file_name: String::new(),
+                row_group: 0,
+                chunk: chunk.clone(),
+            },
+            redactor,
+        )
+    };
+    compared(a) != compared(b)
+}
+
+/// Whether a row group differs between two files, in its row count, size or number of
+/// column chunks, or is missing from either.
+pub fn row_groups_differ(a: &FileMetaData, b: &FileMetaData, row_group: usize) -> bool {
+    match (a.row_groups.get(row_group), b.row_groups.get(row_group)) {
+        (Some(a), Some(b)) => {
+            a.num_rows() != b.num_rows()
+                || a.compressed_size() != b.compressed_size()
+                || a.columns().len() != b.columns().len()
+        }
+        _ => true,
+    }
+}
+
+/// Whether the chunk of `column` of a row group of `a` differs from the chunk of the column
+/// with the same path in `b`, or has no counterpart there.
+pub fn column_differs(
+    a: &FileMetaData,
+    b: &FileMetaData,
+    row_group: usize,
+    column: usize,
+    redactor: &Redactor,
+) -> bool {
+    let Some(chunk) = a
+        .row_groups
+        .get(row_group)
+        .and_then(|row_group| row_group.columns().get(column))
+    else {
+        return true;
+    };
+    let path = &chunk.descriptor().path_in_schema;
+    let other = b.row_groups.get(row_group).and_then(|row_group| {
+        row_group
+            .columns()
+            .iter()
+            .find(|other| other.descriptor().path_in_schema == *path)
+    });
+    other.is_none_or(|other| chunks_differ(chunk, other, redactor))
+}
+
+/// Render the given chunks side by side, one table column per chunk. Attributes that differ
+/// between chunks are highlighted.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    chunks: &[ComparedChunk],
+    redactor: &Redactor,
+    state: &mut TableState,
+) {
+    let columns: Vec<(String, Vec<String>)> = chunks
+        .iter()
+        .map(|compared| {
+            (
+                compared.chunk.descriptor().path_in_schema.join("."),
+                attributes(compared, redactor),
+            )
+        })
+        .collect();
+
+    let header = Row::new(
+        std::iter::once(String::new()).chain(columns.iter().map(|(name, _)| name.clone())),
+    )
+    .bold()
+    .underlined();
+
+    let rows: Vec<Row> = ATTRIBUTES
+        .iter()
+        .enumerate()
+        .map(|(i, attribute)| {
+            let values: Vec<&String> = columns.iter().map(|(_, values)| &values[i]).collect();
+            let differs = values.windows(2).any(|pair| pair[0] != pair[1]);
+            let row =
+                Row::new(std::iter::once(attribute.to_string()).chain(values.into_iter().cloned()));
+            if differs {
+                row.yellow()
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let widths = std::iter::once(Constraint::Length(14))
+        .chain(columns.iter().map(|_| Constraint::Fill(1)))
+        .collect::<Vec<_>>();
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title("Compare Column Chunks")
+                .title_bottom(
+                    Line::from(" Differences in yellow  ESC close ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, area, buf, state);
+}
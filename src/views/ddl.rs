@@ -0,0 +1,60 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, TableState, Widget},
+};
+
+use crate::ddl::{Ddl, Dialect};
+
+/// `CREATE TABLE` statement of the schema in the selected dialect, followed by its lossy
+/// conversions. Scrolls from the offset of `state`.
+pub fn render(area: Rect, buf: &mut Buffer, dialect: Dialect, ddl: &Ddl, state: &mut TableState) {
+    let tabs: Vec<Span> = Dialect::ALL
+        .iter()
+        .flat_map(|&other| {
+            let name = Span::from(format!(" {} ", other.name()));
+            let name = if other == dialect {
+                name.bold().black().on_white()
+            } else {
+                name.gray()
+            };
+            [name, Span::from(" ")]
+        })
+        .collect();
+
+    let mut lines: Vec<Line> = ddl.statement.lines().map(Line::from).collect();
+    lines.push(Line::default());
+    if ddl.warnings.is_empty() {
+        lines.push(Line::from("No lossy conversions").green());
+    } else {
+        lines.push(Line::from("Lossy conversions:").yellow());
+        lines.extend(
+            ddl.warnings
+                .iter()
+                .map(|warning| Line::from(format!("  ⚠ {}", warning)).yellow()),
+        );
+    }
+
+    // Keep the last page in view when scrolled past it. The borders, tabs and the blank line
+    // under them take 4 lines.
+    let visible = area.height.saturating_sub(4) as usize;
+    let offset = state.offset().min(lines.len().saturating_sub(visible));
+    *state.offset_mut() = offset;
+
+    let mut text = vec![Line::from(tabs), Line::default()];
+    text.extend(lines.into_iter().skip(offset).take(visible));
+    Paragraph::new(text)
+        .block(
+            Block::bordered()
+                .title(format!("CREATE TABLE — {}", dialect.name()))
+                .title_bottom(
+                    Line::from(" ←→ dialect  ↑↓ scroll  y copy  ESC close ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        )
+        .render(area, buf);
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    analysis::decompression::{DecompressionProfile, PageTiming},
+    numbers,
+};
+
+fn cells(label: String, timing: &PageTiming) -> Vec<String> {
+    vec![
+        label,
+        timing.page_type.to_string(),
+        numbers::bytes(timing.compressed_size),
+        numbers::bytes(timing.uncompressed_size),
+        format!("{:.2}x", timing.ratio()),
+        format!("{:.1}", timing.duration.as_secs_f64() * 1e6),
+        format!("{:.1}", timing.throughput()),
+    ]
+}
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    profile: &DecompressionProfile,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        "Offset",
+        "Type",
+        "Compressed",
+        "Uncompressed",
+        "Ratio",
+        "Time (µs)",
+        "MB/s",
+    ])
+    .bold()
+    .underlined();
+
+    let total = profile.total();
+    let rows: Vec<Row> = profile
+        .pages
+        .iter()
+        .map(|page| Row::new(cells(page.offset.to_string(), page)))
+        .chain([Row::new(cells("total".to_string(), &total)).bold()])
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(format!(
+                "Decompression of {} — Row Group {} — {}, {:.1} MB/s over {} pages",
+                profile.column,
+                row_group,
+                profile.codec,
+                total.throughput(),
+                profile.pages.len()
+            ))
+            .title_bottom(
+                Line::from(" Sizes in bytes, throughput of decompressed bytes  ESC close ")
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    dataset::{FooterLoader, LoadedFooter},
+    numbers,
+};
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    files: &[PathBuf],
+    footers: Option<&FooterLoader>,
+    current: usize,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        "",
+        "File",
+        "Rows",
+        "Row Groups",
+        "Compressed",
+        "Status",
+    ])
+    .bold()
+    .underlined();
+
+    let rows: Vec<Row> = footers
+        .map(|footers| {
+            footers.with_footers(|summaries| {
+                files
+                    .iter()
+                    .zip(summaries)
+                    .enumerate()
+                    .map(|(i, (path, summary))| file_row(i, path, summary.as_ref(), current))
+                    .collect()
+            })
+        })
+        .unwrap_or_else(|| {
+            files
+                .iter()
+                .enumerate()
+                .map(|(i, path)| file_row(i, path, None, current))
+                .collect()
+        });
+
+    let title = match footers {
+        Some(footers) if !footers.is_done() => format!(
+            "Files — reading footers {}/{}",
+            footers.loaded(),
+            footers.len()
+        ),
+        _ => format!("Files ({})", files.len()),
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(4),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(title)
+            .title_bottom(Line::from(" ENTER open  ESC close ").centered().gray())
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
+
+fn file_row(
+    index: usize,
+    path: &Path,
+    summary: Option<&LoadedFooter>,
+    current: usize,
+) -> Row<'static> {
+    let marker = if index == current { "●" } else { "" };
+    let name = path.display().to_string();
+    match summary {
+        Some(Ok(metadata)) => Row::new(vec![
+            Cell::from(marker),
+            Cell::from(name),
+            Cell::from(numbers::count(metadata.num_rows as u64)),
+            Cell::from(metadata.row_groups.len().to_string()),
+            Cell::from(
+                metadata
+                    .row_groups
+                    .iter()
+                    .map(|row_group| row_group.compressed_size())
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            Cell::from("ok").green(),
+        ]),
+        Some(Err(err)) => Row::new(vec![
+            Cell::from(marker),
+            Cell::from(name),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(err.clone()).red(),
+        ]),
+        None => Row::new(vec![
+            Cell::from(marker),
+            Cell::from(name),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from("reading…").dark_gray(),
+        ]),
+    }
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::App;
+
+/// Render the list of opened files as a row of tabs, scrolled to keep the current file visible.
+pub fn render(area: Rect, buf: &mut Buffer, app: &App) {
+    let names: Vec<String> = app
+        .files
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        })
+        .collect();
+
+    let counter = match &app.footers {
+        Some(footers) if !footers.is_done() => format!(
+            " {}/{} · footers {}/{} ",
+            app.file_index + 1,
+            names.len(),
+            footers.loaded(),
+            footers.len()
+        ),
+        _ => format!(" {}/{} ", app.file_index + 1, names.len()),
+    };
+    let available = (area.width as usize).saturating_sub(counter.width());
+
+    // Start from the current file and add its neighbours, preferring the following ones.
+    let tab_width = |i: usize| names[i].width() + 3;
+    let (mut first, mut last) = (app.file_index, app.file_index);
+    let mut used = tab_width(app.file_index);
+    loop {
+        if last + 1 < names.len() && used + tab_width(last + 1) <= available {
+            last += 1;
+            used += tab_width(last);
+        } else if first > 0 && used + tab_width(first - 1) <= available {
+            first -= 1;
+            used += tab_width(first);
+        } else {
+            break;
+        }
+    }
+
+    let mut spans = vec![Span::from(counter).dark_gray()];
+    for (i, name) in names.iter().enumerate().take(last + 1).skip(first) {
+        let tab = Span::from(format!(" {} ", name));
+        spans.push(if i == app.file_index {
+            tab.style(Style::new().bold().black().on_white())
+        } else {
+            tab
+        });
+        spans.push(Span::from("│").dark_gray());
+    }
+
+    Paragraph::new(Line::from(spans)).render(area, buf);
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, Paragraph, Widget},
+};
+
+use crate::App;
+
+/// Maximum number of autocomplete candidates shown under the input line.
+const MAX_COMPLETIONS: usize = 8;
+
+pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
+    let Some(prompt) = app.goto_prompt.as_ref() else {
+        return;
+    };
+
+    let completions = prompt.completions(&app.parquet_metadata);
+    let shown = completions.len().min(MAX_COMPLETIONS) as u16;
+
+    // Input line, completion list, and the borders of both blocks.
+    let popup = centered(area, 60, 3 + shown + 2);
+    Clear.render(popup, buf);
+
+    let [input_rect, completions_rect] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(popup);
+
+    Paragraph::new(Line::from(vec![
+        Span::from(prompt.input.as_str()),
+        Span::from("_").slow_blink(),
+    ]))
+    .block(
+        Block::bordered()
+            .title("Go to (row group and/or column, or row N)")
+            .title_bottom(Line::from(" TAB complete  ENTER go  ESC cancel ").centered())
+            .border_style(Style::default().green()),
+    )
+    .render(input_rect, buf);
+
+    let items: Vec<ListItem> = completions
+        .iter()
+        .take(MAX_COMPLETIONS)
+        .map(|path| ListItem::new(path.as_str()))
+        .collect();
+    Widget::render(
+        List::new(items).block(Block::bordered().title("Columns").gray()),
+        completions_rect,
+        buf,
+    );
+}
+
+/// Compute a rect of the given size centered in `area`, clamped to fit.
+pub fn centered(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let width = area.width * width_percent / 100;
+    let height = height.min(area.height);
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
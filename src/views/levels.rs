@@ -0,0 +1,84 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    analysis::levels::{LevelCount, LevelDistribution},
+    numbers,
+};
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    distribution: &LevelDistribution,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec!["Kind", "Level", "Values", "Share", "Meaning"])
+        .bold()
+        .underlined();
+
+    let row = |kind: &'static str, level: &LevelCount| {
+        let row = Row::new(vec![
+            kind.to_string(),
+            level.level.to_string(),
+            numbers::count(level.count),
+            format!(
+                "{:.1}%",
+                level.count as f64 / distribution.values.max(1) as f64 * 100.0
+            ),
+            level.meaning.clone(),
+        ]);
+        // Unobserved levels are dimmed: a writer never producing them may be misdeclaring
+        // the nullability of the column.
+        if level.count == 0 {
+            row.dark_gray()
+        } else {
+            row
+        }
+    };
+    let rows: Vec<Row> = distribution
+        .definition
+        .iter()
+        .map(|level| row("definition", level))
+        .chain(
+            distribution
+                .repetition
+                .iter()
+                .map(|level| row("repetition", level)),
+        )
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(format!(
+                "Levels of {} — Row Group {} — {} values, max definition {}, max repetition {}",
+                distribution.column,
+                row_group,
+                distribution.values,
+                distribution.max_def_level,
+                distribution.max_rep_level
+            ))
+            .title_bottom(Line::from(" ESC close ").centered().gray())
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
@@ -0,0 +1,45 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+use tracing::Level;
+
+use crate::logging;
+
+/// Render the most recent log lines that fit in the pane.
+pub fn render(area: Rect, buf: &mut Buffer) {
+    let lines = logging::lines();
+    let visible = area.height.saturating_sub(2) as usize;
+
+    let text: Vec<Line> = lines
+        .iter()
+        .skip(lines.len().saturating_sub(visible))
+        .map(|line| {
+            let level = Span::from(format!("{:>5}", line.level.as_str()));
+            let level = match line.level {
+                Level::ERROR => level.red(),
+                Level::WARN => level.yellow(),
+                Level::INFO => level.green(),
+                _ => level.gray(),
+            };
+            Line::from(vec![
+                Span::from(format!("{:>8.3}s ", line.elapsed.as_secs_f64())).gray(),
+                level,
+                Span::from(format!(" {}: ", line.target)).gray(),
+                Span::from(line.message.as_str()),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(text)
+        .block(
+            Block::bordered()
+                .title(format!("Log ({} lines)", lines.len()))
+                .title_bottom(Line::from(" ~ hide ").centered().gray())
+                .border_style(Style::default().gray()),
+        )
+        .render(area, buf);
+}
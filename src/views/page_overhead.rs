@@ -0,0 +1,78 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{analysis::page_overhead::PageOverhead, numbers};
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    chunks: &[PageOverhead],
+    state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        "Column",
+        "Pages",
+        "Dict pages",
+        "Headers",
+        "Dictionary",
+        "Rep levels",
+        "Def levels",
+        "Values",
+        "Overhead",
+    ])
+    .bold()
+    .underlined();
+
+    let rows: Vec<Row> = chunks
+        .iter()
+        .map(|chunk| {
+            Row::new(vec![
+                chunk.column.clone(),
+                chunk.data_pages.to_string(),
+                chunk.dictionary_pages.to_string(),
+                numbers::bytes(chunk.header_bytes),
+                numbers::bytes(chunk.dictionary_bytes),
+                numbers::bytes(chunk.rep_level_bytes),
+                numbers::bytes(chunk.def_level_bytes),
+                numbers::bytes(chunk.value_bytes),
+                format!("{:.1}%", chunk.overhead_ratio() * 100.0),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(3),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(format!("Page Overhead — Row Group {}", row_group))
+            .title_bottom(
+                Line::from(" Sizes in bytes, levels and values after decompression  ESC close ")
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
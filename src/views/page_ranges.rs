@@ -0,0 +1,136 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{
+        canvas::{Canvas, Line as CanvasLine, Points},
+        Block, TableState, Widget,
+    },
+};
+
+use crate::{
+    analysis::page_index::{axis_position, ColumnPageIndex},
+    redact::Redactor,
+};
+
+/// Width reserved on the left of the chart for the axis labels, in terminal columns.
+const LABEL_WIDTH: f64 = 12.0;
+
+/// Shorten a label to fit the axis.
+fn axis_label(label: String) -> String {
+    if label.chars().count() < LABEL_WIDTH as usize {
+        return label;
+    }
+    let mut short: String = label.chars().take(LABEL_WIDTH as usize - 2).collect();
+    short.push('…');
+    short
+}
+
+/// Strip chart of the min/max range of every page of a column chunk, left to right in file
+/// order. The selected page is highlighted. Values of redacted columns are left off the labels.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    index: &ColumnPageIndex,
+    redactor: &Redactor,
+    state: &mut TableState,
+) {
+    let selected = state.selected().unwrap_or(0);
+    let bounds = index.bounds();
+    let (low, high) = match bounds {
+        Some((min, max)) => (axis_position(min), axis_position(max)),
+        None => (0.0, 1.0),
+    };
+    // Constant columns still get a visible band.
+    let (low, high) = if high > low {
+        (low, high)
+    } else {
+        (low - 1.0, high + 1.0)
+    };
+
+    let width = f64::from(area.width.saturating_sub(2)).max(LABEL_WIDTH + 1.0);
+    let pages = index.pages.len().max(1) as f64;
+    let page_width = (width - LABEL_WIDTH) / pages;
+    let y_of = |position: f64| (position - low) / (high - low);
+
+    let selected_label = index.pages.get(selected).map(|page| {
+        let range = match (&page.min, &page.max) {
+            (Some(min), Some(max)) => redactor.redact(&index.column, format!("{} … {}", min, max)),
+            _ => "all null".to_string(),
+        };
+        format!(
+            " Page {}: {}  nulls {} ",
+            selected,
+            range,
+            page.null_count
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        )
+    });
+
+    let canvas = Canvas::default()
+        .x_bounds([0.0, width])
+        .y_bounds([0.0, 1.0])
+        .marker(ratatui::symbols::Marker::Braille)
+        .block(
+            Block::bordered()
+                .title(format!(
+                    "Page Ranges — {}, Row Group {} — {} pages, {}, {:.1}% overlap",
+                    index.column,
+                    row_group,
+                    index.pages.len(),
+                    index.order.as_str(),
+                    index.overlap * 100.0
+                ))
+                .title_bottom(Line::from(selected_label.unwrap_or_default()).yellow())
+                .title_bottom(
+                    Line::from(" LEFT / RIGHT select page  ESC close ")
+                        .right_aligned()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        )
+        .paint(|ctx| {
+            if let Some((min, max)) = bounds {
+                ctx.print(
+                    0.0,
+                    1.0,
+                    axis_label(redactor.redact(&index.column, max.to_string())),
+                );
+                ctx.print(
+                    0.0,
+                    0.0,
+                    axis_label(redactor.redact(&index.column, min.to_string())),
+                );
+            }
+
+            for (page, range) in index.pages.iter().enumerate() {
+                let (Some(min), Some(max)) = (&range.min, &range.max) else {
+                    continue;
+                };
+                let x = LABEL_WIDTH + page_width * (page as f64 + 0.5);
+                let (y1, y2) = (y_of(axis_position(min)), y_of(axis_position(max)));
+                let color = if page == selected {
+                    Color::Yellow
+                } else {
+                    Color::Cyan
+                };
+                ctx.draw(&CanvasLine {
+                    x1: x,
+                    y1,
+                    x2: x,
+                    y2,
+                    color,
+                });
+                // Narrow ranges would not show as a line.
+                ctx.draw(&Points {
+                    coords: &[(x, y1), (x, y2)],
+                    color,
+                });
+            }
+        });
+
+    canvas.render(area, buf);
+}
@@ -0,0 +1,301 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, Cell, Clear, List, ListItem, ListState, Paragraph, Row, StatefulWidget, Table,
+        Widget,
+    },
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    numbers,
+    preview::{ColumnChooser, Predicate, PreviewState},
+    queries::QueryStore,
+    views::goto_prompt::centered,
+};
+
+/// Widest a column is autosized to. Longer values are truncated with an ellipsis.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Space taken by the highlight symbol to the left of the rows.
+const HIGHLIGHT_WIDTH: u16 = 2;
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    preview_state: &mut PreviewState,
+    queries: &QueryStore,
+) {
+    let preview = &preview_state.preview;
+
+    // Size every displayed column to its widest value, and keep the columns that fit.
+    let available = area.width.saturating_sub(2 + HIGHLIGHT_WIDTH) as usize;
+    let mut used = 0;
+    let mut columns: Vec<(usize, usize)> = Vec::new();
+    for column in preview_state.display_columns() {
+        let width = preview
+            .rows
+            .iter()
+            .map(|row| row[column].width())
+            .chain(std::iter::once(preview.columns[column].width()))
+            .max()
+            .unwrap_or_default()
+            .min(MAX_COLUMN_WIDTH);
+        if !columns.is_empty() && used + width > available {
+            break;
+        }
+        used += width + 1;
+        columns.push((column, width));
+    }
+    let hidden_right = preview_state.display_columns().len() - columns.len();
+    let hidden_left = preview_state.column_offset;
+
+    let pinned = preview_state.pinned.len();
+    let current = preview_state.current_column();
+    let header = Row::new(columns.iter().enumerate().map(|(i, &(column, width))| {
+        let name = truncate(&preview.columns[column], width);
+        if i < pinned {
+            Cell::from(name).cyan()
+        } else if Some(column) == current {
+            Cell::from(name).yellow()
+        } else {
+            Cell::from(name)
+        }
+    }))
+    .bold()
+    .underlined();
+    let rows: Vec<Row> = preview
+        .rows
+        .iter()
+        .map(|row| {
+            Row::new(
+                columns
+                    .iter()
+                    .map(|&(column, width)| truncate(&row[column], width)),
+            )
+        })
+        .collect();
+    let widths: Vec<Constraint> = columns
+        .iter()
+        .map(|&(_, width)| Constraint::Length(width as u16))
+        .collect();
+
+    let title = match &preview_state.filter {
+        Some(filter) => format!(
+            "Data Preview — Row Group {} — {} of {} rows match `{}`",
+            preview_state.row_group, preview.matched, preview.scanned, filter
+        ),
+        None => format!(
+            "Data Preview — Row Group {} — {} rows",
+            preview_state.row_group, preview.scanned
+        ),
+    };
+    let mut scrolled = Vec::new();
+    if hidden_left > 0 {
+        scrolled.push(format!("◀ {} more", hidden_left));
+    }
+    if hidden_right > 0 {
+        scrolled.push(format!("{} more ▶", hidden_right));
+    }
+    let scrolled = if scrolled.is_empty() {
+        String::new()
+    } else {
+        format!(" {} ", scrolled.join("  "))
+    };
+
+    let pushdown = match &preview_state.pushdown {
+        Some(estimate) => format!(
+            " pushdown: {}/{} row groups, {}/{} pages, scans {:.1}% of {} ",
+            estimate.row_groups_kept,
+            estimate.row_groups,
+            estimate.pages_kept,
+            estimate.pages,
+            estimate.scan_ratio() * 100.0,
+            numbers::bytes_labeled(estimate.total_bytes)
+        ),
+        None => String::new(),
+    };
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title(title)
+                .title(Line::from(pushdown).cyan())
+                .title(Line::from(scrolled).right_aligned().gray())
+                .title_bottom(
+                    Line::from(
+                        " / filter  w save filter  o saved filters  c columns  ←→ scroll  p pin  P unpin  y copy cell  ESC close ",
+                    )
+                    .centered()
+                    .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+    StatefulWidget::render(table, area, buf, &mut preview_state.state);
+
+    if let Some(input) = &preview_state.filter_input {
+        render_filter_prompt(area, buf, input);
+    }
+    if let Some(chooser) = preview_state.chooser.as_mut() {
+        render_chooser(area, buf, chooser);
+    }
+    if let Some(name) = &preview_state.save_input {
+        render_save_prompt(area, buf, name);
+    }
+    if let Some(picker) = preview_state.saved_picker.as_mut() {
+        render_saved_picker(area, buf, queries, picker);
+    }
+}
+
+/// Truncate a value to `width` columns, marking truncated values with an ellipsis.
+fn truncate(value: &str, width: usize) -> String {
+    if value.width() <= width {
+        return value.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in value.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width + 1 > width {
+            break;
+        }
+        truncated.push(c);
+        used += c_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Prompt for a filter predicate, showing why the input does not parse yet.
+fn render_filter_prompt(area: Rect, buf: &mut Buffer, input: &str) {
+    let popup = centered(area, 60, 3);
+    Clear.render(popup, buf);
+
+    let hint = if input.trim().is_empty() {
+        Line::from(" empty clears  ENTER apply  ↑↓ history  ESC cancel ").centered()
+    } else {
+        match input.parse::<Predicate>() {
+            Ok(_) => Line::from(" ENTER apply  ↑↓ history  ESC cancel ").centered(),
+            Err(_) => Line::from(" col = value | col > value | col is null ")
+                .centered()
+                .red(),
+        }
+    };
+
+    Paragraph::new(Line::from(vec![
+        Span::from(input),
+        Span::from("_").slow_blink(),
+    ]))
+    .block(
+        Block::bordered()
+            .title("Filter rows")
+            .title_bottom(hint)
+            .border_style(Style::default().green()),
+    )
+    .render(popup, buf);
+}
+
+/// Prompt for the name to save the current filter under.
+fn render_save_prompt(area: Rect, buf: &mut Buffer, name: &str) {
+    let popup = centered(area, 60, 3);
+    Clear.render(popup, buf);
+
+    let hint = if name.trim().is_empty() || name.contains('=') {
+        Line::from(" a name without = ").centered().red()
+    } else {
+        Line::from(" ENTER save  ESC cancel ").centered()
+    };
+    Paragraph::new(Line::from(vec![
+        Span::from(name),
+        Span::from("_").slow_blink(),
+    ]))
+    .block(
+        Block::bordered()
+            .title("Save filter as")
+            .title_bottom(hint)
+            .border_style(Style::default().green()),
+    )
+    .render(popup, buf);
+}
+
+/// List of the saved filters, by name.
+fn render_saved_picker(area: Rect, buf: &mut Buffer, queries: &QueryStore, picker: &mut ListState) {
+    let height = (queries.saved.len() as u16 + 2).min(area.height * 2 / 3);
+    let popup = centered(area, 60, height);
+    Clear.render(popup, buf);
+
+    let items: Vec<ListItem> = queries
+        .saved
+        .iter()
+        .map(|(name, query)| {
+            ListItem::new(Line::from(vec![
+                Span::from(name.as_str()).bold(),
+                Span::from("  "),
+                Span::from(query.as_str()),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title("Saved filters")
+                .title_bottom(
+                    Line::from(" ENTER apply  DEL delete  ESC cancel ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+    StatefulWidget::render(list, popup, buf, picker);
+}
+
+/// Checkbox list of the columns, under a fuzzy search line.
+fn render_chooser(area: Rect, buf: &mut Buffer, chooser: &mut ColumnChooser) {
+    let popup = centered(area, 40, area.height * 2 / 3);
+    Clear.render(popup, buf);
+
+    let [search_rect, list_rect] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(popup);
+
+    Paragraph::new(Line::from(vec![
+        Span::from(chooser.search.as_str()),
+        Span::from("_").slow_blink(),
+    ]))
+    .block(
+        Block::bordered()
+            .title("Columns")
+            .border_style(Style::default().green()),
+    )
+    .render(search_rect, buf);
+
+    let items: Vec<ListItem> = chooser
+        .visible()
+        .into_iter()
+        .map(|i| {
+            let (name, shown) = &chooser.columns[i];
+            ListItem::new(format!("[{}] {}", if *shown { "x" } else { " " }, name))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title_bottom(
+                    Line::from(" SPACE toggle  ENTER apply  ESC cancel ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+    StatefulWidget::render(list, list_rect, buf, &mut chooser.state);
+}
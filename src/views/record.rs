@@ -0,0 +1,49 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::records::RecordField;
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row: u64,
+    fields: &[RecordField],
+    state: &mut TableState,
+) {
+    let header = Row::new(vec!["Column", "Value"]).bold().underlined();
+
+    let rows: Vec<Row> = fields
+        .iter()
+        .map(|field| {
+            let name = format!("{}{}", "  ".repeat(field.depth), field.name);
+            let row = Row::new(vec![name, field.value.clone()]);
+            if field.depth == 0 {
+                row.bold()
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Fill(1), Constraint::Fill(3)])
+        .header(header)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title(format!("Record {}", row))
+                .title_bottom(
+                    Line::from(" n next  p previous  y copy value  ESC close ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, area, buf, state);
+}
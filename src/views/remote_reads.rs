@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{numbers, s3::RequestStats};
+
+/// Number of rows of the table, one per metric.
+pub const ROW_COUNT: usize = 9;
+
+fn latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.1} ms", latency.as_secs_f64() * 1e3),
+        None => "-".to_string(),
+    }
+}
+
+/// The range requests made so far for a file read from S3, the bytes they fetched against the
+/// size of the object, and their latencies.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    uri: &str,
+    object_size: u64,
+    stats: &RequestStats,
+    state: &mut TableState,
+) {
+    let fetched_share = stats.bytes as f64 / object_size.max(1) as f64 * 100.0;
+    let mean_size = stats.bytes / (stats.requests - stats.failed).max(1);
+    let rows: [(&str, String); ROW_COUNT] = [
+        ("Object size", numbers::bytes_labeled(object_size)),
+        ("Range requests", numbers::count(stats.requests)),
+        ("Failed requests", numbers::count(stats.failed)),
+        (
+            "Bytes fetched",
+            format!(
+                "{} ({:.1}% of the object)",
+                numbers::bytes_labeled(stats.bytes),
+                fetched_share
+            ),
+        ),
+        ("Mean response", numbers::bytes_labeled(mean_size)),
+        ("Latency p50", latency(stats.latency(50.0))),
+        ("Latency p90", latency(stats.latency(90.0))),
+        ("Latency p99", latency(stats.latency(99.0))),
+        ("Latency max", latency(stats.latency(100.0))),
+    ];
+
+    let rows: Vec<Row> = rows
+        .into_iter()
+        .map(|(metric, value)| Row::new(vec![Line::from(metric).bold(), Line::from(value)]))
+        .collect();
+    let table = Table::new(rows, [Constraint::Length(20), Constraint::Fill(1)])
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold())
+        .block(
+            Block::bordered()
+                .title(format!("Remote Reads — {}", uri))
+                .title_bottom(
+                    Line::from(
+                        " Updated as requests complete, latency to the last byte  ESC close ",
+                    )
+                    .centered()
+                    .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, area, buf, state);
+}
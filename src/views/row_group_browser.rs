@@ -1,85 +1,266 @@
+use parquet::format::SortingColumn;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Style, Stylize},
-    text::Line,
+    text::{Line, Span},
     widgets::{
         canvas::{Canvas, Rectangle},
         Block, Borders, List, StatefulWidget, Widget,
     },
 };
 
-use crate::{ActivePane, App};
+use crate::{
+    goto::column_paths,
+    graphics::{self, CellSize, Raster},
+    numbers,
+    views::{compare::row_groups_differ, split_compare::difference_marker},
+    ActivePane, App,
+};
 
 pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
-    let items: Vec<String> = (0..app.num_row_groups())
-        .map(|group| format!("Row Group {}", group))
+    if app.row_group_canvas {
+        render_canvas(area, buf, app);
+        return;
+    }
+
+    let paths = column_paths(&app.parquet_metadata);
+    let num_row_groups = app.num_row_groups();
+    let compared = app.compare_file.as_ref().filter(|_| app.split_compare);
+    let differs: Vec<bool> = (0..num_row_groups)
+        .map(|group| {
+            compared.is_some_and(|(_, metadata)| {
+                row_groups_differ(&app.parquet_metadata, metadata, group)
+            })
+        })
         .collect();
+    let title = match compared {
+        Some(_) => format!("Row Groups of {}", app.file_name),
+        None => "Row Groups".to_string(),
+    };
+    let footers = app.load_row_group_footers();
+    let items: Vec<Line> = (0..num_row_groups)
+        .map(|group| {
+            let mut spans = vec![Span::raw(format!("Row Group {}", group))];
+            if let Some(footer) = footers
+                .get(group)
+                .filter(|footer| !footer.sorting_columns.is_empty())
+            {
+                spans.push(
+                    Span::raw(format!(
+                        "  sorted by {}",
+                        describe_sort(&footer.sorting_columns, &paths)
+                    ))
+                    .gray(),
+                );
+            }
+            if differs[group] {
+                spans.push(difference_marker());
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let block = Block::bordered().title(title).border_style(
+        if app.active_pane == ActivePane::RowGroupBrowser {
+            Style::default().green()
+        } else {
+            Style::default().white()
+        },
+    );
 
     let row_group_list = List::new(items)
         .highlight_symbol("> ")
         .highlight_style(Style::new().bold().black().on_white())
-        .block(Block::bordered().title("Row Groups").border_style(
-            if app.active_pane == ActivePane::RowGroupBrowser {
-                Style::default().green()
-            } else {
-                Style::default().white()
-            },
-        ));
+        .block(block);
 
     StatefulWidget::render(row_group_list, area, buf, &mut app.row_group_view_state);
 }
 
+/// Compact description of a declared sort order, e.g. `id↑ name↓`.
+pub fn describe_sort(columns: &[SortingColumn], paths: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| {
+            let name = paths
+                .get(column.column_idx as usize)
+                .cloned()
+                .unwrap_or_else(|| format!("#{}", column.column_idx));
+            format!("{}{}", name, if column.descending { "↓" } else { "↑" })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Smallest and largest height of a row group box in the canvas, in terminal rows.
+const MIN_BOX_HEIGHT: f64 = 2.0;
+const MAX_BOX_HEIGHT: f64 = 6.0;
+/// Vertical gap between row group boxes, in terminal rows.
+const BOX_GAP: f64 = 1.0;
+
 pub fn render_canvas(area: Rect, buf: &mut Buffer, app: &mut App) {
+    let selected = app.row_group_view_state.selected().unwrap();
+    let sizes: Vec<f64> = (0..app.num_row_groups())
+        .map(|row_group| app.compressed_size(row_group) as f64)
+        .collect();
+    let max_size = sizes.iter().cloned().fold(1.0, f64::max);
+
+    // Every box is scaled by its compressed size relative to the largest row group.
+    let heights: Vec<f64> = sizes
+        .iter()
+        .map(|size| (MIN_BOX_HEIGHT + (MAX_BOX_HEIGHT - MIN_BOX_HEIGHT) * size / max_size).round())
+        .collect();
+
+    // Scroll so that the selected row group is visible.
+    let inner_height = f64::from(area.height.saturating_sub(2));
+    let offset = &mut app.row_group_canvas_offset;
+    if selected < *offset {
+        *offset = selected;
+    }
+    while *offset < selected
+        && heights[*offset..=selected]
+            .iter()
+            .map(|h| h + BOX_GAP)
+            .sum::<f64>()
+            > inner_height
+    {
+        *offset += 1;
+    }
+    let offset = *offset;
+
+    let block = Block::default()
+        .title_top(Line::from(app.file_name.as_str()).centered())
+        .title_bottom(
+            Line::from("   UP / DOWN to select row group    ")
+                .centered()
+                .gray(),
+        )
+        .borders(Borders::ALL)
+        .style(if app.active_pane == ActivePane::RowGroupBrowser {
+            Style::default().green()
+        } else {
+            Style::default().white()
+        });
+    if let Some(cell) = graphics::cell_size() {
+        let inner = block.inner(area);
+        block.render(area, buf);
+        render_canvas_raster(inner, buf, app, &sizes, &heights, offset, cell);
+        return;
+    }
+
     let canvas = Canvas::default()
-        .x_bounds([0.0, f64::from(area.width)])
-        .y_bounds([0.0, f64::from(area.height)])
+        .x_bounds([0.0, f64::from(area.width.saturating_sub(2))])
+        .y_bounds([0.0, inner_height])
         .marker(ratatui::symbols::Marker::HalfBlock)
-        .block(
-            Block::default()
-                .title_top(Line::from(app.file_name.as_str()).centered())
-                .title_bottom(
-                    Line::from("   UP / DOWN to select row group    ")
-                        .centered()
-                        .gray(),
-                )
-                .borders(Borders::ALL)
-                .style(if app.active_pane == ActivePane::RowGroupBrowser {
-                    Style::default().green()
-                } else {
-                    Style::default().white()
-                }),
-        )
+        .block(block)
         .paint(|ctx| {
             let x_margin: f64 = 5.0;
-            let y_margin: f64 = 3.0;
-            let row_group_width = f64::from(area.width) - 2.0 * x_margin;
-            let row_group_height = 5.0;
-            for row_group in 0..app.parquet_metadata.row_groups.len() {
-                let box_bottom_left_y = f64::from(area.height)
-                    - y_margin
-                    - row_group_height
-                    - (row_group as f64) * (row_group_height + y_margin);
+            let max_width = f64::from(area.width.saturating_sub(2)) - 2.0 * x_margin;
+            let mut top = inner_height - BOX_GAP;
+
+            for row_group in offset..app.parquet_metadata.row_groups.len() {
+                let height = heights[row_group];
+                if top - height < 0.0 {
+                    break;
+                }
+                let bottom = top - height;
+                let width = (max_width * sizes[row_group] / max_size).max(1.0);
+                let metadata = &app.parquet_metadata.row_groups[row_group];
 
+                ctx.print(0.0, bottom + height / 2.0, format!("{}", row_group));
                 ctx.print(
-                    2.0,
-                    box_bottom_left_y + row_group_height / 2.0,
-                    format!("{}", row_group),
+                    x_margin + 1.0,
+                    bottom + height / 2.0,
+                    format!(
+                        "{} rows  {}",
+                        numbers::count(metadata.num_rows() as u64),
+                        numbers::size(sizes[row_group] as u64)
+                    ),
                 );
 
                 ctx.draw(&Rectangle {
                     x: x_margin,
-                    y: box_bottom_left_y,
-                    color: if row_group == app.row_group_view_state.selected().unwrap() {
+                    y: bottom,
+                    color: if row_group == selected {
                         ratatui::style::Color::Green
                     } else {
                         ratatui::style::Color::White
                     },
-                    width: row_group_width,
-                    height: row_group_height,
-                })
+                    width,
+                    height,
+                });
+
+                top = bottom - BOX_GAP;
             }
         });
 
     canvas.render(area, buf);
 }
+
+/// Columns left of the boxes for row group numbers, and right of them for their sizes, when
+/// the canvas is drawn as an image.
+const NUMBER_WIDTH: u16 = 5;
+const SIZE_WIDTH: u16 = 24;
+const BOX_COLOR: [u8; 3] = [208, 208, 208];
+const SELECTED_BOX_COLOR: [u8; 3] = [95, 175, 95];
+
+/// The row group canvas as a raster image, with the same boxes as the character canvas and
+/// their labels beside them, starting from the row group at `offset`.
+fn render_canvas_raster(
+    area: Rect,
+    buf: &mut Buffer,
+    app: &App,
+    sizes: &[f64],
+    heights: &[f64],
+    offset: usize,
+    cell: CellSize,
+) {
+    let selected = app.row_group_view_state.selected().unwrap();
+    let max_size = sizes.iter().cloned().fold(1.0, f64::max);
+    let image = Rect {
+        x: area.x + NUMBER_WIDTH,
+        width: area.width.saturating_sub(NUMBER_WIDTH + SIZE_WIDTH),
+        ..area
+    };
+    if image.width == 0 {
+        return;
+    }
+    let (cell_width, cell_height) = (usize::from(cell.width), usize::from(cell.height));
+    let mut raster = Raster::new(
+        usize::from(image.width) * cell_width,
+        usize::from(image.height) * cell_height,
+        &[BOX_COLOR, SELECTED_BOX_COLOR],
+    );
+
+    let mut top = BOX_GAP as u16;
+    for row_group in offset..sizes.len() {
+        let height = heights[row_group] as u16;
+        if top + height > area.height {
+            break;
+        }
+        let width = (f64::from(image.width) * sizes[row_group] / max_size).max(1.0);
+        raster.fill(
+            0,
+            usize::from(top) * cell_height,
+            (width * cell_width as f64) as usize,
+            usize::from(height) * cell_height,
+            if row_group == selected { 2 } else { 1 },
+        );
+
+        let middle = area.y + top + height / 2;
+        let metadata = &app.parquet_metadata.row_groups[row_group];
+        buf.set_string(area.x, middle, row_group.to_string(), Style::default());
+        buf.set_string(
+            image.right() + 1,
+            middle,
+            format!(
+                "{} rows  {}",
+                numbers::count(metadata.num_rows() as u64),
+                numbers::size(sizes[row_group] as u64)
+            ),
+            Style::default(),
+        );
+        top += height + BOX_GAP as u16;
+    }
+    graphics::queue(image, raster);
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Paragraph, Row, Table, Widget},
+};
+
+use crate::{goto::column_paths, numbers, views::row_group_browser::describe_sort, App};
+
+/// Details of the row group under the cursor, shown in place of the column detail while the
+/// row group browser has focus.
+pub fn render(area: Rect, buf: &mut Buffer, app: &mut App) {
+    let row_group = app.row_group_view_state.selected().unwrap();
+    let paths = column_paths(&app.parquet_metadata);
+    let footer = app
+        .load_row_group_footers()
+        .get(row_group)
+        .cloned()
+        .unwrap_or_default();
+    let estimates = app.estimate_memory(row_group).to_vec();
+    let in_memory: u64 = estimates
+        .iter()
+        .map(|estimate| estimate.in_memory_bytes)
+        .sum();
+    let metadata = &app.parquet_metadata.row_groups[row_group];
+    let compressed_size = metadata.compressed_size().max(1) as f64;
+
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "not set".to_string());
+    let lines = vec![
+        Line::from(format!(
+            "num_rows = {}",
+            numbers::count(metadata.num_rows() as u64)
+        )),
+        Line::from(format!(
+            "total_byte_size = {}",
+            numbers::bytes(metadata.total_byte_size() as u64)
+        )),
+        Line::from(format!(
+            "compressed_size = {}",
+            numbers::bytes(metadata.compressed_size() as u64)
+        )),
+        Line::from(format!(
+            "in_memory ≈ {} (estimated Arrow size)",
+            numbers::bytes(in_memory)
+        )),
+        Line::from(format!(
+            "file_offset = {}",
+            optional(footer.file_offset.map(|offset| offset.to_string()))
+        )),
+        Line::from(format!(
+            "ordinal = {}",
+            optional(footer.ordinal.map(|ordinal| ordinal.to_string()))
+        )),
+        Line::from(format!(
+            "sorting_columns = {}",
+            if footer.sorting_columns.is_empty() {
+                "none declared".to_string()
+            } else {
+                describe_sort(&footer.sorting_columns, &paths)
+            }
+        )),
+    ];
+
+    let header = Row::new(vec![
+        "Column",
+        "Compressed",
+        "Uncompressed",
+        "In memory",
+        "Share",
+    ])
+    .bold()
+    .underlined();
+    let rows: Vec<Row> = metadata
+        .columns()
+        .iter()
+        .zip(&paths)
+        .zip(&estimates)
+        .map(|((chunk, path), estimate)| {
+            Row::new(vec![
+                path.clone(),
+                numbers::bytes(chunk.compressed_size()),
+                numbers::bytes(chunk.uncompressed_size()),
+                format!("≈{}", numbers::bytes(estimate.in_memory_bytes)),
+                format!(
+                    "{:.1}%",
+                    chunk.compressed_size() as f64 / compressed_size * 100.0
+                ),
+            ])
+        })
+        .collect();
+
+    let block = Block::bordered().title(format!("Row Group {}", row_group));
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let [fields_rect, sizes_rect] = Layout::vertical([
+        Constraint::Length(lines.len() as u16 + 1),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+    Paragraph::new(lines).render(fields_rect, buf);
+    Widget::render(
+        Table::new(
+            rows,
+            [
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header),
+        sizes_rect,
+        buf,
+    );
+}
@@ -0,0 +1,114 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    analysis::scan::{ColumnScan, FloatSpecials},
+    numbers,
+    redact::Redactor,
+};
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    columns: &[ColumnScan],
+    redactor: &Redactor,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec![
+        "Column", "Values", "Nulls", "Distinct", "Min", "Max", "NaN", "+Inf", "-Inf", "-0",
+    ])
+    .bold()
+    .underlined();
+
+    let rows: Vec<Row> = columns
+        .iter()
+        .map(|scan| {
+            let special = |count: fn(&FloatSpecials) -> usize| {
+                scan.floats
+                    .as_ref()
+                    .map(|floats| numbers::count(count(floats) as u64))
+                    .unwrap_or_default()
+            };
+            let row = Row::new(vec![
+                scan.column.clone(),
+                numbers::count(scan.values as u64),
+                numbers::count(scan.nulls as u64),
+                if scan.distinct_exact {
+                    numbers::count(scan.distinct as u64)
+                } else {
+                    format!("≥{}", numbers::count(scan.distinct as u64))
+                },
+                scan.min
+                    .as_ref()
+                    .map(|v| redactor.redact(&scan.column, v.to_string()))
+                    .unwrap_or_default(),
+                scan.max
+                    .as_ref()
+                    .map(|v| redactor.redact(&scan.column, v.to_string()))
+                    .unwrap_or_default(),
+                special(|floats| floats.nan),
+                special(|floats| floats.positive_infinity),
+                special(|floats| floats.negative_infinity),
+                special(|floats| floats.negative_zero),
+            ]);
+            if scan.footer_nan {
+                row.red()
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let polluted: Vec<&str> = columns
+        .iter()
+        .filter(|scan| scan.footer_nan)
+        .map(|scan| scan.column.as_str())
+        .collect();
+
+    let mut block = Block::bordered()
+        .title(format!("Scanned Stats — Row Group {}", row_group))
+        .title_bottom(
+            Line::from(" Computed by decoding every value  ESC close ")
+                .centered()
+                .gray(),
+        )
+        .border_style(Style::default().green());
+    if !polluted.is_empty() {
+        block = block.title_bottom(
+            Line::from(format!(
+                " Footer min/max of {} is NaN: readers may prune wrongly ",
+                polluted.join(", ")
+            ))
+            .left_aligned()
+            .red(),
+        );
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(block);
+
+    StatefulWidget::render(table, area, buf, state);
+}
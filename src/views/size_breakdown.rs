@@ -0,0 +1,102 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{analysis::size_breakdown::SizeBreakdown, numbers};
+
+/// Colors of the parts, in the order of [SizeBreakdown::parts].
+const COLORS: [Color; 8] = [
+    Color::Cyan,
+    Color::Blue,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::LightYellow,
+    Color::Red,
+    Color::DarkGray,
+];
+
+/// Height of the stacked bar, in terminal rows.
+const BAR_HEIGHT: u16 = 3;
+
+/// A stacked bar splitting the file size into its parts, above a legend with their sizes.
+/// The selected part is shaded in the bar.
+pub fn render(area: Rect, buf: &mut Buffer, breakdown: &SizeBreakdown, state: &mut TableState) {
+    let parts = breakdown.parts();
+    let selected = state.selected().unwrap_or(0);
+    let file_size = breakdown.file_size.max(1) as f64;
+
+    let block = Block::bordered()
+        .title(format!(
+            "File Size Breakdown — {}",
+            numbers::bytes_labeled(breakdown.file_size)
+        ))
+        .title_bottom(
+            Line::from(" UP / DOWN to select a part  ESC close ")
+                .centered()
+                .gray(),
+        )
+        .border_style(Style::default().green());
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let [_, bar_rect, _, legend_rect] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(BAR_HEIGHT),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    // Each part spans the columns between its cumulative start and end, so that rounding never
+    // leaves gaps or overflows the bar.
+    let width = f64::from(bar_rect.width);
+    let mut cumulative = 0;
+    for (i, (_, bytes)) in parts.iter().enumerate() {
+        let start = (cumulative as f64 / file_size * width).round() as u16;
+        cumulative += bytes;
+        let end = (cumulative as f64 / file_size * width).round() as u16;
+        let symbol = if i == selected { "▓" } else { "█" };
+        for x in start..end.min(bar_rect.width) {
+            for y in 0..bar_rect.height {
+                buf.get_mut(bar_rect.x + x, bar_rect.y + y)
+                    .set_symbol(symbol)
+                    .set_fg(COLORS[i]);
+            }
+        }
+    }
+
+    let header = Row::new(vec!["", "Part", "Bytes", "Share"])
+        .bold()
+        .underlined();
+    let rows: Vec<Row> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, (name, bytes))| {
+            Row::new(vec![
+                Line::from("██").fg(COLORS[i]),
+                Line::from(*name),
+                Line::from(numbers::bytes(*bytes)),
+                Line::from(format!("{:.1}%", *bytes as f64 / file_size * 100.0)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Length(18),
+            Constraint::Length(14),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold());
+
+    StatefulWidget::render(table, legend_rect, buf, state);
+}
@@ -0,0 +1,200 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, TableState, Widget},
+};
+
+use crate::{
+    analysis::row_group_sizes::RowGroupSize,
+    graphics::{self, CellSize, Raster},
+    numbers,
+};
+
+/// Widest bar drawn, in terminal columns.
+const MAX_BAR_WIDTH: u16 = 9;
+
+fn bar<'a>(row_group: usize, value: i64, text: String, outlier: bool, selected: bool) -> Bar<'a> {
+    let color = if selected {
+        Color::Yellow
+    } else if outlier {
+        Color::Red
+    } else {
+        Color::Cyan
+    };
+    Bar::default()
+        .value(value.max(0) as u64)
+        .text_value(text)
+        .label(Line::from(row_group.to_string()))
+        .style(Style::default().fg(color))
+        .value_style(Style::default().fg(Color::Black).bg(color))
+}
+
+/// Bar charts of the rows and compressed bytes of every row group, with outliers in red.
+/// When the row groups do not fit, the charts scroll to keep the selected one visible.
+pub fn render(area: Rect, buf: &mut Buffer, sizes: &[RowGroupSize], state: &mut TableState) {
+    let selected = state.selected().unwrap_or(0);
+    let outliers = sizes.iter().filter(|size| size.is_outlier()).count();
+
+    let block = Block::bordered()
+        .title(format!(
+            "Row Group Sizes — {} row groups, {} flagged",
+            sizes.len(),
+            outliers
+        ))
+        .title_bottom(Line::from(" LEFT / RIGHT select row group  ESC close ").gray())
+        .border_style(Style::default().green());
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let bar_width = (inner.width / sizes.len().max(1) as u16)
+        .saturating_sub(1)
+        .clamp(1, MAX_BAR_WIDTH);
+    let capacity = (usize::from(inner.width) / usize::from(bar_width + 1)).max(1);
+    let offset = (selected + 1).saturating_sub(capacity);
+    let visible = offset..sizes.len().min(offset + capacity);
+
+    let [rows_rect, bytes_rect] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(inner);
+
+    if let Some(cell) = graphics::cell_size() {
+        let rows: Vec<RasterBar> = visible
+            .clone()
+            .map(|i| RasterBar {
+                row_group: i,
+                value: sizes[i].rows,
+                text: numbers::count(sizes[i].rows),
+                color: raster_color(sizes[i].rows_outlier, i == selected),
+            })
+            .collect();
+        render_raster_chart(rows_rect, buf, "Rows", &rows, bar_width, cell);
+        let bytes: Vec<RasterBar> = visible
+            .map(|i| RasterBar {
+                row_group: i,
+                value: sizes[i].compressed_bytes,
+                text: numbers::size(sizes[i].compressed_bytes),
+                color: raster_color(sizes[i].bytes_outlier, i == selected),
+            })
+            .collect();
+        render_raster_chart(bytes_rect, buf, "Compressed size", &bytes, bar_width, cell);
+        return;
+    }
+
+    let row_bars: Vec<Bar> = visible
+        .clone()
+        .map(|i| {
+            let size = &sizes[i];
+            bar(
+                i,
+                size.rows,
+                numbers::count(size.rows),
+                size.rows_outlier,
+                i == selected,
+            )
+        })
+        .collect();
+    BarChart::default()
+        .block(Block::default().title("Rows"))
+        .data(BarGroup::default().bars(&row_bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .render(rows_rect, buf);
+
+    let byte_bars: Vec<Bar> = visible
+        .map(|i| {
+            let size = &sizes[i];
+            bar(
+                i,
+                size.compressed_bytes,
+                numbers::size(size.compressed_bytes),
+                size.bytes_outlier,
+                i == selected,
+            )
+        })
+        .collect();
+    BarChart::default()
+        .block(Block::default().title("Compressed size"))
+        .data(BarGroup::default().bars(&byte_bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .render(bytes_rect, buf);
+}
+
+/// Colors of the bars drawn as images: regular, outlier and selected bars.
+const RASTER_PALETTE: [[u8; 3]; 3] = [[0, 175, 215], [215, 95, 95], [215, 215, 95]];
+
+fn raster_color(outlier: bool, selected: bool) -> u8 {
+    if selected {
+        3
+    } else if outlier {
+        2
+    } else {
+        1
+    }
+}
+
+/// Bar of a chart drawn as an image, with its color in [RASTER_PALETTE], counted from 1.
+struct RasterBar {
+    row_group: usize,
+    value: i64,
+    text: String,
+    color: u8,
+}
+
+/// Bar chart drawn as an image, laid out like the character one: the row group numbers and
+/// values are written below the bars.
+fn render_raster_chart(
+    area: Rect,
+    buf: &mut Buffer,
+    title: &str,
+    bars: &[RasterBar],
+    bar_width: u16,
+    cell: CellSize,
+) {
+    let block = Block::default().title(title);
+    let [plot, values, labels] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(block.inner(area));
+    block.render(area, buf);
+    if plot.height == 0 {
+        return;
+    }
+
+    let (cell_width, cell_height) = (usize::from(cell.width), usize::from(cell.height));
+    let mut raster = Raster::new(
+        usize::from(plot.width) * cell_width,
+        usize::from(plot.height) * cell_height,
+        &RASTER_PALETTE,
+    );
+    let max = bars
+        .iter()
+        .map(|bar| bar.value)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    for (i, bar) in bars.iter().enumerate() {
+        let x = i as u16 * (bar_width + 1);
+        let height = (raster.height as f64 * bar.value.max(0) as f64 / max as f64) as usize;
+        raster.fill(
+            usize::from(x) * cell_width,
+            raster.height - height,
+            usize::from(bar_width) * cell_width,
+            height,
+            bar.color,
+        );
+        let width = usize::from(bar_width);
+        buf.set_stringn(values.x + x, values.y, &bar.text, width, Style::default());
+        buf.set_stringn(
+            labels.x + x,
+            labels.y,
+            bar.row_group.to_string(),
+            width,
+            Style::default(),
+        );
+    }
+    graphics::queue(plot, raster);
+}
@@ -0,0 +1,106 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListState, StatefulWidget, Widget},
+};
+
+use crate::{
+    goto::column_paths,
+    parquet::PhysicalTypeExt,
+    views::compare::{column_differs, row_groups_differ},
+    App,
+};
+
+/// Marker of rows that differ between the compared files.
+pub fn difference_marker() -> Span<'static> {
+    Span::from("  ≠").yellow()
+}
+
+/// Row group and column browsers of the compare file, next to the inspected file's. They
+/// follow its selection: the same row group, and the column with the same path. Rows that
+/// differ from the inspected file are marked, and columns it lacks are red.
+pub fn render(row_groups_area: Rect, columns_area: Rect, buf: &mut Buffer, app: &App) {
+    let Some((file_name, metadata)) = &app.compare_file else {
+        return;
+    };
+    let row_group = app.row_group_view_state.selected().unwrap();
+
+    let items: Vec<Line> = (0..metadata.row_groups.len())
+        .map(|group| {
+            let mut spans = vec![Span::from(format!("Row Group {}", group))];
+            if row_groups_differ(&app.parquet_metadata, metadata, group) {
+                spans.push(difference_marker());
+            }
+            Line::from(spans)
+        })
+        .collect();
+    let mut state = ListState::default()
+        .with_selected(Some(row_group).filter(|&group| group < metadata.row_groups.len()));
+    StatefulWidget::render(
+        List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().bold().black().on_white())
+            .block(Block::bordered().title(format!("Row Groups of {}", file_name))),
+        row_groups_area,
+        buf,
+        &mut state,
+    );
+
+    let Some(chunks) = metadata
+        .row_groups
+        .get(row_group)
+        .map(|row_group| row_group.columns())
+    else {
+        Block::bordered()
+            .title("Column Chunks")
+            .title_bottom(Line::from(format!(" No row group {} ", row_group)).yellow())
+            .render(columns_area, buf);
+        return;
+    };
+    let paths = column_paths(&app.parquet_metadata);
+    let items: Vec<Line> = chunks
+        .iter()
+        .enumerate()
+        .map(|(column, chunk)| {
+            let path = chunk.descriptor().path_in_schema.join(".");
+            let mut spans = vec![
+                Span::from(path.clone()).bold(),
+                Span::from("  "),
+                Span::from(chunk.physical_type().human_readable()).magenta(),
+            ];
+            if !paths.contains(&path) {
+                spans.push(Span::from("  only here").red());
+            } else if column_differs(
+                metadata,
+                &app.parquet_metadata,
+                row_group,
+                column,
+                &app.redactor,
+            ) {
+                spans.push(difference_marker());
+            }
+            Line::from(spans)
+        })
+        .collect();
+    let selected = app
+        .column_chunk_view_state
+        .selected()
+        .and_then(|column| paths.get(column))
+        .and_then(|path| {
+            column_paths(metadata)
+                .iter()
+                .position(|other| other == path)
+        });
+    let mut state = ListState::default().with_selected(selected);
+    StatefulWidget::render(
+        List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().bold().black().on_white())
+            .block(Block::bordered().title("Column Chunks")),
+        columns_area,
+        buf,
+        &mut state,
+    );
+}
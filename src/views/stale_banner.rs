@@ -0,0 +1,22 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::source::FileChange;
+
+/// Warn that the file changed on disk since it was opened, so that views may no longer match it.
+pub fn render(area: Rect, buf: &mut Buffer, change: FileChange) {
+    Paragraph::new(
+        Line::from(format!(
+            " file {} on disk — press r to reload ",
+            change.as_str()
+        ))
+        .centered(),
+    )
+    .style(Style::new().bold().white().on_red())
+    .render(area, buf);
+}
@@ -0,0 +1,107 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::analysis::stats_presence::{PresenceMatrix, StatsPresence};
+
+/// Width of the column name column, and of a row group cell including its spacing.
+const NAME_WIDTH: u16 = 30;
+const CELL_WIDTH: u16 = 4;
+
+/// A cell of the matrix: one letter per statistic, `·` where it is missing.
+fn cell(presence: &StatsPresence) -> Cell<'static> {
+    let flags = [
+        (presence.min_max, "m"),
+        (presence.null_count, "n"),
+        (presence.distinct_count, "d"),
+    ];
+    let present = flags.iter().filter(|(present, _)| *present).count();
+    let style = match present {
+        3 => Style::new().green(),
+        0 => Style::new().red(),
+        _ => Style::new().yellow(),
+    };
+    let spans: Vec<Span> = flags
+        .iter()
+        .map(|&(present, letter)| Span::styled(if present { letter } else { "·" }, style))
+        .collect();
+    Cell::from(Line::from(spans))
+}
+
+/// Matrix of the statistics present for every column (rows) and row group (columns), scrolled
+/// horizontally from `first_row_group`.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    matrix: &PresenceMatrix,
+    first_row_group: &mut usize,
+    state: &mut TableState,
+) {
+    // Row groups that fit next to the names, inside the borders and the highlight symbol.
+    let visible = (area.width.saturating_sub(NAME_WIDTH + 4) / CELL_WIDTH).max(1) as usize;
+    *first_row_group = (*first_row_group).min(matrix.row_groups.len().saturating_sub(visible));
+    let shown = *first_row_group..(*first_row_group + visible).min(matrix.row_groups.len());
+
+    let header =
+        Row::new(std::iter::once("Column".to_string()).chain(shown.clone().map(|i| i.to_string())))
+            .bold()
+            .underlined();
+
+    let rows: Vec<Row> = matrix
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            Row::new(
+                std::iter::once(Cell::from(name.clone())).chain(
+                    matrix.row_groups[shown.clone()]
+                        .iter()
+                        .map(|row_group| cell(&row_group[column])),
+                ),
+            )
+        })
+        .collect();
+
+    let widths = std::iter::once(Constraint::Length(NAME_WIDTH - 1))
+        .chain(shown.clone().map(|_| Constraint::Length(CELL_WIDTH - 1)));
+
+    let (min_max, null_count, distinct_count) = matrix.coverage();
+    let scrolled = if shown.len() < matrix.row_groups.len() {
+        format!(
+            "row groups {}-{} of {}",
+            shown.start,
+            shown.end.saturating_sub(1),
+            matrix.row_groups.len()
+        )
+    } else {
+        String::new()
+    };
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold())
+        .block(
+            Block::bordered()
+                .title(format!(
+                    "Statistics Presence — min/max {:.1}%, null count {:.1}%, distinct count {:.1}% of {} chunks",
+                    min_max * 100.0,
+                    null_count * 100.0,
+                    distinct_count * 100.0,
+                    matrix.num_chunks()
+                ))
+                .title(Line::from(scrolled).right_aligned().gray())
+                .title_bottom(
+                    Line::from(" m min/max  n null count  d distinct count  ←→ scroll  ESC close ")
+                        .centered()
+                        .gray(),
+                )
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, area, buf, state);
+}
@@ -0,0 +1,73 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{goto::column_paths, ActivePane, App};
+
+/// Most relevant keys of the focused pane, or of the view shown over the browsers.
+fn hints(app: &App) -> &'static str {
+    if app.overlay.is_some() {
+        return "Esc close  z zoom  q quit";
+    }
+    match app.active_pane {
+        ActivePane::RowGroupBrowser => {
+            "↑↓ row group  Tab columns  d preview  g goto  z zoom  q quit"
+        }
+        ActivePane::ColumnBrowser => "↑↓ column  → stats  Space mark  t types  c compare  z zoom",
+        ActivePane::ColumnDetail => {
+            "↑↓ statistic  Enter expand  y copy  / filter  <> sampling  ← back"
+        }
+    }
+}
+
+/// Render the bottom status bar: the file, the selected row group and column, active filters
+/// and background tasks, with the keys of the focused pane on the right.
+pub fn render(area: Rect, buf: &mut Buffer, app: &App) {
+    let row_group = app.row_group_view_state.selected().unwrap_or_default();
+    let column = app.column_chunk_view_state.selected().unwrap_or_default();
+    let column = column_paths(&app.parquet_metadata)
+        .get(column)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut items = vec![
+        Span::from(app.file_name.as_str()).bold(),
+        Span::from(format!("rg {} / col {}", row_group, column)),
+    ];
+    if let Some(type_filter) = &app.column_view.type_filter {
+        items.push(Span::from(format!("type {}", type_filter.name())).yellow());
+    }
+    if !app.selected_columns.is_empty() {
+        items.push(Span::from(format!("{} marked", app.selected_columns.len())).yellow());
+    }
+    if app.redactor.is_enabled() {
+        items.push(Span::from("redacted").yellow());
+    }
+    if let Some(footers) = app.footers.as_ref().filter(|footers| !footers.is_done()) {
+        items.push(Span::from(format!("footers {}/{}", footers.loaded(), footers.len())).cyan());
+    }
+
+    let mut spans = Vec::new();
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::from(" │ ").dark_gray());
+        }
+        spans.push(item);
+    }
+    let status = Line::from(spans);
+
+    // Keys are dropped first when the bar is too narrow for both.
+    let hints = hints(app);
+    if status.width() + hints.width() + 2 <= area.width as usize {
+        Line::from(hints)
+            .dark_gray()
+            .right_aligned()
+            .render(area, buf);
+    }
+    Paragraph::new(status).render(area, buf);
+}
@@ -0,0 +1,42 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Clear, Paragraph, Widget},
+};
+
+use crate::toasts::{ToastLevel, Toasts};
+
+/// Render the notifications in the bottom right corner of `area`, the newest at the bottom,
+/// each on one line as wide as its text, up to half of the area or 40 columns.
+pub fn render(area: Rect, buf: &mut Buffer, toasts: &Toasts) {
+    let max_width = (area.width / 2).max(40).min(area.width.saturating_sub(2));
+    let mut y = area.bottom().saturating_sub(1);
+    for toast in toasts.iter().rev() {
+        if y <= area.y {
+            break;
+        }
+        let line = Line::from(format!(" {} {} ", toast.level.symbol(), toast.text));
+        let width = (line.width() as u16).min(max_width);
+        let rect = Rect {
+            x: area.right() - width - 1,
+            y,
+            width,
+            height: 1,
+        };
+        Clear.render(rect, buf);
+        Paragraph::new(line)
+            .style(style(toast.level))
+            .render(rect, buf);
+        y -= 1;
+    }
+}
+
+fn style(level: ToastLevel) -> Style {
+    match level {
+        ToastLevel::Info => Style::new().black().on_white(),
+        ToastLevel::Warn => Style::new().black().on_yellow(),
+        ToastLevel::Error => Style::new().bold().white().on_red(),
+    }
+}
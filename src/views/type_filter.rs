@@ -0,0 +1,51 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{column_view::TypeFilter, views::goto_prompt::centered};
+
+/// Popup choosing the type of the columns shown in the column browser.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    types: &[(Option<TypeFilter>, usize)],
+    state: &mut TableState,
+) {
+    let popup = centered(area, 40, types.len() as u16 + 2);
+    Clear.render(popup, buf);
+
+    let rows: Vec<Row> = types
+        .iter()
+        .map(|(type_filter, count)| {
+            let (kind, name) = match type_filter {
+                None => ("", "all types"),
+                Some(TypeFilter::Physical(name)) => ("physical", *name),
+                Some(TypeFilter::Logical(name)) => ("logical", *name),
+            };
+            Row::new(vec![name.to_string(), kind.to_string(), count.to_string()])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(9),
+            Constraint::Length(6),
+        ],
+    )
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title("Show columns of type")
+            .title_bottom(Line::from(" ENTER filter  ESC cancel ").centered().gray())
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, popup, buf, state);
+}
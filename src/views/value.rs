@@ -0,0 +1,135 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, TableState, Widget},
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::views::goto_prompt::centered;
+
+/// Bytes shown per line of the hex dump.
+const HEX_BYTES_PER_LINE: usize = 16;
+
+/// Wrap text to `width` columns, breaking lines after the last space that fits and breaking
+/// words longer than a line where they overflow.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut used = 0;
+        // Byte offset in `line` just past its last space, and the width up to it.
+        let mut last_space: Option<(usize, usize)> = None;
+        for c in paragraph.chars() {
+            let c_width = c.width().unwrap_or(0);
+            if used + c_width > width && !line.is_empty() {
+                match last_space {
+                    Some((offset, offset_width)) if offset < line.len() => {
+                        let rest = line.split_off(offset);
+                        lines.push(std::mem::replace(&mut line, rest));
+                        used -= offset_width;
+                    }
+                    _ => {
+                        lines.push(std::mem::take(&mut line));
+                        used = 0;
+                    }
+                }
+                last_space = None;
+            }
+            line.push(c);
+            used += c_width;
+            if c == ' ' {
+                last_space = Some((line.len(), used));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Hex dump of `bytes`: offset, hex bytes and printable ASCII characters.
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<width$}  |{}|",
+                i * HEX_BYTES_PER_LINE,
+                hex.join(" "),
+                ascii,
+                width = HEX_BYTES_PER_LINE * 3 - 1
+            )
+        })
+        .collect()
+}
+
+/// Popup showing a single value over the whole width, word wrapped or as a hex dump of its
+/// UTF-8 bytes. Values taller than the screen scroll, from the offset of `state`.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    title: &str,
+    value: &str,
+    hex: bool,
+    state: &mut TableState,
+) {
+    let lines = if hex {
+        hex_dump(value.as_bytes())
+    } else {
+        wrap(value, area.width.saturating_sub(2) as usize)
+    };
+    let popup = centered(area, 100, lines.len() as u16 + 2);
+    Clear.render(popup, buf);
+
+    // Keep the last page in view when scrolled past it, e.g. after the popup was resized.
+    let visible = popup.height.saturating_sub(2) as usize;
+    let offset = state.offset().min(lines.len().saturating_sub(visible));
+    *state.offset_mut() = offset;
+
+    let position = if lines.len() > visible {
+        format!(
+            "lines {}-{} of {}",
+            offset + 1,
+            (offset + visible).min(lines.len()),
+            lines.len()
+        )
+    } else {
+        format!("{} bytes", value.len())
+    };
+    let toggle = if hex { " x text" } else { " x hex" };
+
+    Paragraph::new(
+        lines
+            .into_iter()
+            .skip(offset)
+            .take(visible)
+            .map(Line::from)
+            .collect::<Vec<_>>(),
+    )
+    .block(
+        Block::bordered()
+            .title(title)
+            .title(Line::from(position).right_aligned().gray())
+            .title_bottom(
+                Line::from(format!(" ↑↓ scroll {}  y copy  ESC close ", toggle))
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green()),
+    )
+    .render(popup, buf);
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{analysis::value_sizes::ValueSizes, numbers};
+
+/// Columns left of the bars: the selection symbol, the length range, the count and the share,
+/// with the spacing between them, and the borders.
+const LABELS_WIDTH: u16 = 2 + 24 + 12 + 7 + 3 + 2;
+
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    row_group: usize,
+    sizes: &ValueSizes,
+    state: &mut TableState,
+) {
+    let header = Row::new(vec!["Length", "Values", "Share", ""])
+        .bold()
+        .underlined();
+
+    let largest = sizes
+        .buckets
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let bar_width = area.width.saturating_sub(LABELS_WIDTH) as f64;
+    let rows: Vec<Row> = sizes
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let range = if bucket.min == bucket.max {
+                numbers::bytes(bucket.min as u64)
+            } else {
+                format!(
+                    "{}–{}",
+                    numbers::bytes(bucket.min as u64),
+                    numbers::bytes(bucket.max as u64)
+                )
+            };
+            let mut bar = "█".repeat((bar_width * bucket.count as f64 / largest as f64) as usize);
+            if bar.is_empty() && bucket.count > 0 {
+                bar.push('▏');
+            }
+            let row = Row::new(vec![
+                range,
+                numbers::count(bucket.count),
+                format!(
+                    "{:.1}%",
+                    bucket.count as f64 / sizes.values.max(1) as f64 * 100.0
+                ),
+                bar,
+            ]);
+            // Values longer than the 99th percentile are the outliers readers choke on.
+            if bucket.count > 0 && bucket.min > sizes.p99 {
+                row.red()
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(24),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::new().bold().black().on_white())
+    .block(
+        Block::bordered()
+            .title(format!(
+                "Value Sizes of {} — Row Group {} — {} values, {} — p50 {}, p95 {}, p99 {}, max {}",
+                sizes.column,
+                row_group,
+                numbers::count(sizes.values),
+                numbers::bytes_labeled(sizes.total_bytes),
+                numbers::bytes_labeled(sizes.p50 as u64),
+                numbers::bytes_labeled(sizes.p95 as u64),
+                numbers::bytes_labeled(sizes.p99 as u64),
+                numbers::bytes_labeled(sizes.max as u64)
+            ))
+            .title_bottom(
+                Line::from(" Lengths in bytes, longer than p99 in red  ESC close ")
+                    .centered()
+                    .gray(),
+            )
+            .border_style(Style::default().green()),
+    );
+
+    StatefulWidget::render(table, area, buf, state);
+}
@@ -0,0 +1,32 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::views::goto_prompt::centered;
+
+/// Popup listing the values that can be copied from the main view.
+pub fn render(area: Rect, buf: &mut Buffer, items: &[(String, String)], state: &mut TableState) {
+    let popup = centered(area, 60, items.len() as u16 + 2);
+    Clear.render(popup, buf);
+
+    let rows: Vec<Row> = items
+        .iter()
+        .map(|(label, value)| Row::new(vec![label.clone(), value.clone()]))
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+        .highlight_symbol("> ")
+        .highlight_style(Style::new().bold().black().on_white())
+        .block(
+            Block::bordered()
+                .title("Copy to clipboard")
+                .title_bottom(Line::from(" ENTER copy  ESC cancel ").centered().gray())
+                .border_style(Style::default().green()),
+        );
+
+    StatefulWidget::render(table, popup, buf, state);
+}